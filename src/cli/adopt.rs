@@ -0,0 +1,299 @@
+//! Adoption of containers created outside Flaase.
+
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use serde_json::Value;
+
+use crate::core::app_config::{AppConfig, VolumeMount};
+use crate::core::config::ServerConfig;
+use crate::core::context::ExecutionContext;
+use crate::core::env::EnvManager;
+use crate::core::error::AppError;
+use crate::core::registry::{app_name_from_image, detect_default_port, parse_image_reference};
+use crate::providers::container_cli;
+use crate::ui;
+use crate::utils::validation::{
+    is_app_name_available, validate_app_name, validate_domain, validate_port,
+};
+
+/// Settings read from `<runtime> inspect` of an existing container.
+#[derive(Debug)]
+struct InspectedContainer {
+    /// Container name without the leading slash.
+    name: String,
+    image: String,
+    /// App name from the `flaase.app` label.
+    app_label: Option<String>,
+    /// Domain from the `flaase.domain` label.
+    domain_label: Option<String>,
+    /// Exposed container ports, lowest first.
+    ports: Vec<u16>,
+    /// Environment set on the container, minus the image defaults.
+    env: BTreeMap<String, String>,
+    volumes: Vec<VolumeMount>,
+    /// Host paths bind-mounted into the container (not carried over).
+    bind_mounts: Vec<String>,
+}
+
+impl InspectedContainer {
+    /// Parses one element of `inspect` output. `image_env` holds the image's
+    /// own `KEY=value` entries, which are left out of the app environment.
+    fn from_inspect(inspect: &Value, image_env: &[String]) -> Result<Self, AppError> {
+        let config = &inspect["Config"];
+        let image = config["Image"]
+            .as_str()
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| AppError::Validation("Container has no image".into()))?;
+
+        let label = |key: &str| {
+            config["Labels"][key]
+                .as_str()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+
+        let mut ports: Vec<u16> = config["ExposedPorts"]
+            .as_object()
+            .map(|ports| {
+                ports
+                    .keys()
+                    .filter(|p| p.ends_with("/tcp"))
+                    .filter_map(|p| p.trim_end_matches("/tcp").parse().ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        ports.sort_unstable();
+
+        let env = config["Env"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .filter(|entry| !image_env.iter().any(|default| default == entry))
+            .filter_map(|entry| entry.split_once('='))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+
+        let mut volumes = Vec::new();
+        let mut bind_mounts = Vec::new();
+        for mount in inspect["Mounts"].as_array().into_iter().flatten() {
+            let destination = mount["Destination"].as_str().unwrap_or_default();
+            match mount["Type"].as_str() {
+                Some("volume") => {
+                    let mut volume =
+                        VolumeMount::new(destination, mount["Name"].as_str().unwrap_or_default());
+                    volume.read_only = mount["RW"].as_bool() == Some(false);
+                    volumes.push(volume);
+                }
+                _ => bind_mounts.push(format!(
+                    "{} -> {}",
+                    mount["Source"].as_str().unwrap_or_default(),
+                    destination
+                )),
+            }
+        }
+
+        Ok(Self {
+            name: inspect["Name"]
+                .as_str()
+                .unwrap_or_default()
+                .trim_start_matches('/')
+                .to_string(),
+            image: image.to_string(),
+            app_label: label("flaase.app"),
+            domain_label: label("flaase.domain"),
+            ports,
+            env,
+            volumes,
+            bind_mounts,
+        })
+    }
+}
+
+/// Scaffolds an image app from an existing container and renames the
+/// container to the app's web container so Flaase commands can manage it.
+pub fn adopt(
+    container: &str,
+    name: Option<&str>,
+    domain: Option<&str>,
+    port: Option<u16>,
+    verbose: bool,
+) -> Result<(), AppError> {
+    if !ServerConfig::is_initialized() {
+        return Err(AppError::Config(
+            "Server not initialized. Run 'fl server init' first.".into(),
+        ));
+    }
+
+    let ctx = ExecutionContext::new(false, verbose);
+    let inspected = inspect_container(container, &ctx)?;
+
+    let name = match name {
+        Some(name) => name.to_string(),
+        None => inspected
+            .app_label
+            .clone()
+            .unwrap_or_else(|| app_name_from_image(&inspected.name)),
+    };
+    validate_app_name(&name)?;
+    if !is_app_name_available(&name) {
+        return Err(AppError::Validation(format!("App '{}' already exists", name)));
+    }
+
+    let domain = domain
+        .map(str::to_string)
+        .or_else(|| inspected.domain_label.clone())
+        .ok_or_else(|| {
+            AppError::Validation(
+                "No flaase.domain label on the container. Pass --domain.".into(),
+            )
+        })?;
+    validate_domain(&domain)?;
+
+    let image = parse_image_reference(&inspected.image)?;
+    let port = port
+        .or_else(|| inspected.ports.first().copied())
+        .or_else(|| detect_default_port(&image.name))
+        .ok_or_else(|| {
+            AppError::Validation("Could not detect the app's port. Pass --port.".into())
+        })?;
+    for warning in validate_port(port)? {
+        ui::warning(&warning);
+    }
+
+    let mut config = AppConfig::new_image(
+        name.clone(),
+        image,
+        domain,
+        port,
+        inspected.volumes.clone(),
+        None,
+        None,
+        None,
+    );
+    // The container is already serving, so the app counts as deployed
+    config.deployed_at = Some(Utc::now());
+
+    // Rename first: an app config must never point at a container it can't manage
+    let web_container = format!("flaase-{}-web", name);
+    let renamed = inspected.name != web_container;
+    if renamed {
+        let output = ctx.run_command(container_cli(), &["rename", container, &web_container])?;
+        output.ensure_success("Failed to rename container")?;
+    }
+
+    let scaffolded = ctx
+        .create_dir(&config.app_dir().display().to_string())
+        .and_then(|_| ctx.create_dir(&config.data_path().display().to_string()))
+        .and_then(|_| config.save())
+        .and_then(|_| match inspected.env.is_empty() {
+            true => Ok(()),
+            false => EnvManager::save_user(&config.app_dir(), &inspected.env),
+        });
+    if let Err(e) = scaffolded {
+        let _ = std::fs::remove_dir_all(config.app_dir());
+        if renamed {
+            let _ = ctx.run_command(container_cli(), &["rename", &web_container, &inspected.name]);
+        }
+        return Err(e);
+    }
+
+    ui::success(&format!(
+        "Adopted '{}' as app '{}' ({} on port {})",
+        inspected.name, name, inspected.image, port
+    ));
+    if !inspected.env.is_empty() {
+        ui::info(&format!(
+            "Saved {} environment variable(s) to {}",
+            inspected.env.len(),
+            config.env_path().display()
+        ));
+    }
+    for bind in &inspected.bind_mounts {
+        ui::warning(&format!("Bind mount {} was not carried over", bind));
+    }
+    ui::info(&format!(
+        "Run 'fl deploy {}' to recreate it with Flaase routing and SSL",
+        name
+    ));
+
+    Ok(())
+}
+
+/// Inspects a container and the defaults of its image.
+fn inspect_container(
+    container: &str,
+    ctx: &ExecutionContext,
+) -> Result<InspectedContainer, AppError> {
+    let output = ctx.run_command(container_cli(), &["inspect", "--type", "container", container])?;
+    if !output.success {
+        return Err(AppError::Validation(format!(
+            "Container '{}' not found",
+            container
+        )));
+    }
+
+    let inspect: Value = serde_json::from_str(&output.stdout)
+        .map_err(|e| AppError::Command(format!("Failed to parse inspect output: {}", e)))?;
+    let inspect = inspect
+        .get(0)
+        .ok_or_else(|| AppError::Validation(format!("Container '{}' not found", container)))?;
+
+    let image_env = inspect["Config"]["Image"]
+        .as_str()
+        .and_then(|image| {
+            ctx.run_command(
+                container_cli(),
+                &["image", "inspect", "--format", "{{json .Config.Env}}", image],
+            )
+            .ok()
+        })
+        .filter(|output| output.success)
+        .and_then(|output| serde_json::from_str::<Vec<String>>(output.stdout.trim()).ok())
+        .unwrap_or_default();
+
+    InspectedContainer::from_inspect(inspect, &image_env)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_inspect_reads_labels_ports_env_and_mounts() {
+        let inspect = serde_json::json!({
+            "Name": "/legacy-blog",
+            "Config": {
+                "Image": "ghost:5",
+                "Env": ["PATH=/usr/local/bin", "url=https://blog.example.com"],
+                "ExposedPorts": {"2368/tcp": {}, "53/udp": {}},
+                "Labels": {"flaase.domain": "blog.example.com"}
+            },
+            "Mounts": [
+                {"Type": "volume", "Name": "ghost-content",
+                 "Destination": "/var/lib/ghost/content", "RW": true},
+                {"Type": "bind", "Source": "/srv/ghost.json",
+                 "Destination": "/config.json", "RW": false}
+            ]
+        });
+
+        let inspected =
+            InspectedContainer::from_inspect(&inspect, &["PATH=/usr/local/bin".into()]).unwrap();
+
+        assert_eq!(inspected.name, "legacy-blog");
+        assert_eq!(inspected.image, "ghost:5");
+        assert_eq!(inspected.app_label, None);
+        assert_eq!(inspected.domain_label.as_deref(), Some("blog.example.com"));
+        assert_eq!(inspected.ports, vec![2368]);
+        assert_eq!(
+            inspected.env.into_iter().collect::<Vec<_>>(),
+            vec![("url".to_string(), "https://blog.example.com".to_string())]
+        );
+        assert_eq!(inspected.volumes.len(), 1);
+        assert_eq!(inspected.volumes[0].volume_name, "ghost-content");
+        assert!(!inspected.volumes[0].read_only);
+        assert_eq!(inspected.bind_mounts, vec!["/srv/ghost.json -> /config.json"]);
+    }
+}
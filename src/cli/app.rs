@@ -1,21 +1,29 @@
-//! Application initialization command handler.
+//! Application initialization and spec export command handlers.
 
-use std::path::PathBuf;
+use std::os::unix::fs::DirBuilderExt;
+use std::path::{Path, PathBuf};
 
 use crate::core::app_config::{
     AppConfig, CacheConfig, CacheType, DatabaseConfig, DatabaseType, DeploymentType, Framework,
-    HealthCheckConfig, ImageConfig, PackageManager, RegistryCredentials, Stack, StackConfig,
-    VolumeMount,
+    HealthCheckConfig, HealthCheckType, ImageConfig, PackageManager, RegistryCredentials,
+    ResourcesConfig, Stack, StackConfig, VolumeMount,
 };
 use crate::core::context::ExecutionContext;
+use crate::core::env::EnvManager;
 use crate::core::error::AppError;
-use crate::core::registry::{detect_default_port, parse_image_reference, save_credentials};
-use crate::core::secrets::{AppSecrets, SecretsManager};
+use crate::core::registry::{
+    app_name_from_image, detect_default_port, parse_image_reference, save_credentials,
+    verify_credentials,
+};
+use crate::core::secrets::{AppSecrets, DatabaseSecrets, SecretsManager};
+use crate::core::stack_detection::{detect_stack, DetectionConfidence, DetectionResult};
 use crate::core::FLAASE_APPS_PATH;
-use crate::providers::ssh::{SshKeyType, SshProvider};
+use crate::providers::git::GitProvider;
+use crate::providers::ssh::{GitConnectionStatus, SshKeyType, SshProvider};
 use crate::ui;
 use crate::utils::validation::{
-    is_app_name_available, validate_app_name, validate_domain, validate_git_ssh_url,
+    is_app_name_available, normalize_git_url, parse_memory_size, validate_app_name,
+    validate_domain, validate_git_ssh_url, validate_memory_size, validate_port,
 };
 
 /// Source deployment configuration.
@@ -27,10 +35,68 @@ struct SourceInitData {
     stack: Stack,
     stack_config: Option<StackConfig>,
     port: Option<u16>,
-    database: Option<DatabaseType>,
+    database: Option<DatabaseChoice>,
     cache: Option<CacheType>,
     domain: String,
     autodeploy: bool,
+    limits: Option<ResourcesConfig>,
+    /// Stack detected from the repository, reused when a field is modified.
+    detected: Option<DetectionResult>,
+}
+
+/// Database selected during `fl init`.
+#[derive(Debug, Clone)]
+struct DatabaseChoice {
+    db_type: DatabaseType,
+    /// Connection details when the database is hosted outside Flaase.
+    external: Option<ExternalDatabase>,
+}
+
+/// Connection details of an external database (RDS, Supabase, Neon, ...).
+#[derive(Debug, Clone)]
+struct ExternalDatabase {
+    host: String,
+    port: u16,
+    name: String,
+    username: String,
+    password: String,
+}
+
+impl DatabaseChoice {
+    /// Summary label, e.g. "PostgreSQL" or "PostgreSQL (external: db.example.com)".
+    fn label(&self) -> String {
+        match &self.external {
+            Some(external) => format!(
+                "{} (external: {}:{})",
+                self.db_type.display_name(),
+                external.host,
+                external.port
+            ),
+            None => self.db_type.display_name().to_string(),
+        }
+    }
+
+    /// Builds the database config for an app.
+    fn config(&self, app_name: &str) -> DatabaseConfig {
+        match &self.external {
+            Some(external) => DatabaseConfig::external(
+                self.db_type,
+                &external.name,
+                &external.host,
+                Some(external.port),
+            ),
+            None => DatabaseConfig::new(self.db_type, app_name),
+        }
+    }
+
+    /// User-provided credentials, for external databases.
+    fn credentials(&self) -> Option<DatabaseSecrets> {
+        self.external.as_ref().map(|external| DatabaseSecrets {
+            username: external.username.clone(),
+            password: external.password.clone(),
+            root_password: None,
+        })
+    }
 }
 
 /// Image deployment configuration.
@@ -40,11 +106,12 @@ struct ImageInitData {
     image: ImageConfig,
     port: u16,
     volumes: Vec<VolumeMount>,
-    database: Option<DatabaseType>,
+    database: Option<DatabaseChoice>,
     cache: Option<CacheType>,
     domain: String,
     health_check: HealthCheckConfig,
     credentials: Option<RegistryCredentials>,
+    limits: Option<ResourcesConfig>,
 }
 
 /// Fields that can be modified in the summary (source deployment).
@@ -60,6 +127,7 @@ enum SourceModifiableField {
     Cache,
     Domain,
     Autodeploy,
+    Limits,
 }
 
 /// Fields that can be modified in the summary (image deployment).
@@ -73,6 +141,7 @@ enum ImageModifiableField {
     Cache,
     Domain,
     HealthCheck,
+    Limits,
 }
 
 /// Executes the app init command.
@@ -97,6 +166,78 @@ pub fn init(verbose: bool) -> Result<(), AppError> {
     }
 }
 
+/// Configures and deploys a registry image in one step, without a Git repository.
+pub fn deploy_image(
+    image: &str,
+    domain: &str,
+    port: Option<u16>,
+    name: Option<&str>,
+    private: bool,
+    verbose: bool,
+) -> Result<(), AppError> {
+    if !crate::core::config::ServerConfig::is_initialized() {
+        return Err(AppError::Config(
+            "Server not initialized. Run 'fl server init' first.".into(),
+        ));
+    }
+
+    let mut image = parse_image_reference(image)?;
+    validate_domain(domain)?;
+
+    let name = match name {
+        Some(name) => name.to_string(),
+        None => app_name_from_image(&image.name),
+    };
+    validate_app_name(&name)?;
+    if !is_app_name_available(&name) {
+        return Err(AppError::Validation(format!(
+            "App '{}' already exists. Use 'fl deploy {}' to redeploy it.",
+            name, name
+        )));
+    }
+
+    let port = match port.or_else(|| detect_default_port(&image.name)) {
+        Some(port) => port,
+        None => {
+            ui::warning("Could not detect the image port, using 8080 (override with --port)");
+            8080
+        }
+    };
+    for warning in validate_port(port)? {
+        ui::warning(&warning);
+    }
+
+    image.private = private;
+    let credentials = if private {
+        Some(prompt_registry_credentials(&image)?)
+    } else {
+        None
+    };
+
+    let data = ImageInitData {
+        name: name.clone(),
+        image,
+        port,
+        volumes: Vec::new(),
+        database: None,
+        cache: None,
+        domain: domain.to_string(),
+        health_check: HealthCheckConfig::default(),
+        credentials,
+        limits: None,
+    };
+
+    let ctx = ExecutionContext::new(false, verbose);
+    create_image_app(&data, &ctx)?;
+    ui::success(&format!(
+        "App '{}' configured at {}/{}/ (port {})",
+        name, FLAASE_APPS_PATH, name, port
+    ));
+    println!();
+
+    crate::cli::deploy::deploy(&name, false, None, None, None, None, false, verbose)
+}
+
 /// Prompts for deployment type.
 fn prompt_deployment_type() -> Result<DeploymentType, AppError> {
     let options = ["From Git repository", "From Docker image"];
@@ -200,26 +341,25 @@ fn collect_source_data(ctx: &ExecutionContext) -> Result<SourceInitData, AppErro
     // 3. SSH key selection or generation
     let ssh_key = prompt_ssh_key(ctx)?;
 
-    // 4. Test SSH connection
-    ui::info("Testing SSH connection to repository...");
-    let connected = SshProvider::test_git_connection(&repository, &ssh_key, ctx)?;
-
-    if connected {
-        ui::success("SSH connection successful");
+    // 4. Test SSH connection, then inspect the repository to detect the stack
+    let detected = if check_git_connection(&repository, &ssh_key, ctx)? {
+        if ui::confirm("Inspect the repository to detect the stack?", true)? {
+            detect_repository_stack(&repository, &ssh_key, ctx)
+        } else {
+            None
+        }
     } else {
-        ui::warning(
-            "Could not verify SSH connection. Make sure the key is added to your Git provider.",
-        );
         let proceed = ui::confirm("Continue anyway?", false)?;
         if !proceed {
             return Err(AppError::Cancelled);
         }
-    }
+        None
+    };
 
     println!();
 
     // 5. Stack selection
-    let stack = prompt_stack()?;
+    let stack = prompt_stack(detected.as_ref())?;
 
     // 6. Database selection
     let database = prompt_database()?;
@@ -234,11 +374,13 @@ fn collect_source_data(ctx: &ExecutionContext) -> Result<SourceInitData, AppErro
     let autodeploy = prompt_autodeploy()?;
 
     // Get stack configuration details
-    let stack_config = prompt_stack_config(stack)?;
+    let stack_config = prompt_stack_config(stack, detected_for(detected.as_ref(), stack))?;
 
     // Get port if not using default
     let port = prompt_port(stack)?;
 
+    let limits = prompt_resource_limits()?;
+
     Ok(SourceInitData {
         name,
         repository,
@@ -250,9 +392,16 @@ fn collect_source_data(ctx: &ExecutionContext) -> Result<SourceInitData, AppErro
         cache,
         domain,
         autodeploy,
+        limits,
+        detected,
     })
 }
 
+/// Returns the detection result when it is about `stack`.
+fn detected_for(detected: Option<&DetectionResult>, stack: Stack) -> Option<&DetectionResult> {
+    detected.filter(|d| d.stack == Some(stack))
+}
+
 /// Prompts for app name with validation.
 fn prompt_app_name() -> Result<String, AppError> {
     loop {
@@ -281,6 +430,7 @@ fn prompt_repository() -> Result<String, AppError> {
             "GitHub repository URL?",
             Some("git@github.com:user/repo.git"),
         )?;
+        let url = normalize_git_url(&url);
 
         if let Err(e) = validate_git_ssh_url(&url) {
             ui::error(&e.to_string());
@@ -359,30 +509,189 @@ fn generate_new_key(ctx: &ExecutionContext) -> Result<PathBuf, AppError> {
     Ok(key_path)
 }
 
-/// Prompts for stack selection.
-fn prompt_stack() -> Result<Stack, AppError> {
+/// Tests SSH access to the repository, explaining any failure and how to fix it.
+fn check_git_connection(
+    repository: &str,
+    ssh_key: &Path,
+    ctx: &ExecutionContext,
+) -> Result<bool, AppError> {
+    ui::info("Testing SSH connection to repository...");
+    let status = SshProvider::test_git_connection(repository, ssh_key, ctx)?;
+
+    if status.is_connected() {
+        ui::success("SSH connection successful");
+        return Ok(true);
+    }
+
+    let host = SshProvider::extract_host(repository).unwrap_or_default();
+    ui::warning(&status.reason(&host));
+    if let Some(hint) = status.hint(repository) {
+        ui::info(&hint);
+    }
+    if status == GitConnectionStatus::PermissionDenied {
+        if let Ok(pub_key) = SshProvider::get_public_key(ssh_key) {
+            println!();
+            println!("{}", pub_key.trim_end());
+            println!();
+        }
+    }
+
+    Ok(false)
+}
+
+/// Shallow-clones the repository into a temporary directory and runs stack
+/// detection on it. Returns None when the clone fails.
+fn detect_repository_stack(
+    repository: &str,
+    ssh_key: &Path,
+    ctx: &ExecutionContext,
+) -> Option<DetectionResult> {
+    // A fresh private directory, so nobody can have planted files or
+    // symlinks where the clone lands
+    let work_dir = std::env::temp_dir().join(format!(
+        "flaase-detect-{}",
+        SecretsManager::generate_password(16)
+    ));
+    if let Err(e) = std::fs::DirBuilder::new().mode(0o700).create(&work_dir) {
+        ui::warning(&format!("Could not inspect the repository: {}", e));
+        return None;
+    }
+    let dir = work_dir.join("repository");
+
+    let spinner = ui::ProgressBar::spinner("Inspecting repository");
+    let result = GitProvider::clone(repository, &dir, ssh_key, ctx).map(|_| detect_stack(&dir));
+    let _ = std::fs::remove_dir_all(&work_dir);
+
+    match result {
+        Ok(detected) => {
+            spinner.finish("done");
+            Some(detected)
+        }
+        Err(e) => {
+            spinner.finish_error("failed");
+            ui::warning(&format!("Could not inspect the repository: {}", e));
+            None
+        }
+    }
+}
+
+/// Prompts for stack selection, pre-selecting the detected stack if any.
+fn prompt_stack(detected: Option<&DetectionResult>) -> Result<Stack, AppError> {
     let stacks: Vec<&str> = Stack::all().iter().map(|s| s.display_name()).collect();
-    let selected = ui::select("Which stack does your app use?", &stacks)?;
+    let prompt = "Which stack does your app use?";
+
+    let detected = detected
+        .filter(|d| d.confidence != DetectionConfidence::None)
+        .and_then(|d| d.stack.map(|stack| (d, stack)));
+    let Some((detected, stack)) = detected else {
+        let selected = ui::select(prompt, &stacks)?;
+        return Ok(Stack::all()[selected]);
+    };
+
+    let confidence = detected.confidence;
+    ui::info(&format!(
+        "Detected {} ({}) from {}",
+        stack.display_name(),
+        confidence.display(),
+        detected.detected_files.join(", ")
+    ));
+
+    if confidence == DetectionConfidence::High {
+        let mut options = vec![format!(
+            "Use detected: {} ({})",
+            stack.display_name(),
+            confidence.display()
+        )];
+        options.extend(stacks.iter().map(|s| s.to_string()));
+
+        let selected = ui::select(prompt, &options)?;
+        return Ok(match selected {
+            0 => stack,
+            n => Stack::all()[n - 1],
+        });
+    }
+
+    let default = Stack::all().iter().position(|s| *s == stack).unwrap_or(0);
+    let selected = ui::select_with_default(prompt, &stacks, default)?;
     Ok(Stack::all()[selected])
 }
 
 /// Prompts for database selection.
-fn prompt_database() -> Result<Option<DatabaseType>, AppError> {
+fn prompt_database() -> Result<Option<DatabaseChoice>, AppError> {
     let mut options: Vec<&str> = DatabaseType::all()
         .iter()
         .map(|d| d.display_name())
         .collect();
+    options.push("PostgreSQL (external)");
     options.push("None");
 
     let selected = ui::select("Do you need a database?", &options)?;
-
-    if selected == DatabaseType::all().len() {
-        Ok(None)
+    let managed = DatabaseType::all().len();
+
+    if selected < managed {
+        Ok(Some(DatabaseChoice {
+            db_type: DatabaseType::all()[selected],
+            external: None,
+        }))
+    } else if selected == managed {
+        Ok(Some(DatabaseChoice {
+            db_type: DatabaseType::PostgreSQL,
+            external: Some(prompt_external_database(DatabaseType::PostgreSQL)?),
+        }))
     } else {
-        Ok(Some(DatabaseType::all()[selected]))
+        Ok(None)
     }
 }
 
+/// Prompts for the connection details of an external database.
+fn prompt_external_database(db_type: DatabaseType) -> Result<ExternalDatabase, AppError> {
+    let host = loop {
+        let host = ui::input("Database host?")?.trim().to_string();
+        if !host.is_empty() {
+            break host;
+        }
+        ui::error("Please enter the database host");
+    };
+
+    let default_port = db_type.default_port();
+    let port = loop {
+        match ui::input_with_default("Database port?", &default_port.to_string())?
+            .trim()
+            .parse::<u16>()
+        {
+            Ok(port) if port > 0 => break port,
+            _ => ui::error("Please enter a valid port number (1-65535)"),
+        }
+    };
+
+    let name = ui::input_with_default("Database name?", "postgres")?
+        .trim()
+        .to_string();
+    let (username, password) = prompt_database_credentials()?;
+
+    Ok(ExternalDatabase {
+        host,
+        port,
+        name,
+        username,
+        password,
+    })
+}
+
+/// Prompts for the user and password of an external database.
+fn prompt_database_credentials() -> Result<(String, String), AppError> {
+    let username = loop {
+        let username = ui::input("Database user?")?.trim().to_string();
+        if !username.is_empty() {
+            break username;
+        }
+        ui::error("Please enter the database user");
+    };
+    let password = ui::password("Database password?")?;
+
+    Ok((username, password))
+}
+
 /// Prompts for cache selection.
 fn prompt_cache() -> Result<Option<CacheType>, AppError> {
     let mut options: Vec<&str> = CacheType::all().iter().map(|c| c.display_name()).collect();
@@ -417,7 +726,10 @@ fn prompt_autodeploy() -> Result<bool, AppError> {
 }
 
 /// Prompts for stack configuration details.
-fn prompt_stack_config(stack: Stack) -> Result<Option<StackConfig>, AppError> {
+fn prompt_stack_config(
+    stack: Stack,
+    detected: Option<&DetectionResult>,
+) -> Result<Option<StackConfig>, AppError> {
     // Skip for stacks that don't need extra config
     if matches!(stack, Stack::NextJs | Stack::NestJs | Stack::Laravel) {
         return Ok(None);
@@ -444,21 +756,33 @@ fn prompt_stack_config(stack: Stack) -> Result<Option<StackConfig>, AppError> {
         _ => None,
     };
 
-    let version = if let Some(placeholder) = version_placeholder {
-        let input = ui::input_with_placeholder(
-            &format!("{} version?", stack.display_name()),
-            Some(placeholder),
-        )?;
-        if input.is_empty() { None } else { Some(input) }
-    } else {
-        None
+    let detected_version = detected.and_then(|d| d.version.as_deref());
+    let version = match (version_placeholder, detected_version) {
+        (Some(_), Some(detected)) => {
+            let input = ui::input_with_default(
+                &format!("{} version? (detected: {})", stack.display_name(), detected),
+                detected,
+            )?;
+            if input.is_empty() { None } else { Some(input) }
+        }
+        (Some(placeholder), None) => {
+            let input = ui::input_with_placeholder(
+                &format!("{} version?", stack.display_name()),
+                Some(placeholder),
+            )?;
+            if input.is_empty() { None } else { Some(input) }
+        }
+        _ => None,
     };
 
     // Package manager (for stacks with multiple options)
-    let package_manager = prompt_package_manager(stack)?;
+    let package_manager = match detected {
+        Some(detected) => prompt_detected_package_manager(stack, detected)?,
+        None => prompt_package_manager(stack)?,
+    };
 
     // Framework (optional)
-    let framework = prompt_framework(stack)?;
+    let framework = prompt_framework(stack, detected.and_then(|d| d.framework))?;
 
     // Start command (required for custom stacks)
     let start_command = if stack.requires_start_command() {
@@ -528,8 +852,43 @@ fn prompt_package_manager(stack: Stack) -> Result<Option<PackageManager>, AppErr
     Ok(Some(options[selected].1))
 }
 
+/// Confirms the package manager found by stack detection, asking the user
+/// to pick one when the repository has lockfiles for several.
+fn prompt_detected_package_manager(
+    stack: Stack,
+    detected: &DetectionResult,
+) -> Result<Option<PackageManager>, AppError> {
+    if !detected.lockfile_conflict.is_empty() {
+        let names: Vec<&str> = detected
+            .lockfile_conflict
+            .iter()
+            .map(|pm| pm.display_name())
+            .collect();
+        ui::warning(&format!(
+            "Found lockfiles for several package managers: {}",
+            names.join(", ")
+        ));
+        ui::info("Set \"packageManager\" in package.json to make the choice explicit.");
+
+        let selected = ui::select("Which package manager does this app use?", &names)?;
+        return Ok(Some(detected.lockfile_conflict[selected]));
+    }
+
+    if let Some(pm) = detected.package_manager {
+        let prompt = format!("Use detected package manager {}?", pm.display_name());
+        if ui::confirm(&prompt, true)? {
+            return Ok(Some(pm));
+        }
+    }
+
+    prompt_package_manager(stack)
+}
+
 /// Prompts for framework selection.
-fn prompt_framework(stack: Stack) -> Result<Option<Framework>, AppError> {
+fn prompt_framework(
+    stack: Stack,
+    detected: Option<Framework>,
+) -> Result<Option<Framework>, AppError> {
     let options: Vec<(&str, Framework)> = match stack {
         Stack::NodeJs => vec![
             ("Express", Framework::Express),
@@ -570,6 +929,11 @@ fn prompt_framework(stack: Stack) -> Result<Option<Framework>, AppError> {
             ("Symfony", Framework::Symfony),
             ("Other / None", Framework::Other),
         ],
+        Stack::Static => vec![
+            ("Vite", Framework::Vite),
+            ("Create React App", Framework::CreateReactApp),
+            ("Plain HTML / None", Framework::Other),
+        ],
         _ => return Ok(None),
     };
 
@@ -577,13 +941,25 @@ fn prompt_framework(stack: Stack) -> Result<Option<Framework>, AppError> {
         return Ok(None);
     }
 
-    let use_framework = ui::confirm("Specify a framework?", false)?;
-    if !use_framework {
-        return Ok(None);
-    }
-
     let labels: Vec<&str> = options.iter().map(|(l, _)| *l).collect();
-    let selected = ui::select("Which framework?", &labels)?;
+    let detected_index = detected.and_then(|fw| options.iter().position(|(_, o)| *o == fw));
+
+    let selected = match detected_index {
+        Some(index) => {
+            let prompt = format!("Use detected framework {}?", labels[index]);
+            if ui::confirm(&prompt, true)? {
+                return Ok(Some(options[index].1));
+            }
+            ui::select_with_default("Which framework?", &labels, index)?
+        }
+        None => {
+            let use_framework = ui::confirm("Specify a framework?", false)?;
+            if !use_framework {
+                return Ok(None);
+            }
+            ui::select("Which framework?", &labels)?
+        }
+    };
 
     let framework = options[selected].1;
     if framework == Framework::Other {
@@ -609,7 +985,8 @@ fn prompt_port(stack: Stack) -> Result<Option<u16>, AppError> {
     loop {
         let input = ui::input("Port number?")?;
         match input.parse::<u16>() {
-            Ok(port) if port > 0 => return Ok(Some(port)),
+            Ok(port) if accept_port(port)? => return Ok(Some(port)),
+            Ok(_) => continue,
             _ => {
                 ui::error("Please enter a valid port number (1-65535)");
                 continue;
@@ -618,6 +995,27 @@ fn prompt_port(stack: Stack) -> Result<Option<u16>, AppError> {
     }
 }
 
+/// Validates a port, asking for confirmation when it raises warnings.
+fn accept_port(port: u16) -> Result<bool, AppError> {
+    let warnings = match validate_port(port) {
+        Ok(warnings) => warnings,
+        Err(e) => {
+            ui::error(&e.to_string());
+            return Ok(false);
+        }
+    };
+
+    if warnings.is_empty() {
+        return Ok(true);
+    }
+
+    for warning in &warnings {
+        ui::warning(warning);
+    }
+
+    Ok(ui::confirm(&format!("Use port {} anyway?", port), false)?)
+}
+
 /// Displays the source deployment configuration summary.
 fn display_source_summary(data: &SourceInitData) {
     println!();
@@ -625,8 +1023,8 @@ fn display_source_summary(data: &SourceInitData) {
     let db_str = data
         .database
         .as_ref()
-        .map(|d| d.display_name())
-        .unwrap_or("None");
+        .map(DatabaseChoice::label)
+        .unwrap_or_else(|| "None".to_string());
     let cache_str = data
         .cache
         .as_ref()
@@ -656,6 +1054,7 @@ fn display_source_summary(data: &SourceInitData) {
     println!("  Cache:        {}", cache_str);
     println!("  Domain:       {}", data.domain);
     println!("  Autodeploy:   {}", autodeploy_str);
+    println!("  Limits:       {}", limits_label(data.limits.as_ref()));
     println!();
 }
 
@@ -695,6 +1094,7 @@ fn prompt_source_summary_action() -> Result<SourceSummaryAction, AppError> {
         "Modify cache",
         "Modify domain",
         "Modify autodeploy",
+        "Modify resource limits",
         "Cancel",
     ];
 
@@ -712,6 +1112,7 @@ fn prompt_source_summary_action() -> Result<SourceSummaryAction, AppError> {
         8 => SourceSummaryAction::Modify(SourceModifiableField::Cache),
         9 => SourceSummaryAction::Modify(SourceModifiableField::Domain),
         10 => SourceSummaryAction::Modify(SourceModifiableField::Autodeploy),
+        11 => SourceSummaryAction::Modify(SourceModifiableField::Limits),
         _ => SourceSummaryAction::Cancel,
     })
 }
@@ -729,32 +1130,22 @@ fn modify_source_field(
         SourceModifiableField::Repository => {
             data.repository = prompt_repository()?;
             // Re-test SSH connection
-            ui::info("Testing SSH connection to repository...");
-            let connected = SshProvider::test_git_connection(&data.repository, &data.ssh_key, ctx)?;
-            if connected {
-                ui::success("SSH connection successful");
-            } else {
-                ui::warning("Could not verify SSH connection");
-            }
+            check_git_connection(&data.repository, &data.ssh_key, ctx)?;
         }
         SourceModifiableField::SshKey => {
             data.ssh_key = prompt_ssh_key(ctx)?;
             // Re-test SSH connection
-            ui::info("Testing SSH connection to repository...");
-            let connected = SshProvider::test_git_connection(&data.repository, &data.ssh_key, ctx)?;
-            if connected {
-                ui::success("SSH connection successful");
-            } else {
-                ui::warning("Could not verify SSH connection");
-            }
+            check_git_connection(&data.repository, &data.ssh_key, ctx)?;
         }
         SourceModifiableField::Stack => {
-            data.stack = prompt_stack()?;
+            data.stack = prompt_stack(data.detected.as_ref())?;
             // Reset stack config when stack changes
-            data.stack_config = prompt_stack_config(data.stack)?;
+            let detected = detected_for(data.detected.as_ref(), data.stack);
+            data.stack_config = prompt_stack_config(data.stack, detected)?;
         }
         SourceModifiableField::StackConfig => {
-            data.stack_config = prompt_stack_config(data.stack)?;
+            let detected = detected_for(data.detected.as_ref(), data.stack);
+            data.stack_config = prompt_stack_config(data.stack, detected)?;
         }
         SourceModifiableField::Port => {
             data.port = prompt_port(data.stack)?;
@@ -771,6 +1162,9 @@ fn modify_source_field(
         SourceModifiableField::Autodeploy => {
             data.autodeploy = prompt_autodeploy()?;
         }
+        SourceModifiableField::Limits => {
+            data.limits = prompt_resource_limits()?;
+        }
     }
 
     Ok(())
@@ -789,11 +1183,12 @@ fn create_source_app(data: &SourceInitData, ctx: &ExecutionContext) -> Result<()
     // Build app config
     let database_config = data
         .database
-        .map(|db_type| DatabaseConfig::new(db_type, &data.name));
+        .as_ref()
+        .map(|database| database.config(&data.name));
 
     let cache_config = data.cache.map(CacheConfig::new);
 
-    let config = AppConfig::new_source(
+    let mut config = AppConfig::new_source(
         data.name.clone(),
         data.repository.clone(),
         data.ssh_key.clone(),
@@ -801,46 +1196,17 @@ fn create_source_app(data: &SourceInitData, ctx: &ExecutionContext) -> Result<()
         data.stack_config.clone(),
         data.domain.clone(),
         data.port,
-        database_config.clone(),
-        cache_config.clone(),
+        database_config,
+        cache_config,
         data.autodeploy,
     );
+    config.resources = data.limits.clone();
 
     // Save config.yml
     config.save()?;
 
     // Generate and save secrets if needed
-    let mut secrets = AppSecrets::default();
-
-    if let Some(db_type) = data.database {
-        secrets.database = Some(SecretsManager::generate_database_secrets(
-            db_type, &data.name,
-        ));
-    }
-
-    if let Some(cache_type) = data.cache {
-        secrets.cache = Some(SecretsManager::generate_cache_secrets(cache_type));
-    }
-
-    // Save secrets file
-    if secrets.database.is_some() || secrets.cache.is_some() {
-        SecretsManager::save_secrets(&config.secrets_path(), &secrets)?;
-
-        // Generate .env file with connection URLs
-        let db_name = database_config
-            .as_ref()
-            .map(|d| d.name.as_str())
-            .unwrap_or("");
-        let env_vars = SecretsManager::generate_env_vars(
-            &secrets,
-            data.database,
-            db_name,
-            data.cache,
-            &data.name,
-        );
-
-        SecretsManager::write_env_file(&config.auto_env_path(), &env_vars)?;
-    }
+    write_service_secrets(&config, data.database.as_ref().and_then(DatabaseChoice::credentials))?;
 
     Ok(())
 }
@@ -883,6 +1249,9 @@ fn collect_image_data() -> Result<ImageInitData, AppError> {
     // 8. Health check
     let health_check = prompt_health_check()?;
 
+    // 9. Resource limits
+    let limits = prompt_resource_limits()?;
+
     Ok(ImageInitData {
         name,
         image,
@@ -893,6 +1262,7 @@ fn collect_image_data() -> Result<ImageInitData, AppError> {
         domain,
         health_check,
         credentials,
+        limits,
     })
 }
 
@@ -908,9 +1278,7 @@ fn prompt_docker_image() -> Result<(ImageConfig, Option<RegistryCredentials>), A
                 image.private = is_private;
 
                 let credentials = if is_private {
-                    let username = ui::input("Registry username?")?;
-                    let password = ui::password("Registry password?")?;
-                    Some(RegistryCredentials::new(&username, &password))
+                    Some(prompt_registry_credentials(&image)?)
                 } else {
                     None
                 };
@@ -925,6 +1293,32 @@ fn prompt_docker_image() -> Result<(ImageConfig, Option<RegistryCredentials>), A
     }
 }
 
+/// Prompts for registry credentials until they are accepted by the registry.
+fn prompt_registry_credentials(image: &ImageConfig) -> Result<RegistryCredentials, AppError> {
+    let ctx = ExecutionContext::new(false, false);
+
+    loop {
+        let username = ui::input("Registry username?")?;
+        let password = ui::password("Registry password?")?;
+        let credentials = RegistryCredentials::new(&username, &password);
+
+        let spinner = ui::ProgressBar::spinner("Verifying registry credentials");
+        match verify_credentials(image, &credentials, &ctx) {
+            Ok(()) => {
+                spinner.finish("verified");
+                return Ok(credentials);
+            }
+            Err(e) => {
+                spinner.finish_error("failed");
+                ui::error(&e.to_string());
+                if !ui::confirm("Try again?", true)? {
+                    return Err(AppError::Cancelled);
+                }
+            }
+        }
+    }
+}
+
 /// Prompts for port with auto-detected default.
 fn prompt_image_port(detected: Option<u16>) -> Result<u16, AppError> {
     let default_port = detected.unwrap_or(8080);
@@ -933,7 +1327,8 @@ fn prompt_image_port(detected: Option<u16>) -> Result<u16, AppError> {
     loop {
         let input = ui::input_with_default(&prompt, &default_port.to_string())?;
         match input.parse::<u16>() {
-            Ok(port) if port > 0 => return Ok(port),
+            Ok(port) if accept_port(port)? => return Ok(port),
+            Ok(_) => continue,
             _ => {
                 ui::error("Please enter a valid port number (1-65535)");
                 continue;
@@ -979,19 +1374,118 @@ fn prompt_volumes(app_name: &str) -> Result<Vec<VolumeMount>, AppError> {
     Ok(volumes)
 }
 
+/// Prompts for optional hard memory and CPU limits.
+fn prompt_resource_limits() -> Result<Option<ResourcesConfig>, AppError> {
+    if !ui::confirm("Limit memory and CPU usage?", false)? {
+        return Ok(None);
+    }
+
+    let memory = loop {
+        let input = ui::input_with_placeholder("Memory limit? (empty for none)", Some("512m"))?;
+        let input = input.trim().to_lowercase();
+        if input.is_empty() {
+            break None;
+        }
+        match validate_memory_size(&input) {
+            Ok(()) => break Some(input),
+            Err(e) => ui::error(&e.to_string()),
+        }
+    };
+
+    let cpus = loop {
+        let input = ui::input_with_placeholder("CPU limit? (empty for none)", Some("1.0"))?;
+        let input = input.trim();
+        if input.is_empty() {
+            break None;
+        }
+        match input.parse::<f64>() {
+            Ok(cpus) if validate_cpus(cpus).is_ok() => break Some(cpus),
+            _ => ui::error("Use a positive number, e.g. 0.5 or 2"),
+        }
+    };
+
+    let limits = ResourcesConfig {
+        memory,
+        cpus,
+        ..Default::default()
+    };
+    Ok((!limits.is_empty()).then_some(limits))
+}
+
+/// Summary label for resource limits, e.g. "512m memory, 1.5 CPUs".
+fn limits_label(limits: Option<&ResourcesConfig>) -> String {
+    let Some(limits) = limits else {
+        return "None".to_string();
+    };
+
+    let mut parts = Vec::new();
+    if let Some(memory) = &limits.memory {
+        parts.push(format!("{} memory", memory));
+    }
+    if let Some(cpus) = limits.cpus {
+        parts.push(format!("{} CPUs", cpus));
+    }
+
+    if parts.is_empty() {
+        "None".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
 /// Prompts for health check configuration.
 fn prompt_health_check() -> Result<HealthCheckConfig, AppError> {
-    let endpoint = ui::input_with_default("Health check endpoint?", "/")?;
-    let timeout = ui::input_with_default("Health check timeout (seconds)?", "30")?
+    let check_type = match ui::select(
+        "Health check type?",
+        &[
+            "HTTP (GET an endpoint)",
+            "TCP (port accepts connections)",
+            "Command (exit code inside the container)",
+        ],
+    )? {
+        0 => HealthCheckType::Http,
+        1 => HealthCheckType::Tcp,
+        _ => HealthCheckType::Command,
+    };
+
+    let mut health = HealthCheckConfig {
+        check_type,
+        ..Default::default()
+    };
+
+    match check_type {
+        HealthCheckType::Http => {
+            health.endpoint = ui::input_with_default("Health check endpoint?", "/")?;
+        }
+        HealthCheckType::Tcp => {}
+        HealthCheckType::Command => {
+            let command = loop {
+                let input =
+                    ui::input_with_placeholder("Health check command?", Some("pg_isready"))?;
+                if !input.trim().is_empty() {
+                    break input.trim().to_string();
+                }
+                ui::warning("A command is required");
+            };
+            health.command = Some(command);
+        }
+    }
+
+    health.timeout = ui::input_with_default("Health check timeout (seconds)?", "30")?
         .parse::<u32>()
         .unwrap_or(30);
 
-    Ok(HealthCheckConfig {
-        endpoint,
-        timeout,
-        interval: 5,
-        retries: 3,
-    })
+    Ok(health)
+}
+
+/// Summary label for a health check, e.g. "/health (30s timeout)".
+fn health_check_label(health: &HealthCheckConfig) -> String {
+    let target = match health.check_type {
+        HealthCheckType::Http => health.endpoint.clone(),
+        HealthCheckType::Tcp => "TCP port".to_string(),
+        HealthCheckType::Command => format!("`{}`", health.command.as_deref().unwrap_or("")),
+    };
+    format!("{} ({}s timeout)", target, health.timeout)
 }
 
 /// Displays the image deployment configuration summary.
@@ -1001,8 +1495,8 @@ fn display_image_summary(data: &ImageInitData) {
     let db_str = data
         .database
         .as_ref()
-        .map(|d| d.display_name())
-        .unwrap_or("None");
+        .map(DatabaseChoice::label)
+        .unwrap_or_else(|| "None".to_string());
     let cache_str = data
         .cache
         .as_ref()
@@ -1030,7 +1524,8 @@ fn display_image_summary(data: &ImageInitData) {
     println!("  Database:     {}", db_str);
     println!("  Cache:        {}", cache_str);
     println!("  Domain:       {}", data.domain);
-    println!("  Health check: {} ({}s timeout)", data.health_check.endpoint, data.health_check.timeout);
+    println!("  Health check: {}", health_check_label(&data.health_check));
+    println!("  Limits:       {}", limits_label(data.limits.as_ref()));
     println!();
 }
 
@@ -1046,6 +1541,7 @@ fn prompt_image_summary_action() -> Result<ImageSummaryAction, AppError> {
         "Modify cache",
         "Modify domain",
         "Modify health check",
+        "Modify resource limits",
         "Cancel",
     ];
 
@@ -1061,6 +1557,7 @@ fn prompt_image_summary_action() -> Result<ImageSummaryAction, AppError> {
         6 => ImageSummaryAction::Modify(ImageModifiableField::Cache),
         7 => ImageSummaryAction::Modify(ImageModifiableField::Domain),
         8 => ImageSummaryAction::Modify(ImageModifiableField::HealthCheck),
+        9 => ImageSummaryAction::Modify(ImageModifiableField::Limits),
         _ => ImageSummaryAction::Cancel,
     })
 }
@@ -1098,6 +1595,9 @@ fn modify_image_field(
         ImageModifiableField::HealthCheck => {
             data.health_check = prompt_health_check()?;
         }
+        ImageModifiableField::Limits => {
+            data.limits = prompt_resource_limits()?;
+        }
     }
 
     Ok(())
@@ -1115,20 +1615,22 @@ fn create_image_app(data: &ImageInitData, ctx: &ExecutionContext) -> Result<(),
     // Build app config
     let database_config = data
         .database
-        .map(|db_type| DatabaseConfig::new(db_type, &data.name));
+        .as_ref()
+        .map(|database| database.config(&data.name));
 
     let cache_config = data.cache.map(CacheConfig::new);
 
-    let config = AppConfig::new_image(
+    let mut config = AppConfig::new_image(
         data.name.clone(),
         data.image.clone(),
         data.domain.clone(),
         data.port,
         data.volumes.clone(),
-        database_config.clone(),
-        cache_config.clone(),
+        database_config,
+        cache_config,
         Some(data.health_check.clone()),
     );
+    config.resources = data.limits.clone();
 
     // Save config.yml
     config.save()?;
@@ -1139,37 +1641,291 @@ fn create_image_app(data: &ImageInitData, ctx: &ExecutionContext) -> Result<(),
     }
 
     // Generate and save secrets if needed
+    write_service_secrets(&config, data.database.as_ref().and_then(DatabaseChoice::credentials))?;
+
+    Ok(())
+}
+
+/// Generates database/cache credentials for a new app and writes the
+/// auto-generated env file with their connection URLs. `db_credentials`
+/// are the user-provided ones of an external database.
+fn write_service_secrets(
+    config: &AppConfig,
+    db_credentials: Option<DatabaseSecrets>,
+) -> Result<(), AppError> {
     let mut secrets = AppSecrets::default();
+    let db_type = config.database.as_ref().map(|d| d.db_type);
+    let cache_type = config.primary_cache().map(|c| c.cache_type);
+
+    if let Some(db_type) = db_type {
+        secrets.database = Some(db_credentials.unwrap_or_else(|| {
+            SecretsManager::generate_database_secrets(db_type, &config.name)
+        }));
+    }
+
+    if let Some(cache_type) = cache_type {
+        secrets.cache = SecretsManager::generate_cache_secrets(cache_type);
+    }
+
+    // Memcached has no secrets but still needs its URL in the env file
+    if secrets.database.is_none() && cache_type.is_none() {
+        return Ok(());
+    }
+
+    SecretsManager::save_secrets(&config.secrets_path(), &secrets)?;
+
+    // Generate .env file with connection URLs
+    let env_vars = SecretsManager::generate_env_vars(
+        &secrets,
+        config.database.as_ref(),
+        &config.caches,
+        &config.name,
+    );
+
+    SecretsManager::write_env_file(&config.auto_env_path(), &env_vars)
+}
+
+// ============================================================================
+// Spec Export / Import
+// ============================================================================
+
+/// Prints a sanitized, re-importable spec of an app's configuration.
+pub fn export_config(app: &str, with_env: bool) -> Result<(), AppError> {
+    let config = AppConfig::load(app)?;
+
+    let env = if with_env {
+        Some(EnvManager::load_user(&config.app_dir())?)
+    } else {
+        None
+    };
+
+    print!("{}", config.export_spec(env.as_ref())?);
+
+    Ok(())
+}
 
-    if let Some(db_type) = data.database {
-        secrets.database = Some(SecretsManager::generate_database_secrets(
-            db_type, &data.name,
+/// Shows or updates the app's CPU/memory reservations and limits. Settings
+/// given in `changes` replace the current ones.
+pub fn resources(app: &str, changes: ResourcesConfig, clear: bool) -> Result<(), AppError> {
+    let mut config = AppConfig::load(app)?;
+
+    if clear || !changes.is_empty() {
+        let mut resources = if clear {
+            ResourcesConfig::default()
+        } else {
+            config.resources.clone().unwrap_or_default()
+        };
+
+        if let Some(shares) = changes.cpu_shares {
+            resources.cpu_shares = Some(shares);
+        }
+        if let Some(size) = changes.memory_reservation {
+            validate_memory_size(&size)?;
+            resources.memory_reservation = Some(size.to_lowercase());
+        }
+        if let Some(size) = changes.memory {
+            validate_memory_size(&size)?;
+            resources.memory = Some(size.to_lowercase());
+        }
+        if let Some(cpus) = changes.cpus {
+            validate_cpus(cpus)?;
+            resources.cpus = Some(cpus);
+        }
+        if let Some(size) = changes.service_memory {
+            validate_memory_size(&size)?;
+            resources.service_memory = Some(size.to_lowercase());
+        }
+        if let Some(cpus) = changes.service_cpus {
+            validate_cpus(cpus)?;
+            resources.service_cpus = Some(cpus);
+        }
+        validate_resources(&resources)?;
+
+        config.resources = if resources.is_empty() {
+            None
+        } else {
+            Some(resources)
+        };
+        config.save()?;
+
+        ui::success(&format!("Resources updated for {}", app));
+        ui::info(&format!("Run 'fl update {}' to apply to the running containers", app));
+        println!();
+    }
+
+    let resources = config.resources.unwrap_or_default();
+    println!("Resources for {}", console::style(app).cyan().bold());
+    println!();
+    println!(
+        "  CPU shares:          {}",
+        resources
+            .cpu_shares
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| console::style("default (1024)").dim().to_string())
+    );
+    println!(
+        "  Memory reservation:  {}",
+        resources
+            .memory_reservation
+            .unwrap_or_else(|| console::style("none").dim().to_string())
+    );
+    println!(
+        "  Memory limit:        {}",
+        resources
+            .memory
+            .unwrap_or_else(|| console::style("unlimited").dim().to_string())
+    );
+    println!(
+        "  CPU limit:           {}",
+        resources
+            .cpus
+            .map(|c| c.to_string())
+            .unwrap_or_else(|| console::style("unlimited").dim().to_string())
+    );
+    println!(
+        "  Service memory:      {}",
+        resources
+            .service_memory
+            .unwrap_or_else(|| console::style("unlimited").dim().to_string())
+    );
+    println!(
+        "  Service CPU limit:   {}",
+        resources
+            .service_cpus
+            .map(|c| c.to_string())
+            .unwrap_or_else(|| console::style("unlimited").dim().to_string())
+    );
+    println!();
+
+    Ok(())
+}
+
+/// Validates a `--cpus` value.
+fn validate_cpus(cpus: f64) -> Result<(), AppError> {
+    if !cpus.is_finite() || cpus <= 0.0 {
+        return Err(AppError::Validation(format!(
+            "Invalid CPU limit '{}'. Use a positive number, e.g. 0.5 or 2",
+            cpus
+        )));
+    }
+    Ok(())
+}
+
+/// Checks that the memory reservation fits under the memory limit.
+fn validate_resources(resources: &ResourcesConfig) -> Result<(), AppError> {
+    if let (Some(reservation), Some(limit)) = (&resources.memory_reservation, &resources.memory) {
+        if parse_memory_size(reservation)? > parse_memory_size(limit)? {
+            return Err(AppError::Validation(format!(
+                "Memory reservation ({}) must not exceed the memory limit ({})",
+                reservation, limit
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Creates an app from a spec written by `fl app export-config`.
+pub fn init_from_file(path: &Path, verbose: bool) -> Result<(), AppError> {
+    ui::header();
+
+    let ctx = ExecutionContext::new(false, verbose);
+
+    if !crate::core::config::ServerConfig::is_initialized() {
+        return Err(AppError::Config(
+            "Server not initialized. Run 'fl server init' first.".into(),
+        ));
+    }
+
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        AppError::Config(format!("Failed to read {}: {}", path.display(), e))
+    })?;
+    let (mut config, env) = AppConfig::from_spec(&content)?;
+
+    validate_app_name(&config.name)?;
+    if !is_app_name_available(&config.name) {
+        return Err(AppError::Validation(format!(
+            "App '{}' already exists",
+            config.name
+        )));
+    }
+    for domain in &config.domains {
+        validate_domain(&domain.domain)?;
+    }
+    if let Some(port) = config.port {
+        for warning in validate_port(port)? {
+            ui::warning(&warning);
+        }
+    }
+
+    if config.is_source_deployment() {
+        let repository = config.repository.as_deref().ok_or_else(|| {
+            AppError::Config("Source app spec is missing 'repository'".into())
+        })?;
+        let repository = normalize_git_url(repository);
+        validate_git_ssh_url(&repository)?;
+        config.repository = Some(repository);
+        config.ssh_key = Some(prompt_ssh_key(&ctx)?);
+    } else if config.image.is_none() {
+        return Err(AppError::Config("Image app spec is missing 'image'".into()));
+    }
+
+    for channel in config.strip_redacted_notifications() {
+        ui::warning(&format!(
+            "{} notifications were redacted in the spec and have been dropped",
+            channel
         ));
     }
 
-    if let Some(cache_type) = data.cache {
-        secrets.cache = Some(SecretsManager::generate_cache_secrets(cache_type));
+    ui::info("Creating app configuration...");
+
+    let app_dir = config.app_dir();
+    ctx.create_dir(&app_dir.to_string_lossy())?;
+    if config.is_source_deployment() {
+        ctx.create_dir(&config.repo_path().to_string_lossy())?;
     }
+    ctx.create_dir(&config.data_path().to_string_lossy())?;
+
+    // External databases keep the user's credentials, which specs never carry
+    let db_credentials = match &config.database {
+        Some(database) if database.external => {
+            ui::info(&format!(
+                "Credentials for the external database on {}:",
+                database.connection_host(&config.name)
+            ));
+            let (username, password) = prompt_database_credentials()?;
+            Some(DatabaseSecrets {
+                username,
+                password,
+                root_password: None,
+            })
+        }
+        _ => None,
+    };
 
-    // Save secrets file
-    if secrets.database.is_some() || secrets.cache.is_some() {
+    config.save()?;
+    write_service_secrets(&config, db_credentials)?;
+
+    if config.autodeploy_config.is_some() {
+        let mut secrets = SecretsManager::load_secrets(&config.secrets_path())?;
+        secrets.webhook = Some(SecretsManager::generate_webhook_secret());
         SecretsManager::save_secrets(&config.secrets_path(), &secrets)?;
+        ui::warning(&format!(
+            "A new webhook secret was generated. Show it with: fl autodeploy secret {}",
+            config.name
+        ));
+    }
 
-        // Generate .env file with connection URLs
-        let db_name = database_config
-            .as_ref()
-            .map(|d| d.name.as_str())
-            .unwrap_or("");
-        let env_vars = SecretsManager::generate_env_vars(
-            &secrets,
-            data.database,
-            db_name,
-            data.cache,
-            &data.name,
-        );
-
-        SecretsManager::write_env_file(&config.auto_env_path(), &env_vars)?;
+    if !env.is_empty() {
+        EnvManager::save_user(&app_dir, &env)?;
+        ui::info(&format!("Imported {} environment variable(s)", env.len()));
     }
 
+    println!();
+    ui::success(&format!(
+        "App configured at {}/{}/",
+        FLAASE_APPS_PATH, config.name
+    ));
+    ui::info(&format!("Deploy with: fl deploy {}", config.name));
+
     Ok(())
 }
@@ -1,8 +1,9 @@
 //! Authentication command handlers for HTTP Basic Auth.
 
+use crate::cli::domain::regenerate_traefik_config;
 use crate::core::app_config::AppConfig;
 use crate::core::error::AppError;
-use crate::core::secrets::{AppSecrets, SecretsManager};
+use crate::core::secrets::SecretsManager;
 use crate::ui;
 
 /// Lists authentication status for all domains of an app.
@@ -126,7 +127,7 @@ pub fn add(
     SecretsManager::save_secrets(&config.secrets_path(), &secrets)?;
 
     // Update Traefik config
-    update_traefik_config(&config, &secrets)?;
+    regenerate_traefik_config(&config)?;
 
     ui::success(&format!("Basic Auth enabled on {}", domain));
 
@@ -159,7 +160,7 @@ pub fn remove(app: &str, domain: &str) -> Result<(), AppError> {
     SecretsManager::save_secrets(&config.secrets_path(), &secrets)?;
 
     // Update Traefik config
-    update_traefik_config(&config, &secrets)?;
+    regenerate_traefik_config(&config)?;
 
     ui::success(&format!("Basic Auth disabled on {}", domain));
 
@@ -237,44 +238,9 @@ pub fn update(
     SecretsManager::save_secrets(&config.secrets_path(), &secrets)?;
 
     // Update Traefik config
-    update_traefik_config(&config, &secrets)?;
+    regenerate_traefik_config(&config)?;
 
     ui::success(&format!("Basic Auth updated on {}", domain));
 
     Ok(())
 }
-
-/// Updates the Traefik configuration with auth middleware.
-fn update_traefik_config(config: &AppConfig, secrets: &AppSecrets) -> Result<(), AppError> {
-    use crate::core::context::ExecutionContext;
-    use crate::templates::traefik::{generate_app_config, AppDomain};
-
-    let ctx = ExecutionContext::new(false, false);
-
-    // Build domain list with auth info
-    let mut domains = Vec::new();
-
-    for domain_config in &config.domains {
-        let mut app_domain = AppDomain::new(&domain_config.domain, domain_config.primary);
-
-        // Add auth if configured
-        if let Some(auth_secret) = secrets.auth.get(&domain_config.domain) {
-            app_domain = app_domain.with_auth(&auth_secret.password_hash);
-        }
-
-        domains.push(app_domain);
-    }
-
-    // Generate and write Traefik config
-    let traefik_config = generate_app_config(&config.name, &domains, config.effective_port());
-
-    let traefik_path = format!(
-        "{}/{}.yml",
-        crate::core::FLAASE_TRAEFIK_DYNAMIC_PATH,
-        config.name
-    );
-
-    ctx.write_file(&traefik_path, &traefik_config)?;
-
-    Ok(())
-}
@@ -3,17 +3,22 @@
 use crate::cli::webhook;
 use crate::core::app_config::{
     AppConfig, AutodeployConfig, DiscordNotificationConfig, NotificationConfig,
-    RateLimitConfig, SlackNotificationConfig,
+    RateLimitConfig, SlackNotificationConfig, TelegramNotificationConfig,
 };
-use crate::core::deployments::{DeploymentHistory, DeploymentStatus};
+use crate::core::context::ExecutionContext;
+use crate::core::deploy::Deployer;
+use crate::core::deployments::{DeploymentHistory, DeploymentRecord, DeploymentStatus};
 use crate::core::error::AppError;
 use crate::core::notifications::test_notification;
 use crate::core::secrets::SecretsManager;
 use crate::providers::webhook::WebhookProvider;
+use crate::providers::{create_container_runtime, create_reverse_proxy};
 use crate::ui;
+use crate::utils::normalize_git_url;
 
-/// Enables autodeploy for an app via GitHub webhook.
-pub fn enable(app: &str, branch: Option<&str>) -> Result<(), AppError> {
+/// Enables autodeploy for an app via GitHub webhook. `install` decides whether
+/// to install the webhook server service; `None` asks.
+pub fn enable(app: &str, branch: Option<&str>, install: Option<bool>) -> Result<(), AppError> {
     let mut config = AppConfig::load(app)?;
 
     // Check if already enabled
@@ -36,10 +41,10 @@ pub fn enable(app: &str, branch: Option<&str>) -> Result<(), AppError> {
 
     // Generate webhook path and secret
     let webhook_path = WebhookProvider::generate_webhook_path(app);
-    let webhook_secret = SecretsManager::generate_webhook_secret();
-
-    // Load and update secrets
     let mut secrets = SecretsManager::load_secrets(&config.secrets_path())?;
+    let webhook_secret = SecretsManager::generate_fresh_webhook_secret(&secrets);
+
+    // Update secrets
     secrets.webhook = Some(webhook_secret.clone());
     SecretsManager::save_secrets(&config.secrets_path(), &secrets)?;
 
@@ -64,7 +69,12 @@ pub fn enable(app: &str, branch: Option<&str>) -> Result<(), AppError> {
         );
         println!();
 
-        if ui::confirm("Install the webhook server as a system service?", true)? {
+        let install = match install {
+            Some(install) => install,
+            None => ui::confirm("Install the webhook server as a system service?", true)?,
+        };
+
+        if install {
             println!();
             webhook::install()?;
         } else {
@@ -85,7 +95,7 @@ pub fn enable(app: &str, branch: Option<&str>) -> Result<(), AppError> {
 }
 
 /// Disables autodeploy for an app.
-pub fn disable(app: &str) -> Result<(), AppError> {
+pub fn disable(app: &str, rotate_on_disable: bool) -> Result<(), AppError> {
     let mut config = AppConfig::load(app)?;
 
     if config.autodeploy_config.is_none() {
@@ -94,7 +104,7 @@ pub fn disable(app: &str) -> Result<(), AppError> {
     }
 
     // Confirm
-    if !ui::confirm("Disable autodeploy for this app?", false)? {
+    if !ui::confirm_action("Disable autodeploy for this app?", false)? {
         ui::info("Cancelled.");
         return Ok(());
     }
@@ -102,9 +112,13 @@ pub fn disable(app: &str) -> Result<(), AppError> {
     println!();
     ui::step("Disabling autodeploy...");
 
-    // Remove webhook secret
+    // Remove webhook secret, permanently revoking it if requested
     let mut secrets = SecretsManager::load_secrets(&config.secrets_path())?;
-    secrets.webhook = None;
+    if rotate_on_disable {
+        secrets.revoke_webhook();
+    } else {
+        secrets.webhook = None;
+    }
     SecretsManager::save_secrets(&config.secrets_path(), &secrets)?;
 
     // Update config
@@ -113,6 +127,9 @@ pub fn disable(app: &str) -> Result<(), AppError> {
     config.save()?;
 
     ui::success("Autodeploy disabled.");
+    if rotate_on_disable {
+        ui::info("The webhook secret was revoked and will never be accepted again.");
+    }
     println!();
     ui::info("Remember to remove the webhook from your GitHub repository settings.");
 
@@ -132,6 +149,12 @@ pub fn status(app: &str) -> Result<(), AppError> {
             console::style("\u{2713}").green()
         );
         println!("  Branch:  {}", console::style(&autodeploy.branch).cyan());
+        if let Some(pattern) = &autodeploy.deploy_on_tag {
+            println!("  Tags:    {}", console::style(pattern).cyan());
+        }
+        if !autodeploy.paths.is_empty() {
+            println!("  Paths:   {}", console::style(autodeploy.paths.join(", ")).cyan());
+        }
 
         // Show webhook URL
         let webhook_url = WebhookProvider::webhook_url(config.primary_domain(), &autodeploy.webhook_path);
@@ -158,6 +181,90 @@ pub fn status(app: &str) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Shows an autodeploy summary for every app.
+pub fn status_all() -> Result<(), AppError> {
+    let apps = AppConfig::list_all()?;
+
+    if apps.is_empty() {
+        ui::info("No apps configured yet.");
+        return Ok(());
+    }
+
+    println!("Autodeploy status");
+    println!();
+    println!(
+        "  {}  {}  {}  {}",
+        console::style(format!("{:<20}", "APP")).dim(),
+        console::style(format!("{:<8}", "STATE")).dim(),
+        console::style(format!("{:<15}", "BRANCH")).dim(),
+        console::style("LAST DEPLOY").dim()
+    );
+
+    for app in &apps {
+        let config = match AppConfig::load(app) {
+            Ok(config) => config,
+            Err(_) => {
+                println!(
+                    "  {:<20}  {}",
+                    app,
+                    console::style("invalid config").red()
+                );
+                continue;
+            }
+        };
+
+        let (state, branch) = match &config.autodeploy_config {
+            Some(autodeploy) => (
+                console::style(format!("{:<8}", "enabled")).green().to_string(),
+                autodeploy.branch.clone(),
+            ),
+            None => (
+                console::style(format!("{:<8}", "disabled")).dim().to_string(),
+                "-".to_string(),
+            ),
+        };
+
+        let history = DeploymentHistory::load(&config.deployments_path()).unwrap_or_default();
+        let last = match history.recent(1).first() {
+            Some(record) => format!(
+                "{} {}",
+                styled_status(&record.status),
+                console::style(record.timestamp.format("%Y-%m-%d %H:%M")).dim()
+            ),
+            None => console::style("never").dim().to_string(),
+        };
+
+        println!("  {:<20}  {}  {:<15}  {}", app, state, branch, last);
+    }
+
+    println!();
+
+    Ok(())
+}
+
+/// Formats a deployment status with its color.
+fn styled_status(status: &DeploymentStatus) -> String {
+    match status {
+        DeploymentStatus::Triggered => console::style("triggered").yellow().to_string(),
+        DeploymentStatus::PendingApproval => console::style("pending").cyan().to_string(),
+        DeploymentStatus::Success => console::style("success").green().to_string(),
+        DeploymentStatus::Failed => console::style("failed").red().to_string(),
+        DeploymentStatus::RolledBack => console::style("rollback").magenta().to_string(),
+    }
+}
+
+/// Formats the deploy tag and Git tag of a record as a suffix (empty when untagged).
+fn format_label(record: &DeploymentRecord) -> String {
+    let mut suffix = String::new();
+    if let Some(ref label) = record.label {
+        suffix.push_str(&format!(" {}", console::style(format!("[{}]", label)).cyan()));
+    }
+    if let Some(ref tag) = record.git_tag {
+        suffix.push_str(&format!(" {}", console::style(format!("(tag {})", tag)).magenta()));
+    }
+    suffix
+}
+
 /// Shows recent deployment history for an app.
 fn show_deployment_history(config: &AppConfig) -> Result<(), AppError> {
     let history = DeploymentHistory::load(&config.deployments_path())?;
@@ -186,13 +293,7 @@ fn show_deployment_history(config: &AppConfig) -> Result<(), AppError> {
     for record in recent {
         let date = record.timestamp.format("%Y-%m-%d %H:%M:%S");
 
-        let status_str = match record.status {
-            DeploymentStatus::Triggered => console::style("triggered").yellow().to_string(),
-            DeploymentStatus::PendingApproval => console::style("pending").cyan().to_string(),
-            DeploymentStatus::Success => console::style("success").green().to_string(),
-            DeploymentStatus::Failed => console::style("failed").red().to_string(),
-            DeploymentStatus::RolledBack => console::style("rollback").magenta().to_string(),
-        };
+        let status_str = styled_status(&record.status);
 
         // Truncate commit message to 40 chars
         let msg: String = record.commit_message.chars().take(40).collect();
@@ -203,11 +304,12 @@ fn show_deployment_history(config: &AppConfig) -> Result<(), AppError> {
         };
 
         println!(
-            "    {}  {}  {:<10}  {}",
+            "    {}  {}  {:<10}  {}{}",
             console::style(date).dim(),
             console::style(&record.commit_sha).yellow(),
             status_str,
-            msg
+            msg,
+            format_label(record)
         );
     }
 
@@ -235,19 +337,11 @@ fn show_webhook_info(config: &AppConfig) -> Result<(), AppError> {
 /// Converts a Git SSH URL to a GitHub HTTPS URL for the settings page.
 /// e.g., "git@github.com:user/repo.git" -> "https://github.com/user/repo/settings/hooks/new"
 fn repo_to_github_settings_url(repo: &str) -> String {
-    // Handle SSH format: git@github.com:user/repo.git
-    if repo.starts_with("git@github.com:") {
-        let path = repo
-            .trim_start_matches("git@github.com:")
-            .trim_end_matches(".git");
-        return format!("https://github.com/{}/settings/hooks/new", path);
-    }
+    let repo = normalize_git_url(repo);
 
-    // Handle HTTPS format: https://github.com/user/repo.git
-    if repo.starts_with("https://github.com/") {
-        let path = repo
-            .trim_start_matches("https://github.com/")
-            .trim_end_matches(".git");
+    // Canonical SSH format: git@github.com:user/repo.git
+    if let Some(path) = repo.strip_prefix("git@github.com:") {
+        let path = path.trim_end_matches(".git");
         return format!("https://github.com/{}/settings/hooks/new", path);
     }
 
@@ -290,6 +384,11 @@ fn show_setup_instructions(config: &AppConfig, secret: &str) -> Result<(), AppEr
     println!();
     println!("{}", console::style("Save the webhook and you're done!").green());
     println!();
+    println!(
+        "{}",
+        console::style("Using Gitea or Bitbucket? Use the same URL and secret with push events.").dim()
+    );
+    println!();
     println!(
         "{}",
         console::style("Note: Keep the secret safe! You can view it again with:").dim()
@@ -323,7 +422,7 @@ pub fn secret(app: &str) -> Result<(), AppError> {
 
     let secrets = SecretsManager::load_secrets(&config.secrets_path())?;
 
-    if let Some(webhook) = &secrets.webhook {
+    if let Some(webhook) = secrets.active_webhook() {
         println!();
         println!("Webhook secret for {}:", console::style(app).cyan());
         println!();
@@ -353,7 +452,7 @@ pub fn regenerate(app: &str) -> Result<(), AppError> {
     println!("You will need to update the secret in your GitHub repository settings.");
     println!();
 
-    if !ui::confirm("Regenerate webhook secret?", false)? {
+    if !ui::confirm_action("Regenerate webhook secret?", false)? {
         ui::info("Cancelled.");
         return Ok(());
     }
@@ -361,11 +460,10 @@ pub fn regenerate(app: &str) -> Result<(), AppError> {
     println!();
     ui::step("Regenerating webhook secret...");
 
-    // Generate new secret
-    let new_secret = SecretsManager::generate_webhook_secret();
-
-    // Update secrets
+    // Revoke the old secret and generate a new one
     let mut secrets = SecretsManager::load_secrets(&config.secrets_path())?;
+    secrets.revoke_webhook();
+    let new_secret = SecretsManager::generate_fresh_webhook_secret(&secrets);
     secrets.webhook = Some(new_secret.clone());
     SecretsManager::save_secrets(&config.secrets_path(), &secrets)?;
 
@@ -386,10 +484,24 @@ pub fn regenerate(app: &str) -> Result<(), AppError> {
 }
 
 /// Shows deployment logs for an app.
-pub fn logs(app: &str, limit: usize) -> Result<(), AppError> {
+pub fn logs(app: &str, offset: usize, limit: usize, json: bool) -> Result<(), AppError> {
     let config = AppConfig::load(app)?;
     let history = DeploymentHistory::load(&config.deployments_path())?;
-    let deployments = history.recent(limit);
+    let deployments = history.page(offset, limit);
+
+    if json {
+        let page = DeploymentLogPage {
+            app,
+            total: history.deployments.len(),
+            offset,
+            limit,
+            deployments,
+        };
+        let output = serde_json::to_string_pretty(&page)
+            .map_err(|e| AppError::Config(format!("Failed to serialize deployments: {}", e)))?;
+        println!("{}", output);
+        return Ok(());
+    }
 
     println!(
         "Deployment logs for {}",
@@ -458,12 +570,13 @@ pub fn logs(app: &str, limit: usize) -> Result<(), AppError> {
         };
 
         println!(
-            "  {}  {}  {}  {}  {}",
+            "  {}  {}  {}  {}  {}{}",
             console::style(date).dim(),
             console::style(&record.commit_sha).yellow(),
             status_str,
             source_str,
-            msg
+            msg,
+            format_label(record)
         );
 
         // Show triggered by for webhook deployments
@@ -484,7 +597,7 @@ pub fn logs(app: &str, limit: usize) -> Result<(), AppError> {
 
     // Show total count if there are more
     let total = history.deployments.len();
-    if total > limit {
+    if total > offset + deployments.len() {
         println!(
             "  {} {} deployments total. Use {} to see more.",
             console::style(format!("{}", total)).bold(),
@@ -497,6 +610,97 @@ pub fn logs(app: &str, limit: usize) -> Result<(), AppError> {
     Ok(())
 }
 
+/// JSON envelope for one page of `fl autodeploy logs --json`.
+#[derive(serde::Serialize)]
+struct DeploymentLogPage<'a> {
+    app: &'a str,
+    total: usize,
+    offset: usize,
+    limit: usize,
+    deployments: &'a [DeploymentRecord],
+}
+
+// ============================================================================
+// Trigger Commands
+// ============================================================================
+
+/// Sets or clears the tag pattern that triggers deployments.
+pub fn tags(app: &str, pattern: Option<&str>, disable: bool) -> Result<(), AppError> {
+    let mut config = AppConfig::load(app)?;
+
+    let Some(autodeploy) = config.autodeploy_config.as_mut() else {
+        return Err(AppError::Validation(
+            "Autodeploy is not enabled for this app.".into(),
+        ));
+    };
+
+    if disable {
+        autodeploy.deploy_on_tag = None;
+        config.save()?;
+        ui::success("Tag pushes no longer trigger deployments");
+        return Ok(());
+    }
+
+    let pattern = pattern.unwrap_or_default().trim();
+    if pattern.is_empty() || pattern.chars().any(char::is_whitespace) {
+        return Err(AppError::Validation(format!(
+            "Invalid tag pattern '{}'",
+            pattern
+        )));
+    }
+
+    autodeploy.deploy_on_tag = Some(pattern.to_string());
+    let branch = autodeploy.branch.clone();
+    config.save()?;
+
+    ui::success(&format!("Pushing a tag matching {} now deploys {}", pattern, app));
+    ui::info(&format!("Pushes to {} still deploy as before", branch));
+    ui::info("Make sure the repository webhook sends tag push events.");
+
+    Ok(())
+}
+
+/// Sets or clears the monorepo path filters for branch pushes.
+pub fn paths(app: &str, patterns: &[String], clear: bool) -> Result<(), AppError> {
+    let mut config = AppConfig::load(app)?;
+
+    let Some(autodeploy) = config.autodeploy_config.as_mut() else {
+        return Err(AppError::Validation(
+            "Autodeploy is not enabled for this app.".into(),
+        ));
+    };
+
+    if clear {
+        autodeploy.paths.clear();
+        config.save()?;
+        ui::success("Every push to the watched branch deploys again");
+        return Ok(());
+    }
+
+    let mut paths = Vec::new();
+    for pattern in patterns {
+        let pattern = pattern.trim().trim_start_matches("./");
+        if pattern.is_empty() || pattern.starts_with('/') {
+            return Err(AppError::Validation(format!(
+                "Invalid path pattern '{}': use a path relative to the repository root",
+                pattern
+            )));
+        }
+        if !paths.iter().any(|p| p == pattern) {
+            paths.push(pattern.to_string());
+        }
+    }
+
+    autodeploy.paths = paths;
+    let summary = autodeploy.paths.join(", ");
+    config.save()?;
+
+    ui::success(&format!("{} deploys only when a push changes {}", app, summary));
+    ui::info("Tag pushes and pushes without a file list still always deploy.");
+
+    Ok(())
+}
+
 // ============================================================================
 // Rate Limiting Commands
 // ============================================================================
@@ -639,6 +843,16 @@ pub fn notify_status(app: &str) -> Result<(), AppError> {
                 println!("  Discord: {}", console::style("Not configured").dim());
             }
 
+            // Telegram
+            if let Some(telegram) = &notif.telegram {
+                let bot_id = telegram.bot_token.split(':').next().unwrap_or_default();
+                println!("  Telegram:");
+                println!("    Bot: {}:***", bot_id);
+                println!("    Chat: {}", telegram.chat_id);
+            } else {
+                println!("  Telegram: {}", console::style("Not configured").dim());
+            }
+
             println!();
             println!("  Events:");
             println!(
@@ -680,9 +894,11 @@ pub fn notify_enable(app: &str) -> Result<(), AppError> {
     let notif = autodeploy.notifications.as_mut().unwrap();
 
     // Check if at least one provider is configured
-    if notif.slack.is_none() && notif.discord.is_none() {
+    if !notif.has_provider() {
         return Err(AppError::Validation(
-            "Configure at least one notification provider first (Slack or Discord)".into(),
+            "Configure at least one notification provider first \
+             (Slack, Discord, Telegram or Email)"
+                .into(),
         ));
     }
 
@@ -865,7 +1081,82 @@ pub fn notify_discord(
     Ok(())
 }
 
+/// Configures Telegram notifications for an app.
+pub fn notify_telegram(
+    app: &str,
+    bot_token: Option<&str>,
+    chat_id: Option<&str>,
+    remove: bool,
+) -> Result<(), AppError> {
+    let mut config = AppConfig::load(app)?;
+
+    if config.autodeploy_config.is_none() {
+        return Err(AppError::Validation(
+            "Autodeploy is not enabled for this app.".into(),
+        ));
+    }
+
+    let autodeploy = config.autodeploy_config.as_mut().unwrap();
+
+    // Initialize notifications if not present
+    if autodeploy.notifications.is_none() {
+        autodeploy.notifications = Some(NotificationConfig::default());
+    }
+
+    let notif = autodeploy.notifications.as_mut().unwrap();
+
+    if remove {
+        notif.telegram = None;
+        config.save()?;
+        ui::success("Telegram configuration removed");
+        return Ok(());
+    }
+
+    // Get or create Telegram config
+    let telegram = notif.telegram.get_or_insert_with(|| TelegramNotificationConfig {
+        bot_token: String::new(),
+        chat_id: String::new(),
+    });
+
+    if let Some(token) = bot_token {
+        if !token.contains(':') {
+            ui::warning("Bot token doesn't look like a Telegram bot token (<id>:<secret>)");
+        }
+        telegram.bot_token = token.to_string();
+    }
+    if let Some(chat) = chat_id {
+        telegram.chat_id = chat.to_string();
+    }
+
+    // Require both fields
+    if telegram.bot_token.is_empty() {
+        return Err(AppError::Validation(
+            "Bot token is required. Use --bot-token <token>".into(),
+        ));
+    }
+    if telegram.chat_id.is_empty() {
+        return Err(AppError::Validation(
+            "Chat ID is required. Use --chat-id <id>".into(),
+        ));
+    }
+
+    // Enable notifications automatically
+    notif.enabled = true;
+
+    config.save()?;
+
+    ui::success("Telegram notifications configured");
+    println!();
+    println!(
+        "  Test with: {}",
+        console::style(format!("fl autodeploy notify test {}", app)).cyan()
+    );
+
+    Ok(())
+}
+
 /// Configures email (SMTP) notifications for an app.
+#[allow(clippy::too_many_arguments)]
 pub fn notify_email(
     app: &str,
     smtp_host: Option<&str>,
@@ -1069,7 +1360,7 @@ pub fn notify_test(app: &str) -> Result<(), AppError> {
             ));
         }
         Some(notif) => {
-            if notif.slack.is_none() && notif.discord.is_none() {
+            if !notif.has_provider() {
                 return Err(AppError::Validation(
                     "No notification providers configured.".into(),
                 ));
@@ -1090,7 +1381,7 @@ pub fn notify_test(app: &str) -> Result<(), AppError> {
 // Test Configuration Commands
 // ============================================================================
 
-use crate::core::app_config::{TestConfig, HooksConfig, HookCommand, RollbackConfig, EnvironmentConfig, ApprovalConfig, BuildConfig, BlueGreenConfig};
+use crate::core::app_config::{TestConfig, HooksConfig, HookCommand, RollbackConfig, EnvironmentConfig, ApprovalConfig, BuildCacheMode, BuildConfig, BlueGreenConfig};
 
 /// Configures test execution for an app.
 pub fn test_config(
@@ -1224,7 +1515,9 @@ pub fn hooks_list(app: &str) -> Result<(), AppError> {
                         } else {
                             console::style("optional").dim()
                         };
-                        let container_str = if hook.run_in_container {
+                        let container_str = if hook.run_in_ephemeral {
+                            "ephemeral container"
+                        } else if hook.run_in_container {
                             "in container"
                         } else {
                             "on host"
@@ -1256,6 +1549,7 @@ pub fn hooks_list(app: &str) -> Result<(), AppError> {
 }
 
 /// Adds a hook to an app.
+#[allow(clippy::too_many_arguments)]
 pub fn hooks_add(
     app: &str,
     phase: &str,
@@ -1264,6 +1558,7 @@ pub fn hooks_add(
     timeout: Option<u64>,
     required: bool,
     in_container: bool,
+    ephemeral: bool,
 ) -> Result<(), AppError> {
     let mut config = AppConfig::load(app)?;
 
@@ -1288,10 +1583,18 @@ pub fn hooks_add(
         timeout_seconds: timeout.unwrap_or(60),
         required,
         run_in_container: in_container,
+        run_in_ephemeral: ephemeral,
     };
 
     // Add to appropriate phase
     match phase {
+        "pre_build" | "pre-build" if ephemeral => {
+            return Err(AppError::Validation(
+                "--ephemeral needs the app image, which doesn't exist yet before the build. \
+                 Use it with pre_deploy, post_deploy or on_failure hooks."
+                    .into(),
+            ));
+        }
         "pre_build" | "pre-build" => hooks.pre_build.push(hook),
         "pre_deploy" | "pre-deploy" => hooks.pre_deploy.push(hook),
         "post_deploy" | "post-deploy" => hooks.post_deploy.push(hook),
@@ -1701,6 +2004,7 @@ pub fn build_config(
     cache_enabled: Option<bool>,
     buildkit: Option<bool>,
     cache_from: Option<&str>,
+    cache_mode: Option<&str>,
 ) -> Result<(), AppError> {
     let mut config = AppConfig::load(app)?;
 
@@ -1714,11 +2018,7 @@ pub fn build_config(
 
     // Initialize build config if not present
     if autodeploy.build.is_none() {
-        autodeploy.build = Some(BuildConfig {
-            cache_enabled: true,
-            buildkit: true,
-            cache_from: None,
-        });
+        autodeploy.build = Some(BuildConfig::default());
     }
 
     let build = autodeploy.build.as_mut().unwrap();
@@ -1749,10 +2049,18 @@ pub fn build_config(
         ui::info(&format!("Cache from: {}", from));
     }
 
+    if let Some(mode) = cache_mode {
+        build.cache_mode = BuildCacheMode::parse(mode).ok_or_else(|| {
+            AppError::Validation(format!("Unknown cache mode '{}'", mode))
+        })?;
+        ui::info(&format!("Cache mode: {}", build.cache_mode.display_name()));
+    }
+
     // Extract values for display
     let cache = build.cache_enabled;
     let bk = build.buildkit;
     let from = build.cache_from.clone();
+    let mode = build.cache_mode;
 
     config.save()?;
 
@@ -1780,6 +2088,13 @@ pub fn build_config(
         "  Cache from:    {}",
         from.as_deref().unwrap_or("(none)")
     );
+    println!("  Cache mode:    {}", mode.display_name());
+    if mode == BuildCacheMode::Local {
+        println!(
+            "  Cache dir:     {}",
+            console::style(config.build_cache_path().display()).dim()
+        );
+    }
     println!();
 
     Ok(())
@@ -1877,3 +2192,38 @@ pub fn blue_green_config(
 
     Ok(())
 }
+
+/// Stops the previous blue-green slot now instead of after `keep_old`.
+pub fn blue_green_promote(app: &str, verbose: bool) -> Result<(), AppError> {
+    let config = AppConfig::load(app)?;
+    let ctx = ExecutionContext::new(false, verbose);
+    let runtime = create_container_runtime();
+    let proxy = create_reverse_proxy();
+    let deployer = Deployer::new(&config, runtime.as_ref(), proxy.as_ref(), &ctx);
+
+    match deployer.promote_blue_green()? {
+        Some(previous) => ui::success(&format!("Promoted: removed previous slot {}", previous)),
+        None => ui::info("No previous slot to remove, the current deployment is already final"),
+    }
+
+    Ok(())
+}
+
+/// Switches traffic back to the previous blue-green slot.
+pub fn blue_green_abort(app: &str, verbose: bool) -> Result<(), AppError> {
+    let config = AppConfig::load(app)?;
+    let ctx = ExecutionContext::new(false, verbose);
+    let runtime = create_container_runtime();
+    let proxy = create_reverse_proxy();
+    let deployer = Deployer::new(&config, runtime.as_ref(), proxy.as_ref(), &ctx);
+
+    ui::warning("Traffic will switch back to the previous deployment.");
+    if !ui::confirm_action("Continue?", false)? {
+        return Ok(());
+    }
+
+    let live = deployer.abort_blue_green()?;
+    ui::success(&format!("Aborted: traffic is back on {}", live));
+
+    Ok(())
+}
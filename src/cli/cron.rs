@@ -0,0 +1,126 @@
+//! Scheduled job command handlers (`fl cron`).
+
+use console::style;
+
+use crate::core::app_config::{AppConfig, CronJob};
+use crate::core::backup::cron_to_on_calendar;
+use crate::core::context::ExecutionContext;
+use crate::core::cron::{validate_job_name, CronScheduler};
+use crate::core::deploy::Deployer;
+use crate::core::error::AppError;
+use crate::providers::{create_container_runtime, create_reverse_proxy};
+use crate::ui;
+
+/// Lists the app's scheduled jobs.
+pub fn list(app: &str) -> Result<(), AppError> {
+    let config = AppConfig::load(app)?;
+
+    if config.cron_jobs.is_empty() {
+        ui::info(&format!("No scheduled jobs for {}", app));
+        println!(
+            "  Add one with: {}",
+            style(format!("fl cron add {} <name> \"<schedule>\" \"<command>\"", app)).cyan()
+        );
+        return Ok(());
+    }
+
+    let scheduler = CronScheduler::new(&config);
+
+    ui::section(&format!("Scheduled jobs for {}", app));
+    for job in &config.cron_jobs {
+        let state = if scheduler.is_installed(&job.name) {
+            style("active").green()
+        } else {
+            style("not installed").yellow()
+        };
+        println!(
+            "  {} {} ({})",
+            style(&job.name).bold(),
+            style(&job.schedule).cyan(),
+            state
+        );
+        println!("    {}", style(&job.command).dim());
+    }
+
+    println!();
+    println!(
+        "  Logs: {}",
+        style(format!("journalctl -u '{}'", scheduler.unit_name("*"))).dim()
+    );
+
+    Ok(())
+}
+
+/// Adds a job (or replaces one with the same name) and installs its timer.
+pub fn add(app: &str, name: &str, schedule: &str, command: &str) -> Result<(), AppError> {
+    let mut config = AppConfig::load(app)?;
+
+    validate_job_name(name)?;
+    let on_calendar = cron_to_on_calendar(schedule)?;
+    if command.trim().is_empty() {
+        return Err(AppError::Validation("Job command cannot be empty".into()));
+    }
+
+    let job = CronJob {
+        name: name.to_string(),
+        schedule: schedule.to_string(),
+        command: command.to_string(),
+    };
+
+    match config.cron_jobs.iter_mut().find(|j| j.name == name) {
+        Some(existing) => {
+            if !ui::confirm_action(&format!("Job '{}' already exists. Replace it?", name), false)? {
+                return Err(AppError::Cancelled);
+            }
+            *existing = job.clone();
+        }
+        None => config.cron_jobs.push(job.clone()),
+    }
+
+    CronScheduler::new(&config).install(&job)?;
+    config.save()?;
+
+    ui::success(&format!("Scheduled {} for {} ({})", name, app, schedule));
+    ui::info(&format!("systemd OnCalendar: {}", on_calendar));
+    println!(
+        "  Run it now with: {}",
+        style(format!("fl cron run {} {}", app, name)).cyan()
+    );
+
+    Ok(())
+}
+
+/// Removes a job and its timer.
+pub fn remove(app: &str, name: &str) -> Result<(), AppError> {
+    let mut config = AppConfig::load(app)?;
+
+    if config.cron_job(name).is_none() {
+        return Err(AppError::Validation(format!(
+            "No job named '{}' for {}",
+            name, app
+        )));
+    }
+
+    CronScheduler::new(&config).uninstall(name)?;
+    config.cron_jobs.retain(|j| j.name != name);
+    config.save()?;
+
+    ui::success(&format!("Removed job {} from {}", name, app));
+    Ok(())
+}
+
+/// Runs a job once in a throwaway container. Invoked by the systemd timer.
+pub fn run(app: &str, name: &str, verbose: bool) -> Result<(), AppError> {
+    let config = AppConfig::load(app)?;
+
+    let job = config.cron_job(name).ok_or_else(|| {
+        AppError::Validation(format!("No job named '{}' for {}", name, app))
+    })?;
+
+    let ctx = ExecutionContext::new(false, verbose);
+    let runtime = create_container_runtime();
+    let proxy = create_reverse_proxy();
+    let deployer = Deployer::new(&config, runtime.as_ref(), proxy.as_ref(), &ctx);
+
+    deployer.run_cron_job(job)
+}
@@ -0,0 +1,184 @@
+//! Database command handlers.
+
+use std::path::Path;
+
+use crate::core::app_config::AppConfig;
+use crate::core::backup::DatabaseBackup;
+use crate::core::error::AppError;
+use crate::ui;
+
+/// Default retention for scheduled backups.
+pub const DEFAULT_KEEP_DAYS: u32 = 7;
+
+/// Dumps the app's database, optionally pruning old backups afterwards.
+pub fn backup(app: &str, output: Option<&Path>, keep_days: Option<u32>) -> Result<(), AppError> {
+    let config = AppConfig::load(app)?;
+    let backup = DatabaseBackup::new(&config)?;
+
+    let spinner = ui::ProgressBar::spinner(&format!("Backing up {} database", app));
+    let file = match backup.backup(output) {
+        Ok(file) => {
+            spinner.finish("done");
+            file
+        }
+        Err(e) => {
+            spinner.finish_error("failed");
+            return Err(e);
+        }
+    };
+
+    ui::success(&format!(
+        "Backup written to {} ({})",
+        file.path.display(),
+        format_size(file.size)
+    ));
+
+    if let Some(days) = keep_days {
+        let removed = backup.prune(days)?;
+        if removed > 0 {
+            ui::info(&format!(
+                "Removed {} backup(s) older than {} days",
+                removed, days
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Restores the app's database from a dump, asking first unless `force`.
+pub fn restore(app: &str, input: &Path, force: bool) -> Result<(), AppError> {
+    let config = AppConfig::load(app)?;
+    let backup = DatabaseBackup::new(&config)?;
+
+    if !input.is_file() {
+        return Err(AppError::Backup(format!(
+            "Dump file '{}' not found",
+            input.display()
+        )));
+    }
+
+    if !force {
+        ui::warning(&format!(
+            "This replaces all data in the {} database with {}.",
+            app,
+            input.display()
+        ));
+        if !ui::confirm_action("Restore the database?", false)? {
+            ui::info("Restore cancelled.");
+            return Ok(());
+        }
+    }
+
+    let spinner = ui::ProgressBar::spinner(&format!("Restoring {} database", app));
+    match backup.restore(input) {
+        Ok(()) => spinner.finish("done"),
+        Err(e) => {
+            spinner.finish_error("failed");
+            return Err(e);
+        }
+    }
+
+    ui::success(&format!("Database restored from {}", input.display()));
+    Ok(())
+}
+
+/// Opens an interactive shell on the app's database.
+pub fn connect(app: &str) -> Result<(), AppError> {
+    let config = AppConfig::load(app)?;
+    let backup = DatabaseBackup::new(&config)?;
+
+    ui::info(&format!("Connecting to {} ({})", backup.container_name(), app));
+    backup.connect()
+}
+
+/// Lists existing backups and the installed schedule.
+pub fn list(app: &str) -> Result<(), AppError> {
+    let config = AppConfig::load(app)?;
+    let backup = DatabaseBackup::new(&config)?;
+
+    println!();
+    println!("Backups for {}", console::style(app).cyan().bold());
+    println!();
+
+    match backup.scheduled_cron() {
+        Some(cron) => println!("  Schedule: {}", console::style(cron).cyan()),
+        None => println!("  Schedule: {}", console::style("none").dim()),
+    }
+    println!();
+
+    let backups = backup.list()?;
+    if backups.is_empty() {
+        println!("  {}", console::style("No backups yet").dim());
+        println!();
+        return Ok(());
+    }
+
+    for file in &backups {
+        let name = file
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        println!(
+            "  {:<40}  {:>10}  {}",
+            name,
+            format_size(file.size),
+            console::style(file.created_at.format("%Y-%m-%d %H:%M")).dim()
+        );
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Installs a recurring backup timer.
+pub fn schedule(app: &str, cron: &str, keep_days: u32) -> Result<(), AppError> {
+    let config = AppConfig::load(app)?;
+    let backup = DatabaseBackup::new(&config)?;
+
+    let on_calendar = backup.schedule(cron, keep_days)?;
+
+    ui::success(&format!("Scheduled backups for {} ({})", app, cron));
+    ui::info(&format!("systemd OnCalendar: {}", on_calendar));
+    ui::info(&format!(
+        "Dumps go to {}, keeping {} days",
+        backup.backup_dir().display(),
+        keep_days
+    ));
+
+    Ok(())
+}
+
+/// Removes the recurring backup timer.
+pub fn unschedule(app: &str) -> Result<(), AppError> {
+    let config = AppConfig::load(app)?;
+    let backup = DatabaseBackup::new(&config)?;
+
+    if backup.unschedule()? {
+        ui::success(&format!("Scheduled backups removed for {}", app));
+    } else {
+        ui::info("No backup schedule installed for this app.");
+    }
+
+    Ok(())
+}
+
+/// Formats a byte count for display.
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
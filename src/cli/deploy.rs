@@ -2,17 +2,57 @@
 
 use crate::core::app_config::AppConfig;
 use crate::core::context::ExecutionContext;
-use crate::core::deploy::{format_duration, Deployer};
+use crate::core::deploy::{format_duration, previous_depth, Deployer, DestroyPlan};
+use crate::core::deployments::DeploymentHistory;
 use crate::core::error::AppError;
-use crate::providers::{create_container_runtime, create_reverse_proxy};
+use crate::providers::git::GitProvider;
+use crate::providers::{container_cli, create_container_runtime, create_reverse_proxy};
 use crate::ui;
+use crate::utils::validate_deploy_tag;
 
 /// Executes the deploy command.
-pub fn deploy(app_name: &str, verbose: bool) -> Result<(), AppError> {
+#[allow(clippy::too_many_arguments)]
+pub fn deploy(
+    app_name: &str,
+    events: bool,
+    tag: Option<&str>,
+    replicas: Option<u32>,
+    keep_previous: Option<u32>,
+    from_branch: Option<&str>,
+    pin_base: bool,
+    verbose: bool,
+) -> Result<(), AppError> {
+    if events {
+        ui::reserve_stderr();
+    }
     ui::header();
 
     // Load app config
-    let config = AppConfig::load(app_name)?;
+    let mut config = AppConfig::load(app_name)?;
+
+    if let Some(branch) = from_branch {
+        if config.is_image_deployment() {
+            return Err(AppError::Validation(
+                "--from-branch is only supported for source deployments".into(),
+            ));
+        }
+        GitProvider::validate_branch_name(branch)?;
+    }
+
+    if pin_base && config.is_image_deployment() {
+        return Err(AppError::Validation(
+            "--pin-base is only supported for source deployments".into(),
+        ));
+    }
+
+    if let Some(tag) = tag {
+        validate_deploy_tag(tag)?;
+        if config.is_image_deployment() {
+            return Err(AppError::Validation(
+                "--tag is only supported for source deployments".into(),
+            ));
+        }
+    }
 
     // Check if server is initialized
     if !crate::core::config::ServerConfig::is_initialized() {
@@ -21,13 +61,42 @@ pub fn deploy(app_name: &str, verbose: bool) -> Result<(), AppError> {
         ));
     }
 
+    // Persist the replica count so restarts and later deploys keep it
+    if let Some(replicas) = replicas {
+        if !config.is_image_deployment() {
+            return Err(AppError::Validation(
+                "--replicas is only supported for image deployments".into(),
+            ));
+        }
+        config.replicas = if replicas > 1 { Some(replicas) } else { None };
+        config.save()?;
+    }
+
+    // Persist the rollback depth so every later build rotates the same number of points
+    if let Some(keep) = keep_previous {
+        if config.is_image_deployment() {
+            return Err(AppError::Validation(
+                "--keep-previous is only supported for source deployments".into(),
+            ));
+        }
+        config.keep_previous = if keep > 1 { Some(keep) } else { None };
+        config.save()?;
+    }
+
     let ctx = ExecutionContext::new(false, verbose);
     let runtime = create_container_runtime();
     let proxy = create_reverse_proxy();
 
     ui::section(&format!("Deploying {}", app_name));
+    if config.effective_replicas() > 1 {
+        ui::info(&format!("Running {} replicas", config.effective_replicas()));
+    }
 
-    let deployer = Deployer::new(&config, runtime.as_ref(), proxy.as_ref(), &ctx);
+    let deployer = Deployer::new(&config, runtime.as_ref(), proxy.as_ref(), &ctx)
+        .with_events(events)
+        .with_label(tag)
+        .with_branch(from_branch)
+        .with_pin_base(pin_base);
 
     match deployer.deploy() {
         Ok(result) => {
@@ -36,6 +105,14 @@ pub fn deploy(app_name: &str, verbose: bool) -> Result<(), AppError> {
                 "Deployed in {}",
                 format_duration(result.duration)
             ));
+            if let Some(tag) = tag {
+                ui::info(&format!("Tagged as {}", console::style(tag).cyan()));
+            }
+            if let Some(branch) = from_branch {
+                // Reload to keep what the deploy recorded (deployed_at, pinned digests)
+                let mut config = AppConfig::load(app_name)?;
+                watch_branch(&mut config, branch)?;
+            }
             println!();
             ui::url(&result.url);
 
@@ -50,13 +127,35 @@ pub fn deploy(app_name: &str, verbose: bool) -> Result<(), AppError> {
             ui::info(&format!("  fl logs {}", app_name));
             println!();
             ui::info("To cleanup failed deployment:");
-            ui::info(&format!("  docker rm -f flaase-{}-web", app_name));
+            ui::info(&format!("  {} rm -f flaase-{}-web", container_cli(), app_name));
 
             Err(e)
         }
     }
 }
 
+/// Points autodeploy at the branch that was just deployed.
+fn watch_branch(config: &mut AppConfig, branch: &str) -> Result<(), AppError> {
+    let Some(autodeploy) = config.autodeploy_config.as_mut() else {
+        return Ok(());
+    };
+
+    if autodeploy.branch == branch {
+        return Ok(());
+    }
+
+    let previous = std::mem::replace(&mut autodeploy.branch, branch.to_string());
+    config.save()?;
+
+    ui::info(&format!(
+        "Autodeploy now watches {} (was {})",
+        console::style(branch).cyan(),
+        previous
+    ));
+
+    Ok(())
+}
+
 /// Stops an app.
 pub fn stop(app_name: &str, verbose: bool) -> Result<(), AppError> {
     // Load app config
@@ -147,8 +246,68 @@ pub fn restart(app_name: &str, verbose: bool) -> Result<(), AppError> {
     }
 }
 
+/// Changes an app's replica count, reconciling running containers.
+pub fn scale(app_name: &str, count: u32, verbose: bool) -> Result<(), AppError> {
+    let mut config = AppConfig::load(app_name)?;
+
+    if !config.is_image_deployment() {
+        return Err(AppError::Validation(
+            "Scaling is only supported for image deployments".into(),
+        ));
+    }
+
+    let current = config.effective_replicas();
+    if current == count {
+        ui::info(&format!(
+            "{} already runs {} replica{}",
+            app_name,
+            count,
+            if count == 1 { "" } else { "s" }
+        ));
+        return Ok(());
+    }
+
+    config.replicas = if count > 1 { Some(count) } else { None };
+
+    let ctx = ExecutionContext::new(false, verbose);
+    let runtime = create_container_runtime();
+    let proxy = create_reverse_proxy();
+
+    // A stopped app picks up the new count on its next start
+    let running = runtime
+        .container_is_running(&format!("flaase-{}-web", app_name), &ctx)
+        .unwrap_or(false);
+    if !running {
+        config.save()?;
+        ui::success(&format!("{} will run {} replicas when started", app_name, count));
+        return Ok(());
+    }
+
+    ui::section(&format!("Scaling {} from {} to {}", app_name, current, count));
+
+    let deployer = Deployer::new(&config, runtime.as_ref(), proxy.as_ref(), &ctx);
+    deployer.scale(current)?;
+    config.save()?;
+
+    println!();
+    ui::success(&format!(
+        "{} now runs {} replica{}",
+        app_name,
+        count,
+        if count == 1 { "" } else { "s" }
+    ));
+
+    Ok(())
+}
+
 /// Destroys an app completely.
-pub fn destroy(app_name: &str, force: bool, mut keep_data: bool, verbose: bool) -> Result<(), AppError> {
+pub fn destroy(
+    app_name: &str,
+    force: bool,
+    mut keep_data: bool,
+    dry_run: bool,
+    verbose: bool,
+) -> Result<(), AppError> {
     ui::header();
 
     let config = AppConfig::load(app_name)?;
@@ -156,6 +315,14 @@ pub fn destroy(app_name: &str, force: bool, mut keep_data: bool, verbose: bool)
     let runtime = create_container_runtime();
     let proxy = create_reverse_proxy();
 
+    let deployer = Deployer::new(&config, runtime.as_ref(), proxy.as_ref(), &ctx);
+    let plan = deployer.destroy_plan(keep_data)?;
+
+    if dry_run {
+        print_destroy_plan(app_name, &plan);
+        return Ok(());
+    }
+
     // Check if app is currently running
     let web_container = format!("flaase-{}-web", app_name);
     let is_running = runtime.container_is_running(&web_container, &ctx).unwrap_or(false);
@@ -165,33 +332,27 @@ pub fn destroy(app_name: &str, force: bool, mut keep_data: bool, verbose: bool)
         println!();
     }
 
-    let has_database = config.database.is_some();
-    let has_cache = config.cache.is_some();
+    let has_database = config.managed_database().is_some();
+    let cache_suffixes: Vec<String> = config
+        .caches
+        .iter()
+        .map(|cache| cache.container_suffix())
+        .collect();
+    let has_cache = !cache_suffixes.is_empty();
     let has_data = has_database || has_cache;
 
     if !force {
         // Show what will be deleted
         ui::warning("This will permanently delete:");
         println!();
-        println!("  {} App container (flaase-{}-web)", console::style("•").dim(), app_name);
+        print_destroy_sections(&plan);
 
-        if has_database {
-            println!("  {} Database container (flaase-{}-db)", console::style("•").dim(), app_name);
-        }
-        if has_cache {
-            println!("  {} Cache container (flaase-{}-cache)", console::style("•").dim(), app_name);
-        }
-
-        println!("  {} Docker network", console::style("•").dim());
-        println!("  {} Traefik routing config", console::style("•").dim());
-        println!("  {} App directory (/opt/flaase/apps/{})", console::style("•").dim(), app_name);
-        println!("  {} Docker images", console::style("•").dim());
-
-        if !keep_data && has_data {
+        if !plan.volumes.is_empty() {
+            println!();
             println!(
-                "  {} {} Database/cache volumes (ALL DATA WILL BE LOST)",
-                console::style("•").red(),
-                console::style("⚠").red().bold()
+                "  {} {}",
+                console::style("⚠").red().bold(),
+                console::style("ALL DATA IN THESE VOLUMES WILL BE LOST").red()
             );
         }
 
@@ -230,18 +391,8 @@ pub fn destroy(app_name: &str, force: bool, mut keep_data: bool, verbose: bool)
 
     // Step 1: Stop containers
     let spinner = ui::ProgressBar::spinner("Stopping containers...");
-
-    let deployer = Deployer::new(&config, runtime.as_ref(), proxy.as_ref(), &ctx);
-
-    // Stop all containers
-    for container in &[
-        format!("flaase-{}-web", app_name),
-        format!("flaase-{}-db", app_name),
-        format!("flaase-{}-cache", app_name),
-    ] {
-        if runtime.container_exists(container, &ctx).unwrap_or(false) {
-            let _ = runtime.stop_container(container, &ctx);
-        }
+    for container in &plan.containers {
+        let _ = runtime.stop_container(container, &ctx);
     }
     spinner.finish("stopped");
 
@@ -272,10 +423,10 @@ pub fn destroy(app_name: &str, force: bool, mut keep_data: bool, verbose: bool)
         println!();
         ui::info("To delete remaining volumes later:");
         if has_database {
-            println!("  docker volume rm flaase-{}-db-data", app_name);
+            println!("  {} volume rm flaase-{}-db-data", runtime.command(), app_name);
         }
-        if has_cache {
-            println!("  docker volume rm flaase-{}-cache-data", app_name);
+        for suffix in &cache_suffixes {
+            println!("  {} volume rm flaase-{}-{}-data", runtime.command(), app_name, suffix);
         }
     } else {
         ui::success(&format!("App '{}' has been completely destroyed", app_name));
@@ -284,8 +435,38 @@ pub fn destroy(app_name: &str, force: bool, mut keep_data: bool, verbose: bool)
     Ok(())
 }
 
+/// Prints what `fl destroy` would remove.
+fn print_destroy_plan(app_name: &str, plan: &DestroyPlan) {
+    ui::info(&format!("Dry run: 'fl destroy {}' would remove:", app_name));
+    println!();
+    print_destroy_sections(plan);
+    println!();
+    ui::info("Nothing was removed.");
+}
+
+/// Prints the resources of a destroy plan, grouped by kind.
+fn print_destroy_sections(plan: &DestroyPlan) {
+    let section = |title: &str, items: &[String]| {
+        println!("  {}", console::style(title).bold());
+        if items.is_empty() {
+            println!("    {}", console::style("none").dim());
+        }
+        for item in items {
+            println!("    {} {}", console::style("•").dim(), item);
+        }
+    };
+
+    section("Containers", &plan.containers);
+    section("Volumes", &plan.volumes);
+    section("Network", plan.network.as_slice());
+    section("Cron timers", &plan.cron_timers);
+    section("Traefik config", std::slice::from_ref(&plan.proxy_config));
+    section("Images", &plan.images);
+    section("App directory", &[plan.app_dir.display().to_string()]);
+}
+
 /// Updates a deployed app with zero-downtime.
-pub fn update(app_name: &str, verbose: bool) -> Result<(), AppError> {
+pub fn update(app_name: &str, git_tag: Option<&str>, verbose: bool) -> Result<(), AppError> {
     ui::header();
 
     // Load app config
@@ -302,53 +483,19 @@ pub fn update(app_name: &str, verbose: bool) -> Result<(), AppError> {
     let runtime = create_container_runtime();
     let proxy = create_reverse_proxy();
 
-    ui::section(&format!("Updating {}", app_name));
+    match git_tag {
+        Some(tag) => ui::section(&format!("Updating {} to {}", app_name, tag)),
+        None => ui::section(&format!("Updating {}", app_name)),
+    }
     println!();
 
-    let deployer = Deployer::new(&config, runtime.as_ref(), proxy.as_ref(), &ctx);
+    let deployer = Deployer::new(&config, runtime.as_ref(), proxy.as_ref(), &ctx)
+        .with_git_tag(git_tag);
 
     match deployer.update() {
         Ok(result) => {
             println!();
-
-            // Show commit change
-            if result.had_changes {
-                if let Some(old) = &result.old_commit {
-                    let old_short = if old.len() >= 7 { &old[..7] } else { old };
-                    let new_short = if result.new_commit.len() >= 7 {
-                        &result.new_commit[..7]
-                    } else {
-                        &result.new_commit
-                    };
-                    ui::info(&format!(
-                        "Updated: {} → {}",
-                        console::style(old_short).dim(),
-                        console::style(new_short).green()
-                    ));
-                } else {
-                    let new_short = if result.new_commit.len() >= 7 {
-                        &result.new_commit[..7]
-                    } else {
-                        &result.new_commit
-                    };
-                    ui::info(&format!("Deployed: {}", console::style(new_short).green()));
-                }
-            } else {
-                let commit_short = if result.new_commit.len() >= 7 {
-                    &result.new_commit[..7]
-                } else {
-                    &result.new_commit
-                };
-                ui::info(&format!(
-                    "Already up-to-date at {}",
-                    console::style(commit_short).cyan()
-                ));
-            }
-
-            ui::success(&format!(
-                "Updated in {}",
-                format_duration(result.duration)
-            ));
+            ui::success(&result.summary());
             println!();
             ui::url(&result.url);
 
@@ -370,6 +517,15 @@ pub fn update(app_name: &str, verbose: bool) -> Result<(), AppError> {
     }
 }
 
+/// Shortens a commit message to a maximum number of characters.
+fn truncate_message(message: &str, max: usize) -> String {
+    if message.chars().count() > max {
+        format!("{}...", message.chars().take(max).collect::<String>())
+    } else {
+        message.to_string()
+    }
+}
+
 /// Rolls back to a previous deployment.
 pub fn rollback(app_name: &str, target: Option<&str>, list: bool, verbose: bool) -> Result<(), AppError> {
     ui::header();
@@ -405,16 +561,63 @@ pub fn rollback(app_name: &str, target: Option<&str>, list: bool, verbose: bool)
             }
         );
 
+        let history = DeploymentHistory::load(&config.deployments_path()).unwrap_or_default();
+        let width = versions.iter().map(|v| v.len()).max().unwrap_or(7).max(7);
+
         for version in &versions {
-            if version != "previous" {
-                println!("  {}  {}", console::style("●").cyan(), version);
+            if version == "previous" {
+                continue;
             }
+
+            let context = match history.find_version(version) {
+                Some(record) => {
+                    let mut context = format!(
+                        "{}  {}",
+                        console::style(record.timestamp.format("%Y-%m-%d %H:%M")).dim(),
+                        truncate_message(&record.commit_message, 50)
+                    );
+                    if record.triggered_by != "cli" && !record.triggered_by.is_empty() {
+                        context.push_str(&format!(
+                            " {}",
+                            console::style(format!("by @{}", record.triggered_by)).dim()
+                        ));
+                    }
+                    if let Some(ref label) = record.label {
+                        if label != version {
+                            context.push_str(&format!(
+                                " {}",
+                                console::style(format!("[{}]", label)).cyan()
+                            ));
+                        }
+                    }
+                    context
+                }
+                None if previous_depth(version).is_some() => {
+                    console::style("rollback point").dim().to_string()
+                }
+                None => console::style("no deployment record").dim().to_string(),
+            };
+
+            println!(
+                "  {}  {:<width$}  {}",
+                console::style("●").cyan(),
+                version,
+                context,
+                width = width
+            );
         }
 
         println!();
         ui::info("Usage:");
         ui::info(&format!("  fl rollback {}              # Rollback to previous", app_name));
         ui::info(&format!("  fl rollback {} --to <sha>   # Rollback to specific version", app_name));
+        ui::info(&format!("  fl rollback {} --to <tag>   # Rollback to a tagged deploy", app_name));
+        if config.effective_keep_previous() > 1 {
+            ui::info(&format!(
+                "  fl rollback {} --to previous-2  # Rollback two deploys",
+                app_name
+            ));
+        }
 
         return Ok(());
     }
@@ -434,7 +637,7 @@ pub fn rollback(app_name: &str, target: Option<&str>, list: bool, verbose: bool)
     ));
     println!();
 
-    let confirm = ui::confirm("Continue with rollback?", true)?;
+    let confirm = ui::confirm_action("Continue with rollback?", true)?;
 
     if !confirm {
         return Err(AppError::Cancelled);
@@ -451,7 +654,7 @@ pub fn rollback(app_name: &str, target: Option<&str>, list: bool, verbose: bool)
                 target_display
             ));
             println!();
-            ui::url(&format!("https://{}", config.primary_domain()));
+            ui::url(&config.public_url());
             Ok(())
         }
         Err(e) => {
@@ -0,0 +1,301 @@
+//! Doctor command: diagnoses common server problems and repairs them.
+
+use std::path::Path;
+
+use console::style;
+
+use crate::core::app_config::AppConfig;
+use crate::core::config::{ServerConfig, FLAASE_TRAEFIK_DYNAMIC_PATH};
+use crate::core::context::ExecutionContext;
+use crate::core::deploy::Deployer;
+use crate::core::error::AppError;
+use crate::providers::container::{create_container_runtime, ContainerRuntime};
+use crate::providers::reverse_proxy::{find_invalid_dynamic_configs, TraefikProxy};
+use crate::providers::ReverseProxy;
+use crate::ui;
+
+/// Network shared by Traefik and every app's web containers.
+const PROXY_NETWORK: &str = "flaase-network";
+
+/// Number of diagnose/fix rounds; fixing Docker can reveal further problems.
+const MAX_FIX_PASSES: usize = 3;
+
+/// A problem found on the server.
+struct Issue {
+    description: String,
+    fix: Option<Fix>,
+}
+
+/// A safe, non-destructive remediation.
+enum Fix {
+    StartRuntime,
+    CreateNetwork,
+    StartTraefik,
+    ConnectNetwork(String),
+    SyncRouting(String),
+}
+
+impl Fix {
+    fn describe(&self) -> String {
+        match self {
+            Fix::StartRuntime => "Start the container runtime service".to_string(),
+            Fix::CreateNetwork => format!("Create the {} network", PROXY_NETWORK),
+            Fix::StartTraefik => "Start the Traefik container".to_string(),
+            Fix::ConnectNetwork(container) => {
+                format!("Connect {} to {}", container, PROXY_NETWORK)
+            }
+            Fix::SyncRouting(app) => format!("Regenerate the Traefik config for {}", app),
+        }
+    }
+}
+
+/// Main doctor command handler. Returns the process exit code.
+pub fn doctor(fix: bool, verbose: bool) -> Result<i32, AppError> {
+    if !ServerConfig::is_initialized() {
+        ui::error("Server not initialized");
+        ui::info("Run 'fl server init' to set up this server");
+        return Ok(1);
+    }
+
+    let ctx = ExecutionContext::new(false, verbose);
+    let runtime = create_container_runtime();
+    let proxy = TraefikProxy::new();
+
+    let mut issues = diagnose(&*runtime, &proxy, &ctx);
+
+    if fix {
+        for _ in 0..MAX_FIX_PASSES {
+            if !issues.iter().any(|issue| issue.fix.is_some()) {
+                break;
+            }
+
+            print_issues(&issues);
+            if apply_fixes(&issues, &*runtime, &proxy, &ctx)? == 0 {
+                break;
+            }
+            issues = diagnose(&*runtime, &proxy, &ctx);
+        }
+    }
+
+    if issues.is_empty() {
+        ui::success("No problems found");
+        return Ok(0);
+    }
+
+    print_issues(&issues);
+
+    if !fix && issues.iter().any(|issue| issue.fix.is_some()) {
+        println!();
+        ui::info("Run 'fl doctor --fix' to repair what can be fixed automatically");
+    }
+
+    Ok(1)
+}
+
+/// Runs every check and collects the problems found.
+fn diagnose(
+    runtime: &dyn ContainerRuntime,
+    proxy: &TraefikProxy,
+    ctx: &ExecutionContext,
+) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    if !runtime.is_running(ctx).unwrap_or(false) {
+        issues.push(Issue {
+            description: format!("{} is not running", runtime.name()),
+            fix: Some(Fix::StartRuntime),
+        });
+        // Everything else needs the container runtime
+        return issues;
+    }
+
+    if !runtime.network_exists(PROXY_NETWORK, ctx).unwrap_or(false) {
+        issues.push(Issue {
+            description: format!("Network {} is missing", PROXY_NETWORK),
+            fix: Some(Fix::CreateNetwork),
+        });
+    }
+
+    if !proxy.is_running(runtime, ctx).unwrap_or(false) {
+        if proxy.is_installed(runtime, ctx).unwrap_or(false) {
+            issues.push(Issue {
+                description: "Traefik is stopped".to_string(),
+                fix: Some(Fix::StartTraefik),
+            });
+        } else {
+            issues.push(Issue {
+                description: "Traefik container is missing (run 'fl server init')".to_string(),
+                fix: None,
+            });
+        }
+    }
+
+    let dynamic_dir = Path::new(FLAASE_TRAEFIK_DYNAMIC_PATH);
+    let invalid_configs = find_invalid_dynamic_configs(dynamic_dir);
+
+    for name in AppConfig::list_all().unwrap_or_default() {
+        let Ok(config) = AppConfig::load(&name) else {
+            issues.push(Issue {
+                description: format!("{}: config.yml cannot be loaded", name),
+                fix: None,
+            });
+            continue;
+        };
+
+        if config.deployed_at.is_none() {
+            continue;
+        }
+
+        for (container, networks) in web_containers(&name, runtime, ctx) {
+            if !networks.iter().any(|n| n == PROXY_NETWORK) {
+                issues.push(Issue {
+                    description: format!(
+                        "{}: {} is detached from {}",
+                        name, container, PROXY_NETWORK
+                    ),
+                    fix: Some(Fix::ConnectNetwork(container)),
+                });
+            }
+        }
+
+        let traefik_config = dynamic_dir.join(format!("{}.yml", name));
+        if !traefik_config.exists() {
+            issues.push(Issue {
+                description: format!("{}: Traefik config is missing", name),
+                fix: Some(Fix::SyncRouting(name.clone())),
+            });
+        } else if invalid_configs.iter().any(|c| c.path == traefik_config) {
+            issues.push(Issue {
+                description: format!("{}: Traefik config is not valid YAML", name),
+                fix: Some(Fix::SyncRouting(name.clone())),
+            });
+        }
+    }
+
+    // Broken files that don't belong to an app can't be regenerated
+    let app_config_names: Vec<String> = AppConfig::list_all()
+        .unwrap_or_default()
+        .iter()
+        .map(|name| format!("{}.yml", name))
+        .collect();
+    for invalid in &invalid_configs {
+        let file_name = invalid
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if !app_config_names.contains(&file_name) {
+            issues.push(Issue {
+                description: format!(
+                    "{} is not valid YAML: {}",
+                    invalid.path.display(),
+                    invalid.error
+                ),
+                fix: None,
+            });
+        }
+    }
+
+    issues
+}
+
+/// Lists an app's web containers with the networks they are attached to.
+fn web_containers(
+    app: &str,
+    runtime: &dyn ContainerRuntime,
+    ctx: &ExecutionContext,
+) -> Vec<(String, Vec<String>)> {
+    let output = ctx.run_command(
+        runtime.command(),
+        &[
+            "ps",
+            "-a",
+            "--filter",
+            &format!("label=flaase.app={}", app),
+            "--filter",
+            "label=flaase.service=web",
+            "--format",
+            "{{.Names}}|{{.Networks}}",
+        ],
+    );
+
+    let Ok(output) = output else {
+        return Vec::new();
+    };
+
+    output
+        .stdout
+        .lines()
+        .filter_map(|line| line.split_once('|'))
+        .map(|(name, networks)| {
+            let networks = networks
+                .split(',')
+                .map(|n| n.trim().to_string())
+                .filter(|n| !n.is_empty())
+                .collect();
+            (name.to_string(), networks)
+        })
+        .collect()
+}
+
+/// Prints the problems found.
+fn print_issues(issues: &[Issue]) {
+    ui::section("Problems");
+
+    for issue in issues {
+        let hint = if issue.fix.is_some() {
+            style("(fixable)").dim().to_string()
+        } else {
+            String::new()
+        };
+        println!("  {} {} {}", style("✗").red(), issue.description, hint);
+    }
+}
+
+/// Applies the fixes the user confirms. Returns how many succeeded.
+fn apply_fixes(
+    issues: &[Issue],
+    runtime: &dyn ContainerRuntime,
+    proxy: &TraefikProxy,
+    ctx: &ExecutionContext,
+) -> Result<usize, AppError> {
+    let mut applied = 0;
+
+    println!();
+    for fix in issues.iter().filter_map(|issue| issue.fix.as_ref()) {
+        if !ui::confirm_action(&format!("{}?", fix.describe()), true)? {
+            continue;
+        }
+
+        match apply_fix(fix, runtime, proxy, ctx) {
+            Ok(()) => {
+                ui::success(&fix.describe());
+                applied += 1;
+            }
+            Err(e) => ui::error(&format!("{} failed: {}", fix.describe(), e)),
+        }
+    }
+
+    Ok(applied)
+}
+
+/// Applies a single fix.
+fn apply_fix(
+    fix: &Fix,
+    runtime: &dyn ContainerRuntime,
+    proxy: &TraefikProxy,
+    ctx: &ExecutionContext,
+) -> Result<(), AppError> {
+    match fix {
+        Fix::StartRuntime => runtime.start_service(ctx),
+        Fix::CreateNetwork => runtime.create_network(PROXY_NETWORK, ctx),
+        Fix::StartTraefik => ctx
+            .run_command(runtime.command(), &["start", proxy.container_name()])?
+            .ensure_success("Failed to start Traefik"),
+        Fix::ConnectNetwork(container) => runtime.connect_network(container, PROXY_NETWORK, ctx),
+        Fix::SyncRouting(app) => {
+            let config = AppConfig::load(app)?;
+            Deployer::new(&config, runtime, proxy, ctx).sync_routing()
+        }
+    }
+}
@@ -1,15 +1,19 @@
 //! Domain management command handlers.
 
 use std::net::ToSocketAddrs;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
 
-use crate::core::app_config::AppConfig;
+use crate::core::app_config::{AppConfig, DomainConfig};
+use crate::core::certs::{domain_matches, CertificateStore};
 use crate::core::context::ExecutionContext;
+use crate::core::deploy::Deployer;
 use crate::core::error::AppError;
 use crate::core::secrets::SecretsManager;
-use crate::core::FLAASE_TRAEFIK_DYNAMIC_PATH;
-use crate::templates::traefik::{generate_app_config, AppDomain};
+use crate::providers::{create_container_runtime, create_reverse_proxy};
 use crate::ui;
-use crate::utils::validate_domain;
+use crate::utils::{validate_domain, validate_path_prefix};
 
 /// Lists all domains configured for an app.
 pub fn list(app: &str) -> Result<(), AppError> {
@@ -28,7 +32,7 @@ pub fn list(app: &str) -> Result<(), AppError> {
     let max_domain_width = config
         .domains
         .iter()
-        .map(|d| d.domain.len())
+        .map(|d| display_domain(d).len())
         .max()
         .unwrap_or(6)
         .max(6);
@@ -65,7 +69,7 @@ pub fn list(app: &str) -> Result<(), AppError> {
 
         println!(
             "  {:<width$}   {:<12} {}{}",
-            domain_config.domain,
+            display_domain(domain_config),
             ssl,
             status,
             auth_indicator,
@@ -78,20 +82,28 @@ pub fn list(app: &str) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Domain with its path prefix, as shown in listings.
+fn display_domain(domain: &DomainConfig) -> String {
+    format!("{}{}", domain.domain, domain.path_prefix.as_deref().unwrap_or(""))
+}
+
 /// Adds a domain to an app.
-pub fn add(app: &str, domain: &str, skip_dns_check: bool) -> Result<(), AppError> {
+pub fn add(
+    app: &str,
+    domain: &str,
+    path_prefix: Option<&str>,
+    skip_dns_check: bool,
+) -> Result<(), AppError> {
     // Validate domain format
     validate_domain(domain)?;
+    let path_prefix = path_prefix.map(normalize_path_prefix);
+    if let Some(ref prefix) = path_prefix {
+        validate_path_prefix(prefix)?;
+    }
 
     let mut config = AppConfig::load(app)?;
 
-    // Check if domain already exists
-    if config.domains.iter().any(|d| d.domain == domain) {
-        return Err(AppError::Validation(format!(
-            "Domain '{}' is already configured for this app",
-            domain
-        )));
-    }
+    ensure_domain_not_configured(&config, domain)?;
 
     println!();
 
@@ -118,21 +130,77 @@ pub fn add(app: &str, domain: &str, skip_dns_check: bool) -> Result<(), AppError
     // Add domain to config
     ui::step("Adding domain to configuration...");
     config.add_domain(domain);
+    if let Some(added) = config.domains.iter_mut().find(|d| d.domain == domain) {
+        added.path_prefix = path_prefix.clone();
+    }
     config.save()?;
 
     // Regenerate Traefik config
     ui::step("Configuring routing...");
     regenerate_traefik_config(&config)?;
 
-    ui::step("Requesting SSL certificate...");
-    ui::info("SSL certificate will be automatically issued by Let's Encrypt on first request");
+    match CertificateStore::load().ok().as_ref().and_then(|c| c.find_for(domain)) {
+        Some(cert) => {
+            ui::info(&format!("Using imported certificate '{}'", cert.name));
+        }
+        None => {
+            ui::step("Requesting SSL certificate...");
+            ui::info("SSL certificate will be automatically issued by Let's Encrypt on first request");
+        }
+    }
+
+    println!();
+    ui::success(&format!(
+        "Domain added: https://{}{}",
+        domain,
+        path_prefix.as_deref().unwrap_or("")
+    ));
+
+    Ok(())
+}
+
+/// Sets or clears the path prefix an app is routed under on one of its domains.
+pub fn path_prefix(app: &str, domain: &str, prefix: Option<&str>) -> Result<(), AppError> {
+    let prefix = prefix.map(normalize_path_prefix);
+    if let Some(ref prefix) = prefix {
+        validate_path_prefix(prefix)?;
+    }
+
+    let mut config = AppConfig::load(app)?;
+    let domain_config = config
+        .domains
+        .iter_mut()
+        .find(|d| d.domain == domain)
+        .ok_or_else(|| {
+            AppError::Validation(format!(
+                "Domain '{}' is not configured for app '{}'",
+                domain, app
+            ))
+        })?;
+
+    domain_config.path_prefix = prefix;
+    let url = domain_config.url();
+    config.save()?;
+
+    ui::step("Updating routing configuration...");
+    regenerate_traefik_config(&config)?;
 
     println!();
-    ui::success(&format!("Domain added: https://{}", domain));
+    ui::success(&format!("{} is now served at {}", app, url));
 
     Ok(())
 }
 
+/// Trims trailing slashes so "/api/" and "/api" route the same way.
+fn normalize_path_prefix(prefix: &str) -> String {
+    let trimmed = prefix.trim().trim_end_matches('/');
+    if trimmed.is_empty() {
+        prefix.trim().to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
 /// Removes a domain from an app.
 pub fn remove(app: &str, domain: &str) -> Result<(), AppError> {
     let mut config = AppConfig::load(app)?;
@@ -158,7 +226,7 @@ pub fn remove(app: &str, domain: &str) -> Result<(), AppError> {
     println!();
 
     // Confirm removal
-    if !ui::confirm(&format!("Remove domain '{}'?", domain), false)? {
+    if !ui::confirm_action(&format!("Remove domain '{}'?", domain), false)? {
         return Err(AppError::Cancelled);
     }
 
@@ -185,58 +253,274 @@ pub fn remove(app: &str, domain: &str) -> Result<(), AppError> {
     Ok(())
 }
 
-/// Verifies that a domain's DNS points to this server.
-fn verify_dns(domain: &str) -> Result<(), AppError> {
-    // Try to resolve the domain
-    let addr = format!("{}:80", domain);
-    let resolved = addr.to_socket_addrs().map_err(|e| {
-        AppError::Validation(format!(
-            "Could not resolve domain '{}': {}. Ensure DNS A record is configured.",
-            domain, e
-        ))
-    })?;
-
-    let ips: Vec<_> = resolved.map(|a| a.ip()).collect();
-    if ips.is_empty() {
-        return Err(AppError::Validation(format!(
-            "Domain '{}' does not resolve to any IP address",
-            domain
-        )));
+/// Imports a TLS certificate and reroutes every app domain it covers.
+pub fn cert_import(name: &str, cert: &str, key: &str) -> Result<(), AppError> {
+    let ctx = ExecutionContext::new(false, false);
+    let mut store = CertificateStore::load()?;
+
+    ui::step("Importing certificate...");
+    let imported = store.import(name, Path::new(cert), Path::new(key), &ctx)?;
+    ui::success(&format!(
+        "Certificate '{}' imported for: {}",
+        imported.name,
+        imported.domains.join(", ")
+    ));
+
+    let patterns = imported.domains.clone();
+
+    ui::step("Updating routing configuration...");
+    let updated = regenerate_covered_traefik_configs(&patterns)?;
+
+    println!();
+    if updated.is_empty() {
+        ui::info("No configured domain is covered by this certificate yet");
+    } else {
+        ui::success(&format!("Now served with this certificate: {}", updated.join(", ")));
+    }
+
+    Ok(())
+}
+
+/// Lists imported TLS certificates.
+pub fn cert_list() -> Result<(), AppError> {
+    let store = CertificateStore::load()?;
+
+    println!();
+    if store.certificates.is_empty() {
+        ui::info("No imported certificates. Domains use Let's Encrypt.");
+        return Ok(());
+    }
+
+    println!(
+        "  {}  {}  {}",
+        console::style(format!("{:<20}", "NAME")).dim(),
+        console::style(format!("{:<16}", "IMPORTED")).dim(),
+        console::style("DOMAINS").dim()
+    );
+    for cert in &store.certificates {
+        println!(
+            "  {:<20}  {}  {}",
+            cert.name,
+            console::style(cert.imported_at.format("%Y-%m-%d %H:%M")).dim(),
+            cert.domains.join(", ")
+        );
     }
+    println!();
 
-    // Note: We don't verify the IP matches our server as that requires
-    // knowing the server's public IP which can be complex in various network setups
     Ok(())
 }
 
-/// Regenerates the Traefik configuration for all domains of an app.
-fn regenerate_traefik_config(config: &AppConfig) -> Result<(), AppError> {
+/// Removes an imported TLS certificate; covered domains fall back to Let's Encrypt.
+pub fn cert_remove(name: &str) -> Result<(), AppError> {
     let ctx = ExecutionContext::new(false, false);
+    let mut store = CertificateStore::load()?;
 
-    // Load secrets for auth info
-    let secrets = SecretsManager::load_secrets(&config.secrets_path()).ok();
+    let patterns = match store.certificates.iter().find(|c| c.name == name) {
+        Some(cert) => cert.domains.clone(),
+        None => {
+            return Err(AppError::Validation(format!(
+                "Certificate '{}' not found",
+                name
+            )));
+        }
+    };
+
+    if !ui::confirm_action(&format!("Remove certificate '{}'?", name), false)? {
+        return Err(AppError::Cancelled);
+    }
 
-    // Build domain list with auth info
-    let mut domains = Vec::new();
+    store.remove(name, &ctx)?;
 
-    for domain_config in &config.domains {
-        let mut app_domain = AppDomain::new(&domain_config.domain, domain_config.primary);
+    ui::step("Updating routing configuration...");
+    regenerate_covered_traefik_configs(&patterns)?;
+
+    println!();
+    ui::success(&format!("Certificate removed: {}", name));
+
+    Ok(())
+}
+
+/// Regenerates routing for apps with a domain matched by the given
+/// certificate names and returns those domains. Stopped apps keep their
+/// maintenance routing.
+fn regenerate_covered_traefik_configs(patterns: &[String]) -> Result<Vec<String>, AppError> {
+    let mut covered = Vec::new();
+
+    for app in AppConfig::list_all()? {
+        let config = match AppConfig::load(&app) {
+            Ok(config) => config,
+            Err(_) => continue,
+        };
+
+        let matched: Vec<String> = config
+            .domains
+            .iter()
+            .filter(|d| {
+                patterns.iter().any(|p| {
+                    domain_matches(p, &d.domain) || domain_matches(p, &format!("www.{}", d.domain))
+                })
+            })
+            .map(|d| d.domain.clone())
+            .collect();
+
+        if matched.is_empty() {
+            continue;
+        }
+
+        regenerate_traefik_config(&config)?;
+        covered.extend(matched);
+    }
+
+    Ok(covered)
+}
+
+/// Rejects a domain that is already configured for the app.
+fn ensure_domain_not_configured(config: &AppConfig, domain: &str) -> Result<(), AppError> {
+    if config.domains.iter().any(|d| d.domain == domain) {
+        return Err(AppError::Validation(format!(
+            "Domain '{}' is already configured for this app",
+            domain
+        )));
+    }
+    Ok(())
+}
+
+/// Number of resolution attempts before a domain is reported as unresolvable.
+const DNS_ATTEMPTS: u32 = 4;
 
-        // Add auth if configured
-        if let Some(ref secrets) = secrets {
-            if let Some(auth_secret) = secrets.auth.get(&domain_config.domain) {
-                app_domain = app_domain.with_auth(&auth_secret.password_hash);
+/// Delay before the first DNS retry; doubled after each failed attempt.
+const DNS_INITIAL_BACKOFF: Duration = Duration::from_secs(2);
+
+/// Verifies that a domain's DNS points to this server.
+///
+/// Freshly created records often take a few seconds to propagate, so
+/// resolution is retried with exponential backoff before giving up.
+fn verify_dns(domain: &str) -> Result<(), AppError> {
+    let addr = format!("{}:80", domain);
+    let mut backoff = DNS_INITIAL_BACKOFF;
+    let mut attempt = 1;
+
+    loop {
+        let result = addr
+            .to_socket_addrs()
+            .map(|resolved| resolved.map(|a| a.ip()).collect::<Vec<_>>());
+
+        match result {
+            // Note: We don't verify the IP matches our server as that requires
+            // knowing the server's public IP which can be complex in various network setups
+            Ok(ips) if !ips.is_empty() => return Ok(()),
+            _ if attempt < DNS_ATTEMPTS => {
+                ui::info(&format!(
+                    "{} does not resolve yet, retrying in {}s ({}/{})",
+                    domain,
+                    backoff.as_secs(),
+                    attempt,
+                    DNS_ATTEMPTS - 1
+                ));
+                thread::sleep(backoff);
+                backoff *= 2;
+                attempt += 1;
+            }
+            Ok(_) => {
+                return Err(AppError::Validation(format!(
+                    "Domain '{}' does not resolve to any IP address",
+                    domain
+                )));
+            }
+            Err(e) => {
+                return Err(AppError::Validation(format!(
+                    "Could not resolve domain '{}': {}. Ensure DNS A record is configured.",
+                    domain, e
+                )));
             }
         }
+    }
+}
+
+/// Regenerates an app's Traefik configuration, undoing manual edits or deletion.
+pub fn sync(app: &str) -> Result<(), AppError> {
+    let config = AppConfig::load(app)?;
+    let ctx = ExecutionContext::new(false, false);
+    let runtime = create_container_runtime();
+    let proxy = create_reverse_proxy();
+
+    let spinner = ui::ProgressBar::spinner("Regenerating Traefik configuration");
+    let deployer = Deployer::new(&config, runtime.as_ref(), proxy.as_ref(), &ctx);
 
-        domains.push(app_domain);
+    match deployer.sync_routing() {
+        Ok(()) => {
+            spinner.finish("done");
+            ui::success(&format!("Traefik configuration for {} is up to date", app));
+            Ok(())
+        }
+        Err(e) => {
+            spinner.finish("failed");
+            Err(e)
+        }
+    }
+}
+
+/// Prints the Traefik configuration Flaase would write for an app.
+pub fn config(app: &str) -> Result<(), AppError> {
+    let config = AppConfig::load(app)?;
+
+    if config.domains.is_empty() {
+        return Err(AppError::Config(format!("App '{}' has no domains", app)));
     }
 
-    // Generate and write Traefik config
-    let traefik_config = generate_app_config(&config.name, &domains, config.effective_port());
-    let traefik_path = format!("{}/{}.yml", FLAASE_TRAEFIK_DYNAMIC_PATH, config.name);
+    let ctx = ExecutionContext::new(false, false);
+    let runtime = create_container_runtime();
+    let proxy = create_reverse_proxy();
+    let deployer = Deployer::new(&config, runtime.as_ref(), proxy.as_ref(), &ctx);
 
-    ctx.write_file(&traefik_path, &traefik_config)?;
+    // Raw YAML on stdout so it can be piped or diffed
+    print!("{}", deployer.routing_config()?);
 
     Ok(())
 }
+
+/// Regenerates the Traefik configuration for all domains of an app. The
+/// deployer writes it, so replicas and the live blue-green slot keep
+/// receiving traffic.
+pub(crate) fn regenerate_traefik_config(config: &AppConfig) -> Result<(), AppError> {
+    let ctx = ExecutionContext::new(false, false);
+    let runtime = create_container_runtime();
+    let proxy = create_reverse_proxy();
+
+    Deployer::new(config, runtime.as_ref(), proxy.as_ref(), &ctx).sync_routing()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::core::app_config::Stack;
+
+    #[test]
+    fn test_add_duplicate_domain_is_rejected() {
+        let mut config = AppConfig::new_source(
+            "myapp".into(),
+            "git@github.com:me/myapp.git".into(),
+            PathBuf::from("/root/.ssh/id_ed25519_flaase"),
+            Stack::NodeJs,
+            None,
+            "myapp.example.com".into(),
+            None,
+            None,
+            None,
+            false,
+        );
+        config.add_domain("extra.com");
+
+        assert!(ensure_domain_not_configured(&config, "other.com").is_ok());
+        assert!(matches!(
+            ensure_domain_not_configured(&config, "extra.com"),
+            Err(AppError::Validation(_))
+        ));
+        assert!(matches!(
+            ensure_domain_not_configured(&config, "myapp.example.com"),
+            Err(AppError::Validation(_))
+        ));
+    }
+}
@@ -1,16 +1,17 @@
 //! Environment variable command handlers.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use crate::core::app_config::AppConfig;
+use crate::core::app_config::{AppConfig, SecretsFileConfig};
 use crate::core::env::{EnvManager, EnvSource};
+use crate::core::env_crypto::{is_encrypted, EnvCipher};
 use crate::core::error::AppError;
 use crate::core::FLAASE_APPS_PATH;
 use crate::ui;
 
 /// Gets the env file path for a specific environment.
-fn get_env_path(app_dir: &PathBuf, environment: Option<&str>) -> PathBuf {
+fn get_env_path(app_dir: &Path, environment: Option<&str>) -> PathBuf {
     let env = environment.unwrap_or("production");
     if env == "production" || env.is_empty() {
         app_dir.join(".env")
@@ -19,8 +20,14 @@ fn get_env_path(app_dir: &PathBuf, environment: Option<&str>) -> PathBuf {
     }
 }
 
-/// Lists environment variables for an app.
-pub fn list(app: &str, show_values: bool, environment: Option<&str>) -> Result<(), AppError> {
+/// Lists environment variables for an app. Encrypted variables stay masked
+/// unless named in `show_keys`.
+pub fn list(
+    app: &str,
+    show_values: bool,
+    show_keys: &[String],
+    environment: Option<&str>,
+) -> Result<(), AppError> {
     let app_dir = get_app_dir(app)?;
     let env_name = environment.unwrap_or("production");
     let env_path = get_env_path(&app_dir, environment);
@@ -40,8 +47,15 @@ pub fn list(app: &str, show_values: bool, environment: Option<&str>) -> Result<(
         return Ok(());
     }
 
-    // If --show, ask for confirmation
-    if show_values {
+    if let Some(key) = show_keys.iter().find(|k| !vars.iter().any(|v| &v.key == *k)) {
+        return Err(AppError::Validation(format!(
+            "Variable '{}' not found in {}",
+            key, env_name
+        )));
+    }
+
+    // If --show or --show-key, ask for confirmation
+    if show_values || !show_keys.is_empty() {
         ui::warning("Values will be displayed in plain text.");
         let confirm = ui::confirm("Are you sure?", false)?;
         if !confirm {
@@ -50,16 +64,32 @@ pub fn list(app: &str, show_values: bool, environment: Option<&str>) -> Result<(
         println!();
     }
 
+    // Decrypt only the explicitly requested encrypted variables
+    let cipher = if vars.iter().any(|v| v.is_encrypted() && show_keys.contains(&v.key)) {
+        Some(EnvCipher::load()?)
+    } else {
+        None
+    };
+    let revealed = |var: &crate::core::env::EnvVar| -> Result<Option<String>, AppError> {
+        if show_keys.contains(&var.key) {
+            return match (&cipher, var.is_encrypted()) {
+                (Some(cipher), true) => cipher.decrypt(&var.value).map(Some),
+                _ => Ok(Some(var.value.clone())),
+            };
+        }
+        Ok((show_values && !var.is_encrypted()).then(|| var.value.clone()))
+    };
+    let values = vars
+        .iter()
+        .map(|var| Ok(revealed(var)?.unwrap_or_else(|| var.masked_value())))
+        .collect::<Result<Vec<String>, AppError>>()?;
+
     println!("Environment variables for {} ({}):", app, console::style(env_name).cyan());
     println!();
 
     // Calculate column widths
     let max_key_len = vars.iter().map(|v| v.key.len()).max().unwrap_or(0).max(4);
-    let max_val_len = if show_values {
-        vars.iter().map(|v| v.value.len()).max().unwrap_or(0).max(5)
-    } else {
-        22
-    };
+    let max_val_len = values.iter().map(|v| v.chars().count()).max().unwrap_or(0).max(5);
 
     // Header
     println!(
@@ -72,15 +102,10 @@ pub fn list(app: &str, show_values: bool, environment: Option<&str>) -> Result<(
     println!("  {}", "─".repeat(max_key_len + max_val_len + 12));
 
     // Variables
-    for var in &vars {
-        let value = if show_values {
-            var.value.clone()
-        } else {
-            var.masked_value()
-        };
-
+    for (var, value) in vars.iter().zip(&values) {
         let source = match var.source {
             EnvSource::Auto => "(auto)",
+            EnvSource::User if var.is_encrypted() => "(encrypted)",
             EnvSource::User => "",
         };
 
@@ -115,21 +140,141 @@ pub fn list(app: &str, show_values: bool, environment: Option<&str>) -> Result<(
     Ok(())
 }
 
+/// Lists user variables that override auto-generated ones, and which value wins.
+pub fn list_auto_overrides(
+    app: &str,
+    show_values: bool,
+    environment: Option<&str>,
+) -> Result<(), AppError> {
+    let app_dir = get_app_dir(app)?;
+    let env_name = environment.unwrap_or("production");
+    let env_path = get_env_path(&app_dir, environment);
+
+    let overrides = EnvManager::auto_overrides(&app_dir, &env_path)?;
+
+    if overrides.is_empty() {
+        ui::info(&format!(
+            "No user variables override auto-generated ones for {} ({})",
+            app, env_name
+        ));
+        return Ok(());
+    }
+
+    if show_values {
+        ui::warning("Values will be displayed in plain text.");
+        let confirm = ui::confirm("Are you sure?", false)?;
+        if !confirm {
+            return Ok(());
+        }
+        println!();
+    }
+
+    println!(
+        "Variables overriding .env.auto for {} ({}):",
+        app,
+        console::style(env_name).cyan()
+    );
+    println!();
+
+    let display = |var: &crate::core::env::EnvVar| {
+        if show_values {
+            var.value.clone()
+        } else {
+            var.masked_value()
+        }
+    };
+
+    let max_key_len = overrides.iter().map(|o| o.user.key.len()).max().unwrap_or(0).max(4);
+    let max_val_len = overrides
+        .iter()
+        .flat_map(|o| [display(&o.user).chars().count(), display(&o.auto).chars().count()])
+        .max()
+        .unwrap_or(0)
+        .max(10);
+
+    println!(
+        "  {:<width_key$}   {:<width_val$}   {:<width_val$}   Effective",
+        "Name",
+        "User value",
+        "Auto value",
+        width_key = max_key_len,
+        width_val = max_val_len
+    );
+    println!("  {}", "─".repeat(max_key_len + max_val_len * 2 + 18));
+
+    for o in &overrides {
+        let effective = if o.same_value() {
+            console::style("user (same value)").dim().to_string()
+        } else {
+            console::style("user (overrides auto)").yellow().to_string()
+        };
+
+        println!(
+            "  {:<width_key$}   {}   {}   {}",
+            o.user.key,
+            pad(&display(&o.user), max_val_len),
+            pad(&display(&o.auto), max_val_len),
+            effective,
+            width_key = max_key_len,
+        );
+    }
+
+    println!();
+    println!(
+        "{}",
+        console::style(format!(
+            "{} is loaded after .env.auto when the container starts, so user values win.",
+            env_path.file_name().unwrap_or_default().to_string_lossy()
+        ))
+        .dim()
+    );
+
+    Ok(())
+}
+
+/// Pads a value to a width counted in characters (masked values use '•').
+fn pad(value: &str, width: usize) -> String {
+    format!("{}{}", value, " ".repeat(width.saturating_sub(value.chars().count())))
+}
+
 /// Sets environment variables for an app.
-pub fn set(app: &str, assignments: &[String], environment: Option<&str>) -> Result<(), AppError> {
+pub fn set(
+    app: &str,
+    assignments: &[String],
+    environment: Option<&str>,
+    encrypt: bool,
+) -> Result<(), AppError> {
     let app_dir = get_app_dir(app)?;
     let env_name = environment.unwrap_or("production");
     let env_path = get_env_path(&app_dir, environment);
+    let mut config = AppConfig::load(app)?;
 
     // Parse all assignments
     let mut parsed: Vec<(String, String)> = Vec::new();
     for assignment in assignments {
         let (key, value) = EnvManager::parse_assignment(assignment)?;
+        let value = EnvManager::resolve_file_value(&value)?;
         parsed.push((key, value));
     }
 
+    // Variables flagged sensitive once stay encrypted on every later set
+    let sensitive: Vec<String> = parsed
+        .iter()
+        .map(|(key, _)| key.clone())
+        .filter(|key| encrypt || config.sensitive_env.contains(key))
+        .collect();
+    if !sensitive.is_empty() {
+        let cipher = EnvCipher::load_or_create()?;
+        for (key, value) in parsed.iter_mut() {
+            if sensitive.contains(key) {
+                *value = cipher.encrypt(value)?;
+            }
+        }
+    }
+
     // Set variables in the environment-specific file
-    let count = EnvManager::set_to_file(&env_path, &parsed)?;
+    let result = EnvManager::set_to_file(&env_path, &parsed)?;
+    let count = result.count();
 
     ui::success(&format!(
         "Set {} environment variable{} for {}",
@@ -138,6 +283,32 @@ pub fn set(app: &str, assignments: &[String], environment: Option<&str>) -> Resu
         env_name
     ));
 
+    if !result.added.is_empty() {
+        ui::info(&format!("Added: {}", result.added.join(", ")));
+    }
+    if !result.overwritten.is_empty() {
+        ui::warning(&format!(
+            "Replaced existing value for: {}",
+            result.overwritten.join(", ")
+        ));
+    }
+    if !result.unchanged.is_empty() {
+        ui::info(&format!("Unchanged: {}", result.unchanged.join(", ")));
+    }
+    if !sensitive.is_empty() {
+        ui::info(&format!("Encrypted: {}", sensitive.join(", ")));
+
+        let new_keys: Vec<String> = sensitive
+            .into_iter()
+            .filter(|key| !config.sensitive_env.contains(key))
+            .collect();
+        if !new_keys.is_empty() {
+            config.sensitive_env.extend(new_keys);
+            config.sensitive_env.sort();
+            config.save()?;
+        }
+    }
+
     // Ask to restart only if production
     if env_name == "production" {
         prompt_restart(app)?;
@@ -155,6 +326,13 @@ pub fn remove(app: &str, key: &str, environment: Option<&str>) -> Result<(), App
     let removed = EnvManager::remove_from_file(&env_path, key)?;
 
     if removed {
+        // Drop the sensitive flag once no environment defines the key anymore
+        let mut config = AppConfig::load(app)?;
+        if config.sensitive_env.iter().any(|k| k == key) && !defined_in_any_env(&app_dir, key)? {
+            config.sensitive_env.retain(|k| k != key);
+            config.save()?;
+        }
+
         ui::success(&format!("Removed {} from {}", key, env_name));
         if env_name == "production" {
             prompt_restart(app)?;
@@ -166,6 +344,17 @@ pub fn remove(app: &str, key: &str, environment: Option<&str>) -> Result<(), App
     Ok(())
 }
 
+/// Returns true if any of the app's environments still defines `key`.
+fn defined_in_any_env(app_dir: &Path, key: &str) -> Result<bool, AppError> {
+    for env in EnvManager::list_environments(app_dir)? {
+        let path = get_env_path(app_dir, Some(&env));
+        if path.exists() && EnvManager::load_from_file(&path)?.iter().any(|v| v.key == key) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
 /// Opens the env file in the user's editor.
 pub fn edit(app: &str, environment: Option<&str>) -> Result<(), AppError> {
     let app_dir = get_app_dir(app)?;
@@ -189,20 +378,65 @@ pub fn edit(app: &str, environment: Option<&str>) -> Result<(), AppError> {
         }
     }
 
+    // Edit a copy so changes made on disk meanwhile can be detected on save
+    let original = std::fs::read_to_string(&env_path)
+        .map_err(|e| AppError::Config(format!("Failed to read env file: {}", e)))?;
+    let original_checksum = EnvManager::checksum(&original);
+    let edit_path = PathBuf::from(format!("{}.edit", env_path.display()));
+    EnvManager::write_env_file(&edit_path, &original)?;
+
     let editor = EnvManager::get_editor();
 
     ui::info(&format!("Opening {} in {}...", env_path.display(), editor));
 
     // Open editor
     let status = Command::new(&editor)
-        .arg(&env_path)
+        .arg(&edit_path)
         .status()
         .map_err(|e| AppError::Command(format!("Failed to open editor '{}': {}", editor, e)))?;
 
     if !status.success() {
+        let _ = std::fs::remove_file(&edit_path);
         return Err(AppError::Command("Editor exited with error".into()));
     }
 
+    let edited = std::fs::read_to_string(&edit_path)
+        .map_err(|e| AppError::Config(format!("Failed to read env file: {}", e)))?;
+
+    if edited == original {
+        let _ = std::fs::remove_file(&edit_path);
+        ui::info("No changes made");
+        return Ok(());
+    }
+
+    let current = std::fs::read_to_string(&env_path).unwrap_or_default();
+
+    if EnvManager::checksum(&current) == original_checksum {
+        EnvManager::write_env_file(&env_path, &edited)?;
+    } else {
+        ui::warning(&format!(
+            "{} was modified by another process while you were editing",
+            env_path.display()
+        ));
+
+        let merge = EnvManager::merge(&original, &edited, &current);
+        if !merge.conflicts.is_empty() {
+            ui::warning(&format!(
+                "Changed on both sides (your edit wins, including removals): {}",
+                merge.conflicts.join(", ")
+            ));
+        }
+
+        if !ui::confirm("Merge your changes into the current file?", true)? {
+            ui::info(&format!("Your edits were kept in {}", edit_path.display()));
+            return Err(AppError::Cancelled);
+        }
+
+        EnvManager::write_env_file(&env_path, &merge.content)?;
+    }
+
+    let _ = std::fs::remove_file(&edit_path);
+
     ui::success(&format!("Environment file saved ({})", env_name));
 
     // Validate the file after editing
@@ -278,7 +512,7 @@ pub fn copy(app: &str, from: &str, to: &str) -> Result<(), AppError> {
             "Environment '{}' already exists and will be overwritten.",
             to
         ));
-        let confirm = ui::confirm("Continue?", false)?;
+        let confirm = ui::confirm_action("Continue?", false)?;
         if !confirm {
             return Ok(());
         }
@@ -297,6 +531,255 @@ pub fn copy(app: &str, from: &str, to: &str) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Prints the environment's variables in dotenv format. With `include_auto`,
+/// the generated variables come first so user values win on re-import.
+/// Encrypted values are printed as ciphertext bound to this server's env key,
+/// so they only import on a server sharing `/opt/flaase/env.key`.
+pub fn export(
+    app: &str,
+    include_auto: bool,
+    show_values: bool,
+    environment: Option<&str>,
+) -> Result<(), AppError> {
+    let app_dir = get_app_dir(app)?;
+    let env_path = get_env_path(&app_dir, environment);
+
+    let mut output = String::new();
+    if include_auto {
+        let auto_path = app_dir.join(".env.auto");
+        let mut auto_vars = EnvManager::load_from_file(&auto_path)?;
+        for var in &mut auto_vars {
+            var.source = EnvSource::Auto;
+        }
+        output.push_str("# Generated (.env.auto)\n");
+        output.push_str(&EnvManager::to_dotenv(&auto_vars, show_values));
+        output.push_str("\n# User\n");
+    }
+    output.push_str(&EnvManager::to_dotenv(
+        &EnvManager::load_from_file(&env_path)?,
+        show_values,
+    ));
+
+    print!("{}", output);
+    Ok(())
+}
+
+/// Sets every variable of a dotenv file, refusing masked values. Variables
+/// Flaase generates (the `.env.auto` part of `export --include-auto`) are
+/// skipped, as a user copy would pin them past the next deploy.
+pub fn import(app: &str, file: &Path, environment: Option<&str>) -> Result<(), AppError> {
+    let app_dir = get_app_dir(app)?;
+    let env_name = environment.unwrap_or("production");
+    let env_path = get_env_path(&app_dir, environment);
+
+    let content = std::fs::read_to_string(file).map_err(|e| {
+        AppError::Config(format!("Failed to read {}: {}", file.display(), e))
+    })?;
+    let mut vars = EnvManager::parse_env_content(&content);
+
+    let managed = EnvManager::managed_keys(&app_dir)?;
+    let skipped: Vec<String> = vars
+        .keys()
+        .filter(|key| managed.contains(key))
+        .cloned()
+        .collect();
+    if !skipped.is_empty() {
+        ui::warning(&format!(
+            "Skipped generated variable{}: {}",
+            if skipped.len() == 1 { "" } else { "s" },
+            skipped.join(", ")
+        ));
+        vars.retain(|key, _| !managed.contains(key));
+    }
+
+    if vars.is_empty() {
+        return Err(AppError::Validation(format!(
+            "No variables found in {}",
+            file.display()
+        )));
+    }
+
+    let masked: Vec<&str> = vars
+        .iter()
+        .filter(|(_, value)| EnvManager::is_masked(value))
+        .map(|(key, _)| key.as_str())
+        .collect();
+    if !masked.is_empty() {
+        return Err(AppError::Validation(format!(
+            "Masked values for {}. Export with --show to import them.",
+            masked.join(", ")
+        )));
+    }
+
+    // Ciphertext from another server's key would only fail at deploy
+    if vars.values().any(|value| is_encrypted(value)) {
+        let cipher = EnvCipher::load()?;
+        let foreign: Vec<&str> = vars
+            .iter()
+            .filter(|(_, value)| is_encrypted(value) && cipher.decrypt(value).is_err())
+            .map(|(key, _)| key.as_str())
+            .collect();
+        if !foreign.is_empty() {
+            return Err(AppError::Validation(format!(
+                "Encrypted values for {} don't decrypt with this server's key ({}). \
+                 Import their plain values instead.",
+                foreign.join(", "),
+                crate::core::env_crypto::FLAASE_ENV_KEY_PATH
+            )));
+        }
+    }
+
+    // Keys flagged sensitive stay encrypted, as with `fl env set`
+    let config = AppConfig::load(app)?;
+    let mut assignments: Vec<(String, String)> = vars.into_iter().collect();
+    if assignments
+        .iter()
+        .any(|(key, value)| config.sensitive_env.contains(key) && !is_encrypted(value))
+    {
+        let cipher = EnvCipher::load_or_create()?;
+        for (key, value) in assignments.iter_mut() {
+            if config.sensitive_env.contains(key) && !is_encrypted(value) {
+                *value = cipher.encrypt(value)?;
+            }
+        }
+    }
+
+    let result = EnvManager::set_to_file(&env_path, &assignments)?;
+
+    ui::success(&format!(
+        "Imported {} variable{} into {}",
+        assignments.len(),
+        if assignments.len() == 1 { "" } else { "s" },
+        env_name
+    ));
+    if !result.added.is_empty() {
+        ui::info(&format!("Added: {}", result.added.join(", ")));
+    }
+    if !result.overwritten.is_empty() {
+        ui::warning(&format!(
+            "Replaced existing value for: {}",
+            result.overwritten.join(", ")
+        ));
+    }
+
+    if env_name == "production" {
+        prompt_restart(app)?;
+    }
+
+    Ok(())
+}
+
+/// Compares the merged variables (user + auto) of two apps.
+pub fn diff(app_a: &str, app_b: &str, show_values: bool) -> Result<(), AppError> {
+    let vars_a = EnvManager::load(&get_app_dir(app_a)?)?;
+    let vars_b = EnvManager::load(&get_app_dir(app_b)?)?;
+
+    let mut diff = EnvManager::diff(&vars_a, &vars_b);
+
+    let any_encrypted = diff
+        .only_left
+        .iter()
+        .chain(&diff.only_right)
+        .chain(diff.changed.iter().flat_map(|(a, b)| [a, b]))
+        .any(|var| var.is_encrypted());
+    let cipher = if any_encrypted {
+        Some(EnvCipher::load()?)
+    } else {
+        None
+    };
+    let plain = |var: &crate::core::env::EnvVar| match &cipher {
+        Some(cipher) if var.is_encrypted() => cipher.decrypt(&var.value),
+        _ => Ok(var.value.clone()),
+    };
+
+    // Ciphertexts use a fresh nonce each time, and a value may be encrypted
+    // on one side only, so compare the plaintexts
+    let mut changed = Vec::new();
+    for (a, b) in diff.changed {
+        if (a.is_encrypted() || b.is_encrypted()) && plain(&a)? == plain(&b)? {
+            continue;
+        }
+        changed.push((a, b));
+    }
+    diff.changed = changed;
+
+    if diff.is_empty() {
+        ui::success(&format!("{} and {} have the same variables", app_a, app_b));
+        return Ok(());
+    }
+
+    if show_values {
+        ui::warning("Values will be displayed in plain text.");
+        let confirm = ui::confirm("Are you sure?", false)?;
+        if !confirm {
+            return Ok(());
+        }
+        println!();
+
+        for var in diff
+            .only_left
+            .iter_mut()
+            .chain(&mut diff.only_right)
+            .chain(diff.changed.iter_mut().flat_map(|(a, b)| [a, b]))
+        {
+            var.value = plain(var)?;
+        }
+    }
+
+    let display = |var: &crate::core::env::EnvVar| {
+        if show_values {
+            var.value.clone()
+        } else {
+            var.masked_value()
+        }
+    };
+    let removed = |var: &crate::core::env::EnvVar| {
+        println!("  {}", console::style(format!("- {}={}", var.key, display(var))).red());
+    };
+    let added = |var: &crate::core::env::EnvVar| {
+        println!("  {}", console::style(format!("+ {}={}", var.key, display(var))).green());
+    };
+
+    println!(
+        "Comparing {} and {}:",
+        console::style(app_a).cyan(),
+        console::style(app_b).cyan()
+    );
+
+    if !diff.only_left.is_empty() {
+        println!();
+        println!("Only in {}:", app_a);
+        diff.only_left.iter().for_each(removed);
+    }
+
+    if !diff.only_right.is_empty() {
+        println!();
+        println!("Only in {}:", app_b);
+        diff.only_right.iter().for_each(added);
+    }
+
+    if !diff.changed.is_empty() {
+        println!();
+        println!("Different values:");
+        for (a, b) in &diff.changed {
+            removed(a);
+            added(b);
+        }
+    }
+
+    println!();
+    println!(
+        "{} only in {}, {} only in {}, {} different",
+        diff.only_left.len(),
+        app_a,
+        diff.only_right.len(),
+        app_b,
+        diff.changed.len()
+    );
+
+    Ok(())
+}
+
 /// Lists all environments with their variable counts.
 pub fn envs(app: &str) -> Result<(), AppError> {
     let app_dir = get_app_dir(app)?;
@@ -336,3 +819,111 @@ pub fn envs(app: &str) -> Result<(), AppError> {
 
     Ok(())
 }
+
+/// Configures the secrets file mounted into the app container.
+pub fn secrets_file(
+    app: &str,
+    path: Option<&str>,
+    keys: &[String],
+    file_only: Option<bool>,
+    disable: bool,
+) -> Result<(), AppError> {
+    let mut config = AppConfig::load(app)?;
+
+    if disable {
+        if config.secrets_file.take().is_none() {
+            ui::info("No secrets file configured for this app.");
+            return Ok(());
+        }
+        config.save()?;
+        let rendered = config.secrets_file_path();
+        if rendered.exists() {
+            std::fs::remove_file(&rendered)?;
+        }
+        ui::success("Secrets file disabled; generated secrets are passed as environment variables");
+        ui::info(&format!("Run 'fl restart {}' for changes to take effect", app));
+        return Ok(());
+    }
+
+    let available: Vec<String> = EnvManager::load_from_file(&config.auto_env_path())?
+        .into_iter()
+        .map(|var| var.key)
+        .collect();
+
+    if path.is_some() || !keys.is_empty() || file_only.is_some() {
+        let mut secrets_file = config.secrets_file.clone().unwrap_or_default();
+
+        if let Some(path) = path {
+            if !path.starts_with('/') || path == "/" || path.contains(':') {
+                return Err(AppError::Validation(format!(
+                    "Invalid secrets file path '{}'. Use an absolute path like /run/secrets/app.env",
+                    path
+                )));
+            }
+            secrets_file.path = path.to_string();
+        }
+
+        if !keys.is_empty() {
+            for key in keys.iter().filter(|k| !available.contains(k)) {
+                ui::warning(&format!("{} is not a generated secret for this app yet", key));
+            }
+            secrets_file.keys = keys.to_vec();
+        }
+
+        if let Some(file_only) = file_only {
+            secrets_file.file_only = file_only;
+        }
+
+        config.secrets_file = Some(secrets_file);
+        config.save()?;
+
+        ui::success("Secrets file updated");
+        ui::info(&format!("Run 'fl restart {}' for changes to take effect", app));
+        println!();
+    }
+
+    print_secrets_file_status(app, config.secrets_file.as_ref(), &available);
+
+    Ok(())
+}
+
+/// Prints the secrets file configuration and the variables it will contain.
+fn print_secrets_file_status(
+    app: &str,
+    secrets_file: Option<&SecretsFileConfig>,
+    available: &[String],
+) {
+    println!("Secrets file for {}", console::style(app).cyan().bold());
+    println!();
+
+    let Some(secrets_file) = secrets_file else {
+        println!("  {}", console::style("Not configured").dim());
+        println!();
+        ui::info(&format!(
+            "Enable with 'fl env secrets-file {} --path /run/secrets/app.env'",
+            app
+        ));
+        return;
+    };
+
+    println!("  Path:       {}", console::style(&secrets_file.path).cyan());
+    println!(
+        "  Delivery:   {}",
+        if secrets_file.file_only {
+            "file only"
+        } else {
+            "file and environment variables"
+        }
+    );
+
+    let included: Vec<&String> = available.iter().filter(|k| secrets_file.includes(k)).collect();
+    if included.is_empty() {
+        println!("  Variables:  {}", console::style("none generated yet").dim());
+    } else {
+        println!("  Variables:");
+        for key in included {
+            println!("    {}", key);
+        }
+    }
+    println!();
+}
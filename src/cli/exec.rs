@@ -0,0 +1,85 @@
+//! One-off commands in the running app container (`fl exec`).
+
+use std::io::IsTerminal;
+use std::process::Command;
+
+use crate::core::app_config::AppConfig;
+use crate::core::context::ExecutionContext;
+use crate::core::deploy::Deployer;
+use crate::core::error::AppError;
+use crate::providers::{container_cli, create_container_runtime, create_reverse_proxy};
+
+/// Command run when none is given.
+const DEFAULT_SHELL: &str = "/bin/sh";
+
+/// Runs a command (or an interactive shell) in the app's web container with
+/// inherited stdio. Returns the command's exit code.
+pub fn exec(app: &str, command: &[String], verbose: bool) -> Result<i32, AppError> {
+    let config = AppConfig::load(app)?;
+
+    let ctx = ExecutionContext::new(false, verbose);
+    let runtime = create_container_runtime();
+    let proxy = create_reverse_proxy();
+    let deployer = Deployer::new(&config, runtime.as_ref(), proxy.as_ref(), &ctx);
+
+    // Follows the live blue-green slot
+    let container = deployer.web_slot_container("active")?;
+
+    if !runtime.container_is_running(&container, &ctx).unwrap_or(false) {
+        return Err(AppError::Deploy(format!(
+            "Container '{}' is not running. Start the app with 'fl start {}'.",
+            container, app
+        )));
+    }
+
+    let args = exec_args(&container, command, std::io::stdin().is_terminal());
+    if verbose {
+        crate::ui::info(&format!("Running: {} {}", container_cli(), args.join(" ")));
+    }
+
+    let status = Command::new(container_cli())
+        .args(&args)
+        .status()
+        .map_err(|e| AppError::Command(format!("Failed to run {}: {}", container_cli(), e)))?;
+
+    Ok(status.code().unwrap_or(1))
+}
+
+/// Builds the `docker exec` arguments, allocating a TTY only when stdin is one
+/// so piped input and scripts keep working.
+fn exec_args(container: &str, command: &[String], tty: bool) -> Vec<String> {
+    let mut args = vec!["exec".to_string(), "-i".to_string()];
+    if tty {
+        args.push("-t".to_string());
+    }
+    args.push(container.to_string());
+
+    if command.is_empty() {
+        args.push(DEFAULT_SHELL.to_string());
+    } else {
+        args.extend(command.iter().cloned());
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exec_args() {
+        assert_eq!(
+            exec_args("flaase-shop-web", &[], true),
+            ["exec", "-i", "-t", "flaase-shop-web", "/bin/sh"]
+        );
+
+        let migrate: Vec<String> = ["php", "artisan", "migrate", "--force"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            exec_args("flaase-shop-web", &migrate, false),
+            ["exec", "-i", "flaase-shop-web", "php", "artisan", "migrate", "--force"]
+        );
+    }
+}
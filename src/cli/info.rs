@@ -0,0 +1,157 @@
+//! Machine-readable app descriptor (`fl app info`).
+
+use serde_json::{json, Value};
+
+use crate::cli::status::{app_status, parse_container_states};
+use crate::core::app_config::AppConfig;
+use crate::core::certs::{domain_matches, CertificateStore};
+use crate::core::config::FLAASE_TRAEFIK_PATH;
+use crate::core::context::ExecutionContext;
+use crate::core::deployments::DeploymentHistory;
+use crate::core::error::AppError;
+use crate::providers::container_cli;
+use crate::providers::webhook::WebhookProvider;
+
+/// Prints one JSON object describing the resolved state of an app:
+/// redacted config, domains with SSL status, containers, last deployment,
+/// autodeploy and resource settings.
+pub fn info(app: &str) -> Result<(), AppError> {
+    let config = AppConfig::load(app)?;
+    // Never verbose: command traces on stdout would corrupt the JSON
+    let ctx = ExecutionContext::new(false, false);
+
+    let output = ctx.run_command(
+        container_cli(),
+        &[
+            "ps",
+            "-a",
+            "--filter",
+            &format!("label=flaase.app={}", app),
+            "--format",
+            "{{.Names}}\t{{.State}}\t{{.Status}}",
+        ],
+    )?;
+    output.ensure_success("Failed to list containers")?;
+    let states = parse_container_states(&output.stdout);
+
+    let mut containers: Vec<Value> = states
+        .iter()
+        .map(|(name, (state, status))| json!({"name": name, "state": state, "status": status}))
+        .collect();
+    containers.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+
+    let status = app_status(config.deployed_at, states.get(&format!("flaase-{}-web", app)));
+
+    let certificates = CertificateStore::load().unwrap_or_default();
+    let issued = std::fs::read_to_string(format!("{}/acme.json", FLAASE_TRAEFIK_PATH))
+        .map(|content| acme_domains(&content))
+        .unwrap_or_default();
+    let domains: Vec<Value> = config
+        .domains
+        .iter()
+        .map(|domain| {
+            let ssl = match certificates.find_for(&domain.domain) {
+                Some(cert) => json!({"source": "imported", "certificate": cert.name}),
+                None => json!({
+                    "source": "letsencrypt",
+                    "issued": issued.iter().any(|d| domain_matches(d, &domain.domain)),
+                }),
+            };
+            json!({
+                "domain": domain.domain,
+                "path_prefix": domain.path_prefix,
+                "primary": domain.primary,
+                "auth": domain.auth.as_ref().is_some_and(|auth| auth.enabled),
+                "ssl": ssl,
+            })
+        })
+        .collect();
+
+    let last_deployment = DeploymentHistory::load(&config.deployments_path())?
+        .recent(1)
+        .first()
+        .cloned();
+
+    let autodeploy = match &config.autodeploy_config {
+        Some(autodeploy) => json!({
+            "enabled": autodeploy.enabled,
+            "branch": autodeploy.branch,
+            "webhook_url": WebhookProvider::webhook_url(
+                config.primary_domain(),
+                &autodeploy.webhook_path,
+            ),
+            "webhook_service_running": WebhookProvider::is_running(),
+        }),
+        None => json!({"enabled": false}),
+    };
+
+    let document = json!({
+        "name": config.name,
+        "status": status.as_str(),
+        "url": config.public_url(),
+        "port": config.effective_port(),
+        "replicas": config.effective_replicas(),
+        "runtime": container_cli(),
+        "domains": domains,
+        "containers": containers,
+        "last_deployment": last_deployment,
+        "autodeploy": autodeploy,
+        "resources": config.resources,
+        "config": config.redacted(),
+    });
+
+    let rendered = serde_json::to_string_pretty(&document)
+        .map_err(|e| AppError::Config(format!("Failed to serialize app info: {}", e)))?;
+    println!("{}", rendered);
+
+    Ok(())
+}
+
+/// Lists the domains (main and SANs) with a certificate in Traefik's
+/// `acme.json`, across all resolvers.
+fn acme_domains(content: &str) -> Vec<String> {
+    let Ok(acme) = serde_json::from_str::<Value>(content) else {
+        return Vec::new();
+    };
+
+    acme.as_object()
+        .into_iter()
+        .flat_map(|resolvers| resolvers.values())
+        .filter_map(|resolver| resolver["Certificates"].as_array())
+        .flatten()
+        .flat_map(|cert| {
+            let main = cert["domain"]["main"].as_str().into_iter();
+            let sans = cert["domain"]["sans"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str);
+            main.chain(sans).map(str::to_string).collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_acme_domains() {
+        let content = r#"{
+            "letsencrypt": {
+                "Account": {"Email": "ops@example.com"},
+                "Certificates": [
+                    {"domain": {"main": "app.example.com"}, "certificate": "..."},
+                    {"domain": {"main": "shop.example.com", "sans": ["www.shop.example.com"]}}
+                ]
+            },
+            "staging": {"Certificates": null}
+        }"#;
+
+        assert_eq!(
+            acme_domains(content),
+            vec!["app.example.com", "shop.example.com", "www.shop.example.com"]
+        );
+        assert!(acme_domains("").is_empty());
+    }
+}
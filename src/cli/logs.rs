@@ -6,22 +6,52 @@ use std::process::{Command, Stdio};
 use console::Style;
 
 use crate::core::app_config::AppConfig;
+use crate::core::context::ExecutionContext;
+use crate::core::deploy::Deployer;
+use crate::core::deployments::{DeploymentHistory, DeploymentStatus};
 use crate::core::error::AppError;
+use crate::providers::{container_cli, create_container_runtime, create_reverse_proxy};
+use crate::ui;
+
+/// Lines shown when neither --lines nor --since is given.
+const DEFAULT_LINES: u32 = 100;
 
 /// Shows logs for an app.
+#[allow(clippy::too_many_arguments)]
 pub fn logs(
     app_name: &str,
     follow: bool,
     no_follow: bool,
-    lines: u32,
+    lines: Option<u32>,
     service: &str,
     since: Option<&str>,
+    since_deploy: bool,
+    container: Option<&str>,
+    web_slot: &str,
+    no_pager: bool,
     verbose: bool,
 ) -> Result<(), AppError> {
     let config = AppConfig::load(app_name)?;
 
+    // Resolve --since-deploy to the latest deployment timestamp
+    let deploy_since = if since_deploy {
+        Some(latest_deploy_time(&config)?)
+    } else {
+        None
+    };
+    let since = deploy_since.as_deref().or(since);
+
     // Determine which containers to show
-    let containers = get_service_containers(app_name, service, &config)?;
+    let containers = match container {
+        Some(name) => {
+            validate_app_container(app_name, name)?;
+            vec![name.to_string()]
+        }
+        None => {
+            let web = web_slot_container(&config, web_slot)?;
+            get_service_containers(app_name, service, &config, &web)?
+        }
+    };
 
     if containers.is_empty() {
         return Err(AppError::Deploy(format!(
@@ -33,10 +63,16 @@ pub fn logs(
     // Follow by default unless --no-follow is specified
     let should_follow = !no_follow || follow;
 
-    // Validate --since format if provided
-    if let Some(since_val) = since {
-        validate_since(since_val)?;
-    }
+    // Normalize --since to a form docker and podman both accept
+    let since = since.map(parse_since).transpose()?;
+    let since = since.as_deref();
+
+    // A time window replaces the default tail, unless --lines is explicit
+    let lines = match (lines, since) {
+        (Some(lines), _) => Some(lines),
+        (None, Some(_)) => None,
+        (None, None) => Some(DEFAULT_LINES),
+    };
 
     if verbose {
         println!(
@@ -45,45 +81,67 @@ pub fn logs(
         );
     }
 
-    if containers.len() == 1 {
-        // Single container - stream directly
-        stream_container_logs(&containers[0], lines, since, should_follow)?;
-    } else {
-        // Multiple containers - show header for each
-        if should_follow {
-            // For follow mode with multiple containers, we need to merge streams
-            stream_multi_container_logs(&containers, lines, since)?;
-        } else {
-            // Show each container's logs sequentially
-            for container in &containers {
+    if !should_follow {
+        // Collect the dump so it can go through a pager as a whole
+        let mut output = String::new();
+        for container in &containers {
+            if containers.len() > 1 {
                 let service_name = extract_service_name(container);
-                println!(
-                    "\n{} {}",
+                output.push_str(&format!(
+                    "\n{} {}\n{}\n",
                     Style::new().bold().cyan().apply_to("==="),
-                    Style::new().bold().apply_to(&service_name)
-                );
-                println!("{}", Style::new().dim().apply_to("=".repeat(40)));
-                stream_container_logs(container, lines, since, false)?;
+                    Style::new().bold().apply_to(&service_name),
+                    Style::new().dim().apply_to("=".repeat(40))
+                ));
             }
+            output.push_str(&read_container_logs(container, lines, since)?);
+        }
+
+        if no_pager {
+            print!("{}", output);
+        } else {
+            ui::page(&output);
         }
+    } else if containers.len() == 1 {
+        // Single container - stream directly
+        stream_container_logs(&containers[0], lines, since)?;
+    } else {
+        // For follow mode with multiple containers, we need to merge streams
+        stream_multi_container_logs(&containers, lines, since)?;
     }
 
     Ok(())
 }
 
+/// Resolves the web container for `--web-slot`, following the active
+/// blue-green slot by default.
+fn web_slot_container(config: &AppConfig, slot: &str) -> Result<String, AppError> {
+    let ctx = ExecutionContext::new(false, false);
+    let runtime = create_container_runtime();
+    let proxy = create_reverse_proxy();
+    let deployer = Deployer::new(config, runtime.as_ref(), proxy.as_ref(), &ctx);
+
+    deployer.web_slot_container(&slot.to_lowercase())
+}
+
 /// Gets the container names for a given service.
 fn get_service_containers(
     app_name: &str,
     service: &str,
     config: &AppConfig,
+    web_container: &str,
 ) -> Result<Vec<String>, AppError> {
     let prefix = format!("flaase-{}", app_name);
 
     match service.to_lowercase().as_str() {
-        "app" | "web" => Ok(vec![format!("{}-web", prefix)]),
+        "app" | "web" => Ok(vec![web_container.to_string()]),
         "database" | "db" => {
-            if config.database.is_some() {
+            if config.managed_database().is_some() {
                 Ok(vec![format!("{}-db", prefix)])
+            } else if config.database.is_some() {
+                Err(AppError::Validation(
+                    "The database is external; check its logs with your provider".into(),
+                ))
             } else {
                 Err(AppError::Validation(
                     "No database configured for this app".into(),
@@ -91,8 +149,12 @@ fn get_service_containers(
             }
         }
         "cache" | "redis" => {
-            if config.cache.is_some() {
-                Ok(vec![format!("{}-cache", prefix)])
+            if !config.caches.is_empty() {
+                Ok(config
+                    .caches
+                    .iter()
+                    .map(|cache| format!("{}-{}", prefix, cache.container_suffix()))
+                    .collect())
             } else {
                 Err(AppError::Validation(
                     "No cache configured for this app".into(),
@@ -100,12 +162,12 @@ fn get_service_containers(
             }
         }
         "all" => {
-            let mut containers = vec![format!("{}-web", prefix)];
-            if config.database.is_some() {
+            let mut containers = vec![web_container.to_string()];
+            if config.managed_database().is_some() {
                 containers.push(format!("{}-db", prefix));
             }
-            if config.cache.is_some() {
-                containers.push(format!("{}-cache", prefix));
+            for cache in &config.caches {
+                containers.push(format!("{}-{}", prefix, cache.container_suffix()));
             }
             Ok(containers)
         }
@@ -116,47 +178,129 @@ fn get_service_containers(
     }
 }
 
-/// Validates the --since format.
-fn validate_since(since: &str) -> Result<(), AppError> {
-    // Duration format: 1h, 30m, 2s, 1d
-    if since.chars().last().map(|c| "hmsд".contains(c)).unwrap_or(false) {
-        let num_part = &since[..since.len() - 1];
-        if num_part.parse::<u64>().is_ok() {
-            return Ok(());
-        }
+/// Ensures a container passed with --container is managed by Flaase for this app.
+fn validate_app_container(app_name: &str, container: &str) -> Result<(), AppError> {
+    let output = Command::new(container_cli())
+        .args([
+            "inspect",
+            "--format",
+            "{{index .Config.Labels \"flaase.managed\"}}|{{index .Config.Labels \"flaase.app\"}}",
+            container,
+        ])
+        .output()
+        .map_err(|e| AppError::Command(format!("Failed to inspect container: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(AppError::Validation(format!(
+            "Container '{}' not found",
+            container
+        )));
     }
 
-    // Handle "Xh" "Xm" patterns
-    if since.ends_with('h') || since.ends_with('m') || since.ends_with('s') || since.ends_with('d') {
-        let num_part = &since[..since.len() - 1];
-        if num_part.parse::<u64>().is_ok() {
-            return Ok(());
-        }
+    let labels = String::from_utf8_lossy(&output.stdout);
+    let (managed, app) = labels.trim().split_once('|').unwrap_or(("", ""));
+
+    if managed != "true" {
+        return Err(AppError::Validation(format!(
+            "Container '{}' is not managed by Flaase",
+            container
+        )));
     }
 
-    // ISO date format: 2024-01-15 or 2024-01-15T10:30:00
-    if since.contains('-') && since.len() >= 10 {
-        return Ok(());
+    if app != app_name {
+        return Err(AppError::Validation(format!(
+            "Container '{}' does not belong to app '{}'",
+            container, app_name
+        )));
     }
 
-    // Relative format: "1 hour ago"
-    if since.contains("ago") {
-        return Ok(());
+    Ok(())
+}
+
+/// Returns the time of the most recent successful deployment (RFC 3339),
+/// from the config or the deployment history.
+fn latest_deploy_time(config: &AppConfig) -> Result<String, AppError> {
+    let history = DeploymentHistory::load(&config.deployments_path()).unwrap_or_default();
+    let from_history = history
+        .deployments
+        .iter()
+        .find(|record| record.status == DeploymentStatus::Success)
+        .map(|record| record.timestamp);
+
+    config
+        .deployed_at
+        .into_iter()
+        .chain(from_history)
+        .max()
+        .map(|time| time.to_rfc3339())
+        .ok_or_else(|| {
+            AppError::Validation(format!("App '{}' has not been deployed yet", config.name))
+        })
+}
+
+/// Parses a --since value into an argument for `docker logs --since`.
+///
+/// Accepts durations made of whole numbers with s, m, h or d units (`10m`,
+/// `2h`, `1d`, `1h30m`), converted to seconds since docker has no day unit,
+/// and RFC 3339 timestamps or `YYYY-MM-DD[THH:MM:SS]` dates, passed through.
+fn parse_since(since: &str) -> Result<String, AppError> {
+    let since = since.trim();
+    let invalid = || {
+        AppError::Validation(format!(
+            "Invalid --since value '{}'. Examples: 10m, 2h, 1d, 2024-01-15T10:30:00Z",
+            since
+        ))
+    };
+
+    let is_duration = since.starts_with(|c: char| c.is_ascii_digit())
+        && since.chars().all(|c| c.is_ascii_alphanumeric());
+
+    if is_duration {
+        let mut seconds: u64 = 0;
+        let mut number = String::new();
+        for c in since.chars() {
+            if c.is_ascii_digit() {
+                number.push(c);
+                continue;
+            }
+            let unit = match c {
+                's' => 1,
+                'm' => 60,
+                'h' => 3600,
+                'd' => 86400,
+                _ => return Err(invalid()),
+            };
+            let value: u64 = number.parse().map_err(|_| invalid())?;
+            seconds = value
+                .checked_mul(unit)
+                .and_then(|s| seconds.checked_add(s))
+                .ok_or_else(invalid)?;
+            number.clear();
+        }
+        if seconds == 0 || !number.is_empty() {
+            return Err(invalid());
+        }
+        return Ok(format!("{}s", seconds));
     }
 
-    Err(AppError::Validation(format!(
-        "Invalid --since format '{}'. Examples: 1h, 30m, 1d, 2024-01-15",
-        since
-    )))
+    let is_timestamp = chrono::DateTime::parse_from_rfc3339(since).is_ok()
+        || chrono::NaiveDateTime::parse_from_str(since, "%Y-%m-%dT%H:%M:%S").is_ok()
+        || chrono::NaiveDate::parse_from_str(since, "%Y-%m-%d").is_ok();
+
+    if is_timestamp {
+        Ok(since.to_string())
+    } else {
+        Err(invalid())
+    }
 }
 
-/// Streams logs from a single container.
-fn stream_container_logs(
+/// Builds the `docker logs` arguments for a container.
+fn docker_logs_args(
     container: &str,
-    lines: u32,
+    lines: Option<u32>,
     since: Option<&str>,
     follow: bool,
-) -> Result<(), AppError> {
+) -> Vec<String> {
     let mut args = vec!["logs".to_string()];
 
     if follow {
@@ -164,7 +308,7 @@ fn stream_container_logs(
     }
 
     args.push("--tail".to_string());
-    args.push(lines.to_string());
+    args.push(lines.map_or("all".to_string(), |l| l.to_string()));
 
     // Add timestamps
     args.push("-t".to_string());
@@ -175,41 +319,54 @@ fn stream_container_logs(
     }
 
     args.push(container.to_string());
+    args
+}
 
+/// Streams logs from a single container until interrupted.
+fn stream_container_logs(
+    container: &str,
+    lines: Option<u32>,
+    since: Option<&str>,
+) -> Result<(), AppError> {
+    let args = docker_logs_args(container, lines, since, true);
     let args_ref: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
 
-    if follow {
-        // Stream with colorization
-        stream_with_colorization("docker", &args_ref)?;
-    } else {
-        // Get output and colorize
-        let output = Command::new("docker")
-            .args(&args_ref)
-            .output()
-            .map_err(|e| AppError::Command(format!("Failed to get logs: {}", e)))?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
-
-        // Combine stdout and stderr (docker logs outputs to both)
-        let combined = if stderr.is_empty() {
-            stdout.to_string()
-        } else if stdout.is_empty() {
-            stderr.to_string()
-        } else {
-            format!("{}{}", stdout, stderr)
-        };
+    // Stream with colorization
+    stream_with_colorization(container_cli(), &args_ref)
+}
 
-        print_colorized_logs(&combined);
-    }
+/// Reads the recent logs of a container and returns them colorized.
+fn read_container_logs(
+    container: &str,
+    lines: Option<u32>,
+    since: Option<&str>,
+) -> Result<String, AppError> {
+    let args = docker_logs_args(container, lines, since, false);
+
+    let output = Command::new(container_cli())
+        .args(&args)
+        .output()
+        .map_err(|e| AppError::Command(format!("Failed to get logs: {}", e)))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    // Combine stdout and stderr (docker logs outputs to both)
+    let combined = if stderr.is_empty() {
+        stdout.to_string()
+    } else if stdout.is_empty() {
+        stderr.to_string()
+    } else {
+        format!("{}{}", stdout, stderr)
+    };
 
-    Ok(())
+    Ok(colorize_logs(&combined))
 }
 
 /// Streams logs from multiple containers (merged).
 fn stream_multi_container_logs(
     containers: &[String],
-    lines: u32,
+    lines: Option<u32>,
     since: Option<&str>,
 ) -> Result<(), AppError> {
     // For multiple containers in follow mode, we use a simple approach:
@@ -226,21 +383,9 @@ fn stream_multi_container_logs(
         let tx = tx.clone();
 
         thread::spawn(move || {
-            let mut args = vec!["logs", "-f", "--tail"];
-            let lines_str = lines.to_string();
-            args.push(&lines_str);
-            args.push("-t");
-
-            let since_owned;
-            if let Some(ref s) = since {
-                since_owned = s.clone();
-                args.push("--since");
-                args.push(&since_owned);
-            }
+            let args = docker_logs_args(&container, lines, since.as_deref(), true);
 
-            args.push(&container);
-
-            let child = Command::new("docker")
+            let child = Command::new(container_cli())
                 .args(&args)
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped())
@@ -305,12 +450,11 @@ fn stream_with_colorization(cmd: &str, args: &[&str]) -> Result<(), AppError> {
     Ok(())
 }
 
-/// Prints logs with colorization.
-fn print_colorized_logs(logs: &str) {
-    for line in logs.lines() {
-        let colored = colorize_log_line(line);
-        println!("{}", colored);
-    }
+/// Colorizes every line of a log dump.
+fn colorize_logs(logs: &str) -> String {
+    logs.lines()
+        .map(|line| format!("{}\n", colorize_log_line(line)))
+        .collect()
 }
 
 /// Colorizes a single log line based on content.
@@ -415,6 +559,8 @@ fn extract_service_name(container: &str) -> String {
         "database".to_string()
     } else if container.ends_with("-cache") {
         "cache".to_string()
+    } else if let Some((_, name)) = container.rsplit_once("-cache-") {
+        format!("cache-{}", name)
     } else {
         container.to_string()
     }
@@ -429,3 +575,46 @@ fn get_service_color(service: &str) -> Style {
         _ => Style::new().white().bold(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_since_durations() {
+        assert_eq!(parse_since("30s").unwrap(), "30s");
+        assert_eq!(parse_since("10m").unwrap(), "600s");
+        assert_eq!(parse_since("2h").unwrap(), "7200s");
+        assert_eq!(parse_since("1d").unwrap(), "86400s");
+        assert_eq!(parse_since("1h30m").unwrap(), "5400s");
+    }
+
+    #[test]
+    fn test_parse_since_timestamps() {
+        for since in [
+            "2024-01-15T10:30:00Z",
+            "2024-01-15T10:30:00+02:00",
+            "2024-01-15T10:30:00",
+            "2024-01-15",
+        ] {
+            assert_eq!(parse_since(since).unwrap(), since);
+        }
+    }
+
+    #[test]
+    fn test_parse_since_rejects_garbage() {
+        let garbage = ["", "abc", "10", "10x", "0m", "m10", "1 hour ago", "2024-13-45", "1m5"];
+        for since in garbage {
+            assert!(parse_since(since).is_err(), "{} should be rejected", since);
+        }
+    }
+
+    #[test]
+    fn test_docker_logs_args_without_tail_limit() {
+        let args = docker_logs_args("flaase-app-web", None, Some("600s"), true);
+        assert_eq!(
+            args,
+            ["logs", "-f", "--tail", "all", "-t", "--since", "600s", "flaase-app-web"]
+        );
+    }
+}
@@ -1,14 +1,26 @@
+use std::path::PathBuf;
+
 use clap::{Parser, Subcommand};
 
+pub mod adopt;
 pub mod app;
 pub mod auth;
 pub mod autodeploy;
+pub mod cron;
+pub mod db;
 pub mod deploy;
+pub mod doctor;
 pub mod domain;
 pub mod env;
+pub mod exec;
+pub mod info;
 pub mod logs;
+pub mod notify;
+pub mod registry;
+pub mod remote;
 pub mod server;
 pub mod server_status;
+pub mod server_update;
 pub mod status;
 pub mod webhook;
 
@@ -29,6 +41,14 @@ pub struct Cli {
     /// Enable verbose output
     #[arg(short, long, global = true)]
     pub verbose: bool,
+
+    /// Answer yes to all confirmation prompts
+    #[arg(short, long, global = true)]
+    pub yes: bool,
+
+    /// Run the command on a server from ~/.flaase/servers.yml over SSH
+    #[arg(long, global = true, value_name = "ALIAS")]
+    pub server: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -40,21 +60,106 @@ pub enum Commands {
     },
 
     /// Initialize a new app configuration
-    Init,
+    Init {
+        /// Create the app from a spec exported with `fl app export-config`
+        #[arg(long, value_name = "PATH")]
+        from_file: Option<PathBuf>,
+    },
+
+    /// Manage app configuration
+    App {
+        #[command(subcommand)]
+        command: AppCommands,
+    },
+
+    /// Manage private registry credentials
+    Registry {
+        #[command(subcommand)]
+        command: RegistryCommands,
+    },
+
+    /// Diagnose common server problems (Docker, Traefik, networks, routing)
+    Doctor {
+        /// Repair what can be fixed safely, confirming each fix (skip with --yes)
+        #[arg(long)]
+        fix: bool,
+    },
 
     /// Show status of all deployed apps
-    Status,
+    Status {
+        /// Redraw the app table live until Ctrl+C
+        #[arg(long)]
+        watch: bool,
+
+        /// Seconds between refreshes with --watch
+        #[arg(long, value_name = "SECONDS", default_value_t = status::DEFAULT_WATCH_INTERVAL_SECS)]
+        interval: u64,
+
+        /// Print the apps as a JSON array (for scripts and monitoring)
+        #[arg(long, conflicts_with = "watch")]
+        json: bool,
+    },
 
     /// Deploy an app
     Deploy {
         /// Name of the app to deploy
         app: String,
+
+        /// Emit each deploy step as a JSON line on stderr
+        #[arg(long)]
+        events: bool,
+
+        /// Label this deployment (e.g. release-2024-06) to roll back to it by name
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// Run this many load-balanced containers (image apps only, saved to config)
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=20))]
+        replicas: Option<u32>,
+
+        /// Keep this many earlier images as previous-1..N rollback points (saved to config)
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..=10))]
+        keep_previous: Option<u32>,
+
+        /// Deploy this branch and make autodeploy watch it (source apps only)
+        #[arg(long, value_name = "BRANCH")]
+        from_branch: Option<String>,
+
+        /// Pin the generated Dockerfile's base images to their current digests (saved to config)
+        #[arg(long)]
+        pin_base: bool,
+    },
+
+    /// Configure and deploy a registry image without a Git repository
+    DeployImage {
+        /// Image reference (e.g. nginx:latest, ghcr.io/user/app:v1)
+        image: String,
+
+        /// Domain to route to the container (HTTPS via Let's Encrypt)
+        #[arg(long)]
+        domain: String,
+
+        /// Port the container listens on (detected for well-known images)
+        #[arg(long)]
+        port: Option<u16>,
+
+        /// App name (defaults to the image name)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Prompt for registry credentials for a private image
+        #[arg(long)]
+        private: bool,
     },
 
     /// Update a deployed app
     Update {
         /// Name of the app to update
         app: String,
+
+        /// Check out this Git tag instead of pulling the current branch
+        #[arg(long, value_name = "TAG")]
+        git_tag: Option<String>,
     },
 
     /// Stop a running app
@@ -75,18 +180,42 @@ pub enum Commands {
         app: String,
     },
 
+    /// Run a command in the app container (an interactive shell by default)
+    Exec {
+        /// Name of the app
+        app: String,
+
+        /// Command and arguments (e.g., php artisan migrate)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+    },
+
+    /// Change the number of load-balanced containers without downtime (image apps only)
+    Scale {
+        /// Name of the app
+        app: String,
+
+        /// Number of replicas to run
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=20))]
+        count: u32,
+    },
+
     /// Remove an app completely
     Destroy {
         /// Name of the app to destroy
         app: String,
 
-        /// Skip confirmation prompt (for scripting)
-        #[arg(long, short = 'y')]
+        /// Skip confirmation prompt (for scripting, same as --yes)
+        #[arg(long)]
         force: bool,
 
         /// Keep database and cache volumes (preserve data)
         #[arg(long)]
         keep_data: bool,
+
+        /// List everything that would be removed without removing it
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Rollback to a previous deployment
@@ -94,7 +223,7 @@ pub enum Commands {
         /// Name of the app to rollback
         app: String,
 
-        /// Target version (commit SHA). If not provided, rolls back to previous version
+        /// Target version (commit SHA or deploy tag). If not provided, rolls back to previous version
         #[arg(long)]
         to: Option<String>,
 
@@ -116,17 +245,40 @@ pub enum Commands {
         #[arg(long)]
         no_follow: bool,
 
-        /// Number of lines to show
-        #[arg(short = 'n', long, default_value = "100")]
-        lines: u32,
+        /// Number of lines to show (default: 100, or every line with --since)
+        #[arg(short = 'n', long)]
+        lines: Option<u32>,
 
         /// Filter by service: app, database, cache, or all
         #[arg(short, long, default_value = "app")]
         service: String,
 
-        /// Show logs since timestamp or duration (e.g., "1h", "30m", "2024-01-15")
+        /// Show logs since a duration ago or a timestamp (e.g., "10m", "2h", "1d",
+        /// "2024-01-15T10:30:00Z")
         #[arg(long)]
         since: Option<String>,
+
+        /// Show only logs produced since the latest deployment
+        #[arg(long, conflicts_with = "since")]
+        since_deploy: bool,
+
+        /// Read logs from this exact container (e.g., flaase-myapp-web-green)
+        #[arg(long, conflicts_with = "service")]
+        container: Option<String>,
+
+        /// Web container for blue-green apps: active (default), blue, or green
+        #[arg(
+            long,
+            value_name = "SLOT",
+            default_value = "active",
+            value_parser = ["active", "blue", "green"],
+            conflicts_with = "container"
+        )]
+        web_slot: String,
+
+        /// Print --no-follow output directly instead of through $PAGER
+        #[arg(long)]
+        no_pager: bool,
     },
 
     /// Manage environment variables
@@ -153,11 +305,165 @@ pub enum Commands {
         command: AuthCommands,
     },
 
+    /// Set up and test deployment notifications
+    Notify {
+        #[command(subcommand)]
+        command: NotificationCommands,
+    },
+
+    /// Manage scheduled jobs run in the app image
+    Cron {
+        #[command(subcommand)]
+        command: CronCommands,
+    },
+
     /// Webhook server for autodeploy
     Webhook {
         #[command(subcommand)]
         command: WebhookCommands,
     },
+
+    /// Manage the app's database
+    Db {
+        #[command(subcommand)]
+        command: DbCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AppCommands {
+    /// Print the full resolved app state as one JSON document (secrets redacted)
+    Info {
+        /// Name of the app
+        app: String,
+    },
+
+    /// Turn a container created outside Flaase into a managed image app
+    Adopt {
+        /// Name or ID of the existing container
+        container: String,
+
+        /// App name (default: the flaase.app label, else the container name)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Domain to route to the app (default: the flaase.domain label)
+        #[arg(long)]
+        domain: Option<String>,
+
+        /// Port the app listens on (default: the container's first exposed port)
+        #[arg(long)]
+        port: Option<u16>,
+    },
+
+    /// Print a secret-free config spec, re-importable with `fl init --from-file`
+    ExportConfig {
+        /// Name of the app
+        app: String,
+
+        /// Include the app's environment variables (may contain secrets)
+        #[arg(long)]
+        with_env: bool,
+    },
+
+    /// Show or set CPU/memory reservations and hard limits for the app
+    Resources {
+        /// Name of the app
+        app: String,
+
+        /// Relative CPU weight under contention (Docker default: 1024)
+        #[arg(long, value_parser = clap::value_parser!(u32).range(2..=262144))]
+        cpu_shares: Option<u32>,
+
+        /// Memory the container is guaranteed under pressure (e.g. 256m, 1g)
+        #[arg(long, value_name = "SIZE")]
+        memory_reservation: Option<String>,
+
+        /// Hard memory limit for the app container (e.g. 512m, 1g)
+        #[arg(long, value_name = "SIZE")]
+        memory: Option<String>,
+
+        /// Maximum CPUs for the app container (e.g. 1.5)
+        #[arg(long)]
+        cpus: Option<f64>,
+
+        /// Hard memory limit for each database and cache container (e.g. 256m)
+        #[arg(long, value_name = "SIZE")]
+        service_memory: Option<String>,
+
+        /// Maximum CPUs for each database and cache container (e.g. 0.5)
+        #[arg(long)]
+        service_cpus: Option<f64>,
+
+        /// Remove all reservations and limits (applied before any new values)
+        #[arg(long)]
+        clear: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RegistryCommands {
+    /// Verify and save credentials for an image app's private registry
+    Login {
+        /// Name of the app
+        app: String,
+
+        /// Registry username (prompted if omitted)
+        #[arg(long, short)]
+        username: Option<String>,
+
+        /// Read the password from stdin instead of prompting
+        #[arg(long)]
+        password_stdin: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DbCommands {
+    /// Dump the database to a file (gzipped when the name ends in .gz)
+    Backup {
+        /// Name of the app
+        app: String,
+
+        /// Output file (default: timestamped file under the app's data/backups)
+        #[arg(long, conflicts_with_all = ["schedule", "list", "unschedule"])]
+        output: Option<PathBuf>,
+
+        /// Install a systemd timer running the backup on this cron schedule
+        #[arg(long, value_name = "CRON", conflicts_with_all = ["list", "unschedule"])]
+        schedule: Option<String>,
+
+        /// Delete backups older than this many days (default for --schedule: 7)
+        #[arg(long)]
+        keep_days: Option<u32>,
+
+        /// List existing backups and the installed schedule
+        #[arg(long, conflicts_with = "unschedule")]
+        list: bool,
+
+        /// Remove the scheduled backup timer
+        #[arg(long)]
+        unschedule: bool,
+    },
+
+    /// Load a dump into the database, replacing its content (.gz is decompressed)
+    Restore {
+        /// Name of the app
+        app: String,
+
+        /// Dump file written by `fl db backup`
+        input: PathBuf,
+
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Open an interactive shell (psql, mysql, mariadb or mongosh) on the database
+    Connect {
+        /// Name of the app
+        app: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -167,10 +473,48 @@ pub enum ServerCommands {
         /// Run without making any changes
         #[arg(long)]
         dry_run: bool,
+
+        /// Answer all prompts with safe defaults (keep existing components)
+        #[arg(long)]
+        unattended: bool,
+
+        /// Email for Let's Encrypt certificate notifications
+        #[arg(long)]
+        acme_email: Option<String>,
+
+        /// Issue certificates from the Let's Encrypt staging CA (untrusted, for testing)
+        #[arg(long)]
+        acme_staging: bool,
     },
 
     /// Show server health status
-    Status,
+    Status {
+        /// List each app with its state and last deploy time
+        #[arg(long)]
+        apps: bool,
+    },
+
+    /// Show or change server-wide settings
+    Config {
+        /// Maximum number of image builds running at the same time
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=16))]
+        max_concurrent_builds: Option<u32>,
+
+        /// Path prefix of the webhook and log endpoints (default: /flaase)
+        #[arg(long, value_name = "PATH")]
+        webhook_prefix: Option<String>,
+
+        /// Port the webhook server listens on (default: 9876)
+        #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+        webhook_port: Option<u16>,
+    },
+
+    /// Update fl to the latest release
+    Update {
+        /// Install this release instead of the latest (e.g. 0.16.0)
+        #[arg(long)]
+        version: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -180,13 +524,21 @@ pub enum EnvCommands {
         /// Name of the app
         app: String,
 
-        /// Show actual values (requires confirmation)
+        /// Show actual values (requires confirmation, encrypted ones stay masked)
         #[arg(long)]
         show: bool,
 
+        /// Decrypt and show this encrypted variable (repeatable, requires confirmation)
+        #[arg(long, value_name = "KEY", conflicts_with = "diff_auto")]
+        show_key: Vec<String>,
+
         /// Target environment (default: production)
         #[arg(long, short)]
         env: Option<String>,
+
+        /// Only show user variables that override auto-generated ones
+        #[arg(long)]
+        diff_auto: bool,
     },
 
     /// Set environment variable(s)
@@ -194,13 +546,17 @@ pub enum EnvCommands {
         /// Name of the app
         app: String,
 
-        /// KEY=value pairs to set
+        /// KEY=value pairs to set (KEY=@path reads the value from a file)
         #[arg(required = true)]
         vars: Vec<String>,
 
         /// Target environment (default: production)
         #[arg(long, short)]
         env: Option<String>,
+
+        /// Mark the variables sensitive: store them encrypted and always mask them
+        #[arg(long)]
+        encrypt: bool,
     },
 
     /// Remove an environment variable
@@ -238,11 +594,77 @@ pub enum EnvCommands {
         to: String,
     },
 
+    /// Print variables in dotenv format, re-importable with `fl env import`
+    Export {
+        /// Name of the app
+        app: String,
+
+        /// Also print the generated variables from .env.auto
+        #[arg(long)]
+        include_auto: bool,
+
+        /// Print actual values instead of masking secret-looking ones
+        #[arg(long)]
+        show: bool,
+
+        /// Target environment (default: production)
+        #[arg(long, short)]
+        env: Option<String>,
+    },
+
+    /// Set variables from a dotenv file (e.g. the output of `fl env export --show`)
+    Import {
+        /// Name of the app
+        app: String,
+
+        /// Dotenv file to read
+        file: PathBuf,
+
+        /// Target environment (default: production)
+        #[arg(long, short)]
+        env: Option<String>,
+    },
+
+    /// Compare the variables of two apps
+    Diff {
+        /// First app
+        app_a: String,
+
+        /// Second app
+        app_b: String,
+
+        /// Print actual values of differing variables instead of masking them
+        #[arg(long)]
+        show: bool,
+    },
+
     /// List all environments with their variable counts
     Envs {
         /// Name of the app
         app: String,
     },
+
+    /// Mount generated secrets into the container as a file (shows status without flags)
+    SecretsFile {
+        /// Name of the app
+        app: String,
+
+        /// Path of the file inside the container (default: /run/secrets/app.env)
+        #[arg(long)]
+        path: Option<String>,
+
+        /// Only write these generated variables (comma-separated, default: all)
+        #[arg(long, value_delimiter = ',')]
+        keys: Vec<String>,
+
+        /// Stop passing generated secrets as environment variables
+        #[arg(long)]
+        file_only: Option<bool>,
+
+        /// Stop mounting the secrets file
+        #[arg(long, conflicts_with_all = ["path", "keys", "file_only"])]
+        disable: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -261,6 +683,10 @@ pub enum DomainCommands {
         /// Domain to add (e.g., api.example.com)
         domain: String,
 
+        /// Only route requests under this path (e.g. /api)
+        #[arg(long, value_name = "PATH")]
+        path_prefix: Option<String>,
+
         /// Skip DNS verification
         #[arg(long)]
         skip_dns_check: bool,
@@ -274,6 +700,62 @@ pub enum DomainCommands {
         /// Domain to remove
         domain: String,
     },
+
+    /// Route an app only under a path on one of its domains (omit PATH to clear)
+    PathPrefix {
+        /// Name of the app
+        app: String,
+
+        /// Domain to update
+        domain: String,
+
+        /// Path prefix (e.g. /api)
+        path: Option<String>,
+    },
+
+    /// Print the Traefik configuration generated for an app (nothing is written)
+    Config {
+        /// Name of the app
+        app: String,
+    },
+
+    /// Regenerate an app's Traefik configuration (restores edited or deleted files)
+    Sync {
+        /// Name of the app
+        app: String,
+    },
+
+    /// Manage imported TLS certificates
+    Cert {
+        #[command(subcommand)]
+        command: CertCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CertCommands {
+    /// Import a certificate (SANs and wildcards are served via SNI)
+    Import {
+        /// Name to store the certificate under
+        name: String,
+
+        /// Path to the PEM certificate (full chain)
+        #[arg(long)]
+        cert: String,
+
+        /// Path to the PEM private key
+        #[arg(long)]
+        key: String,
+    },
+
+    /// List imported certificates
+    List,
+
+    /// Remove an imported certificate
+    Remove {
+        /// Name of the certificate
+        name: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -286,18 +768,63 @@ pub enum AutodeployCommands {
         /// Branch to watch for deployments (default: main)
         #[arg(long, short)]
         branch: Option<String>,
+
+        /// Install the webhook server service without asking
+        #[arg(long, conflicts_with = "no_install")]
+        install: bool,
+
+        /// Don't offer to install the webhook server service (for scripts and CI)
+        #[arg(long)]
+        no_install: bool,
     },
 
     /// Disable auto-deployment
     Disable {
         /// Name of the app
         app: String,
+
+        /// Revoke the webhook secret so re-enabling can never bring it back
+        #[arg(long)]
+        rotate_on_disable: bool,
     },
 
     /// Show auto-deployment status
     Status {
+        /// Name of the app
+        #[arg(required_unless_present = "all")]
+        app: Option<String>,
+
+        /// Summarize autodeploy for every app
+        #[arg(long, conflicts_with = "app")]
+        all: bool,
+    },
+
+    /// Deploy when a Git tag matching a glob pattern is pushed
+    Tags {
         /// Name of the app
         app: String,
+
+        /// Tag pattern, `*` and `?` wildcards (e.g. "v*")
+        #[arg(required_unless_present = "disable")]
+        pattern: Option<String>,
+
+        /// Stop deploying on tag pushes
+        #[arg(long, conflicts_with = "pattern")]
+        disable: bool,
+    },
+
+    /// Only deploy branch pushes that change files under these paths (monorepos)
+    Paths {
+        /// Name of the app
+        app: String,
+
+        /// Path patterns, `*` and `?` wildcards (e.g. "apps/api" "packages/*")
+        #[arg(required_unless_present = "clear")]
+        patterns: Vec<String>,
+
+        /// Deploy on every push again
+        #[arg(long, conflicts_with = "patterns")]
+        clear: bool,
     },
 
     /// Show webhook secret (for reconfiguration)
@@ -320,9 +847,17 @@ pub enum AutodeployCommands {
         /// Number of recent deployments to show (default: 10)
         #[arg(long, short, default_value = "10")]
         limit: usize,
+
+        /// Skip this many of the most recent deployments
+        #[arg(long, default_value = "0")]
+        offset: usize,
+
+        /// Print the page as JSON, including the total deployment count
+        #[arg(long)]
+        json: bool,
     },
 
-    /// Configure notifications (Slack/Discord)
+    /// Configure notifications (Slack/Discord/Telegram/Email)
     #[command(subcommand)]
     Notify(NotifyCommands),
 
@@ -424,6 +959,10 @@ pub enum AutodeployCommands {
         /// Docker registry to use for cache (e.g., "registry.example.com/myapp")
         #[arg(long)]
         cache_from: Option<String>,
+
+        /// Where to keep build cache: previous image, or a local BuildKit cache dir
+        #[arg(long, value_parser = ["image", "local"])]
+        cache_mode: Option<String>,
     },
 
     /// Configure blue-green deployment (zero-downtime)
@@ -446,6 +985,14 @@ pub enum AutodeployCommands {
         /// Disable auto-cleanup of old container
         #[arg(long)]
         no_auto_cleanup: bool,
+
+        /// Stop the previous slot now, keeping the current one live
+        #[arg(long, conflicts_with_all = ["abort", "enable", "disable"])]
+        promote: bool,
+
+        /// Switch traffic back to the previous slot and remove the current one
+        #[arg(long, conflicts_with_all = ["promote", "enable", "disable"])]
+        abort: bool,
     },
 }
 
@@ -482,6 +1029,11 @@ pub enum HooksCommands {
         /// Run inside the app container instead of on host
         #[arg(long)]
         in_container: bool,
+
+        /// Run in a throwaway container from the app image (removed afterwards;
+        /// not for pre_build hooks)
+        #[arg(long, conflicts_with = "in_container")]
+        ephemeral: bool,
     },
 
     /// Remove a hook
@@ -580,6 +1132,69 @@ pub enum ApprovalCommands {
     },
 }
 
+#[derive(Subcommand)]
+pub enum NotificationCommands {
+    /// Configure a provider (Slack, Discord, Telegram) and events interactively
+    Set {
+        /// Name of the app
+        app: String,
+    },
+
+    /// Send a real test message to every configured provider
+    Test {
+        /// Name of the app
+        app: String,
+    },
+
+    /// Show notification configuration
+    Status {
+        /// Name of the app
+        app: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CronCommands {
+    /// List scheduled jobs
+    List {
+        /// Name of the app
+        app: String,
+    },
+
+    /// Schedule a command to run in a throwaway app container
+    Add {
+        /// Name of the app
+        app: String,
+
+        /// Job name (lowercase letters, digits and dashes)
+        name: String,
+
+        /// Cron expression (e.g., "* * * * *" for every minute)
+        schedule: String,
+
+        /// Shell command to run (e.g., "php artisan schedule:run")
+        command: String,
+    },
+
+    /// Remove a scheduled job
+    Remove {
+        /// Name of the app
+        app: String,
+
+        /// Job name
+        name: String,
+    },
+
+    /// Run a job once now (used by the systemd timer)
+    Run {
+        /// Name of the app
+        app: String,
+
+        /// Job name
+        name: String,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum NotifyCommands {
     /// Show notification configuration
@@ -640,6 +1255,24 @@ pub enum NotifyCommands {
         remove: bool,
     },
 
+    /// Configure Telegram notifications
+    Telegram {
+        /// Name of the app
+        app: String,
+
+        /// Bot token from @BotFather
+        #[arg(long)]
+        bot_token: Option<String>,
+
+        /// Chat, group or channel ID to post to
+        #[arg(long)]
+        chat_id: Option<String>,
+
+        /// Remove Telegram configuration
+        #[arg(long)]
+        remove: bool,
+    },
+
     /// Configure Email notifications (SMTP)
     Email {
         /// Name of the app
@@ -763,9 +1396,9 @@ pub enum AuthCommands {
 pub enum WebhookCommands {
     /// Start the webhook server
     Serve {
-        /// Port to listen on
-        #[arg(long, short, default_value = "9876")]
-        port: u16,
+        /// Port to listen on (default: the server's webhook port, 9876)
+        #[arg(long, short)]
+        port: Option<u16>,
 
         /// Host to bind to
         #[arg(long, default_value = "127.0.0.1")]
@@ -781,3 +1414,15 @@ pub enum WebhookCommands {
     /// Show webhook server status
     Status,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition() {
+        // Catches flags clashing with the global ones, e.g. `-y`
+        Cli::command().debug_assert();
+    }
+}
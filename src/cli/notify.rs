@@ -0,0 +1,153 @@
+//! Interactive notification setup (`fl notify`).
+
+use crate::core::app_config::{
+    AppConfig, DiscordNotificationConfig, NotificationConfig, SlackNotificationConfig,
+    TelegramNotificationConfig,
+};
+use crate::cli::autodeploy::notify_test;
+use crate::core::error::AppError;
+use crate::ui;
+
+/// Providers offered by the interactive setup, in prompt order.
+const PROVIDERS: &[&str] = &["Slack", "Discord", "Telegram"];
+
+/// Walks through configuring a notification provider and the events that
+/// trigger it, then offers to send a test message.
+pub fn set(app: &str) -> Result<(), AppError> {
+    let mut config = AppConfig::load(app)?;
+
+    // Notifications are sent by the webhook deploy pipeline
+    let Some(autodeploy) = config.autodeploy_config.as_mut() else {
+        return Err(AppError::Validation(format!(
+            "Autodeploy is not enabled for this app. Run 'fl autodeploy enable {}' first.",
+            app
+        )));
+    };
+
+    let notif = autodeploy
+        .notifications
+        .get_or_insert_with(NotificationConfig::default);
+
+    let provider = ui::select("Notification provider", PROVIDERS)?;
+    match PROVIDERS[provider] {
+        "Slack" => {
+            let current = notif.slack.as_ref().map(|s| s.webhook_url.as_str());
+            let webhook_url = prompt_webhook_url("Slack webhook URL", current)?;
+            if !webhook_url.starts_with("https://hooks.slack.com/") {
+                ui::warning("Webhook URL doesn't look like a Slack webhook URL");
+            }
+            let slack = notif.slack.get_or_insert_with(|| SlackNotificationConfig {
+                webhook_url: String::new(),
+                channel: None,
+                username: None,
+            });
+            slack.webhook_url = webhook_url;
+        }
+        "Discord" => {
+            let current = notif.discord.as_ref().map(|d| d.webhook_url.as_str());
+            let webhook_url = prompt_webhook_url("Discord webhook URL", current)?;
+            if !webhook_url.starts_with("https://discord.com/api/webhooks/")
+                && !webhook_url.starts_with("https://discordapp.com/api/webhooks/")
+            {
+                ui::warning("Webhook URL doesn't look like a Discord webhook URL");
+            }
+            let discord = notif.discord.get_or_insert_with(|| DiscordNotificationConfig {
+                webhook_url: String::new(),
+                username: None,
+            });
+            discord.webhook_url = webhook_url;
+        }
+        _ => {
+            let current = notif.telegram.clone();
+            let bot_token = match &current {
+                Some(telegram) if !ui::confirm("Replace the saved bot token?", false)? => {
+                    telegram.bot_token.clone()
+                }
+                _ => prompt_required_secret("Telegram bot token")?,
+            };
+            let chat_id = prompt_required(
+                "Telegram chat ID",
+                current.as_ref().map(|t| t.chat_id.as_str()).unwrap_or(""),
+            )?;
+            notif.telegram = Some(TelegramNotificationConfig { bot_token, chat_id });
+        }
+    }
+
+    println!();
+    notif.events.on_start = ui::confirm("Notify when a deployment starts?", notif.events.on_start)?;
+    notif.events.on_success =
+        ui::confirm("Notify when a deployment succeeds?", notif.events.on_success)?;
+    notif.events.on_failure =
+        ui::confirm("Notify when a deployment fails?", notif.events.on_failure)?;
+    notif.enabled = true;
+
+    config.save()?;
+
+    ui::success(&format!("{} notifications configured", PROVIDERS[provider]));
+
+    println!();
+    if ui::confirm("Send a test notification now?", true)? {
+        notify_test(app)?;
+    } else {
+        println!(
+            "  Test with: {}",
+            console::style(format!("fl notify test {}", app)).cyan()
+        );
+    }
+
+    Ok(())
+}
+
+/// Prompts for a webhook URL. The saved URL is a credential, so the default
+/// only shows its host; accepting it keeps the saved URL.
+fn prompt_webhook_url(prompt: &str, current: Option<&str>) -> Result<String, AppError> {
+    let masked = current.map(mask_webhook_url).unwrap_or_default();
+    let value = prompt_required(prompt, &masked)?;
+    match current {
+        Some(current) if value == masked => Ok(current.to_string()),
+        _ => Ok(value),
+    }
+}
+
+/// Keeps the scheme and host of a webhook URL and hides the rest.
+fn mask_webhook_url(url: &str) -> String {
+    let host_start = url.find("://").map_or(0, |i| i + 3);
+    let host_end = url[host_start..].find('/').map_or(url.len(), |i| host_start + i);
+    format!("{}/•••", &url[..host_end])
+}
+
+/// Prompts until a non-empty value is entered, keeping `current` on empty input.
+fn prompt_required(prompt: &str, current: &str) -> Result<String, AppError> {
+    loop {
+        let value = ui::input_with_default(prompt, current)?.trim().to_string();
+        if !value.is_empty() {
+            return Ok(value);
+        }
+        ui::warning("A value is required");
+    }
+}
+
+/// Prompts for a secret until a non-empty value is entered.
+fn prompt_required_secret(prompt: &str) -> Result<String, AppError> {
+    loop {
+        let value = ui::password(prompt)?.trim().to_string();
+        if !value.is_empty() {
+            return Ok(value);
+        }
+        ui::warning("A value is required");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_webhook_url() {
+        assert_eq!(
+            mask_webhook_url("https://hooks.slack.com/services/T000/B000/XXXX"),
+            "https://hooks.slack.com/•••"
+        );
+        assert_eq!(mask_webhook_url("https://example.com"), "https://example.com/•••");
+    }
+}
@@ -0,0 +1,63 @@
+//! Private registry command handlers.
+
+use std::io::Read;
+
+use crate::core::app_config::{AppConfig, RegistryCredentials};
+use crate::core::context::ExecutionContext;
+use crate::core::error::AppError;
+use crate::core::registry::{save_credentials, verify_credentials};
+use crate::ui;
+
+/// Verifies and stores registry credentials for an image app.
+pub fn login(
+    app: &str,
+    username: Option<&str>,
+    password_stdin: bool,
+    verbose: bool,
+) -> Result<(), AppError> {
+    let mut config = AppConfig::load(app)?;
+    let image = config.image.clone().ok_or_else(|| {
+        AppError::Validation(format!("App '{}' is not an image deployment", app))
+    })?;
+
+    let username = match username {
+        Some(username) => username.to_string(),
+        None => ui::input("Registry username?")?,
+    };
+    let password = if password_stdin {
+        let mut password = String::new();
+        std::io::stdin().read_to_string(&mut password)?;
+        password.trim_end_matches(['\n', '\r']).to_string()
+    } else {
+        ui::password("Registry password?")?
+    };
+
+    if username.is_empty() || password.is_empty() {
+        return Err(AppError::Validation("Username and password are required".into()));
+    }
+
+    let credentials = RegistryCredentials::new(&username, &password);
+    let ctx = ExecutionContext::new(false, verbose);
+
+    let spinner = ui::ProgressBar::spinner(&format!(
+        "Verifying access to {}",
+        image.full_reference()
+    ));
+    if let Err(e) = verify_credentials(&image, &credentials, &ctx) {
+        spinner.finish_error("failed");
+        return Err(e);
+    }
+    spinner.finish("verified");
+
+    save_credentials(&config.registry_auth_path(), &credentials)?;
+    if let Some(ref mut image) = config.image {
+        if !image.private {
+            image.private = true;
+            config.save()?;
+        }
+    }
+
+    ui::success(&format!("Registry credentials saved for {}", app));
+
+    Ok(())
+}
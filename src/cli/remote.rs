@@ -0,0 +1,122 @@
+//! Running `fl` against a remote server (`--server <alias>`).
+
+use std::io::IsTerminal;
+
+use crate::cli::{CertCommands, Commands, DbCommands, DomainCommands, EnvCommands};
+use crate::core::context::ExecutionContext;
+use crate::core::error::AppError;
+use crate::core::remote::ServersConfig;
+
+/// Runs this `fl` invocation on the server registered as `alias` and returns
+/// its exit code. App configs, history and containers all live on the
+/// server, so the whole command runs there rather than step by step.
+pub fn forward(alias: &str, command: Option<&Commands>, verbose: bool) -> Result<i32, AppError> {
+    if let Some(name) = command.and_then(local_file_command) {
+        return Err(AppError::Validation(format!(
+            "'fl {}' reads a local file, which the server can't see. Copy the file to \
+             the server (e.g. with scp) and run the command there.",
+            name
+        )));
+    }
+
+    let servers = ServersConfig::load()?;
+    let server = servers.resolve(alias)?.clone();
+
+    let args = forwarded_args(std::env::args().skip(1));
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    // A TTY keeps the remote prompts, spinners and colors when run by hand
+    let ssh_args = server.ssh_args("fl", &args, std::io::stdin().is_terminal());
+    let ssh_args: Vec<&str> = ssh_args.iter().map(String::as_str).collect();
+
+    ExecutionContext::new(false, verbose).run_interactive("ssh", &ssh_args)
+}
+
+/// Name of a command that takes a path on this machine, which the remote
+/// `fl` would resolve on the server instead.
+fn local_file_command(command: &Commands) -> Option<&'static str> {
+    match command {
+        Commands::Init {
+            from_file: Some(_),
+        } => Some("init --from-file"),
+        Commands::Env {
+            command: EnvCommands::Import { .. },
+        } => Some("env import"),
+        Commands::Db {
+            command: DbCommands::Restore { .. },
+        } => Some("db restore"),
+        Commands::Domain {
+            command:
+                DomainCommands::Cert {
+                    command: CertCommands::Import { .. },
+                },
+        } => Some("domain cert import"),
+        _ => None,
+    }
+}
+
+/// Drops the `--server <alias>` flag from the command line, keeping the rest
+/// (including `--verbose`) for the remote `fl`.
+fn forwarded_args(args: impl Iterator<Item = String>) -> Vec<String> {
+    let mut forwarded = Vec::new();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        if arg == "--server" {
+            args.next();
+        } else if !arg.starts_with("--server=") {
+            // Everything after `--` belongs to the command, not to fl
+            let end_of_flags = arg == "--";
+            forwarded.push(arg);
+            if !end_of_flags {
+                continue;
+            }
+        }
+        forwarded.extend(args);
+        break;
+    }
+
+    forwarded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::Parser;
+
+    fn forward_of(line: &str) -> Vec<String> {
+        forwarded_args(line.split(' ').map(String::from))
+    }
+
+    fn local_file_command_of(line: &str) -> Option<&'static str> {
+        let cli = Cli::try_parse_from(format!("fl {}", line).split(' ')).unwrap();
+        cli.command.as_ref().and_then(local_file_command)
+    }
+
+    #[test]
+    fn test_local_file_commands_are_not_forwarded() {
+        assert_eq!(local_file_command_of("init --from-file app.yml"), Some("init --from-file"));
+        assert_eq!(local_file_command_of("env import shop .env"), Some("env import"));
+        assert_eq!(local_file_command_of("db restore shop dump.sql"), Some("db restore"));
+        assert_eq!(
+            local_file_command_of("domain cert import shop --cert a.pem --key b.pem"),
+            Some("domain cert import")
+        );
+
+        assert_eq!(local_file_command_of("init"), None);
+        assert_eq!(local_file_command_of("deploy shop"), None);
+    }
+
+    #[test]
+    fn test_forwarded_args() {
+        assert_eq!(forward_of("deploy shop --server prod"), ["deploy", "shop"]);
+        assert_eq!(forward_of("--server=prod -v logs shop"), ["-v", "logs", "shop"]);
+
+        // Only the flag parsed by fl is dropped, not one meant for the app
+        assert_eq!(
+            forward_of("exec shop --server prod -- app --server x"),
+            ["exec", "shop", "--", "app", "--server", "x"]
+        );
+    }
+}
@@ -1,15 +1,78 @@
-use crate::core::config::{ExistingComponentAction, ServerConfig, FLAASE_BASE_PATH};
+use crate::core::config::{
+    normalize_webhook_prefix, ExistingComponentAction, ServerConfig, FLAASE_BASE_PATH,
+};
 use crate::core::context::ExecutionContext;
 use crate::core::error::AppError;
 use crate::providers::{
     create_container_runtime, create_firewall, create_package_manager, create_reverse_proxy,
-    ContainerRuntime, Firewall, PackageManager, Protocol, RequiredPorts, ReverseProxy,
-    SystemProvider, UserManager,
+    ContainerRuntime, Firewall, PackageManager, PortListener, Protocol, RequiredPorts,
+    ReverseProxy, SystemProvider, UserManager,
 };
 use crate::ui;
 
+/// Shows server-wide settings, updating the ones given.
+pub fn config(
+    max_concurrent_builds: Option<u32>,
+    webhook_prefix: Option<&str>,
+    webhook_port: Option<u16>,
+) -> Result<(), AppError> {
+    let mut config = ServerConfig::load()?;
+
+    if max_concurrent_builds.is_some() || webhook_prefix.is_some() || webhook_port.is_some() {
+        if let Some(max) = max_concurrent_builds {
+            config.server.max_concurrent_builds = Some(max);
+            ui::success(&format!("Max concurrent builds set to {}", max));
+        }
+        if let Some(prefix) = webhook_prefix {
+            let prefix = normalize_webhook_prefix(prefix)?;
+            ui::success(&format!("Webhook prefix set to {}", prefix));
+            config.server.webhook_prefix = Some(prefix);
+        }
+        if let Some(port) = webhook_port {
+            config.server.webhook_port = Some(port);
+            ui::success(&format!("Webhook port set to {}", port));
+        }
+
+        config.server.updated_at = Some(chrono::Utc::now());
+        config.save()?;
+
+        if webhook_prefix.is_some() || webhook_port.is_some() {
+            ui::info("Run 'fl webhook install' to apply the change to routing and the service.");
+            if webhook_prefix.is_some() {
+                ui::warning(
+                    "Update the webhook URL at your Git provider ('fl autodeploy status <app>').",
+                );
+            }
+        }
+        return Ok(());
+    }
+
+    ui::section("Server Config");
+    println!("  Email:                 {}", config.server.email);
+    if config.server.acme_staging {
+        println!("  Certificates:          Let's Encrypt staging (untrusted)");
+    }
+    println!(
+        "  Max concurrent builds: {}",
+        config.server.effective_max_concurrent_builds()
+    );
+    println!("  Webhook prefix:        {}", config.server.effective_webhook_prefix());
+    println!("  Webhook port:          {}", config.server.effective_webhook_port());
+
+    Ok(())
+}
+
 /// Executes the server init command.
-pub fn init(dry_run: bool, verbose: bool) -> Result<(), AppError> {
+///
+/// In unattended mode no prompt is shown: existing components are kept and
+/// the ACME email must be provided with `--acme-email`.
+pub fn init(
+    dry_run: bool,
+    verbose: bool,
+    unattended: bool,
+    acme_email: Option<String>,
+    acme_staging: bool,
+) -> Result<(), AppError> {
     ui::header();
 
     // Create execution context
@@ -20,6 +83,12 @@ pub fn init(dry_run: bool, verbose: bool) -> Result<(), AppError> {
         println!();
     }
 
+    if unattended && acme_email.is_none() {
+        return Err(AppError::Validation(
+            "--acme-email is required when running unattended".into(),
+        ));
+    }
+
     // Step 1: Check root privileges
     ui::info("Checking root privileges...");
     if dry_run {
@@ -36,6 +105,18 @@ pub fn init(dry_run: bool, verbose: bool) -> Result<(), AppError> {
 
     SystemProvider::validate_os(&os_info)?;
     ui::success("Operating system is supported");
+
+    // Missing kernel features only surface later as obscure container errors
+    ui::info("Checking kernel prerequisites...");
+    let kernel_warnings = SystemProvider::check_kernel_prerequisites();
+    if kernel_warnings.is_empty() {
+        ui::success("Kernel supports cgroups, swap accounting and overlayfs");
+    } else {
+        for warning in &kernel_warnings {
+            ui::warning(&warning.message);
+            ui::info(&format!("  {}", warning.remediation));
+        }
+    }
     println!();
 
     // Initialize providers
@@ -45,7 +126,7 @@ pub fn init(dry_run: bool, verbose: bool) -> Result<(), AppError> {
     let reverse_proxy = create_reverse_proxy();
 
     // Step 3: Install container runtime (Docker)
-    install_container_runtime(&*container_runtime, &*pkg_manager, &ctx)?;
+    install_container_runtime(&*container_runtime, &*pkg_manager, unattended, &ctx)?;
 
     // Step 4: Configure firewall
     configure_firewall(&*firewall, &*pkg_manager, &ctx)?;
@@ -57,16 +138,35 @@ pub fn init(dry_run: bool, verbose: bool) -> Result<(), AppError> {
     let user_info = create_deploy_user(&ctx)?;
 
     // Step 7: Get email for SSL
-    println!();
-    ui::info("Email is required for SSL certificate notifications (Let's Encrypt).");
-    let email = ui::input("Email for SSL certificates")?;
+    let email = match acme_email {
+        Some(email) => email.trim().to_string(),
+        None => {
+            println!();
+            ui::info("Email is required for SSL certificate notifications (Let's Encrypt).");
+            ui::input("Email for SSL certificates")?
+        }
+    };
 
     if email.is_empty() {
         return Err(AppError::Config("Email is required".into()));
     }
 
+    if !email.contains('@') {
+        return Err(AppError::Validation(format!(
+            "Invalid email address: {}",
+            email
+        )));
+    }
+
     // Step 8: Install reverse proxy (Traefik)
-    install_reverse_proxy(&*reverse_proxy, &*container_runtime, &email, &ctx)?;
+    install_reverse_proxy(
+        &*reverse_proxy,
+        &*container_runtime,
+        &email,
+        acme_staging,
+        unattended,
+        &ctx,
+    )?;
 
     // Step 9: Save configuration
     println!();
@@ -75,7 +175,14 @@ pub fn init(dry_run: bool, verbose: bool) -> Result<(), AppError> {
     let runtime_info = container_runtime.get_info(&ctx)?;
     let proxy_info = reverse_proxy.get_info(&*container_runtime, &ctx)?;
 
-    let config = ServerConfig::new(email, os_info, runtime_info, proxy_info, user_info.into());
+    let mut config = ServerConfig::new(email, os_info, runtime_info, proxy_info, user_info.into());
+    config.server.acme_staging = acme_staging;
+    // Keep settings changed with `fl server config` across re-inits
+    if let Ok(existing) = ServerConfig::load() {
+        config.server.max_concurrent_builds = existing.server.max_concurrent_builds;
+        config.server.webhook_prefix = existing.server.webhook_prefix;
+        config.server.webhook_port = existing.server.webhook_port;
+    }
 
     if !ctx.is_dry_run() {
         config.save()?;
@@ -97,6 +204,7 @@ pub fn init(dry_run: bool, verbose: bool) -> Result<(), AppError> {
 fn install_container_runtime(
     runtime: &dyn ContainerRuntime,
     pkg_manager: &dyn PackageManager,
+    unattended: bool,
     ctx: &ExecutionContext,
 ) -> Result<(), AppError> {
     ui::info(&format!("Checking {}...", runtime.name()));
@@ -114,7 +222,7 @@ fn install_container_runtime(
         ));
 
         // Ask what to do
-        let action = ask_existing_action(runtime.name())?;
+        let action = ask_existing_action(runtime.name(), unattended)?;
 
         match action {
             ExistingComponentAction::Skip => {
@@ -151,6 +259,17 @@ fn install_container_runtime(
         "{} service is running and enabled",
         runtime.name()
     ));
+
+    // Apply daemon defaults (log rotation, storage driver)
+    ui::info(&format!("Configuring {} daemon defaults...", runtime.name()));
+    if runtime.configure_daemon(ctx)? {
+        ui::success("Log rotation and storage defaults configured");
+    } else {
+        ui::warning(&format!(
+            "Custom {} configuration found, keeping it unchanged",
+            runtime.name()
+        ));
+    }
     println!();
 
     Ok(())
@@ -257,6 +376,8 @@ fn install_reverse_proxy(
     proxy: &dyn ReverseProxy,
     runtime: &dyn ContainerRuntime,
     email: &str,
+    acme_staging: bool,
+    unattended: bool,
     ctx: &ExecutionContext,
 ) -> Result<(), AppError> {
     ui::info(&format!("Checking {}...", proxy.name()));
@@ -272,7 +393,7 @@ fn install_reverse_proxy(
                 .unwrap_or_else(|_| "unknown".to_string());
             ui::success(&format!("{} {} is already running", proxy.name(), version));
 
-            let action = ask_existing_action(proxy.name())?;
+            let action = ask_existing_action(proxy.name(), unattended)?;
 
             match action {
                 ExistingComponentAction::Skip => {
@@ -281,7 +402,7 @@ fn install_reverse_proxy(
                 }
                 ExistingComponentAction::Update | ExistingComponentAction::Reinstall => {
                     ui::info(&format!("Reinstalling {}...", proxy.name()));
-                    proxy.install(runtime, email, ctx)?;
+                    proxy.install(runtime, email, acme_staging, ctx)?;
                     ui::success(&format!("{} reinstalled", proxy.name()));
                 }
             }
@@ -290,13 +411,15 @@ fn install_reverse_proxy(
                 "{} container exists but is not running",
                 proxy.name()
             ));
+            resolve_port_conflicts(proxy.name(), unattended, ctx)?;
             ui::info(&format!("Starting {}...", proxy.name()));
-            proxy.install(runtime, email, ctx)?;
+            proxy.install(runtime, email, acme_staging, ctx)?;
             ui::success(&format!("{} started", proxy.name()));
         }
     } else {
+        resolve_port_conflicts(proxy.name(), unattended, ctx)?;
         ui::info(&format!("Installing {}...", proxy.name()));
-        proxy.install(runtime, email, ctx)?;
+        proxy.install(runtime, email, acme_staging, ctx)?;
         ui::success(&format!("{} installed and running", proxy.name()));
     }
 
@@ -304,8 +427,72 @@ fn install_reverse_proxy(
     Ok(())
 }
 
+/// Makes sure nothing else listens on 80/443 before the reverse proxy starts,
+/// offering to stop and disable known web servers (Apache, nginx, ...).
+fn resolve_port_conflicts(
+    proxy_name: &str,
+    unattended: bool,
+    ctx: &ExecutionContext,
+) -> Result<(), AppError> {
+    const PROXY_PORTS: [u16; 2] = [80, 443];
+
+    let listeners = SystemProvider::port_listeners(&PROXY_PORTS, ctx);
+    if listeners.is_empty() {
+        return Ok(());
+    }
+
+    for listener in &listeners {
+        ui::warning(&format!(
+            "Port {} is already in use by {}",
+            listener.port,
+            listener.process.as_deref().unwrap_or("an unknown process")
+        ));
+    }
+
+    let mut services: Vec<&str> = listeners
+        .iter()
+        .filter_map(PortListener::stoppable_service)
+        .collect();
+    services.sort_unstable();
+    services.dedup();
+
+    let all_stoppable = listeners.iter().all(|l| l.stoppable_service().is_some());
+    if all_stoppable && !unattended {
+        let prompt = format!(
+            "Stop and disable {} so {} can use ports 80/443?",
+            services.join(", "),
+            proxy_name
+        );
+        if ui::confirm(&prompt, true)? {
+            for service in &services {
+                ctx.run_command("systemctl", &["disable", "--now", service])?
+                    .ensure_success(&format!("Failed to stop {}", service))?;
+                ui::success(&format!("Stopped and disabled {}", service));
+            }
+
+            if SystemProvider::port_listeners(&PROXY_PORTS, ctx).is_empty() {
+                return Ok(());
+            }
+        }
+    }
+
+    Err(AppError::Config(format!(
+        "{} needs ports 80 and 443. Stop the process listening on them \
+         (e.g. 'systemctl disable --now apache2') and run 'fl server init' again.",
+        proxy_name
+    )))
+}
+
 /// Asks the user what to do with an existing component.
-fn ask_existing_action(component_name: &str) -> Result<ExistingComponentAction, AppError> {
+/// Unattended runs always keep the existing component.
+fn ask_existing_action(
+    component_name: &str,
+    unattended: bool,
+) -> Result<ExistingComponentAction, AppError> {
+    if unattended {
+        return Ok(ExistingComponentAction::Skip);
+    }
+
     let options = ["Skip (keep existing)", "Update", "Reinstall"];
     let selected = ui::select(
         &format!(
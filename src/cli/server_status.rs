@@ -5,12 +5,16 @@ use console::{style, Term};
 use serde::Deserialize;
 use std::process::Command;
 
+use crate::cli::status::format_relative_time;
 use crate::core::app_config::AppConfig;
-use crate::core::config::{ServerConfig, FLAASE_TRAEFIK_PATH};
+use crate::core::certs::{domain_matches, CertificateStore};
+use crate::core::config::{ServerConfig, FLAASE_TRAEFIK_DYNAMIC_PATH, FLAASE_TRAEFIK_PATH};
 use crate::core::context::ExecutionContext;
 use crate::core::error::AppError;
-use crate::providers::container::{ContainerRuntime, DockerRuntime};
-use crate::providers::reverse_proxy::TraefikProxy;
+use crate::providers::container::{create_container_runtime, ContainerRuntime};
+use crate::providers::reverse_proxy::{
+    find_invalid_dynamic_configs, InvalidProxyConfig, TraefikProxy,
+};
 use crate::providers::ReverseProxy;
 use crate::ui;
 
@@ -121,6 +125,24 @@ struct AppsSummary {
     stopped: usize,
     error: usize,
     not_deployed: usize,
+    /// Deployed apps that are stopped or in error.
+    needs_attention: Vec<AppAttention>,
+    /// Every configured app, for the per-app listing.
+    apps: Vec<AppLine>,
+}
+
+/// One app in the per-app listing.
+struct AppLine {
+    name: String,
+    state: &'static str,
+    deployed_at: Option<DateTime<Utc>>,
+}
+
+/// An app that is down, with its last-known domain.
+struct AppAttention {
+    name: String,
+    state: &'static str,
+    domain: Option<String>,
 }
 
 impl AppsSummary {
@@ -155,9 +177,17 @@ impl AppsSummary {
 /// SSL certificate info.
 struct SslInfo {
     domain: String,
+    /// Additional names covered by the certificate.
+    sans: Vec<String>,
     expires_at: Option<DateTime<Utc>>,
 }
 
+/// A configured app domain without any certificate.
+struct MissingCertificate {
+    domain: String,
+    app: String,
+}
+
 impl SslInfo {
     fn format_expiry(&self) -> String {
         match self.expires_at {
@@ -181,8 +211,8 @@ impl SslInfo {
     }
 }
 
-/// Gets Docker service status and version.
-fn get_docker_info(runtime: &DockerRuntime, ctx: &ExecutionContext) -> ServiceInfo {
+/// Gets container runtime service status and version.
+fn get_runtime_info(runtime: &dyn ContainerRuntime, ctx: &ExecutionContext) -> ServiceInfo {
     let is_running = runtime.is_running(ctx).unwrap_or(false);
     let is_installed = runtime.is_installed(ctx).unwrap_or(false);
 
@@ -204,7 +234,7 @@ fn get_docker_info(runtime: &DockerRuntime, ctx: &ExecutionContext) -> ServiceIn
     };
 
     ServiceInfo {
-        name: "Docker".to_string(),
+        name: runtime.name().to_string(),
         status,
         version,
     }
@@ -213,7 +243,7 @@ fn get_docker_info(runtime: &DockerRuntime, ctx: &ExecutionContext) -> ServiceIn
 /// Gets Traefik service status and version.
 fn get_traefik_info(
     proxy: &TraefikProxy,
-    runtime: &DockerRuntime,
+    runtime: &dyn ContainerRuntime,
     ctx: &ExecutionContext,
 ) -> ServiceInfo {
     let is_running = proxy.is_running(runtime, ctx).unwrap_or(false);
@@ -343,13 +373,16 @@ fn get_disk_info() -> Option<DiskInfo> {
     None
 }
 
-/// Gets server uptime.
-fn get_uptime() -> Option<String> {
+/// Uptime below which the host is flagged as recently rebooted.
+const RECENT_REBOOT_SECS: u64 = 30 * 60;
+
+/// Gets server uptime in seconds.
+fn get_uptime() -> Option<u64> {
     // Try /proc/uptime first (Linux)
     if let Ok(content) = std::fs::read_to_string("/proc/uptime") {
         if let Some(seconds_str) = content.split_whitespace().next() {
             if let Ok(seconds) = seconds_str.parse::<f64>() {
-                return Some(format_uptime(seconds as u64));
+                return Some(seconds as u64);
             }
         }
     }
@@ -362,7 +395,7 @@ fn get_uptime() -> Option<String> {
     if let Ok(boot_time) = chrono::NaiveDateTime::parse_from_str(&stdout, "%Y-%m-%d %H:%M:%S") {
         let boot_utc = Utc.from_utc_datetime(&boot_time);
         let duration = Utc::now().signed_duration_since(boot_utc);
-        return Some(format_uptime(duration.num_seconds() as u64));
+        return Some(duration.num_seconds().max(0) as u64);
     }
 
     None
@@ -384,7 +417,7 @@ fn format_uptime(total_seconds: u64) -> String {
 }
 
 /// Gets apps summary (running/stopped counts).
-fn get_apps_summary(runtime: &DockerRuntime, ctx: &ExecutionContext) -> AppsSummary {
+fn get_apps_summary(runtime: &dyn ContainerRuntime, ctx: &ExecutionContext) -> AppsSummary {
     let app_names = AppConfig::list_all().unwrap_or_default();
 
     let mut summary = AppsSummary {
@@ -392,30 +425,70 @@ fn get_apps_summary(runtime: &DockerRuntime, ctx: &ExecutionContext) -> AppsSumm
         stopped: 0,
         error: 0,
         not_deployed: 0,
+        needs_attention: Vec::new(),
+        apps: Vec::new(),
     };
 
     for name in app_names {
         match AppConfig::load(&name) {
             Ok(config) => {
+                let line = |state| AppLine {
+                    name: name.clone(),
+                    state,
+                    deployed_at: config.deployed_at,
+                };
+
                 if config.deployed_at.is_none() {
                     summary.not_deployed += 1;
+                    summary.apps.push(line("not deployed"));
                     continue;
                 }
 
+                let domain = Some(config.primary_domain().to_string());
                 let container_name = format!("flaase-{}-web", name);
-                match runtime.container_is_running(&container_name, ctx) {
-                    Ok(true) => summary.running += 1,
-                    Ok(false) => {
-                        match runtime.container_exists(&container_name, ctx) {
-                            Ok(true) => summary.stopped += 1,
-                            Ok(false) => summary.not_deployed += 1,
-                            Err(_) => summary.error += 1,
+                let state = match runtime.container_is_running(&container_name, ctx) {
+                    Ok(true) => {
+                        summary.running += 1;
+                        summary.apps.push(line("running"));
+                        continue;
+                    }
+                    Ok(false) => match runtime.container_exists(&container_name, ctx) {
+                        Ok(true) => {
+                            summary.stopped += 1;
+                            "stopped"
                         }
+                        Ok(false) => {
+                            summary.not_deployed += 1;
+                            summary.apps.push(line("not deployed"));
+                            continue;
+                        }
+                        Err(_) => {
+                            summary.error += 1;
+                            "error"
+                        }
+                    },
+                    Err(_) => {
+                        summary.error += 1;
+                        "error"
                     }
-                    Err(_) => summary.error += 1,
-                }
+                };
+
+                summary.apps.push(line(state));
+                summary.needs_attention.push(AppAttention { name, state, domain });
+            }
+            Err(_) => {
+                summary.error += 1;
+                summary.apps.push(AppLine {
+                    name: name.clone(),
+                    state: "invalid config",
+                    deployed_at: None,
+                });
+                summary.needs_attention.push(AppAttention {
+                    name,
+                    state: "invalid config",
+                    domain: None,
+                });
             }
-            Err(_) => summary.error += 1,
         }
     }
 
@@ -444,6 +517,8 @@ struct AcmeCertificate {
 #[derive(Debug, Deserialize)]
 struct AcmeDomain {
     main: String,
+    #[serde(default)]
+    sans: Option<Vec<String>>,
 }
 
 /// Gets SSL certificate information from acme.json.
@@ -471,6 +546,7 @@ fn get_ssl_info() -> Vec<SslInfo> {
         let expires_at = parse_certificate_expiry(&cert.certificate);
         ssl_infos.push(SslInfo {
             domain: cert.domain.main,
+            sans: cert.domain.sans.unwrap_or_default(),
             expires_at,
         });
     }
@@ -478,6 +554,37 @@ fn get_ssl_info() -> Vec<SslInfo> {
     ssl_infos
 }
 
+/// Lists domains of deployed apps covered by neither an ACME certificate
+/// nor an imported one (usually a DNS or issuance problem).
+fn find_missing_certificates(ssl_infos: &[SslInfo]) -> Vec<MissingCertificate> {
+    let imported = CertificateStore::load().unwrap_or_default();
+    let mut missing = Vec::new();
+
+    for name in AppConfig::list_all().unwrap_or_default() {
+        let config = match AppConfig::load(&name) {
+            Ok(config) if config.deployed_at.is_some() => config,
+            _ => continue,
+        };
+
+        for domain in &config.domains {
+            let has_acme = ssl_infos.iter().any(|ssl| {
+                std::iter::once(&ssl.domain)
+                    .chain(ssl.sans.iter())
+                    .any(|pattern| domain_matches(pattern, &domain.domain))
+            });
+
+            if !has_acme && imported.find_for(&domain.domain).is_none() {
+                missing.push(MissingCertificate {
+                    domain: domain.domain.clone(),
+                    app: config.name.clone(),
+                });
+            }
+        }
+    }
+
+    missing
+}
+
 /// Parses certificate expiry from base64 encoded certificate.
 fn parse_certificate_expiry(cert_base64: &str) -> Option<DateTime<Utc>> {
     use base64::{engine::general_purpose::STANDARD, Engine};
@@ -546,12 +653,22 @@ fn print_services_table(term: &Term, services: &[ServiceInfo]) {
 }
 
 /// Prints the resources section.
-fn print_resources(term: &Term, cpu: Option<f64>, memory: Option<&MemoryInfo>, disk: Option<&DiskInfo>, uptime: Option<&str>) {
+fn print_resources(term: &Term, cpu: Option<f64>, memory: Option<&MemoryInfo>, disk: Option<&DiskInfo>, uptime: Option<u64>) {
     ui::section("Resources");
 
-    // Uptime
-    if let Some(up) = uptime {
-        let _ = term.write_line(&format!("  {:<12}  {}", style("Uptime").dim(), up));
+    // Uptime, highlighted when the host came back from a reboot recently
+    if let Some(secs) = uptime {
+        let note = if secs < RECENT_REBOOT_SECS {
+            format!("  {}", style("⚠ recently rebooted").yellow().bold())
+        } else {
+            String::new()
+        };
+        let _ = term.write_line(&format!(
+            "  {:<12}  {}{}",
+            style("Uptime").dim(),
+            format_uptime(secs),
+            note
+        ));
     }
 
     // CPU
@@ -590,8 +707,8 @@ fn print_resources(term: &Term, cpu: Option<f64>, memory: Option<&MemoryInfo>, d
 }
 
 /// Prints the SSL certificates section.
-fn print_ssl_info(term: &Term, ssl_infos: &[SslInfo]) {
-    if ssl_infos.is_empty() {
+fn print_ssl_info(term: &Term, ssl_infos: &[SslInfo], missing: &[MissingCertificate]) {
+    if ssl_infos.is_empty() && missing.is_empty() {
         return;
     }
 
@@ -613,10 +730,38 @@ fn print_ssl_info(term: &Term, ssl_infos: &[SslInfo]) {
             ssl.format_expiry()
         ));
     }
+
+    for cert in missing {
+        let _ = term.write_line(&format!(
+            "  {:<30}  {} {}",
+            cert.domain,
+            style("no certificate").red(),
+            style(format!("({})", cert.app)).dim()
+        ));
+    }
 }
 
-/// Prints the apps summary.
-fn print_apps_summary(term: &Term, summary: &AppsSummary) {
+/// Prints Traefik dynamic config files that fail to parse.
+fn print_invalid_proxy_configs(term: &Term, invalid: &[InvalidProxyConfig]) {
+    if invalid.is_empty() {
+        return;
+    }
+
+    ui::section("Proxy Config");
+
+    for config in invalid {
+        let _ = term.write_line(&format!(
+            "  {} {}  {}",
+            style("✗").red(),
+            config.path.display(),
+            style("invalid YAML, ignored by Traefik").red()
+        ));
+        let _ = term.write_line(&format!("    {}", style(&config.error).dim()));
+    }
+}
+
+/// Prints the apps summary, optionally listing each app's last deploy.
+fn print_apps_summary(term: &Term, summary: &AppsSummary, show_apps: bool) {
     ui::section("Apps");
 
     if summary.total() == 0 {
@@ -624,10 +769,55 @@ fn print_apps_summary(term: &Term, summary: &AppsSummary) {
     } else {
         let _ = term.write_line(&format!("  {}", summary.format()));
     }
+
+    if show_apps && !summary.apps.is_empty() {
+        println!();
+        let _ = term.write_line(&format!(
+            "  {:<20}  {:<14}  {}",
+            style("APP").bold(),
+            style("STATE").bold(),
+            style("LAST DEPLOY").bold()
+        ));
+
+        for app in &summary.apps {
+            let state = match app.state {
+                "running" => style(app.state).green(),
+                "not deployed" => style(app.state).dim(),
+                _ => style(app.state).red(),
+            };
+            let last_deploy = app
+                .deployed_at
+                .map(format_relative_time)
+                .unwrap_or_else(|| "never".to_string());
+
+            let _ = term.write_line(&format!(
+                "  {:<20}  {:<14}  {}",
+                app.name,
+                state,
+                style(last_deploy).dim()
+            ));
+        }
+    }
+
+    if summary.needs_attention.is_empty() {
+        return;
+    }
+
+    ui::section("Needs attention");
+
+    for app in &summary.needs_attention {
+        let _ = term.write_line(&format!(
+            "  {} {:<20}  {:<14}  {}",
+            style("✗").red(),
+            app.name,
+            style(app.state).red(),
+            style(app.domain.as_deref().unwrap_or("-")).dim()
+        ));
+    }
 }
 
 /// Main server status command handler.
-pub fn status(_verbose: bool) -> Result<i32, AppError> {
+pub fn status(show_apps: bool, _verbose: bool) -> Result<i32, AppError> {
     let term = Term::stdout();
 
     // Check if server is initialized
@@ -638,13 +828,13 @@ pub fn status(_verbose: bool) -> Result<i32, AppError> {
     }
 
     let ctx = ExecutionContext::new(false, false);
-    let runtime = DockerRuntime::new();
+    let runtime = create_container_runtime();
     let proxy = TraefikProxy::new();
 
     // Gather service information
-    let docker_info = get_docker_info(&runtime, &ctx);
-    let traefik_info = get_traefik_info(&proxy, &runtime, &ctx);
-    let services = vec![docker_info, traefik_info];
+    let runtime_info = get_runtime_info(&*runtime, &ctx);
+    let traefik_info = get_traefik_info(&proxy, &*runtime, &ctx);
+    let services = vec![runtime_info, traefik_info];
 
     // Gather resource information
     let cpu = get_cpu_usage();
@@ -653,16 +843,22 @@ pub fn status(_verbose: bool) -> Result<i32, AppError> {
     let uptime = get_uptime();
 
     // Gather apps summary
-    let apps_summary = get_apps_summary(&runtime, &ctx);
+    let apps_summary = get_apps_summary(&*runtime, &ctx);
 
     // Gather SSL info
     let ssl_infos = get_ssl_info();
+    let missing_certs = find_missing_certificates(&ssl_infos);
+
+    // Check Traefik dynamic configs parse
+    let invalid_configs =
+        find_invalid_dynamic_configs(std::path::Path::new(FLAASE_TRAEFIK_DYNAMIC_PATH));
 
     // Print everything
     print_services_table(&term, &services);
-    print_resources(&term, cpu, memory.as_ref(), disk.as_ref(), uptime.as_deref());
-    print_ssl_info(&term, &ssl_infos);
-    print_apps_summary(&term, &apps_summary);
+    print_resources(&term, cpu, memory.as_ref(), disk.as_ref(), uptime);
+    print_ssl_info(&term, &ssl_infos, &missing_certs);
+    print_invalid_proxy_configs(&term, &invalid_configs);
+    print_apps_summary(&term, &apps_summary, show_apps);
 
     println!();
 
@@ -0,0 +1,324 @@
+//! Self-update of the `fl` binary (`fl server update`).
+
+use std::fs;
+use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::cli::webhook::deployment_in_progress;
+use crate::core::app_config::AppConfig;
+use crate::core::build_slots;
+use crate::core::error::AppError;
+use crate::core::secrets::SecretsManager;
+use crate::providers::SystemProvider;
+use crate::ui;
+
+/// GitHub repository publishing the release binaries.
+const REPOSITORY: &str = "MaxenceMahieux/flaase-cli-rust";
+
+/// Where the install script puts the binary.
+const INSTALL_PATH: &str = "/usr/local/bin/flaase";
+
+/// Short name symlinked to the binary.
+const SYMLINK_PATH: &str = "/usr/local/bin/fl";
+
+#[derive(Deserialize)]
+struct Release {
+    tag_name: String,
+}
+
+/// Replaces the installed binary with the latest release, or `version`.
+pub fn update(version: Option<&str>, verbose: bool) -> Result<(), AppError> {
+    SystemProvider::require_root()?;
+
+    if let Some(activity) = deploy_activity()? {
+        return Err(AppError::Validation(format!(
+            "Can't update while {} is in progress. Retry once it has finished.",
+            activity
+        )));
+    }
+
+    let current = env!("CARGO_PKG_VERSION");
+    let asset = release_asset()?;
+
+    let target = match version {
+        Some(version) => normalize_version(version)?,
+        None => {
+            let spinner = ui::ProgressBar::spinner("Checking the latest release");
+            let latest = latest_version()?;
+            spinner.finish(&latest);
+            latest
+        }
+    };
+
+    if target == current {
+        ui::success(&format!("fl is already at version {}", current));
+        return Ok(());
+    }
+
+    let work_dir = create_work_dir()?;
+    let result = download_and_install(&target, asset, &work_dir, verbose);
+    let _ = fs::remove_dir_all(&work_dir);
+    result?;
+
+    ui::success(&format!("Updated fl {} → {}", current, target));
+    ui::info("Restart the webhook server to use it: systemctl restart flaase-webhook");
+    Ok(())
+}
+
+fn download_and_install(
+    version: &str,
+    asset: &str,
+    work_dir: &Path,
+    verbose: bool,
+) -> Result<(), AppError> {
+    let base_url = format!(
+        "https://github.com/{}/releases/download/v{}/{}.tar.gz",
+        REPOSITORY, version, asset
+    );
+    let archive = work_dir.join(format!("{}.tar.gz", asset));
+    let checksum = work_dir.join(format!("{}.tar.gz.sha256", asset));
+
+    let spinner = ui::ProgressBar::spinner(&format!("Downloading {} v{}", asset, version));
+    curl(&["-fsSL", "-o", &archive.to_string_lossy(), &base_url], verbose)?;
+    curl(
+        &["-fsSL", "-o", &checksum.to_string_lossy(), &format!("{}.sha256", base_url)],
+        verbose,
+    )?;
+    spinner.finish("done");
+
+    verify_checksum(&archive, &fs::read_to_string(&checksum)?)?;
+
+    let output = Command::new("tar")
+        .arg("-xzf")
+        .arg(&archive)
+        .arg("-C")
+        .arg(work_dir)
+        .arg("flaase")
+        .output()
+        .map_err(|e| AppError::Command(format!("Failed to run tar: {}", e)))?;
+    if !output.status.success() {
+        return Err(AppError::Command(format!(
+            "Failed to extract {}: {}",
+            archive.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let binary = work_dir.join("flaase");
+    fs::set_permissions(&binary, fs::Permissions::from_mode(0o755))?;
+
+    // Never install a binary that can't even start on this machine
+    let reported = binary_version(&binary)?;
+    if reported != version {
+        return Err(AppError::Validation(format!(
+            "Downloaded binary reports version {}, expected {}",
+            reported, version
+        )));
+    }
+
+    install_binary(&binary, Path::new(INSTALL_PATH), Path::new(SYMLINK_PATH))
+}
+
+/// Creates a fresh private directory for the download. It must not exist
+/// yet, so nobody can have planted files or symlinks in it beforehand.
+fn create_work_dir() -> Result<PathBuf, AppError> {
+    let work_dir = std::env::temp_dir().join(format!(
+        "flaase-update-{}",
+        SecretsManager::generate_password(16)
+    ));
+    fs::DirBuilder::new().mode(0o700).create(&work_dir)?;
+    Ok(work_dir)
+}
+
+/// Describes the deployment running on the server, if any: a webhook
+/// deployment holding its app's lock, or any deploy, update, start or
+/// rollback running through the deployer.
+fn deploy_activity() -> Result<Option<String>, AppError> {
+    if let Some(app) = AppConfig::list_all()?
+        .into_iter()
+        .find(|app| deployment_in_progress(app))
+    {
+        return Ok(Some(format!("a deployment of {}", app)));
+    }
+
+    Ok(build_slots::deploy_in_progress()?.then(|| "a deployment".to_string()))
+}
+
+/// Name of the release archive for this machine, without extension.
+fn release_asset() -> Result<&'static str, AppError> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("linux", "x86_64") => Ok("flaase-linux-x64"),
+        ("linux", "aarch64") => Ok("flaase-linux-arm64"),
+        (os, arch) => Err(AppError::UnsupportedOs(format!(
+            "no release binary for {} {}",
+            os, arch
+        ))),
+    }
+}
+
+/// Strips the `v` of a tag and checks the version looks like `1.2.3[-pre]`.
+fn normalize_version(version: &str) -> Result<String, AppError> {
+    let version = version.trim().trim_start_matches('v');
+    let (core, pre) = version.split_once('-').unwrap_or((version, ""));
+
+    let parts: Vec<&str> = core.split('.').collect();
+    let valid = parts.len() == 3
+        && parts.iter().all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+        && pre.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+
+    if valid && !(version.contains('-') && pre.is_empty()) {
+        Ok(version.to_string())
+    } else {
+        Err(AppError::Validation(format!(
+            "Invalid version '{}'. Expected a release like 0.16.0",
+            version
+        )))
+    }
+}
+
+/// Asks the GitHub API for the newest published release.
+fn latest_version() -> Result<String, AppError> {
+    let url = format!("https://api.github.com/repos/{}/releases/latest", REPOSITORY);
+    let output = Command::new("curl")
+        .args(["-fsSL", "-H", "Accept: application/vnd.github+json", &url])
+        .output()
+        .map_err(|e| AppError::Command(format!("Failed to execute curl: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(AppError::Command(format!(
+            "Failed to fetch the latest release: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let release: Release = serde_json::from_slice(&output.stdout)
+        .map_err(|e| AppError::Command(format!("Unexpected GitHub API response: {}", e)))?;
+    normalize_version(&release.tag_name)
+}
+
+fn curl(args: &[&str], verbose: bool) -> Result<(), AppError> {
+    if verbose {
+        ui::info(&format!("Running: curl {}", args.join(" ")));
+    }
+
+    let output = Command::new("curl")
+        .args(args)
+        .output()
+        .map_err(|e| AppError::Command(format!("Failed to execute curl: {}", e)))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(AppError::Command(format!(
+            "Download failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+/// Compares a file with the first field of a `sha256sum` line.
+fn verify_checksum(path: &Path, sha256sum: &str) -> Result<(), AppError> {
+    let expected = sha256sum.split_whitespace().next().unwrap_or_default();
+    let actual = hex::encode(Sha256::digest(fs::read(path)?));
+
+    if actual.eq_ignore_ascii_case(expected) {
+        Ok(())
+    } else {
+        Err(AppError::Validation(format!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            path.display(),
+            expected,
+            actual
+        )))
+    }
+}
+
+/// Runs `<binary> --version` and returns the version it prints.
+fn binary_version(binary: &Path) -> Result<String, AppError> {
+    let output = Command::new(binary)
+        .arg("--version")
+        .output()
+        .map_err(|e| AppError::Command(format!("Downloaded binary doesn't run: {}", e)))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    match stdout.split_whitespace().nth(1) {
+        Some(version) if output.status.success() => Ok(version.to_string()),
+        _ => Err(AppError::Command(format!(
+            "Downloaded binary doesn't run: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))),
+    }
+}
+
+/// Moves `binary` over `install_path` and points `symlink_path` at it. Both
+/// are staged next to their target and renamed, so a running `fl` never sees
+/// a half-written file.
+fn install_binary(binary: &Path, install_path: &Path, symlink_path: &Path) -> Result<(), AppError> {
+    let staged = staging_path(install_path);
+    fs::copy(binary, &staged)?;
+    fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))?;
+    fs::rename(&staged, install_path)?;
+
+    let staged_link = staging_path(symlink_path);
+    let _ = fs::remove_file(&staged_link);
+    std::os::unix::fs::symlink(install_path, &staged_link)?;
+    fs::rename(&staged_link, symlink_path)?;
+
+    Ok(())
+}
+
+/// Hidden sibling of `path` used while writing its replacement.
+fn staging_path(path: &Path) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!(".{}.new", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_version() {
+        assert_eq!(normalize_version("v0.16.0").unwrap(), "0.16.0");
+        assert_eq!(normalize_version("0.16.0").unwrap(), "0.16.0");
+        assert_eq!(normalize_version("v1.0.0-beta.1").unwrap(), "1.0.0-beta.1");
+
+        assert!(normalize_version("latest").is_err());
+        assert!(normalize_version("1.2").is_err());
+        assert!(normalize_version("1.2.3-").is_err());
+        assert!(normalize_version("1.2.3/../x").is_err());
+    }
+
+    #[test]
+    fn test_install_binary_replaces_file_and_symlink() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = dir.path().join("download");
+        let install_path = dir.path().join("flaase");
+        let symlink_path = dir.path().join("fl");
+        fs::write(&binary, "new").unwrap();
+        fs::write(&install_path, "old").unwrap();
+        fs::write(&symlink_path, "stale copy").unwrap();
+
+        install_binary(&binary, &install_path, &symlink_path).unwrap();
+
+        assert_eq!(fs::read_to_string(&install_path).unwrap(), "new");
+        assert_eq!(fs::read_link(&symlink_path).unwrap(), install_path);
+        let mode = fs::metadata(&install_path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+
+    #[test]
+    fn test_verify_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("archive.tar.gz");
+        fs::write(&file, "hello").unwrap();
+
+        let digest = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        assert!(verify_checksum(&file, &format!("{}  archive.tar.gz\n", digest)).is_ok());
+        assert!(verify_checksum(&file, "deadbeef  archive.tar.gz").is_err());
+    }
+}
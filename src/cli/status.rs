@@ -1,31 +1,55 @@
 //! Status command implementation for listing all apps.
 
-use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Local, Utc};
 use console::{style, Term};
+use serde::Serialize;
 
 use crate::core::app_config::AppConfig;
 use crate::core::context::ExecutionContext;
+use crate::core::deploy::Deployer;
 use crate::core::error::AppError;
-use crate::providers::container::{ContainerRuntime, DockerRuntime};
+use crate::providers::container::{create_container_runtime, ContainerRuntime};
+use crate::providers::create_reverse_proxy;
 use crate::ui;
 
 /// App status for display.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AppStatus {
     Running,
+    /// Running, but the health check hasn't passed yet.
+    Starting,
+    /// Running, but the health check is failing.
+    Unhealthy,
     Stopped,
     Error,
     NotDeployed,
 }
 
 impl AppStatus {
+    /// Returns the plain status string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AppStatus::Running => "running",
+            AppStatus::Starting => "starting",
+            AppStatus::Unhealthy => "unhealthy",
+            AppStatus::Stopped => "stopped",
+            AppStatus::Error => "error",
+            AppStatus::NotDeployed => "not deployed",
+        }
+    }
+
     /// Returns the display string with color.
     pub fn display(&self) -> console::StyledObject<&'static str> {
+        let text = style(self.as_str());
         match self {
-            AppStatus::Running => style("running").green(),
-            AppStatus::Stopped => style("stopped").yellow(),
-            AppStatus::Error => style("error").red(),
-            AppStatus::NotDeployed => style("not deployed").dim(),
+            AppStatus::Running => text.green(),
+            AppStatus::Starting => text.cyan(),
+            AppStatus::Unhealthy | AppStatus::Error => text.red(),
+            AppStatus::Stopped => text.yellow(),
+            AppStatus::NotDeployed => text.dim(),
         }
     }
 }
@@ -37,10 +61,12 @@ struct AppInfo {
     domain: String,
     stack: String,
     deployed_at: Option<DateTime<Utc>>,
+    /// Web container serving traffic (the active slot with blue-green).
+    container: String,
 }
 
 /// Formats a datetime as a relative time string.
-fn format_relative_time(dt: DateTime<Utc>) -> String {
+pub(crate) fn format_relative_time(dt: DateTime<Utc>) -> String {
     let now = Utc::now();
     let duration = now.signed_duration_since(dt);
 
@@ -93,35 +119,139 @@ fn format_relative_time(dt: DateTime<Utc>) -> String {
     }
 }
 
-/// Gets the container status for an app.
-fn get_app_status(
-    app_name: &str,
-    deployed_at: Option<DateTime<Utc>>,
-    runtime: &DockerRuntime,
+/// How often `--watch` redraws the table by default.
+pub const DEFAULT_WATCH_INTERVAL_SECS: u64 = 2;
+
+/// Lists the state and status text (e.g. "Up 2 hours (healthy)") of every
+/// Flaase container in a single runtime call, keyed by container name.
+fn container_states(
+    runtime: &dyn ContainerRuntime,
     ctx: &ExecutionContext,
+) -> Result<HashMap<String, (String, String)>, AppError> {
+    let output = ctx.run_command(
+        runtime.command(),
+        &[
+            "ps",
+            "-a",
+            "--filter",
+            "name=flaase-",
+            "--format",
+            "{{.Names}}\t{{.State}}\t{{.Status}}",
+        ],
+    )?;
+    output.ensure_success("Failed to list containers")?;
+
+    Ok(parse_container_states(&output.stdout))
+}
+
+/// Maps every Flaase container name to its short ID.
+fn container_ids(
+    runtime: &dyn ContainerRuntime,
+    ctx: &ExecutionContext,
+) -> Result<HashMap<String, String>, AppError> {
+    let output = ctx.run_command(
+        runtime.command(),
+        &["ps", "-a", "--filter", "name=flaase-", "--format", "{{.Names}}\t{{.ID}}"],
+    )?;
+    output.ensure_success("Failed to list containers")?;
+
+    Ok(output
+        .stdout
+        .lines()
+        .filter_map(|line| {
+            let (name, id) = line.split_once('\t')?;
+            Some((name.trim().to_string(), id.trim().to_string()))
+        })
+        .collect())
+}
+
+/// Parses `ps --format '{{.Names}}\t{{.State}}\t{{.Status}}'` output.
+pub(crate) fn parse_container_states(stdout: &str) -> HashMap<String, (String, String)> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let name = fields.next()?.trim();
+            let state = fields.next()?.trim().to_lowercase();
+            let status = fields.next().unwrap_or_default().trim().to_string();
+            Some((name.to_string(), (state, status)))
+        })
+        .collect()
+}
+
+/// Derives an app's status from its web container's state.
+pub(crate) fn app_status(
+    deployed_at: Option<DateTime<Utc>>,
+    container: Option<&(String, String)>,
 ) -> AppStatus {
     // If never deployed, return NotDeployed
     if deployed_at.is_none() {
         return AppStatus::NotDeployed;
     }
 
-    let container_name = format!("flaase-{}-web", app_name);
-
-    // Check if container exists and is running
-    match runtime.container_is_running(&container_name, ctx) {
-        Ok(true) => AppStatus::Running,
-        Ok(false) => {
-            // Container exists but not running
-            match runtime.container_exists(&container_name, ctx) {
-                Ok(true) => AppStatus::Stopped,
-                Ok(false) => AppStatus::NotDeployed,
-                Err(_) => AppStatus::Error,
+    match container {
+        None => AppStatus::NotDeployed,
+        Some((state, status)) if state == "running" => {
+            if status.contains("(unhealthy)") {
+                AppStatus::Unhealthy
+            } else if status.contains("(health: starting)") || status.contains("(starting)") {
+                AppStatus::Starting
+            } else {
+                AppStatus::Running
             }
         }
-        Err(_) => AppStatus::Error,
+        Some((state, _)) if state == "dead" => AppStatus::Error,
+        Some(_) => AppStatus::Stopped,
     }
 }
 
+/// Loads every app and its current status, using one runtime call for all apps.
+fn gather_apps(
+    app_names: &[String],
+    runtime: &dyn ContainerRuntime,
+    ctx: &ExecutionContext,
+) -> Vec<AppInfo> {
+    let states = container_states(runtime, ctx);
+    let proxy = create_reverse_proxy();
+
+    app_names
+        .iter()
+        .map(|name| match AppConfig::load(name) {
+            Ok(config) => {
+                let container = Deployer::new(&config, runtime, proxy.as_ref(), ctx)
+                    .web_slot_container("active")
+                    .unwrap_or_else(|_| format!("flaase-{}-web", name));
+                let status = match &states {
+                    Ok(states) => app_status(config.deployed_at, states.get(&container)),
+                    Err(_) => AppStatus::Error,
+                };
+                AppInfo {
+                    container,
+                    status,
+                    domain: config.primary_domain().to_string(),
+                    stack: config
+                        .stack
+                        .as_ref()
+                        .map(|s| s.display_name())
+                        .unwrap_or("Image")
+                        .to_string(),
+                    deployed_at: config.deployed_at,
+                    name: config.name,
+                }
+            }
+            // Config exists but failed to load
+            Err(_) => AppInfo {
+                name: name.clone(),
+                status: AppStatus::Error,
+                domain: "-".to_string(),
+                stack: "-".to_string(),
+                deployed_at: None,
+                container: format!("flaase-{}-web", name),
+            },
+        })
+        .collect()
+}
+
 /// Prints the status table header.
 fn print_table_header(term: &Term, col_widths: &[usize]) {
     let header = format!(
@@ -175,6 +305,8 @@ fn print_summary(term: &Term, apps: &[AppInfo]) {
     let total = apps.len();
     let running = apps.iter().filter(|a| a.status == AppStatus::Running).count();
     let stopped = apps.iter().filter(|a| a.status == AppStatus::Stopped).count();
+    let starting = apps.iter().filter(|a| a.status == AppStatus::Starting).count();
+    let unhealthy = apps.iter().filter(|a| a.status == AppStatus::Unhealthy).count();
     let errors = apps.iter().filter(|a| a.status == AppStatus::Error).count();
     let not_deployed = apps
         .iter()
@@ -186,6 +318,12 @@ fn print_summary(term: &Term, apps: &[AppInfo]) {
     if running > 0 {
         parts.push(format!("{} {}", style(running).green(), "running"));
     }
+    if starting > 0 {
+        parts.push(format!("{} {}", style(starting).cyan(), "starting"));
+    }
+    if unhealthy > 0 {
+        parts.push(format!("{} {}", style(unhealthy).red(), "unhealthy"));
+    }
     if stopped > 0 {
         parts.push(format!("{} {}", style(stopped).yellow(), "stopped"));
     }
@@ -205,54 +343,8 @@ fn print_summary(term: &Term, apps: &[AppInfo]) {
     let _ = term.write_line(&summary);
 }
 
-/// Main status command handler.
-pub fn status(_verbose: bool) -> Result<(), AppError> {
-    let term = Term::stdout();
-    let ctx = ExecutionContext::new(false, false);
-    let runtime = DockerRuntime::new();
-
-    // Get all apps
-    let app_names = AppConfig::list_all()?;
-
-    if app_names.is_empty() {
-        ui::info("No apps configured");
-        println!();
-        println!(
-            "Run {} to configure your first app",
-            style("fl init").cyan()
-        );
-        return Ok(());
-    }
-
-    // Load app info
-    let mut apps: Vec<AppInfo> = Vec::new();
-
-    for name in &app_names {
-        match AppConfig::load(name) {
-            Ok(config) => {
-                let status = get_app_status(name, config.deployed_at, &runtime, &ctx);
-                let domain = config.primary_domain().to_string();
-                apps.push(AppInfo {
-                    name: config.name,
-                    status,
-                    domain,
-                    stack: config.stack.as_ref().map(|s| s.display_name()).unwrap_or("Image").to_string(),
-                    deployed_at: config.deployed_at,
-                });
-            }
-            Err(_) => {
-                // Config exists but failed to load
-                apps.push(AppInfo {
-                    name: name.clone(),
-                    status: AppStatus::Error,
-                    domain: "-".to_string(),
-                    stack: "-".to_string(),
-                    deployed_at: None,
-                });
-            }
-        }
-    }
-
+/// Prints the app table and summary. Returns the number of lines written.
+fn print_table(term: &Term, apps: &[AppInfo]) -> usize {
     // Calculate column widths
     let col_widths = [
         apps.iter()
@@ -274,17 +366,181 @@ pub fn status(_verbose: bool) -> Result<(), AppError> {
         12, // DEPLOYED (relative time)
     ];
 
-    // Print header
-    ui::section("Apps");
-    print_table_header(&term, &col_widths);
+    print_table_header(term, &col_widths);
 
-    // Print each app
-    for app in &apps {
-        print_app_row(&term, app, &col_widths);
+    for app in apps {
+        print_app_row(term, app, &col_widths);
     }
 
-    // Print summary
-    print_summary(&term, &apps);
+    print_summary(term, apps);
 
+    // Header and separator, one row per app, blank line and summary
+    apps.len() + 4
+}
+
+/// One app in `fl status --json`.
+#[derive(Debug, Serialize)]
+struct AppStatusJson {
+    name: String,
+    status: &'static str,
+    domain: Option<String>,
+    stack: Option<String>,
+    deployed_at: Option<DateTime<Utc>>,
+    container: String,
+    container_id: Option<String>,
+}
+
+impl AppStatusJson {
+    fn new(app: AppInfo, ids: &HashMap<String, String>) -> Self {
+        // Apps whose config failed to load are shown with "-" placeholders
+        let known = |value: String| (value != "-").then_some(value);
+        Self {
+            container_id: ids.get(&app.container).cloned(),
+            status: app.status.as_str(),
+            domain: known(app.domain),
+            stack: known(app.stack),
+            deployed_at: app.deployed_at,
+            name: app.name,
+            container: app.container,
+        }
+    }
+}
+
+/// Prints every app's status as a JSON array, without any decoration.
+fn print_json(
+    app_names: &[String],
+    runtime: &dyn ContainerRuntime,
+    ctx: &ExecutionContext,
+) -> Result<(), AppError> {
+    let ids = container_ids(runtime, ctx).unwrap_or_default();
+    let apps: Vec<AppStatusJson> = gather_apps(app_names, runtime, ctx)
+        .into_iter()
+        .map(|app| AppStatusJson::new(app, &ids))
+        .collect();
+
+    let json = serde_json::to_string_pretty(&apps)
+        .map_err(|e| AppError::Config(format!("Failed to serialize status: {}", e)))?;
+    println!("{}", json);
     Ok(())
 }
+
+/// Main status command handler.
+///
+/// With `watch`, redraws the table every `interval_secs` until Ctrl+C.
+/// With `json`, prints a JSON array instead of the table.
+pub fn status(watch: bool, interval_secs: u64, json: bool) -> Result<(), AppError> {
+    let term = Term::stdout();
+    let ctx = ExecutionContext::new(false, false);
+    let runtime = create_container_runtime();
+
+    // Get all apps
+    let app_names = AppConfig::list_all()?;
+
+    if json {
+        return print_json(&app_names, &*runtime, &ctx);
+    }
+
+    if app_names.is_empty() {
+        ui::info("No apps configured");
+        println!();
+        println!(
+            "Run {} to configure your first app",
+            style("fl init").cyan()
+        );
+        return Ok(());
+    }
+
+    ui::section("Apps");
+
+    let apps = gather_apps(&app_names, &*runtime, &ctx);
+    let mut drawn = print_table(&term, &apps);
+
+    if !watch {
+        return Ok(());
+    }
+
+    let _ = term.write_line(&style("Watching for changes, press Ctrl+C to stop").dim().to_string());
+    drawn += 1;
+
+    loop {
+        std::thread::sleep(Duration::from_secs(interval_secs.max(1)));
+
+        // Pick up apps created or destroyed while watching
+        let app_names = AppConfig::list_all()?;
+        let apps = gather_apps(&app_names, &*runtime, &ctx);
+
+        let _ = term.clear_last_lines(drawn);
+        drawn = print_table(&term, &apps);
+        let _ = term.write_line(
+            &style(format!(
+                "Updated {}, press Ctrl+C to stop",
+                Local::now().format("%H:%M:%S")
+            ))
+            .dim()
+            .to_string(),
+        );
+        drawn += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_app_status_from_container_state() {
+        let states = parse_container_states(
+            "flaase-api-web\trunning\tUp 2 hours (healthy)\n\
+             flaase-blog-web\trunning\tUp 5 seconds (health: starting)\n\
+             flaase-shop-web\trunning\tUp 1 minute (unhealthy)\n\
+             flaase-docs-web\texited\tExited (0) 3 hours ago\n",
+        );
+        let deployed = Some(Utc::now());
+
+        assert_eq!(app_status(deployed, states.get("flaase-api-web")), AppStatus::Running);
+        assert_eq!(app_status(deployed, states.get("flaase-blog-web")), AppStatus::Starting);
+        assert_eq!(app_status(deployed, states.get("flaase-shop-web")), AppStatus::Unhealthy);
+        assert_eq!(app_status(deployed, states.get("flaase-docs-web")), AppStatus::Stopped);
+        assert_eq!(app_status(deployed, states.get("flaase-gone-web")), AppStatus::NotDeployed);
+        assert_eq!(app_status(None, states.get("flaase-api-web")), AppStatus::NotDeployed);
+    }
+
+    #[test]
+    fn test_status_json_entry() {
+        let ids =
+            HashMap::from([("flaase-api-web-green".to_string(), "3f2a1b9c0d4e".to_string())]);
+
+        let running = AppStatusJson::new(
+            AppInfo {
+                name: "api".into(),
+                status: AppStatus::Running,
+                domain: "api.example.com".into(),
+                stack: "Node.js".into(),
+                deployed_at: None,
+                container: "flaase-api-web-green".into(),
+            },
+            &ids,
+        );
+        let value = serde_json::to_value(&running).unwrap();
+        assert_eq!(value["status"], "running");
+        assert_eq!(value["domain"], "api.example.com");
+        assert_eq!(value["container"], "flaase-api-web-green");
+        assert_eq!(value["container_id"], "3f2a1b9c0d4e");
+
+        let broken = AppStatusJson::new(
+            AppInfo {
+                name: "broken".into(),
+                status: AppStatus::Error,
+                domain: "-".into(),
+                stack: "-".into(),
+                deployed_at: None,
+                container: "flaase-broken-web".into(),
+            },
+            &ids,
+        );
+        let value = serde_json::to_value(&broken).unwrap();
+        assert_eq!(value["status"], "error");
+        assert!(value["domain"].is_null());
+        assert!(value["container_id"].is_null());
+    }
+}
@@ -14,12 +14,14 @@ use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tiny_http::{Response, Server, StatusCode};
 
 use crate::core::app_config::{AppConfig, EnvironmentConfig};
+use crate::core::config::{webhook_port, webhook_prefix};
 use crate::core::deployments::{DeploymentHistory, DeploymentRecord, DeploymentStatus, PendingApproval};
 use crate::core::notifications::{send_notifications, DeploymentEvent};
 use crate::core::error::AppError;
 use crate::core::secrets::SecretsManager;
 use crate::core::FLAASE_APPS_PATH;
-use crate::providers::webhook::WebhookProvider;
+use crate::providers::container_cli;
+use crate::providers::webhook::{LogStreamOptions, PushEvent, WebhookProvider, WebhookSource};
 use crate::ui;
 
 /// Rate limiting state for tracking webhook requests per app.
@@ -130,6 +132,11 @@ impl DeploymentLock {
     }
 }
 
+/// Returns true while a webhook deployment of the app holds its lock.
+pub fn deployment_in_progress(app_name: &str) -> bool {
+    DeploymentLock::is_locked(app_name)
+}
+
 /// Pending approvals storage.
 struct PendingApprovalsStore;
 
@@ -255,14 +262,13 @@ fn requires_approval(
     false
 }
 
-/// Default port for the webhook server.
-pub const DEFAULT_PORT: u16 = 9876;
-
 /// Systemd service name.
 const SERVICE_NAME: &str = "flaase-webhook";
 
-/// Starts the webhook server.
-pub fn serve(host: &str, port: u16, verbose: bool) -> Result<(), AppError> {
+/// Starts the webhook server, on the server's configured port unless `port` is given.
+pub fn serve(host: &str, port: Option<u16>, verbose: bool) -> Result<(), AppError> {
+    let port = port.unwrap_or_else(webhook_port);
+    let prefix = webhook_prefix();
     let addr = format!("{}:{}", host, port);
 
     ui::info(&format!("Starting webhook server on {}", addr));
@@ -274,7 +280,8 @@ pub fn serve(host: &str, port: u16, verbose: bool) -> Result<(), AppError> {
     ui::success(&format!("Webhook server listening on http://{}", addr));
     println!();
     println!("Endpoints:");
-    println!("  POST /webhook/{{app-token}}  - GitHub webhook endpoint");
+    println!("  POST /webhook/{{app-token}}  - GitHub/Gitea/Bitbucket webhook endpoint");
+    println!("  GET  /logs/{{app-token}}     - Stream app logs (Bearer webhook secret)");
     println!("  GET  /health               - Health check");
     println!();
     println!("Press Ctrl+C to stop the server.");
@@ -312,20 +319,19 @@ pub fn serve(host: &str, port: u16, verbose: bool) -> Result<(), AppError> {
         }
 
         // Route and handle requests
-        // Support both /webhook/xxx (direct) and /flaase/webhook/xxx (via Traefik)
-        match (method.as_str(), url.as_str()) {
+        // Support both /webhook/xxx (direct) and <prefix>/webhook/xxx (via Traefik)
+        let path = url.strip_prefix(prefix.as_str()).unwrap_or(&url);
+        match (method.as_str(), path) {
             ("GET", "/health") => {
                 let response = handle_health();
                 let _ = request.respond(response);
             }
-            ("POST", path) if path.starts_with("/flaase/webhook/") => {
-                // Strip /flaase prefix for handler
-                let webhook_path = path.strip_prefix("/flaase").unwrap_or(path);
-                handle_webhook(request, webhook_path, verbose, Arc::clone(&rate_limit_state));
-            }
             ("POST", path) if path.starts_with("/webhook/") => {
                 handle_webhook(request, path, verbose, Arc::clone(&rate_limit_state));
             }
+            ("GET", path) if path.starts_with("/logs/") => {
+                handle_logs(request, path, verbose);
+            }
             _ => {
                 let response = Response::from_string("Not Found")
                     .with_status_code(StatusCode(404));
@@ -356,7 +362,7 @@ fn handle_health() -> Response<std::io::Cursor<Vec<u8>>> {
         .with_status_code(StatusCode(200))
 }
 
-/// Handles webhook requests from GitHub.
+/// Handles webhook requests from GitHub, Gitea or Bitbucket.
 fn handle_webhook(
     mut request: tiny_http::Request,
     path: &str,
@@ -388,19 +394,21 @@ fn handle_webhook(
         }
     };
 
+    // Detect the Git provider from the headers it sends
+    let source = WebhookSource::detect(request.headers().iter().map(|h| h.field.as_str().as_str()));
+
     // Get headers before reading body (need to clone values we need)
-    let signature = request
-        .headers()
-        .iter()
-        .find(|h| h.field.as_str().to_ascii_lowercase() == "x-hub-signature-256")
-        .map(|h| h.value.to_string());
+    let find_header = |name: &str| {
+        request
+            .headers()
+            .iter()
+            .find(|h| h.field.as_str().as_str().eq_ignore_ascii_case(name))
+            .map(|h| h.value.to_string())
+    };
 
-    let event_type = request
-        .headers()
-        .iter()
-        .find(|h| h.field.as_str().to_ascii_lowercase() == "x-github-event")
-        .map(|h| h.value.to_string())
-        .unwrap_or_else(|| "unknown".to_string());
+    let signature = find_header(source.signature_header());
+
+    let event_type = find_header(source.event_header()).unwrap_or_else(|| "unknown".to_string());
 
     // Read request body
     let mut body = Vec::new();
@@ -410,8 +418,8 @@ fn handle_webhook(
         return;
     }
 
-    // Validate GitHub signature
-    let webhook_secret = match &app_secrets.webhook {
+    // Validate provider signature
+    let webhook_secret = match app_secrets.active_webhook() {
         Some(ws) => &ws.secret,
         None => {
             ui::error("No webhook secret configured for app");
@@ -422,9 +430,9 @@ fn handle_webhook(
 
     match &signature {
         Some(sig) => {
-            if !WebhookProvider::validate_signature(&body, sig, webhook_secret) {
+            if !source.validate_signature(&body, sig, webhook_secret) {
                 if verbose {
-                    ui::warning("Invalid webhook signature");
+                    ui::warning(&format!("Invalid {} webhook signature", source));
                 }
                 let _ = request.respond(json_error(401, "Invalid signature"));
                 return;
@@ -432,7 +440,7 @@ fn handle_webhook(
         }
         None => {
             if verbose {
-                ui::warning("Missing X-Hub-Signature-256 header");
+                ui::warning(&format!("Missing {} header", source.signature_header()));
             }
             let _ = request.respond(json_error(401, "Missing signature"));
             return;
@@ -441,32 +449,32 @@ fn handle_webhook(
 
     if verbose {
         println!(
-            "  {} Received {} event for {}",
+            "  {} Received {} {} event for {}",
             console::style("\u{2713}").green(),
+            source,
             console::style(&event_type).cyan(),
             console::style(&app_config.name).bold()
         );
     }
 
     // Only handle push events
-    if event_type != "push" {
+    if !source.is_push_event(&event_type) {
         let _ = request.respond(json_response(200, &format!("Ignored event type: {}", event_type)));
         return;
     }
 
-    // Parse push event to get branch
-    let payload: serde_json::Value = match serde_json::from_slice(&body) {
-        Ok(v) => v,
+    // Parse push event to get branch and commit
+    let push = match PushEvent::parse(source, &String::from_utf8_lossy(&body)) {
+        Ok(p) => p,
         Err(e) => {
             ui::error(&format!("Failed to parse webhook payload: {}", e));
             let _ = request.respond(json_error(400, "Invalid JSON payload"));
             return;
         }
     };
-
-    // Extract branch from ref (refs/heads/main -> main)
-    let ref_str = payload["ref"].as_str().unwrap_or("");
-    let branch = ref_str.strip_prefix("refs/heads/").unwrap_or(ref_str);
+    let git_tag = push.tag.clone();
+    // Tag pushes are reported under the tag name wherever a branch is shown
+    let branch = git_tag.as_deref().unwrap_or(&push.branch);
 
     // Check if this is the watched branch
     let autodeploy_config = match &app_config.autodeploy_config {
@@ -477,15 +485,31 @@ fn handle_webhook(
         }
     };
 
-    // Determine target environment based on branch
+    if let Some(tag) = &git_tag {
+        if push.deleted || !autodeploy_config.matches_tag(tag) {
+            if verbose {
+                println!(
+                    "  {} Ignoring tag {} (deploy_on_tag: {})",
+                    console::style("-").dim(),
+                    tag,
+                    autodeploy_config.deploy_on_tag.as_deref().unwrap_or("not set")
+                );
+            }
+            let _ = request.respond(json_response(200, &format!("Ignored tag: {}", tag)));
+            return;
+        }
+    }
+
+    // Determine target environment based on branch (tags go to the default one)
     let (environment, env_config) = determine_environment(
-        branch,
+        if git_tag.is_some() { "" } else { branch },
         autodeploy_config.environments.as_ref(),
     );
 
     // Check if this branch should trigger deployment
     // Either it's the main autodeploy branch OR it's mapped to an environment
-    let should_deploy = branch == autodeploy_config.branch || env_config.is_some();
+    let should_deploy =
+        git_tag.is_some() || branch == autodeploy_config.branch || env_config.is_some();
 
     if !should_deploy {
         if verbose {
@@ -509,6 +533,42 @@ fn handle_webhook(
         );
     }
 
+    // Monorepo path filters: skip branch pushes that touch none of the paths.
+    // Tag pushes are releases and always deploy; payloads without complete
+    // file lists (Bitbucket, truncated pushes) can't be filtered and deploy.
+    if git_tag.is_none()
+        && !autodeploy_config.paths.is_empty()
+        && !push.changed_files.is_empty()
+    {
+        match autodeploy_config.matching_path(&push.changed_files) {
+            Some((pattern, file)) => {
+                if verbose {
+                    println!(
+                        "  {} {} matches path {}",
+                        console::style("\u{279C}").cyan(),
+                        file,
+                        console::style(pattern).yellow()
+                    );
+                }
+            }
+            None => {
+                if verbose {
+                    println!(
+                        "  {} Ignoring push to {}: no changed file under {}",
+                        console::style("-").dim(),
+                        branch,
+                        autodeploy_config.paths.join(", ")
+                    );
+                }
+                let _ = request.respond(json_response(
+                    200,
+                    "Ignored push: no changes under configured paths",
+                ));
+                return;
+            }
+        }
+    }
+
     // Check rate limiting
     if let Some(rate_limit) = &autodeploy_config.rate_limit {
         if rate_limit.enabled {
@@ -540,25 +600,9 @@ fn handle_webhook(
     }
 
     // Extract deployment info
-    let commit_sha = payload["after"]
-        .as_str()
-        .unwrap_or("")
-        .chars()
-        .take(7)
-        .collect::<String>();
-
-    let commit_msg = payload["head_commit"]["message"]
-        .as_str()
-        .unwrap_or("")
-        .lines()
-        .next()
-        .unwrap_or("")
-        .to_string();
-
-    let pusher = payload["pusher"]["name"]
-        .as_str()
-        .unwrap_or("unknown")
-        .to_string();
+    let commit_sha = push.commit_sha.clone();
+    let commit_msg = push.commit_message.clone();
+    let pusher = push.pusher.clone();
 
     // Check if this deployment requires approval
     let needs_approval = requires_approval(env_config, autodeploy_config.approval.as_ref());
@@ -570,7 +614,7 @@ fn handle_webhook(
             .map(|a| a.timeout_minutes)
             .unwrap_or(60);
 
-        let approval = PendingApproval::new(
+        let mut approval = PendingApproval::new(
             &app_config.name,
             &commit_sha,
             &commit_msg,
@@ -579,6 +623,7 @@ fn handle_webhook(
             &pusher,
             timeout_minutes,
         );
+        approval.git_tag = git_tag.clone();
 
         println!(
             "  {} Deployment for {} requires approval (env: {})",
@@ -601,7 +646,8 @@ fn handle_webhook(
             branch,
             &pusher,
             &environment,
-        );
+        )
+        .with_git_tag(git_tag.as_deref());
         let mut record = deployment_record;
         record.status = DeploymentStatus::PendingApproval;
 
@@ -651,7 +697,8 @@ fn handle_webhook(
         branch,
         &pusher,
         &environment,
-    );
+    )
+    .with_git_tag(git_tag.as_deref());
 
     if let Err(e) = log_deployment(&app_config, deployment_record) {
         if verbose {
@@ -693,7 +740,7 @@ fn handle_webhook(
         let start_time = Instant::now();
 
         // Run deployment and capture result
-        let result = run_deployment(&app_name);
+        let result = run_deployment(&app_name, git_tag.as_deref());
 
         let duration_secs = start_time.elapsed().as_secs();
 
@@ -748,15 +795,153 @@ fn handle_webhook(
     });
 }
 
+/// Maximum duration of a log stream before the connection is closed.
+const LOG_STREAM_MAX_SECONDS: u64 = 600;
+
+/// Maximum bytes sent on a single log stream.
+const LOG_STREAM_MAX_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Handles log streaming requests, authenticated with the app's webhook secret.
+fn handle_logs(request: tiny_http::Request, path: &str, verbose: bool) {
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+    let token = path.trim_start_matches("/logs/");
+
+    if token.is_empty() {
+        let _ = request.respond(json_error(400, "Missing app token"));
+        return;
+    }
+
+    let (app_config, app_secrets) = match find_app_by_webhook_path(token) {
+        Ok(Some((config, secrets))) => (config, secrets),
+        Ok(None) => {
+            let _ = request.respond(json_error(404, "App not found"));
+            return;
+        }
+        Err(e) => {
+            ui::error(&format!("Error finding app: {}", e));
+            let _ = request.respond(json_error(500, "Internal error"));
+            return;
+        }
+    };
+
+    // Authenticate with "Authorization: Bearer <webhook secret>"
+    let bearer = request
+        .headers()
+        .iter()
+        .find(|h| h.field.as_str().as_str().eq_ignore_ascii_case("Authorization"))
+        .and_then(|h| h.value.as_str().strip_prefix("Bearer ").map(|t| t.trim().to_string()));
+
+    let authorized = match (&bearer, app_secrets.active_webhook()) {
+        (Some(token), Some(webhook)) => WebhookProvider::validate_token(token, &webhook.secret),
+        _ => false,
+    };
+
+    if !authorized {
+        if verbose {
+            ui::warning(&format!("Unauthorized log request for {}", app_config.name));
+        }
+        let _ = request.respond(json_error(401, "Unauthorized"));
+        return;
+    }
+
+    let container = match running_web_container(&app_config.name) {
+        Some(container) => container,
+        None => {
+            let _ = request.respond(json_error(404, "App is not running"));
+            return;
+        }
+    };
+
+    let options = LogStreamOptions::from_query(query);
+
+    if verbose {
+        println!(
+            "  Streaming logs of {} ({} lines{})",
+            console::style(&container).cyan(),
+            options.tail,
+            if options.follow { ", following" } else { "" }
+        );
+    }
+
+    // Stream in the background so other requests keep being served
+    std::thread::spawn(move || {
+        let tail = options.tail.to_string();
+        let timeout = LOG_STREAM_MAX_SECONDS.to_string();
+        let follow = if options.follow { "--follow" } else { "" };
+
+        // Merge stderr into stdout and stop after the maximum duration
+        let child = Command::new("sh")
+            .args([
+                "-c",
+                "exec timeout \"$1\" \"$2\" logs --tail \"$3\" $4 \"$5\" 2>&1",
+                "sh",
+                &timeout,
+                container_cli(),
+                &tail,
+                follow,
+                &container,
+            ])
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::null())
+            .spawn();
+
+        let mut child = match child {
+            Ok(child) => child,
+            Err(e) => {
+                let _ = request.respond(json_error(500, &format!("Failed to read logs: {}", e)));
+                return;
+            }
+        };
+
+        if let Some(stdout) = child.stdout.take() {
+            let response = Response::new(
+                StatusCode(200),
+                vec![tiny_http::Header::from_bytes(
+                    &b"Content-Type"[..],
+                    &b"text/plain; charset=utf-8"[..],
+                )
+                .unwrap()],
+                stdout.take(LOG_STREAM_MAX_BYTES),
+                None,
+                None,
+            );
+            let _ = request.respond(response);
+        }
+
+        // Client disconnected or limit reached
+        let _ = child.kill();
+        let _ = child.wait();
+    });
+}
+
+/// Returns the running web container of an app (standard or blue-green slot).
+fn running_web_container(app_name: &str) -> Option<String> {
+    ["web", "web-blue", "web-green"]
+        .iter()
+        .map(|suffix| format!("flaase-{}-{}", app_name, suffix))
+        .find(|container| {
+            Command::new(container_cli())
+                .args(["inspect", "-f", "{{.State.Running}}", container])
+                .output()
+                .map(|o| o.status.success() && String::from_utf8_lossy(&o.stdout).trim() == "true")
+                .unwrap_or(false)
+        })
+}
+
 /// Runs the deployment synchronously and returns the result.
-fn run_deployment(app_name: &str) -> Result<(), AppError> {
+fn run_deployment(app_name: &str, git_tag: Option<&str>) -> Result<(), AppError> {
     // Get the path to the current executable
     let exe_path = std::env::current_exe()
         .map_err(|e| AppError::Config(format!("Failed to get executable path: {}", e)))?;
 
+    let mut args = vec!["update", app_name];
+    if let Some(tag) = git_tag {
+        args.extend(["--git-tag", tag]);
+    }
+
     // Run fl update and wait for completion
     let output = Command::new(&exe_path)
-        .args(["update", app_name])
+        .args(&args)
         .output()
         .map_err(|e| AppError::Config(format!("Failed to run update command: {}", e)))?;
 
@@ -829,9 +1014,15 @@ fn json_response(status: u16, message: &str) -> Response<std::io::Cursor<Vec<u8>
 /// Tries to detect the Docker bridge gateway IP at runtime.
 /// Falls back to 172.17.0.1 (common default) or host.docker.internal.
 fn get_docker_host_address() -> String {
-    // Try to get gateway from docker network inspect (works on Linux)
-    if let Ok(output) = Command::new("docker")
-        .args(["network", "inspect", "bridge", "--format", "{{range .IPAM.Config}}{{.Gateway}}{{end}}"])
+    // Podman names its default network differently and reports subnets, not IPAM
+    let (network, format, default_gateway) = match container_cli() {
+        "podman" => ("podman", "{{range .Subnets}}{{.Gateway}}{{end}}", "10.88.0.1"),
+        _ => ("bridge", "{{range .IPAM.Config}}{{.Gateway}}{{end}}", "172.17.0.1"),
+    };
+
+    // Try to get gateway from network inspect (works on Linux)
+    if let Ok(output) = Command::new(container_cli())
+        .args(["network", "inspect", network, "--format", format])
         .output()
     {
         let gateway = String::from_utf8_lossy(&output.stdout).trim().to_string();
@@ -843,7 +1034,7 @@ fn get_docker_host_address() -> String {
     // Fallback: try common Linux default, otherwise use host.docker.internal
     // Check if we're likely on Linux by looking for /etc/os-release
     if std::path::Path::new("/etc/os-release").exists() {
-        return "172.17.0.1".to_string();
+        return default_gateway.to_string();
     }
 
     // macOS/Windows: use host.docker.internal
@@ -853,6 +1044,7 @@ fn get_docker_host_address() -> String {
 /// Generates Traefik configuration for webhook routing.
 fn generate_traefik_webhook_config() -> String {
     let host_address = get_docker_host_address();
+    let prefix = webhook_prefix();
 
     format!(
         r#"# Traefik configuration for Flaase webhook endpoint
@@ -861,7 +1053,7 @@ fn generate_traefik_webhook_config() -> String {
 http:
   routers:
     flaase-webhook:
-      rule: "PathPrefix(`/flaase/webhook/`)"
+      rule: "PathPrefix(`{prefix}/webhook/`)"
       entryPoints:
         - websecure
       service: flaase-webhook
@@ -870,20 +1062,30 @@ http:
         certResolver: letsencrypt
 
     flaase-webhook-http:
-      rule: "PathPrefix(`/flaase/webhook/`)"
+      rule: "PathPrefix(`{prefix}/webhook/`)"
       entryPoints:
         - web
       service: flaase-webhook
       priority: 100
 
+    flaase-logs:
+      rule: "PathPrefix(`{prefix}/logs/`)"
+      entryPoints:
+        - websecure
+      service: flaase-webhook
+      priority: 100
+      tls:
+        certResolver: letsencrypt
+
   services:
     flaase-webhook:
       loadBalancer:
         servers:
           - url: "http://{host}:{port}"
 "#,
+        prefix = prefix,
         host = host_address,
-        port = DEFAULT_PORT
+        port = webhook_port()
     )
 }
 
@@ -919,7 +1121,7 @@ After=network.target
 
 [Service]
 Type=simple
-ExecStart={exe_path} webhook serve --host {host}
+ExecStart={exe_path} webhook serve --host {host} --port {port}
 Restart=always
 RestartSec=5
 StandardOutput=journal
@@ -929,7 +1131,8 @@ StandardError=journal
 WantedBy=multi-user.target
 "#,
         exe_path = exe_path.display(),
-        host = bind_host
+        host = bind_host,
+        port = webhook_port()
     );
 
     let service_path = format!("/etc/systemd/system/{}.service", SERVICE_NAME);
@@ -949,14 +1152,15 @@ WantedBy=multi-user.target
         .status()
         .map_err(|e| AppError::Config(format!("Failed to enable service: {}", e)))?;
 
+    // Restart so a reinstall picks up a changed port or prefix
     Command::new("systemctl")
-        .args(["start", SERVICE_NAME])
+        .args(["restart", SERVICE_NAME])
         .status()
         .map_err(|e| AppError::Config(format!("Failed to start service: {}", e)))?;
 
     ui::success("Webhook server installed and started!");
     println!();
-    println!("Traefik will route /flaase/webhook/* to the webhook server.");
+    println!("Traefik will route {}/webhook/* to the webhook server.", webhook_prefix());
     println!();
     println!("Service commands:");
     println!("  systemctl status {}   - Check status", SERVICE_NAME);
@@ -1066,7 +1270,7 @@ pub fn approve_deployment(app_name: &str, approval_id: Option<&str>) -> Result<(
 
     // Trigger deployment
     ui::step("Starting deployment...");
-    run_deployment(app_name)?;
+    run_deployment(app_name, approval.git_tag.as_deref())?;
 
     ui::success("Deployment completed successfully!");
 
@@ -1193,7 +1397,8 @@ pub fn status() -> Result<(), AppError> {
         );
     }
 
-    println!("  Port:    {}", DEFAULT_PORT);
+    println!("  Port:    {}", webhook_port());
+    println!("  Prefix:  {}", webhook_prefix());
     println!();
 
     // Count apps with autodeploy enabled
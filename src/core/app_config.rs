@@ -1,5 +1,6 @@
 //! Application configuration management.
 
+use std::collections::BTreeMap;
 use std::fmt;
 use std::path::{Path, PathBuf};
 
@@ -8,6 +9,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::core::error::AppError;
 use crate::core::FLAASE_APPS_PATH;
+use crate::utils::validate_cache_name;
+
+/// Placeholder written in place of credentials when exporting an app spec.
+pub const REDACTED_VALUE: &str = "<redacted>";
 
 /// Application configuration stored in /opt/flaase/apps/<name>/config.yml
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,16 +55,47 @@ pub struct AppConfig {
     /// Port the application listens on.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub port: Option<u16>,
+    /// Number of load-balanced app containers (image deployments only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replicas: Option<u32>,
+    /// Number of earlier images kept as `:previous-N` rollback points (source deployments only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_previous: Option<u32>,
+    /// Base images of the generated Dockerfile pinned to a digest
+    /// (`node:20-alpine` -> `node:20-alpine@sha256:...`), set by `fl deploy --pin-base`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub base_image_digests: BTreeMap<String, String>,
+    /// CPU/memory reservations and hard limits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resources: Option<ResourcesConfig>,
+    /// Generated secrets rendered into a file mounted into the app container.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secrets_file: Option<SecretsFileConfig>,
+    /// Env var keys flagged sensitive with `fl env set --encrypt`: stored
+    /// encrypted and always masked.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sensitive_env: Vec<String>,
+    /// Set `NODE_ENV=production` on source deployments unless `.env` defines it (default: true).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inject_node_env: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub database: Option<DatabaseConfig>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Legacy single cache field (for backward compatibility).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cache: Option<CacheConfig>,
+    /// Cache instances: the primary one (no name) and named ones (e.g. sessions).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub caches: Vec<CacheConfig>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub health_check: Option<HealthCheckConfig>,
+    /// Scheduled commands run in short-lived app containers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cron_jobs: Vec<CronJob>,
     pub autodeploy: bool,
     /// Detailed autodeploy configuration (webhook settings).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub autodeploy_config: Option<AutodeployConfig>,
+    #[serde(default = "Utc::now")]
     pub created_at: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deployed_at: Option<DateTime<Utc>>,
@@ -92,9 +128,18 @@ impl AppConfig {
             domain: None,
             domains: vec![DomainConfig::new(&domain, true)],
             port,
+            replicas: None,
+            keep_previous: None,
+            base_image_digests: BTreeMap::new(),
+            sensitive_env: Vec::new(),
+            inject_node_env: None,
+            resources: None,
+            secrets_file: None,
             database,
-            cache,
+            cache: None,
+            caches: cache.into_iter().collect(),
             health_check: None,
+            cron_jobs: Vec::new(),
             autodeploy,
             autodeploy_config: None,
             created_at: Utc::now(),
@@ -126,9 +171,18 @@ impl AppConfig {
             domain: None,
             domains: vec![DomainConfig::new(&domain, true)],
             port: Some(port),
+            replicas: None,
+            keep_previous: None,
+            base_image_digests: BTreeMap::new(),
+            sensitive_env: Vec::new(),
+            inject_node_env: None,
+            resources: None,
+            secrets_file: None,
             database,
-            cache,
+            cache: None,
+            caches: cache.into_iter().collect(),
             health_check,
+            cron_jobs: Vec::new(),
             autodeploy: false,
             autodeploy_config: None,
             created_at: Utc::now(),
@@ -165,6 +219,17 @@ impl AppConfig {
         self.domain.as_deref().unwrap_or("localhost")
     }
 
+    /// Returns the URL the app is reachable at, including any path prefix
+    /// configured on the primary domain.
+    pub fn public_url(&self) -> String {
+        self.domains
+            .iter()
+            .find(|d| d.primary)
+            .or_else(|| self.domains.first())
+            .map(|d| d.url())
+            .unwrap_or_else(|| format!("https://{}", self.primary_domain()))
+    }
+
     /// Returns all domains for this app (including legacy single domain).
     pub fn all_domains(&self) -> Vec<&DomainConfig> {
         self.domains.iter().collect()
@@ -189,6 +254,13 @@ impl AppConfig {
         self.domains.iter().any(|d| d.domain == domain)
     }
 
+    /// Migrates the legacy single cache into the cache list.
+    fn migrate_caches(&mut self) {
+        if let Some(cache) = self.cache.take() {
+            self.caches.insert(0, cache);
+        }
+    }
+
     /// Migrates legacy single-domain config to multi-domain format.
     fn migrate_domains(&mut self) {
         if self.domains.is_empty() {
@@ -209,6 +281,36 @@ impl AppConfig {
         })
     }
 
+    /// Returns the number of app containers to run.
+    /// Replicas are only supported for image deployments.
+    pub fn effective_replicas(&self) -> u32 {
+        if self.is_image_deployment() {
+            self.replicas.unwrap_or(1).max(1)
+        } else {
+            1
+        }
+    }
+
+    /// Returns how many previous images to retain for rollback.
+    pub fn effective_keep_previous(&self) -> u32 {
+        self.keep_previous.unwrap_or(1).max(1)
+    }
+
+    /// Returns whether `NODE_ENV=production` may be injected into containers.
+    pub fn effective_inject_node_env(&self) -> bool {
+        self.inject_node_env.unwrap_or(true)
+    }
+
+    /// Returns the database when Flaase runs it in a container (not external).
+    pub fn managed_database(&self) -> Option<&DatabaseConfig> {
+        self.database.as_ref().filter(|db| !db.external)
+    }
+
+    /// Returns the primary (unnamed) cache.
+    pub fn primary_cache(&self) -> Option<&CacheConfig> {
+        self.caches.iter().find(|cache| cache.name.is_none())
+    }
+
     /// Returns the health check configuration with defaults.
     pub fn effective_health_check(&self) -> HealthCheckConfig {
         self.health_check.clone().unwrap_or_default()
@@ -234,6 +336,11 @@ impl AppConfig {
         self.app_dir().join(".env.auto")
     }
 
+    /// Returns the host path of the rendered secrets file mounted into the container.
+    pub fn secrets_file_path(&self) -> PathBuf {
+        self.app_dir().join("secrets").join("secrets.env")
+    }
+
     /// Returns the .secrets file path.
     pub fn secrets_path(&self) -> PathBuf {
         self.app_dir().join(".secrets")
@@ -249,16 +356,31 @@ impl AppConfig {
         self.app_dir().join("data")
     }
 
+    /// Returns the local BuildKit cache directory path.
+    pub fn build_cache_path(&self) -> PathBuf {
+        self.app_dir().join("build-cache")
+    }
+
     /// Returns the deployments history file path.
     pub fn deployments_path(&self) -> PathBuf {
         self.app_dir().join("deployments.json")
     }
 
+    /// Returns the file naming the blue-green slot receiving traffic.
+    pub fn active_slot_path(&self) -> PathBuf {
+        self.app_dir().join("active-slot")
+    }
+
     /// Loads an app configuration from disk.
     /// Automatically migrates legacy single-domain configs to multi-domain format.
     pub fn load(name: &str) -> Result<Self, AppError> {
-        let config_path = format!("{}/{}/config.yml", FLAASE_APPS_PATH, name);
-        let path = Path::new(&config_path);
+        Self::load_in(Path::new(FLAASE_APPS_PATH), name)
+    }
+
+    /// Loads an app configuration from an apps directory.
+    pub fn load_in(apps_dir: &Path, name: &str) -> Result<Self, AppError> {
+        let config_path = apps_dir.join(name).join("config.yml");
+        let path = config_path.as_path();
 
         if !path.exists() {
             return Err(AppError::AppNotFound(name.to_string()));
@@ -272,10 +394,53 @@ impl AppConfig {
 
         // Migrate legacy single-domain to multi-domain format
         config.migrate_domains();
+        config.migrate_caches();
+        config.validate()?;
 
         Ok(config)
     }
 
+    /// Checks settings that parse fine but can't work, so a hand-edited
+    /// config fails when loaded rather than halfway through a deploy.
+    pub fn validate(&self) -> Result<(), AppError> {
+        if let Some(health_check) = &self.health_check {
+            health_check.validate()?;
+        }
+
+        // Without a host, the app would silently connect to a container never started
+        if let Some(database) = self.database.as_ref().filter(|db| db.external) {
+            if database.host.as_deref().is_none_or(|host| host.trim().is_empty()) {
+                return Err(AppError::Config(
+                    "An external database (external: true) requires database.host".into(),
+                ));
+            }
+        }
+
+        let exposed_addresses = self
+            .database
+            .iter()
+            .map(|db| &db.expose_address)
+            .chain(self.caches.iter().map(|cache| &cache.expose_address));
+        for address in exposed_addresses.flatten() {
+            validate_expose_address(address)?;
+        }
+
+        let mut names = Vec::new();
+        for cache in &self.caches {
+            if let Some(name) = &cache.name {
+                validate_cache_name(name)?;
+            }
+            if names.contains(&&cache.name) {
+                return Err(AppError::Config(match &cache.name {
+                    Some(name) => format!("Cache '{}' is configured twice", name),
+                    None => "Only one cache can be left unnamed".into(),
+                }));
+            }
+            names.push(&cache.name);
+        }
+        Ok(())
+    }
+
     /// Saves the app configuration to disk.
     pub fn save(&self) -> Result<(), AppError> {
         let content = serde_yaml::to_string(self)
@@ -287,8 +452,11 @@ impl AppConfig {
 
     /// Lists all configured apps.
     pub fn list_all() -> Result<Vec<String>, AppError> {
-        let apps_path = Path::new(FLAASE_APPS_PATH);
+        Self::list_in(Path::new(FLAASE_APPS_PATH))
+    }
 
+    /// Lists the apps configured in an apps directory.
+    pub fn list_in(apps_path: &Path) -> Result<Vec<String>, AppError> {
         if !apps_path.exists() {
             return Ok(Vec::new());
         }
@@ -315,6 +483,131 @@ impl AppConfig {
         apps.sort();
         Ok(apps)
     }
+
+    /// Returns a copy with notification credentials replaced by a placeholder.
+    pub fn redacted(&self) -> Self {
+        let mut config = self.clone();
+
+        if let Some(notifications) = config
+            .autodeploy_config
+            .as_mut()
+            .and_then(|a| a.notifications.as_mut())
+        {
+            if let Some(slack) = notifications.slack.as_mut() {
+                slack.webhook_url = REDACTED_VALUE.to_string();
+            }
+            if let Some(discord) = notifications.discord.as_mut() {
+                discord.webhook_url = REDACTED_VALUE.to_string();
+            }
+            if let Some(email) = notifications.email.as_mut() {
+                email.smtp_password = REDACTED_VALUE.to_string();
+            }
+            if let Some(telegram) = notifications.telegram.as_mut() {
+                telegram.bot_token = REDACTED_VALUE.to_string();
+            }
+        }
+
+        config
+    }
+
+    /// Renders a portable spec of this app for `fl init --from-file`.
+    /// Server-specific state (timestamps, SSH key path) is dropped and
+    /// notification credentials are replaced with a placeholder.
+    pub fn export_spec(&self, env: Option<&BTreeMap<String, String>>) -> Result<String, AppError> {
+        let mut config = self.redacted();
+        config.ssh_key = None;
+        config.deployed_at = None;
+
+        let mut value = serde_yaml::to_value(&config)
+            .map_err(|e| AppError::Config(format!("Failed to serialize app config: {}", e)))?;
+
+        if let serde_yaml::Value::Mapping(map) = &mut value {
+            map.remove("created_at");
+
+            if let Some(env) = env {
+                let env_value = serde_yaml::to_value(env).map_err(|e| {
+                    AppError::Config(format!("Failed to serialize environment: {}", e))
+                })?;
+                map.insert("env".into(), env_value);
+            }
+        }
+
+        serde_yaml::to_string(&value)
+            .map_err(|e| AppError::Config(format!("Failed to serialize app config: {}", e)))
+    }
+
+    /// Parses a spec produced by `export_spec`, returning the config and the
+    /// environment variables it carries (if exported with `--with-env`).
+    pub fn from_spec(content: &str) -> Result<(Self, BTreeMap<String, String>), AppError> {
+        let mut value: serde_yaml::Value = serde_yaml::from_str(content)
+            .map_err(|e| AppError::Config(format!("Failed to parse app spec: {}", e)))?;
+
+        let env = match &mut value {
+            serde_yaml::Value::Mapping(map) => match map.remove("env") {
+                Some(env) => serde_yaml::from_value(env).map_err(|e| {
+                    AppError::Config(format!("Invalid 'env' section in app spec: {}", e))
+                })?,
+                None => BTreeMap::new(),
+            },
+            _ => return Err(AppError::Config("App spec must be a YAML mapping".into())),
+        };
+
+        let mut config: Self = serde_yaml::from_value(value)
+            .map_err(|e| AppError::Config(format!("Failed to parse app spec: {}", e)))?;
+
+        config.created_at = Utc::now();
+        config.deployed_at = None;
+        config.migrate_domains();
+        config.migrate_caches();
+        config.validate()?;
+
+        Ok((config, env))
+    }
+
+    /// Drops notification channels whose credentials were redacted on export.
+    /// Returns the names of the removed channels.
+    pub fn strip_redacted_notifications(&mut self) -> Vec<&'static str> {
+        let mut removed = Vec::new();
+
+        let Some(notifications) = self
+            .autodeploy_config
+            .as_mut()
+            .and_then(|a| a.notifications.as_mut())
+        else {
+            return removed;
+        };
+
+        if notifications.slack.as_ref().is_some_and(|s| s.webhook_url == REDACTED_VALUE) {
+            notifications.slack = None;
+            removed.push("Slack");
+        }
+        if notifications
+            .discord
+            .as_ref()
+            .is_some_and(|d| d.webhook_url == REDACTED_VALUE)
+        {
+            notifications.discord = None;
+            removed.push("Discord");
+        }
+        if notifications
+            .email
+            .as_ref()
+            .is_some_and(|e| e.smtp_password == REDACTED_VALUE)
+        {
+            notifications.email = None;
+            removed.push("Email");
+        }
+        if notifications
+            .telegram
+            .as_ref()
+            .is_some_and(|t| t.bot_token == REDACTED_VALUE)
+        {
+            notifications.telegram = None;
+            removed.push("Telegram");
+        }
+
+        removed
+    }
 }
 
 /// Application stack type.
@@ -419,7 +712,7 @@ impl Stack {
     pub fn has_build_step(&self) -> bool {
         matches!(
             self,
-            Stack::NextJs | Stack::NestJs | Stack::Rust | Stack::Go | Stack::Java
+            Stack::NextJs | Stack::NestJs | Stack::Rust | Stack::Go | Stack::Java | Stack::Static
         )
     }
 
@@ -445,6 +738,7 @@ impl Stack {
             Stack::Rust => Some("cargo build --release"),
             Stack::Go => Some("go build -o app ."),
             Stack::Java => Some("mvn package -DskipTests"),
+            Stack::Static => Some("npm run build"),
             _ => None,
         }
     }
@@ -461,7 +755,7 @@ impl fmt::Display for Stack {
 // ============================================================================
 
 /// Detailed stack configuration for customizable stacks.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct StackConfig {
     /// Runtime version (e.g., "3.12" for Python, "22" for Node)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -483,19 +777,6 @@ pub struct StackConfig {
     pub install_command: Option<String>,
 }
 
-impl Default for StackConfig {
-    fn default() -> Self {
-        Self {
-            version: None,
-            package_manager: None,
-            framework: None,
-            build_command: None,
-            start_command: None,
-            install_command: None,
-        }
-    }
-}
-
 /// Package managers supported by Flaase.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -542,7 +823,13 @@ impl PackageManager {
             Stack::Java => &[PackageManager::Maven, PackageManager::Gradle],
             Stack::Go => &[PackageManager::GoMod],
             Stack::Rust => &[PackageManager::Cargo],
-            Stack::Static | Stack::Dockerfile => &[PackageManager::None],
+            Stack::Static => &[
+                PackageManager::None,
+                PackageManager::Npm,
+                PackageManager::Yarn,
+                PackageManager::Pnpm,
+            ],
+            Stack::Dockerfile => &[PackageManager::None],
         }
     }
 
@@ -622,6 +909,10 @@ pub enum Framework {
     Express,
     Fastify,
     Hono,
+    // Static frontends
+    Vite,
+    #[serde(rename = "cra")]
+    CreateReactApp,
     Other,
 }
 
@@ -636,6 +927,7 @@ impl Framework {
             Stack::Go => &[Framework::Gin, Framework::Echo, Framework::Fiber, Framework::Chi, Framework::Other],
             Stack::Rust => &[Framework::Actix, Framework::Axum, Framework::Rocket, Framework::Other],
             Stack::NodeJs => &[Framework::Express, Framework::Fastify, Framework::Hono, Framework::Other],
+            Stack::Static => &[Framework::Vite, Framework::CreateReactApp, Framework::Other],
             _ => &[],
         }
     }
@@ -661,6 +953,8 @@ impl Framework {
             Framework::Express => "Express",
             Framework::Fastify => "Fastify",
             Framework::Hono => "Hono",
+            Framework::Vite => "Vite",
+            Framework::CreateReactApp => "Create React App",
             Framework::Other => "Other / None",
         }
     }
@@ -679,7 +973,7 @@ impl Framework {
             Framework::Gin | Framework::Echo | Framework::Fiber | Framework::Chi => "./main",
             Framework::Actix | Framework::Axum | Framework::Rocket => "./app",
             Framework::Express | Framework::Fastify | Framework::Hono => "node dist/index.js",
-            Framework::Other => "",
+            Framework::Vite | Framework::CreateReactApp | Framework::Other => "",
         }
     }
 }
@@ -690,12 +984,55 @@ impl fmt::Display for Framework {
     }
 }
 
+/// Default host address for exposed database/cache ports (reachable via SSH tunnel only).
+pub const DEFAULT_EXPOSE_ADDRESS: &str = "127.0.0.1";
+
+/// Checks an `expose_address` is an IPv4 address, the form published port
+/// bindings are written and compared in.
+fn validate_expose_address(address: &str) -> Result<(), AppError> {
+    match address.trim().parse::<std::net::Ipv4Addr>() {
+        Ok(_) => Ok(()),
+        Err(_) => Err(AppError::Config(format!(
+            "Invalid expose_address '{}': expected an IPv4 address like 127.0.0.1",
+            address
+        ))),
+    }
+}
+
+/// Resolves the host binding for a service's optional exposed port.
+fn exposed_binding(port: Option<u16>, address: Option<&str>) -> Option<(&str, u16)> {
+    let address = address
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .unwrap_or(DEFAULT_EXPOSE_ADDRESS);
+    port.map(|port| (address, port))
+}
+
 /// Database configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DatabaseConfig {
     #[serde(rename = "type")]
     pub db_type: DatabaseType,
     pub name: String,
+    /// Image tag overriding the default version (e.g. "15" or "postgres:15").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_tag: Option<String>,
+    /// Host port to publish the database on, for external tools.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expose_port: Option<u16>,
+    /// Host address the exposed port binds to (default: 127.0.0.1).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expose_address: Option<String>,
+    /// Database hosted outside Flaase (RDS, Supabase, Neon, ...): no container
+    /// is started, only the connection URL is injected.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub external: bool,
+    /// Hostname of the external database.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// Port of the external database (default: the type's default port).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
 }
 
 impl DatabaseConfig {
@@ -705,6 +1042,57 @@ impl DatabaseConfig {
         Self {
             db_type,
             name: db_name,
+            image_tag: None,
+            expose_port: None,
+            expose_address: None,
+            external: false,
+            host: None,
+            port: None,
+        }
+    }
+
+    /// Creates the config of a database hosted outside Flaase.
+    pub fn external(db_type: DatabaseType, name: &str, host: &str, port: Option<u16>) -> Self {
+        Self {
+            external: true,
+            host: Some(host.to_string()),
+            port,
+            name: name.to_string(),
+            ..Self::new(db_type, name)
+        }
+    }
+
+    /// Host the app connects to: the external host, or the app's database container.
+    pub fn connection_host(&self, app_name: &str) -> String {
+        match (&self.host, self.external) {
+            (Some(host), true) => host.clone(),
+            _ => format!("flaase-{}-db", app_name),
+        }
+    }
+
+    /// Port the app connects to.
+    pub fn connection_port(&self) -> u16 {
+        match self.port {
+            Some(port) if self.external => port,
+            _ => self.db_type.default_port(),
+        }
+    }
+
+    /// Returns the host address and port to publish the database on, if exposed.
+    pub fn exposed_binding(&self) -> Option<(&str, u16)> {
+        exposed_binding(self.expose_port, self.expose_address.as_deref())
+    }
+
+    /// Returns the Docker image, honoring the pinned tag if any.
+    pub fn docker_image(&self) -> String {
+        match self.image_tag.as_deref().map(str::trim) {
+            Some(tag) if tag.contains(':') => tag.to_string(),
+            Some(tag) if !tag.is_empty() => {
+                let default = self.db_type.docker_image();
+                let repo = default.split(':').next().unwrap_or(default);
+                format!("{}:{}", repo, tag)
+            }
+            _ => self.db_type.docker_image().to_string(),
         }
     }
 }
@@ -715,6 +1103,7 @@ impl DatabaseConfig {
 pub enum DatabaseType {
     PostgreSQL,
     MySQL,
+    MariaDB,
     MongoDB,
 }
 
@@ -724,6 +1113,7 @@ impl DatabaseType {
         &[
             DatabaseType::PostgreSQL,
             DatabaseType::MySQL,
+            DatabaseType::MariaDB,
             DatabaseType::MongoDB,
         ]
     }
@@ -733,6 +1123,7 @@ impl DatabaseType {
         match self {
             DatabaseType::PostgreSQL => "PostgreSQL",
             DatabaseType::MySQL => "MySQL",
+            DatabaseType::MariaDB => "MariaDB",
             DatabaseType::MongoDB => "MongoDB",
         }
     }
@@ -741,7 +1132,7 @@ impl DatabaseType {
     pub fn default_port(&self) -> u16 {
         match self {
             DatabaseType::PostgreSQL => 5432,
-            DatabaseType::MySQL => 3306,
+            DatabaseType::MySQL | DatabaseType::MariaDB => 3306,
             DatabaseType::MongoDB => 27017,
         }
     }
@@ -751,6 +1142,7 @@ impl DatabaseType {
         match self {
             DatabaseType::PostgreSQL => "postgres:16-alpine",
             DatabaseType::MySQL => "mysql:8",
+            DatabaseType::MariaDB => "mariadb:11",
             DatabaseType::MongoDB => "mongo:7",
         }
     }
@@ -759,7 +1151,7 @@ impl DatabaseType {
     pub fn url_env_var(&self) -> &str {
         match self {
             DatabaseType::PostgreSQL => "DATABASE_URL",
-            DatabaseType::MySQL => "DATABASE_URL",
+            DatabaseType::MySQL | DatabaseType::MariaDB => "DATABASE_URL",
             DatabaseType::MongoDB => "MONGODB_URL",
         }
     }
@@ -776,11 +1168,62 @@ impl fmt::Display for DatabaseType {
 pub struct CacheConfig {
     #[serde(rename = "type")]
     pub cache_type: CacheType,
+    /// Name of an additional cache instance (None for the primary cache).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Host port to publish the cache on, for external tools.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expose_port: Option<u16>,
+    /// Host address the exposed port binds to (default: 127.0.0.1).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expose_address: Option<String>,
 }
 
 impl CacheConfig {
     pub fn new(cache_type: CacheType) -> Self {
-        Self { cache_type }
+        Self {
+            cache_type,
+            name: None,
+            expose_port: None,
+            expose_address: None,
+        }
+    }
+
+    /// Creates a named cache instance.
+    pub fn named(cache_type: CacheType, name: &str) -> Self {
+        Self {
+            cache_type,
+            name: Some(name.to_string()),
+            expose_port: None,
+            expose_address: None,
+        }
+    }
+
+    /// Returns the host address and port to publish the cache on, if exposed.
+    pub fn exposed_binding(&self) -> Option<(&str, u16)> {
+        exposed_binding(self.expose_port, self.expose_address.as_deref())
+    }
+
+    /// Container name suffix: "cache" or "cache-<name>".
+    pub fn container_suffix(&self) -> String {
+        match &self.name {
+            Some(name) => format!("cache-{}", name),
+            None => "cache".to_string(),
+        }
+    }
+
+    /// Environment variable holding the connection URL
+    /// (e.g. REDIS_URL, or REDIS_SESSIONS_URL for a cache named "sessions").
+    pub fn url_env_var(&self) -> String {
+        let base = self.cache_type.url_env_var();
+        match &self.name {
+            Some(name) => format!(
+                "{}_{}_URL",
+                base.trim_end_matches("_URL"),
+                name.to_uppercase().replace('-', "_")
+            ),
+            None => base.to_string(),
+        }
     }
 }
 
@@ -789,18 +1232,20 @@ impl CacheConfig {
 #[serde(rename_all = "lowercase")]
 pub enum CacheType {
     Redis,
+    Memcached,
 }
 
 impl CacheType {
     /// Returns all available cache types.
     pub fn all() -> &'static [CacheType] {
-        &[CacheType::Redis]
+        &[CacheType::Redis, CacheType::Memcached]
     }
 
     /// Returns the display name.
     pub fn display_name(&self) -> &str {
         match self {
             CacheType::Redis => "Redis",
+            CacheType::Memcached => "Memcached",
         }
     }
 
@@ -808,6 +1253,7 @@ impl CacheType {
     pub fn default_port(&self) -> u16 {
         match self {
             CacheType::Redis => 6379,
+            CacheType::Memcached => 11211,
         }
     }
 
@@ -815,6 +1261,7 @@ impl CacheType {
     pub fn docker_image(&self) -> &str {
         match self {
             CacheType::Redis => "redis:7-alpine",
+            CacheType::Memcached => "memcached:alpine",
         }
     }
 
@@ -822,6 +1269,16 @@ impl CacheType {
     pub fn url_env_var(&self) -> &str {
         match self {
             CacheType::Redis => "REDIS_URL",
+            CacheType::Memcached => "MEMCACHED_URL",
+        }
+    }
+
+    /// Returns true if the cache is password-protected. Memcached has no
+    /// built-in auth and is only reachable on the app network.
+    pub fn requires_auth(&self) -> bool {
+        match self {
+            CacheType::Redis => true,
+            CacheType::Memcached => false,
         }
     }
 }
@@ -835,6 +1292,13 @@ impl fmt::Display for CacheType {
 /// Health check configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HealthCheckConfig {
+    /// How the app is probed (default: http).
+    #[serde(default, skip_serializing_if = "HealthCheckType::is_http")]
+    pub check_type: HealthCheckType,
+    /// Command run inside the container for the `command` check type;
+    /// exit code 0 means healthy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
     /// HTTP endpoint to check (default: "/health" or "/").
     #[serde(default = "HealthCheckConfig::default_endpoint")]
     pub endpoint: String,
@@ -847,6 +1311,60 @@ pub struct HealthCheckConfig {
     /// Interval between retries in seconds (default: 5).
     #[serde(default = "HealthCheckConfig::default_interval")]
     pub interval: u32,
+    /// Seconds to wait before the first probe, on top of the retry budget
+    /// (default: 0). The check gives up after `start_period + retries * interval`.
+    #[serde(default)]
+    pub start_period: u64,
+    /// Number of container log lines shown when the check fails
+    /// (default: 50, or 30 for blue-green slots).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_log_lines: Option<u32>,
+    /// After a successful deploy, GET the endpoint through the public domain
+    /// and warn if it isn't reachable yet (default: true).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_check: Option<bool>,
+    /// How probes are spaced (default: interval).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub probe: Option<HealthProbeMode>,
+}
+
+/// What a health check probes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthCheckType {
+    /// GET the endpoint on the app port.
+    #[default]
+    Http,
+    /// Open a TCP connection to the app port (gRPC, game servers, ...).
+    Tcp,
+    /// Run `command` inside the container and check its exit code.
+    Command,
+}
+
+impl HealthCheckType {
+    fn is_http(&self) -> bool {
+        *self == HealthCheckType::Http
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HealthCheckType::Http => "http",
+            HealthCheckType::Tcp => "tcp",
+            HealthCheckType::Command => "command",
+        }
+    }
+}
+
+/// How health check probes are spaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthProbeMode {
+    /// `retries` probes, `interval` seconds apart.
+    #[default]
+    Interval,
+    /// A probe every second until `retries * interval` seconds have passed,
+    /// so a fast-starting app passes as soon as it is up.
+    Continuous,
 }
 
 impl HealthCheckConfig {
@@ -865,15 +1383,58 @@ impl HealthCheckConfig {
     fn default_interval() -> u32 {
         5
     }
+
+    /// Checks that the `command` check type has a command to run.
+    pub fn validate(&self) -> Result<(), AppError> {
+        let has_command = self.command.as_deref().is_some_and(|c| !c.trim().is_empty());
+        if self.check_type == HealthCheckType::Command && !has_command {
+            return Err(AppError::Config(
+                "Health check type 'command' requires healthcheck.command".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Seconds to wait between two probes.
+    pub fn probe_delay(&self) -> u64 {
+        match self.probe.unwrap_or_default() {
+            HealthProbeMode::Interval => self.interval as u64,
+            HealthProbeMode::Continuous => 1,
+        }
+    }
+
+    /// Time the retry budget covers: `retries × interval`.
+    pub fn probe_window(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(u64::from(self.retries) * u64::from(self.interval))
+    }
+
+    /// Whether to probe again after `attempts` failed probes, `elapsed` into
+    /// the check. Continuous mode goes on until the probe window has passed,
+    /// however long each probe took, and probes at least `retries` times.
+    pub fn should_retry(&self, attempts: u32, elapsed: std::time::Duration) -> bool {
+        match self.probe.unwrap_or_default() {
+            HealthProbeMode::Interval => attempts < self.retries,
+            HealthProbeMode::Continuous => {
+                let next_probe = elapsed + std::time::Duration::from_secs(self.probe_delay());
+                attempts < self.retries || next_probe < self.probe_window()
+            }
+        }
+    }
 }
 
 impl Default for HealthCheckConfig {
     fn default() -> Self {
         Self {
+            check_type: HealthCheckType::Http,
+            command: None,
             endpoint: Self::default_endpoint(),
             timeout: Self::default_timeout(),
             retries: Self::default_retries(),
             interval: Self::default_interval(),
+            start_period: 0,
+            failure_log_lines: None,
+            public_check: None,
+            probe: None,
         }
     }
 }
@@ -886,6 +1447,9 @@ pub struct DomainConfig {
     pub primary: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub auth: Option<DomainAuth>,
+    /// Only route requests under this path on the domain (e.g. "/api").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_prefix: Option<String>,
 }
 
 impl DomainConfig {
@@ -894,9 +1458,15 @@ impl DomainConfig {
             domain: domain.to_string(),
             primary,
             auth: None,
+            path_prefix: None,
         }
     }
 
+    /// Public URL the app is reachable at on this domain.
+    pub fn url(&self) -> String {
+        format!("https://{}{}", self.domain, self.path_prefix.as_deref().unwrap_or(""))
+    }
+
     pub fn with_auth(mut self, username: &str) -> Self {
         self.auth = Some(DomainAuth {
             enabled: true,
@@ -922,6 +1492,13 @@ pub struct AutodeployConfig {
     /// Branch to watch for deployments (used when environments is not configured).
     #[serde(default = "AutodeployConfig::default_branch")]
     pub branch: String,
+    /// Glob pattern of Git tags that trigger a deployment (e.g. `v*`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deploy_on_tag: Option<String>,
+    /// Glob patterns of repository paths; when set, branch pushes only deploy
+    /// if a changed file matches one of them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
     /// Webhook endpoint path (unique per app).
     pub webhook_path: String,
     /// Rate limiting configuration.
@@ -962,6 +1539,8 @@ impl AutodeployConfig {
         Self {
             enabled: true,
             branch: Self::default_branch(),
+            deploy_on_tag: None,
+            paths: Vec::new(),
             webhook_path: webhook_path.to_string(),
             rate_limit: Some(RateLimitConfig::default()),
             notifications: None,
@@ -979,6 +1558,60 @@ impl AutodeployConfig {
         self.branch = branch.to_string();
         self
     }
+
+    /// Returns true if a pushed tag matches the `deploy_on_tag` pattern.
+    pub fn matches_tag(&self, tag: &str) -> bool {
+        self.deploy_on_tag
+            .as_deref()
+            .is_some_and(|pattern| glob_match(pattern, tag))
+    }
+
+    /// Returns the first `(pattern, file)` pair where a changed file matches
+    /// one of the `paths` patterns. A pattern without wildcards also matches
+    /// everything below it, so `apps/api` covers `apps/api/src/main.rs`.
+    pub fn matching_path<'f>(&self, files: &'f [String]) -> Option<(&str, &'f str)> {
+        self.paths.iter().find_map(|pattern| {
+            let dir = format!("{}/", pattern.trim_end_matches('/'));
+            files
+                .iter()
+                .find(|file| glob_match(pattern, file) || file.starts_with(&dir))
+                .map(|file| (pattern.as_str(), file.as_str()))
+        })
+    }
+}
+
+/// Matches text against a glob pattern where `*` matches any run of
+/// characters and `?` matches exactly one.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text index it was tried at
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, start)) => {
+                    p = star + 1;
+                    t = start + 1;
+                    backtrack = Some((star, start + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
 }
 
 /// Rate limiting configuration for autodeploy.
@@ -1020,7 +1653,7 @@ impl Default for RateLimitConfig {
 }
 
 /// Notification configuration for autodeploy.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct NotificationConfig {
     /// Whether notifications are enabled.
     #[serde(default)]
@@ -1034,20 +1667,21 @@ pub struct NotificationConfig {
     /// Email SMTP configuration.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub email: Option<EmailNotificationConfig>,
+    /// Telegram bot configuration.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telegram: Option<TelegramNotificationConfig>,
     /// Events to notify on.
     #[serde(default)]
     pub events: NotificationEvents,
 }
 
-impl Default for NotificationConfig {
-    fn default() -> Self {
-        Self {
-            enabled: false,
-            slack: None,
-            discord: None,
-            email: None,
-            events: NotificationEvents::default(),
-        }
+impl NotificationConfig {
+    /// Returns true if at least one notification provider is configured.
+    pub fn has_provider(&self) -> bool {
+        self.slack.is_some()
+            || self.discord.is_some()
+            || self.email.is_some()
+            || self.telegram.is_some()
     }
 }
 
@@ -1074,6 +1708,15 @@ pub struct DiscordNotificationConfig {
     pub username: Option<String>,
 }
 
+/// Telegram bot configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelegramNotificationConfig {
+    /// Bot token from @BotFather.
+    pub bot_token: String,
+    /// Chat, group or channel ID the bot posts to.
+    pub chat_id: String,
+}
+
 /// Email SMTP configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmailNotificationConfig {
@@ -1229,6 +1872,10 @@ pub struct HookCommand {
     /// Run inside the app container (vs on host).
     #[serde(default)]
     pub run_in_container: bool,
+    /// Run in a throwaway container from the app image instead of the
+    /// serving one (takes precedence over `run_in_container`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub run_in_ephemeral: bool,
 }
 
 impl HookCommand {
@@ -1247,6 +1894,7 @@ impl HookCommand {
             timeout_seconds: Self::default_timeout(),
             required: Self::default_required(),
             run_in_container: false,
+            run_in_ephemeral: false,
         }
     }
 }
@@ -1361,6 +2009,37 @@ pub struct BuildConfig {
     /// Optional registry for cache-from.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cache_from: Option<String>,
+    /// Where build layer cache is kept between builds.
+    #[serde(default)]
+    pub cache_mode: BuildCacheMode,
+}
+
+/// Storage for Docker build layer cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BuildCacheMode {
+    /// Reuse layers from the previous image (`--cache-from <latest>`).
+    #[default]
+    Image,
+    /// Export the BuildKit cache to a directory under the app dir.
+    Local,
+}
+
+impl BuildCacheMode {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "image" => Some(BuildCacheMode::Image),
+            "local" => Some(BuildCacheMode::Local),
+            _ => None,
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            BuildCacheMode::Image => "image",
+            BuildCacheMode::Local => "local",
+        }
+    }
 }
 
 impl BuildConfig {
@@ -1414,6 +2093,7 @@ impl Default for BuildConfig {
             cache_enabled: Self::default_cache_enabled(),
             buildkit: Self::default_buildkit(),
             cache_from: None,
+            cache_mode: BuildCacheMode::default(),
         }
     }
 }
@@ -1550,6 +2230,103 @@ impl fmt::Display for Registry {
 // Volume Configuration
 // ============================================================================
 
+/// Container resource settings. Reservations and `memory`/`cpus` apply to the
+/// app containers; database and cache containers have their own hard limits.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourcesConfig {
+    /// Relative CPU weight under contention (`docker run --cpu-shares`, default 1024).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_shares: Option<u32>,
+    /// Soft memory limit the kernel reclaims down to under pressure (e.g. "256m").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_reservation: Option<String>,
+    /// Hard memory limit; the container is OOM-killed above it (e.g. "512m").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<String>,
+    /// Maximum number of CPUs the container may use (e.g. 1.5).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpus: Option<f64>,
+    /// Hard memory limit of each database and cache container.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_memory: Option<String>,
+    /// Maximum number of CPUs of each database and cache container.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_cpus: Option<f64>,
+}
+
+impl ResourcesConfig {
+    /// Returns true if no setting is configured.
+    pub fn is_empty(&self) -> bool {
+        self.cpu_shares.is_none()
+            && self.memory_reservation.is_none()
+            && self.memory.is_none()
+            && self.cpus.is_none()
+            && self.service_memory.is_none()
+            && self.service_cpus.is_none()
+    }
+
+    /// Returns true if a hard memory or CPU limit is configured.
+    pub fn has_limits(&self) -> bool {
+        self.memory.is_some()
+            || self.cpus.is_some()
+            || self.service_memory.is_some()
+            || self.service_cpus.is_some()
+    }
+}
+
+/// A command run on a cron schedule in a short-lived container from the app image.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CronJob {
+    /// Job name, unique per app (used in the systemd unit name).
+    pub name: String,
+    /// 5-field cron expression (e.g. "* * * * *").
+    pub schedule: String,
+    /// Shell command run in the container (e.g. "php artisan schedule:run").
+    pub command: String,
+}
+
+impl AppConfig {
+    /// Returns the cron job with the given name.
+    pub fn cron_job(&self, name: &str) -> Option<&CronJob> {
+        self.cron_jobs.iter().find(|job| job.name == name)
+    }
+}
+
+/// Secrets file rendered at deploy and mounted read-only into the app container.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SecretsFileConfig {
+    /// Path of the file inside the container.
+    #[serde(default = "SecretsFileConfig::default_path")]
+    pub path: String,
+    /// Generated variables to include (empty = all of them).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keys: Vec<String>,
+    /// Stop passing the generated secrets as environment variables.
+    #[serde(default)]
+    pub file_only: bool,
+}
+
+impl SecretsFileConfig {
+    pub fn default_path() -> String {
+        "/run/secrets/app.env".to_string()
+    }
+
+    /// Returns true if the variable should be written to the file.
+    pub fn includes(&self, key: &str) -> bool {
+        self.keys.is_empty() || self.keys.iter().any(|k| k == key)
+    }
+}
+
+impl Default for SecretsFileConfig {
+    fn default() -> Self {
+        Self {
+            path: Self::default_path(),
+            keys: Vec::new(),
+            file_only: false,
+        }
+    }
+}
+
 /// Volume mount configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VolumeMount {
@@ -1589,7 +2366,7 @@ impl VolumeMount {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegistryCredentials {
     pub username: String,
-    #[serde(skip_serializing)]
+    #[serde(default, skip_serializing)]
     pub password: String,
     /// Base64-encoded auth string for Docker.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1606,4 +2383,253 @@ impl RegistryCredentials {
             auth_token: Some(auth_token),
         }
     }
+
+    /// Returns the password, recovered from the auth token when the
+    /// credentials were loaded from disk (the password itself is not saved).
+    pub fn secret(&self) -> Option<String> {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+
+        if !self.password.is_empty() {
+            return Some(self.password.clone());
+        }
+
+        let decoded = STANDARD.decode(self.auth_token.as_deref()?).ok()?;
+        let decoded = String::from_utf8(decoded).ok()?;
+        decoded
+            .split_once(':')
+            .map(|(_, password)| password.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_spec_round_trip_without_secrets() {
+        let mut config = AppConfig::new_source(
+            "myapp".into(),
+            "git@github.com:me/myapp.git".into(),
+            PathBuf::from("/root/.ssh/id_ed25519_flaase"),
+            Stack::NodeJs,
+            None,
+            "myapp.example.com".into(),
+            Some(3000),
+            None,
+            None,
+            true,
+        );
+        config.deployed_at = Some(Utc::now());
+        let mut autodeploy = AutodeployConfig::new("/hooks/myapp");
+        autodeploy.notifications = Some(NotificationConfig {
+            enabled: true,
+            slack: Some(SlackNotificationConfig {
+                webhook_url: "https://hooks.slack.com/services/T/B/secret".into(),
+                channel: None,
+                username: None,
+            }),
+            telegram: Some(TelegramNotificationConfig {
+                bot_token: "123456:bot-secret".into(),
+                chat_id: "-100200300".into(),
+            }),
+            ..Default::default()
+        });
+        config.autodeploy_config = Some(autodeploy);
+
+        let mut env = BTreeMap::new();
+        env.insert("API_KEY".to_string(), "abc".to_string());
+
+        let spec = config.export_spec(None).unwrap();
+        assert!(!spec.contains("created_at"));
+        assert!(!spec.contains("deployed_at"));
+        assert!(!spec.contains("ssh_key"));
+        assert!(!spec.contains("hooks.slack.com"));
+        assert!(!spec.contains("bot-secret"));
+        assert!(!spec.contains("API_KEY"));
+
+        let (mut parsed, parsed_env) = AppConfig::from_spec(&spec).unwrap();
+        assert!(parsed_env.is_empty());
+        assert_eq!(parsed.name, "myapp");
+        assert_eq!(parsed.primary_domain(), "myapp.example.com");
+        assert_eq!(parsed.strip_redacted_notifications(), vec!["Slack", "Telegram"]);
+
+        let with_env = config.export_spec(Some(&env)).unwrap();
+        let (_, parsed_env) = AppConfig::from_spec(&with_env).unwrap();
+        assert_eq!(parsed_env.get("API_KEY").map(String::as_str), Some("abc"));
+
+        // Specs are validated like configs loaded from disk
+        let broken = format!("{}health_check:\n  check_type: command\n", spec);
+        assert!(AppConfig::from_spec(&broken).is_err());
+        let hostless = format!(
+            "{}database:\n  type: postgresql\n  name: shop\n  external: true\n",
+            spec
+        );
+        assert!(AppConfig::from_spec(&hostless).is_err());
+        let external = format!("{}  host: db.example.com\n", hostless);
+        assert!(AppConfig::from_spec(&external).is_ok());
+    }
+
+    #[test]
+    fn test_caches_migration_and_names() {
+        let mut config = AppConfig::new_source(
+            "myapp".into(),
+            "git@github.com:me/myapp.git".into(),
+            PathBuf::from("/root/.ssh/id_ed25519_flaase"),
+            Stack::NodeJs,
+            None,
+            "myapp.example.com".into(),
+            None,
+            None,
+            None,
+            false,
+        );
+        config.cache = Some(CacheConfig::new(CacheType::Redis));
+        config.caches = vec![CacheConfig::named(CacheType::Redis, "sessions")];
+
+        config.migrate_caches();
+        assert!(config.cache.is_none());
+        assert_eq!(config.caches.len(), 2);
+        assert_eq!(config.primary_cache().unwrap().cache_type, CacheType::Redis);
+        assert!(config.validate().is_ok());
+
+        config.caches.push(CacheConfig::named(CacheType::Redis, "Sessions_2"));
+        assert!(config.validate().is_err());
+
+        config.caches[2].name = Some("sessions".into());
+        assert!(config.validate().is_err());
+
+        config.caches[2].name = None;
+        assert!(config.validate().is_err());
+
+        config.caches.truncate(2);
+        config.caches[1].expose_address = Some("10.0.0.5".into());
+        assert!(config.validate().is_ok());
+        config.caches[1].expose_address = Some("::1".into());
+        assert!(config.validate().is_err());
+        config.caches[1].expose_address = Some("localhost".into());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_secrets_file_config() {
+        let parsed: SecretsFileConfig = serde_yaml::from_str("keys: [DATABASE_URL]").unwrap();
+        assert_eq!(parsed.path, "/run/secrets/app.env");
+        assert!(!parsed.file_only);
+        assert!(parsed.includes("DATABASE_URL"));
+        assert!(!parsed.includes("REDIS_URL"));
+
+        assert!(SecretsFileConfig::default().includes("REDIS_URL"));
+    }
+
+    #[test]
+    fn test_registry_credentials_secret_from_token() {
+        let creds = RegistryCredentials::new("deploy", "s3cr:et");
+        assert_eq!(creds.secret().as_deref(), Some("s3cr:et"));
+
+        let json = serde_json::to_string(&creds).unwrap();
+        assert!(!json.contains("s3cr:et"));
+        let loaded: RegistryCredentials = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.secret().as_deref(), Some("s3cr:et"));
+    }
+
+    #[test]
+    fn test_health_probe_modes() {
+        use std::time::Duration;
+
+        let interval: HealthCheckConfig = serde_yaml::from_str("retries: 3\ninterval: 5").unwrap();
+        assert_eq!(interval.probe_delay(), 5);
+        assert!(interval.should_retry(2, Duration::from_secs(60)));
+        assert!(!interval.should_retry(3, Duration::ZERO));
+
+        let continuous: HealthCheckConfig =
+            serde_yaml::from_str("retries: 3\ninterval: 5\nprobe: continuous").unwrap();
+        assert_eq!(continuous.probe_delay(), 1);
+        assert_eq!(continuous.probe_window(), Duration::from_secs(15));
+        assert!(continuous.should_retry(10, Duration::from_secs(13)));
+        // Slow probes use up the window in fewer attempts
+        assert!(!continuous.should_retry(5, Duration::from_secs(14)));
+        assert!(continuous.should_retry(2, Duration::from_secs(30)));
+
+        let huge: HealthCheckConfig =
+            serde_yaml::from_str("retries: 4294967295\ninterval: 4294967295").unwrap();
+        assert_eq!(huge.probe_window().as_secs(), u64::from(u32::MAX) * u64::from(u32::MAX));
+    }
+
+    #[test]
+    fn test_health_check_start_period() {
+        assert_eq!(HealthCheckConfig::default().start_period, 0);
+
+        let legacy: HealthCheckConfig = serde_yaml::from_str("retries: 3\ninterval: 5").unwrap();
+        assert_eq!(legacy.start_period, 0);
+
+        let slow: HealthCheckConfig = serde_yaml::from_str("start_period: 60").unwrap();
+        assert_eq!(slow.start_period, 60);
+        assert_eq!(slow.probe_window().as_secs(), 15);
+    }
+
+    #[test]
+    fn test_health_check_types() {
+        let http: HealthCheckConfig = serde_yaml::from_str("endpoint: /up").unwrap();
+        assert_eq!(http.check_type, HealthCheckType::Http);
+        assert!(http.validate().is_ok());
+        // The default type is omitted so existing configs stay unchanged
+        assert!(!serde_yaml::to_string(&http).unwrap().contains("check_type"));
+
+        let tcp: HealthCheckConfig = serde_yaml::from_str("check_type: tcp").unwrap();
+        assert_eq!(tcp.check_type, HealthCheckType::Tcp);
+        let yaml = serde_yaml::to_string(&tcp).unwrap();
+        assert!(yaml.contains("check_type: tcp"));
+        let reloaded: HealthCheckConfig = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(reloaded.check_type, HealthCheckType::Tcp);
+
+        let command: HealthCheckConfig =
+            serde_yaml::from_str("check_type: command\ncommand: ./bin/healthcheck").unwrap();
+        assert_eq!(command.check_type, HealthCheckType::Command);
+        assert_eq!(command.command.as_deref(), Some("./bin/healthcheck"));
+        assert!(command.validate().is_ok());
+
+        let missing: HealthCheckConfig = serde_yaml::from_str("check_type: command").unwrap();
+        assert!(missing.validate().is_err());
+        assert!(serde_yaml::from_str::<HealthCheckConfig>("check_type: grpc").is_err());
+    }
+
+    #[test]
+    fn test_deploy_on_tag_pattern() {
+        let mut autodeploy = AutodeployConfig::new("app-abc");
+        assert!(!autodeploy.matches_tag("v1.2.3"));
+
+        autodeploy.deploy_on_tag = Some("v*".into());
+        assert!(autodeploy.matches_tag("v1.2.3"));
+        assert!(!autodeploy.matches_tag("release-1"));
+
+        autodeploy.deploy_on_tag = Some("v?.*.*".into());
+        assert!(autodeploy.matches_tag("v2.0.1"));
+        assert!(!autodeploy.matches_tag("v10.0.1"));
+
+        autodeploy.deploy_on_tag = Some("*-rc*".into());
+        assert!(autodeploy.matches_tag("v1-rc-rc2"));
+        assert!(!autodeploy.matches_tag("v1"));
+    }
+
+    #[test]
+    fn test_autodeploy_path_filters() {
+        let mut autodeploy = AutodeployConfig::new("app-abc");
+        autodeploy.paths = vec!["apps/api".into(), "*.lock".into()];
+
+        let docs = vec!["docs/index.md".to_string(), "README.md".to_string()];
+        assert_eq!(autodeploy.matching_path(&docs), None);
+
+        let api = vec!["docs/index.md".to_string(), "apps/api/src/main.rs".to_string()];
+        assert_eq!(
+            autodeploy.matching_path(&api),
+            Some(("apps/api", "apps/api/src/main.rs"))
+        );
+
+        let lock = vec!["web/pnpm.lock".to_string()];
+        assert_eq!(autodeploy.matching_path(&lock), Some(("*.lock", "web/pnpm.lock")));
+
+        // A sibling directory sharing the prefix is not a match
+        let sibling = vec!["apps/api-docs/guide.md".to_string()];
+        assert_eq!(autodeploy.matching_path(&sibling), None);
+    }
 }
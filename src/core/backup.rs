@@ -0,0 +1,867 @@
+//! Database backups: dumps from the managed database container, retention
+//! and scheduling through a systemd timer. Also opens interactive shells in
+//! the same container.
+
+use std::fs::{File, OpenOptions};
+use std::io::{IsTerminal, Write};
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use chrono::{DateTime, Duration, Local, Utc};
+
+use crate::core::app_config::{AppConfig, DatabaseConfig, DatabaseType};
+use crate::core::error::AppError;
+use crate::core::secrets::{DatabaseSecrets, SecretsManager};
+use crate::providers::container_cli;
+
+/// Directory holding systemd unit files.
+const SYSTEMD_PATH: &str = "/etc/systemd/system";
+
+/// A backup file on disk.
+#[derive(Debug, Clone)]
+pub struct BackupFile {
+    pub path: PathBuf,
+    pub size: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Database backup operations for an app.
+pub struct DatabaseBackup<'a> {
+    config: &'a AppConfig,
+}
+
+impl<'a> DatabaseBackup<'a> {
+    pub fn new(config: &'a AppConfig) -> Result<Self, AppError> {
+        if config.managed_database().is_none() {
+            let reason = if config.database.is_some() {
+                "uses an external database (use your provider's tools)"
+            } else {
+                "has no managed database"
+            };
+            return Err(AppError::Config(format!("App '{}' {}", config.name, reason)));
+        }
+
+        Ok(Self { config })
+    }
+
+    /// Name of the database container.
+    pub fn container_name(&self) -> String {
+        format!("flaase-{}-db", self.config.name)
+    }
+
+    /// Directory where backups are written by default.
+    pub fn backup_dir(&self) -> PathBuf {
+        self.config.data_path().join("backups")
+    }
+
+    fn db_type(&self) -> DatabaseType {
+        self.config.database.as_ref().map(|d| d.db_type).unwrap()
+    }
+
+    /// Default timestamped path for a new backup.
+    pub fn default_output(&self) -> PathBuf {
+        let extension = match self.db_type() {
+            DatabaseType::MongoDB => "archive.gz",
+            _ => "sql.gz",
+        };
+
+        self.backup_dir().join(format!(
+            "{}-{}.{}",
+            self.config.name,
+            Local::now().format("%Y%m%d-%H%M%S"),
+            extension
+        ))
+    }
+
+    /// Loads the database credentials from the app's secrets.
+    fn db_secrets(&self) -> Result<DatabaseSecrets, AppError> {
+        SecretsManager::load_secrets(&self.config.secrets_path())?
+            .database
+            .ok_or_else(|| AppError::Config("Database secrets not found".into()))
+    }
+
+    /// Builds the `docker exec` command that writes a dump to stdout,
+    /// using the credentials from the app's secrets.
+    fn dump_args(&self) -> Result<ClientExec, AppError> {
+        let db_config = self.config.database.as_ref().unwrap();
+        Ok(dump_args(self.container_name(), db_config, self.db_secrets()?))
+    }
+
+    /// Dumps the database to `output` (or a timestamped file in the backup
+    /// directory), gzipping when the file name ends in `.gz`.
+    pub fn backup(&self, output: Option<&Path>) -> Result<BackupFile, AppError> {
+        if !self.is_container_running() {
+            return Err(AppError::Backup(format!(
+                "Database container '{}' is not running",
+                self.container_name()
+            )));
+        }
+
+        let path = output
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| self.default_output());
+
+        // Dumps hold the whole database: keep them away from other users
+        let backup_dir = self.backup_dir();
+        if path.parent() == Some(backup_dir.as_path()) {
+            create_private_dir(&backup_dir)?;
+        } else if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let file = create_dump_file(&path)?;
+        let result = self.write_dump(file, &path);
+        if result.is_err() {
+            let _ = std::fs::remove_file(&path);
+        }
+        result?;
+
+        let size = std::fs::metadata(&path)?.len();
+        Ok(BackupFile {
+            path,
+            size,
+            created_at: Utc::now(),
+        })
+    }
+
+    fn write_dump(&self, file: File, path: &Path) -> Result<(), AppError> {
+        let gzip = path.extension().is_some_and(|e| e == "gz");
+
+        let mut dump = self
+            .dump_args()?
+            .command()
+            .stdout(if gzip { Stdio::piped() } else { Stdio::from(file.try_clone()?) })
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| AppError::Backup(format!("Failed to start dump: {}", e)))?;
+
+        let compressor = if gzip {
+            let stdin = dump.stdout.take().map(Stdio::from).unwrap_or_else(Stdio::null);
+            Some(
+                Command::new("gzip")
+                    .arg("-c")
+                    .stdin(stdin)
+                    .stdout(Stdio::from(file))
+                    .spawn()
+                    .map_err(|e| AppError::Backup(format!("Failed to start gzip: {}", e)))?,
+            )
+        } else {
+            None
+        };
+
+        let dump_output = dump
+            .wait_with_output()
+            .map_err(|e| AppError::Backup(format!("Dump failed: {}", e)))?;
+
+        if let Some(mut gzip) = compressor {
+            let status = gzip
+                .wait()
+                .map_err(|e| AppError::Backup(format!("gzip failed: {}", e)))?;
+            if !status.success() {
+                return Err(AppError::Backup("gzip failed".into()));
+            }
+        }
+
+        if !dump_output.status.success() {
+            return Err(AppError::Backup(format!(
+                "Dump failed: {}",
+                String::from_utf8_lossy(&dump_output.stderr).trim()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Loads a dump produced by `backup` into the database, replacing its
+    /// current content. `.gz` files are decompressed on the fly.
+    pub fn restore(&self, input: &Path) -> Result<(), AppError> {
+        let size = std::fs::metadata(input)
+            .map_err(|_| AppError::Backup(format!("Dump file '{}' not found", input.display())))?
+            .len();
+        if size == 0 {
+            return Err(AppError::Backup(format!(
+                "Dump file '{}' is empty",
+                input.display()
+            )));
+        }
+
+        if !self.is_container_running() {
+            return Err(AppError::Backup(format!(
+                "Database container '{}' is not running",
+                self.container_name()
+            )));
+        }
+
+        let db_config = self.config.database.as_ref().unwrap();
+        let mut restore = restore_args(self.container_name(), db_config, self.db_secrets()?)
+            .command()
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| AppError::Backup(format!("Failed to start restore: {}", e)))?;
+
+        let mut stdin = restore.stdin.take().unwrap();
+        let copied = stdin
+            .write_all(restore_preamble(db_config.db_type).as_bytes())
+            .and_then(|_| {
+                if input.extension().is_some_and(|e| e == "gz") {
+                    let mut gunzip = Command::new("gzip")
+                        .arg("-dc")
+                        .arg(input)
+                        .stdout(Stdio::piped())
+                        .spawn()?;
+                    let piped = std::io::copy(gunzip.stdout.as_mut().unwrap(), &mut stdin);
+                    if piped.is_err() {
+                        // The restore stopped reading: don't leave gzip blocked on its pipe
+                        let _ = gunzip.kill();
+                    }
+                    let status = gunzip.wait()?;
+                    piped?;
+                    if !status.success() {
+                        return Err(std::io::Error::other("gzip could not decompress the dump"));
+                    }
+                    Ok(())
+                } else {
+                    std::io::copy(&mut File::open(input)?, &mut stdin).map(|_| ())
+                }
+            });
+        drop(stdin);
+
+        let output = restore
+            .wait_with_output()
+            .map_err(|e| AppError::Backup(format!("Restore failed: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(AppError::Backup(format!(
+                "Restore failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        copied.map_err(|e| AppError::Backup(format!("Failed to read dump: {}", e)))
+    }
+
+    /// Opens an interactive database shell with the app's credentials,
+    /// returning when the user exits it.
+    pub fn connect(&self) -> Result<(), AppError> {
+        if !self.is_container_running() {
+            return Err(AppError::Backup(format!(
+                "Database container '{}' is not running",
+                self.container_name()
+            )));
+        }
+
+        let db_config = self.config.database.as_ref().unwrap();
+        let tty = std::io::stdin().is_terminal();
+        let status = connect_args(self.container_name(), db_config, self.db_secrets()?, tty)
+            .command()
+            .status()
+            .map_err(|e| AppError::Backup(format!("Failed to open database shell: {}", e)))?;
+
+        if !status.success() {
+            return Err(AppError::Backup(format!(
+                "Database shell exited with {}",
+                status
+            )));
+        }
+        Ok(())
+    }
+
+    fn is_container_running(&self) -> bool {
+        Command::new(container_cli())
+            .args(["inspect", "-f", "{{.State.Running}}", &self.container_name()])
+            .output()
+            .map(|o| o.status.success() && String::from_utf8_lossy(&o.stdout).trim() == "true")
+            .unwrap_or(false)
+    }
+
+    /// Lists backups in the backup directory, newest first.
+    pub fn list(&self) -> Result<Vec<BackupFile>, AppError> {
+        let dir = self.backup_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut backups = Vec::new();
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if !metadata.is_file() {
+                continue;
+            }
+
+            let created_at = metadata
+                .modified()
+                .map(DateTime::<Utc>::from)
+                .unwrap_or_else(|_| Utc::now());
+
+            backups.push(BackupFile {
+                path: entry.path(),
+                size: metadata.len(),
+                created_at,
+            });
+        }
+
+        backups.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+        Ok(backups)
+    }
+
+    /// Deletes backups older than `keep_days`, always keeping the newest one.
+    /// Returns the number of files removed.
+    pub fn prune(&self, keep_days: u32) -> Result<usize, AppError> {
+        let cutoff = Utc::now() - Duration::days(keep_days as i64);
+        let mut removed = 0;
+
+        for backup in self.list()?.iter().skip(1) {
+            if backup.created_at < cutoff {
+                std::fs::remove_file(&backup.path)?;
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+
+    /// Base name of the systemd units running scheduled backups.
+    fn unit_name(&self) -> String {
+        format!("flaase-backup-{}", self.config.name)
+    }
+
+    fn timer_path(&self) -> PathBuf {
+        Path::new(SYSTEMD_PATH).join(format!("{}.timer", self.unit_name()))
+    }
+
+    fn service_path(&self) -> PathBuf {
+        Path::new(SYSTEMD_PATH).join(format!("{}.service", self.unit_name()))
+    }
+
+    /// Installs (or replaces) a systemd timer running the backup on a cron schedule.
+    pub fn schedule(&self, cron: &str, keep_days: u32) -> Result<String, AppError> {
+        let on_calendar = cron_to_on_calendar(cron)?;
+
+        let exe_path = std::env::current_exe()
+            .map_err(|e| AppError::Config(format!("Failed to get executable path: {}", e)))?;
+
+        let service = format!(
+            r#"[Unit]
+Description=Flaase database backup for {app}
+After=docker.service
+
+[Service]
+Type=oneshot
+ExecStart={exe} db backup {app} --keep-days {keep_days}
+"#,
+            app = self.config.name,
+            exe = exe_path.display(),
+            keep_days = keep_days
+        );
+
+        let timer = format!(
+            r#"[Unit]
+Description=Scheduled database backup for {app}
+
+[Timer]
+# cron: {cron}
+OnCalendar={on_calendar}
+Persistent=true
+
+[Install]
+WantedBy=timers.target
+"#,
+            app = self.config.name,
+            cron = cron,
+            on_calendar = on_calendar
+        );
+
+        std::fs::write(self.service_path(), service)
+            .map_err(|e| AppError::Config(format!("Failed to write service file: {}", e)))?;
+        std::fs::write(self.timer_path(), timer)
+            .map_err(|e| AppError::Config(format!("Failed to write timer file: {}", e)))?;
+
+        systemctl(&["daemon-reload"])?;
+        systemctl(&["enable", "--now", &format!("{}.timer", self.unit_name())])?;
+
+        Ok(on_calendar)
+    }
+
+    /// Removes the backup timer. Returns false if none was installed.
+    pub fn unschedule(&self) -> Result<bool, AppError> {
+        if !self.timer_path().exists() {
+            return Ok(false);
+        }
+
+        let _ = systemctl(&["disable", "--now", &format!("{}.timer", self.unit_name())]);
+
+        for path in [self.timer_path(), self.service_path()] {
+            if path.exists() {
+                std::fs::remove_file(&path)?;
+            }
+        }
+
+        systemctl(&["daemon-reload"])?;
+        Ok(true)
+    }
+
+    /// Returns the cron expression of the installed timer, if any.
+    pub fn scheduled_cron(&self) -> Option<String> {
+        let content = std::fs::read_to_string(self.timer_path()).ok()?;
+        content
+            .lines()
+            .find_map(|line| line.strip_prefix("# cron: "))
+            .map(|cron| cron.trim().to_string())
+    }
+}
+
+pub(crate) fn systemctl(args: &[&str]) -> Result<(), AppError> {
+    let status = Command::new("systemctl")
+        .args(args)
+        .status()
+        .map_err(|e| AppError::Command(format!("Failed to run systemctl: {}", e)))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(AppError::Command(format!("systemctl {} failed", args.join(" "))))
+    }
+}
+
+/// Converts a 5-field cron expression into a systemd `OnCalendar` value.
+/// Supports `*`, numbers, lists, ranges and steps.
+pub fn cron_to_on_calendar(cron: &str) -> Result<String, AppError> {
+    let fields: Vec<&str> = cron.split_whitespace().collect();
+    let [minute, hour, day, month, weekday] = fields[..] else {
+        return Err(AppError::Validation(format!(
+            "Invalid cron expression '{}': expected 5 fields",
+            cron
+        )));
+    };
+
+    if day != "*" && weekday != "*" {
+        return Err(AppError::Validation(
+            "Cron expressions restricting both day of month and day of week are not supported"
+                .into(),
+        ));
+    }
+
+    let minute = cron_field(minute, 0, 59)?;
+    let hour = cron_field(hour, 0, 23)?;
+    let day = cron_field(day, 1, 31)?;
+    let month = cron_field(month, 1, 12)?;
+
+    let date = format!("*-{}-{} {}:{}:00", month, day, hour, minute);
+
+    if weekday == "*" {
+        return Ok(date);
+    }
+
+    Ok(format!("{} {}", cron_weekdays(weekday)?, date))
+}
+
+/// Converts one numeric cron field to systemd syntax.
+fn cron_field(field: &str, min: u32, max: u32) -> Result<String, AppError> {
+    let invalid = || AppError::Validation(format!("Invalid cron field '{}'", field));
+    let check = |value: &str| -> Result<u32, AppError> {
+        value
+            .parse::<u32>()
+            .ok()
+            .filter(|v| (min..=max).contains(v))
+            .ok_or_else(invalid)
+    };
+
+    let parts: Result<Vec<String>, AppError> = field
+        .split(',')
+        .map(|part| {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => (range, Some(step)),
+                None => (part, None),
+            };
+
+            let base = match range {
+                "*" if step.is_some() => min.to_string(),
+                "*" => "*".to_string(),
+                _ => match range.split_once('-') {
+                    Some((start, end)) => format!("{}..{}", check(start)?, check(end)?),
+                    None => format!("{:02}", check(range)?),
+                },
+            };
+
+            match step {
+                Some(step) => {
+                    let step: u32 = step.parse().ok().filter(|s| *s > 0).ok_or_else(invalid)?;
+                    if base.contains("..") {
+                        return Err(invalid());
+                    }
+                    Ok(format!("{}/{}", base, step))
+                }
+                None => Ok(base),
+            }
+        })
+        .collect();
+
+    Ok(parts?.join(","))
+}
+
+/// Converts a cron day-of-week field (0-7, Sunday = 0 or 7) to systemd names.
+fn cron_weekdays(field: &str) -> Result<String, AppError> {
+    const NAMES: [&str; 8] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+    let invalid = || AppError::Validation(format!("Invalid cron day of week '{}'", field));
+    let name = |value: &str| -> Result<&str, AppError> {
+        value
+            .parse::<usize>()
+            .ok()
+            .and_then(|v| NAMES.get(v).copied())
+            .ok_or_else(invalid)
+    };
+
+    let parts: Result<Vec<String>, AppError> = field
+        .split(',')
+        .map(|part| match part.split_once('-') {
+            Some((start, end)) => Ok(format!("{}..{}", name(start)?, name(end)?)),
+            None => name(part).map(str::to_string),
+        })
+        .collect();
+
+    Ok(parts?.join(","))
+}
+
+/// A database client run in the database container with `docker exec`.
+#[derive(Debug)]
+struct ClientExec {
+    /// Arguments for the container CLI.
+    args: Vec<String>,
+    /// Variable holding the password. `args` only pass it by name
+    /// (`-e NAME`), so it must be set on the container CLI's own environment.
+    password_env: (&'static str, String),
+}
+
+impl ClientExec {
+    /// Builds a container CLI command with the password in its environment.
+    fn command(&self) -> Command {
+        let mut command = Command::new(container_cli());
+        command
+            .args(&self.args)
+            .env(self.password_env.0, &self.password_env.1);
+        command
+    }
+}
+
+/// Builds the `docker exec` arguments running `program options..` in
+/// `container` as the app's database user. The password never appears on an
+/// argv: PostgreSQL and MySQL clients read it from their environment, and
+/// the Mongo tools get it through a shell expanding the variable.
+fn client_exec(
+    container: String,
+    exec_flags: &[&str],
+    db_type: DatabaseType,
+    db_secrets: DatabaseSecrets,
+    program: &str,
+    options: Vec<String>,
+) -> ClientExec {
+    let (password_var, user_args): (&'static str, Vec<String>) = match db_type {
+        DatabaseType::PostgreSQL => ("PGPASSWORD", vec!["-U".into(), db_secrets.username]),
+        DatabaseType::MySQL | DatabaseType::MariaDB => {
+            ("MYSQL_PWD", vec!["-u".into(), db_secrets.username])
+        }
+        DatabaseType::MongoDB => (
+            "MONGO_PASSWORD",
+            vec![
+                "--authenticationDatabase".into(),
+                "admin".into(),
+                "--username".into(),
+                db_secrets.username,
+            ],
+        ),
+    };
+
+    let mut args: Vec<String> = std::iter::once("exec")
+        .chain(exec_flags.iter().copied())
+        .map(str::to_string)
+        .collect();
+    args.extend(["-e".into(), password_var.into(), container]);
+    if db_type == DatabaseType::MongoDB {
+        args.extend([
+            "sh".into(),
+            "-c".into(),
+            format!(r#"exec "$0" --password "${}" "$@""#, password_var),
+        ]);
+    }
+    args.push(program.into());
+    args.extend(user_args);
+    args.extend(options);
+
+    ClientExec {
+        args,
+        password_env: (password_var, db_secrets.password),
+    }
+}
+
+/// Builds the command dumping `db_config`'s database from `container` to stdout.
+fn dump_args(
+    container: String,
+    db_config: &DatabaseConfig,
+    db_secrets: DatabaseSecrets,
+) -> ClientExec {
+    let db = db_config.name.clone();
+    let (program, options): (&str, Vec<String>) = match db_config.db_type {
+        DatabaseType::PostgreSQL => ("pg_dump", vec!["-d".into(), db]),
+        // mariadb:11 images only ship the mariadb-* client names
+        DatabaseType::MariaDB => ("mariadb-dump", mysql_dump_options(db)),
+        DatabaseType::MySQL => ("mysqldump", mysql_dump_options(db)),
+        DatabaseType::MongoDB => (
+            "mongodump",
+            vec!["--quiet".into(), "--archive".into(), "--db".into(), db],
+        ),
+    };
+
+    client_exec(container, &[], db_config.db_type, db_secrets, program, options)
+}
+
+fn mysql_dump_options(db: String) -> Vec<String> {
+    vec!["--single-transaction".into(), "--no-tablespaces".into(), db]
+}
+
+/// Builds the command loading a dump from stdin into `db_config`'s database.
+fn restore_args(
+    container: String,
+    db_config: &DatabaseConfig,
+    db_secrets: DatabaseSecrets,
+) -> ClientExec {
+    let db = db_config.name.clone();
+    let (program, options): (&str, Vec<String>) = match db_config.db_type {
+        DatabaseType::PostgreSQL => (
+            "psql",
+            vec![
+                "--quiet".into(),
+                "--single-transaction".into(),
+                "-v".into(),
+                "ON_ERROR_STOP=1".into(),
+                "-d".into(),
+                db,
+            ],
+        ),
+        DatabaseType::MySQL | DatabaseType::MariaDB => {
+            (shell_program(db_config.db_type), vec![db])
+        }
+        DatabaseType::MongoDB => (
+            "mongorestore",
+            vec![
+                "--quiet".into(),
+                "--archive".into(),
+                "--drop".into(),
+                "--nsInclude".into(),
+                format!("{}.*", db),
+            ],
+        ),
+    };
+
+    client_exec(container, &["-i"], db_config.db_type, db_secrets, program, options)
+}
+
+/// Builds the command opening `db_config`'s interactive client, with a TTY
+/// when `tty` is set.
+fn connect_args(
+    container: String,
+    db_config: &DatabaseConfig,
+    db_secrets: DatabaseSecrets,
+    tty: bool,
+) -> ClientExec {
+    let db = db_config.name.clone();
+    let options: Vec<String> = match db_config.db_type {
+        DatabaseType::PostgreSQL => vec!["-d".into(), db],
+        DatabaseType::MongoDB => vec!["--quiet".into(), db],
+        DatabaseType::MySQL | DatabaseType::MariaDB => vec![db],
+    };
+    let exec_flags: &[&str] = if tty { &["-i", "-t"] } else { &["-i"] };
+
+    client_exec(
+        container,
+        exec_flags,
+        db_config.db_type,
+        db_secrets,
+        shell_program(db_config.db_type),
+        options,
+    )
+}
+
+/// Interactive client of each database type.
+fn shell_program(db_type: DatabaseType) -> &'static str {
+    match db_type {
+        DatabaseType::PostgreSQL => "psql",
+        DatabaseType::MySQL => "mysql",
+        DatabaseType::MariaDB => "mariadb",
+        DatabaseType::MongoDB => "mongosh",
+    }
+}
+
+/// Statements sent before the dump so it replaces the existing content.
+/// pg_dump output has no DROP statements, so the schema is recreated first,
+/// in the same transaction as the dump so a failed restore rolls back;
+/// mysqldump drops each table itself and mongorestore runs with `--drop`.
+fn restore_preamble(db_type: DatabaseType) -> &'static str {
+    match db_type {
+        DatabaseType::PostgreSQL => "DROP SCHEMA public CASCADE;\nCREATE SCHEMA public;\n",
+        _ => "",
+    }
+}
+
+/// Creates a directory (and its parents) that only its owner can enter.
+fn create_private_dir(dir: &Path) -> Result<(), AppError> {
+    std::fs::DirBuilder::new().recursive(true).mode(0o700).create(dir)?;
+    // An existing directory keeps its mode otherwise
+    std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))?;
+    Ok(())
+}
+
+/// Creates a new dump file only its owner can read. An existing file is
+/// never overwritten.
+fn create_dump_file(path: &Path) -> Result<File, AppError> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => {
+                AppError::Backup(format!("'{}' already exists", path.display()))
+            }
+            _ => AppError::Backup(format!("Failed to create '{}': {}", path.display(), e)),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dump_files_are_private() {
+        let dir = tempfile::tempdir().unwrap();
+        let backups = dir.path().join("data").join("backups");
+        create_private_dir(&backups).unwrap();
+
+        let path = backups.join("shop.sql.gz");
+        create_dump_file(&path).unwrap();
+        assert!(create_dump_file(&path).is_err());
+
+        let mode = |p: &Path| std::fs::metadata(p).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&path), 0o600);
+        assert_eq!(mode(&backups), 0o700);
+
+        // An existing backups directory is tightened too
+        std::fs::set_permissions(&backups, std::fs::Permissions::from_mode(0o755)).unwrap();
+        create_private_dir(&backups).unwrap();
+        assert_eq!(mode(&backups), 0o700);
+    }
+
+    #[test]
+    fn test_cron_to_on_calendar() {
+        assert_eq!(cron_to_on_calendar("0 3 * * *").unwrap(), "*-*-* 03:00:00");
+        assert_eq!(cron_to_on_calendar("*/15 * * * *").unwrap(), "*-*-* *:0/15:00");
+        assert_eq!(
+            cron_to_on_calendar("30 2 * * 1-5").unwrap(),
+            "Mon..Fri *-*-* 02:30:00"
+        );
+        assert_eq!(cron_to_on_calendar("0 0 1 */2 *").unwrap(), "*-1/2-01 00:00:00");
+        assert_eq!(cron_to_on_calendar("0 4 * * 0,6").unwrap(), "Sun,Sat *-*-* 04:00:00");
+
+        assert!(cron_to_on_calendar("0 3 * *").is_err());
+        assert!(cron_to_on_calendar("60 3 * * *").is_err());
+        assert!(cron_to_on_calendar("0 3 1 * 1").is_err());
+        assert!(cron_to_on_calendar("0 3 * * 8").is_err());
+    }
+
+    /// Runs `build` against the `shop` database of each type.
+    fn client_exec_for(
+        db_type: DatabaseType,
+        build: impl Fn(String, &DatabaseConfig, DatabaseSecrets) -> ClientExec,
+    ) -> ClientExec {
+        let secrets = DatabaseSecrets {
+            username: "shop".into(),
+            password: "s3cret".into(),
+            root_password: None,
+        };
+        build("flaase-shop-db".into(), &DatabaseConfig::new(db_type, "shop"), secrets)
+    }
+
+    #[test]
+    fn test_dump_args_per_database_type() {
+        let postgres = client_exec_for(DatabaseType::PostgreSQL, dump_args);
+        assert_eq!(
+            postgres.args,
+            ["exec", "-e", "PGPASSWORD", "flaase-shop-db", "pg_dump", "-U", "shop", "-d", "shop"]
+        );
+        assert_eq!(postgres.password_env, ("PGPASSWORD", "s3cret".to_string()));
+
+        let mariadb = client_exec_for(DatabaseType::MariaDB, dump_args);
+        assert_eq!(mariadb.args[2], "MYSQL_PWD");
+        assert_eq!(mariadb.args[4..7], ["mariadb-dump", "-u", "shop"]);
+        assert_eq!(client_exec_for(DatabaseType::MySQL, dump_args).args[4], "mysqldump");
+
+        let mongo = client_exec_for(DatabaseType::MongoDB, dump_args);
+        assert_eq!(mongo.args[3..5], ["flaase-shop-db", "sh"]);
+        assert_eq!(mongo.args[7], "mongodump");
+        assert_eq!(mongo.password_env.0, "MONGO_PASSWORD");
+    }
+
+    #[test]
+    fn test_restore_args_per_database_type() {
+        let postgres = client_exec_for(DatabaseType::PostgreSQL, restore_args);
+        assert_eq!(postgres.args[..4], ["exec", "-i", "-e", "PGPASSWORD"]);
+        assert_eq!(postgres.args[4..6], ["flaase-shop-db", "psql"]);
+        assert!(postgres.args.contains(&"ON_ERROR_STOP=1".to_string()));
+        assert!(postgres.args.contains(&"--single-transaction".to_string()));
+        assert!(restore_preamble(DatabaseType::PostgreSQL).starts_with("DROP SCHEMA public"));
+
+        assert_eq!(client_exec_for(DatabaseType::MariaDB, restore_args).args[5], "mariadb");
+        assert_eq!(
+            client_exec_for(DatabaseType::MySQL, restore_args).args[5..],
+            ["mysql", "-u", "shop", "shop"]
+        );
+        assert_eq!(restore_preamble(DatabaseType::MySQL), "");
+
+        let mongo = client_exec_for(DatabaseType::MongoDB, restore_args);
+        assert_eq!(mongo.args[8], "mongorestore");
+        assert!(mongo.args.contains(&"--drop".to_string()));
+        assert!(mongo.args.contains(&"shop.*".to_string()));
+    }
+
+    #[test]
+    fn test_connect_args_per_database_type() {
+        let connect = |tty| move |c, d: &_, s| connect_args(c, d, s, tty);
+
+        let postgres = client_exec_for(DatabaseType::PostgreSQL, connect(true));
+        assert_eq!(postgres.args[..5], ["exec", "-i", "-t", "-e", "PGPASSWORD"]);
+        assert_eq!(postgres.args[5..], ["flaase-shop-db", "psql", "-U", "shop", "-d", "shop"]);
+        assert_eq!(client_exec_for(DatabaseType::PostgreSQL, connect(false)).args[2], "-e");
+        assert_eq!(
+            client_exec_for(DatabaseType::MySQL, connect(false)).args[5..],
+            ["mysql", "-u", "shop", "shop"]
+        );
+        assert_eq!(client_exec_for(DatabaseType::MariaDB, connect(false)).args[5], "mariadb");
+
+        let mongo = client_exec_for(DatabaseType::MongoDB, connect(true));
+        let script = r#"exec "$0" --password "$MONGO_PASSWORD" "$@""#;
+        assert_eq!(mongo.args[6..9], ["sh", "-c", script]);
+        assert_eq!(mongo.args[9], "mongosh");
+        assert_eq!(mongo.args.last().unwrap(), "shop");
+    }
+
+    #[test]
+    fn test_client_exec_keeps_passwords_off_the_argv() {
+        for db_type in [
+            DatabaseType::PostgreSQL,
+            DatabaseType::MySQL,
+            DatabaseType::MariaDB,
+            DatabaseType::MongoDB,
+        ] {
+            for exec in [
+                client_exec_for(db_type, dump_args),
+                client_exec_for(db_type, restore_args),
+                client_exec_for(db_type, |c, d, s| connect_args(c, d, s, false)),
+            ] {
+                assert!(exec.args.iter().all(|arg| !arg.contains("s3cret")), "{:?}", exec);
+                assert_eq!(exec.password_env.1, "s3cret");
+            }
+        }
+    }
+}
@@ -0,0 +1,196 @@
+//! Server-wide build slots.
+//!
+//! Image builds are the most memory hungry part of a deploy, so small
+//! servers should not run several at once. Each slot is a lock file under
+//! `/opt/flaase/locks`; a build holds an exclusive `flock` on one of them
+//! for its whole duration. The kernel releases the lock when the process
+//! exits, so a crashed deploy never leaves a slot taken.
+//!
+//! Every deploy also holds a shared lock on `deploys.lock` for its whole
+//! duration, so `fl server update` can tell whether one is running without
+//! touching the slots.
+
+use std::fs::{File, OpenOptions};
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::core::config::{ServerConfig, FLAASE_LOCKS_PATH};
+use crate::core::error::AppError;
+use crate::ui;
+
+/// Default number of builds allowed to run at the same time.
+pub const DEFAULT_MAX_CONCURRENT_BUILDS: u32 = 1;
+
+/// How long to wait between attempts when every slot is taken.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A held build slot. The slot is released when this is dropped.
+#[derive(Debug)]
+pub struct BuildSlot {
+    file: File,
+    index: u32,
+}
+
+impl BuildSlot {
+    /// Slot number, starting at 1.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+impl Drop for BuildSlot {
+    fn drop(&mut self) {
+        unsafe {
+            libc::flock(self.file.as_raw_fd(), libc::LOCK_UN);
+        }
+    }
+}
+
+/// Waits for a free build slot using the server's configured limit.
+pub fn acquire() -> Result<BuildSlot, AppError> {
+    let max = ServerConfig::load()
+        .map(|c| c.server.effective_max_concurrent_builds())
+        .unwrap_or(DEFAULT_MAX_CONCURRENT_BUILDS);
+
+    acquire_in(Path::new(FLAASE_LOCKS_PATH), max)
+}
+
+/// Waits until one of `max` slots in `dir` can be locked.
+pub fn acquire_in(dir: &Path, max: u32) -> Result<BuildSlot, AppError> {
+    std::fs::create_dir_all(dir)?;
+
+    let mut announced = false;
+    loop {
+        if let Some(slot) = try_acquire_in(dir, max)? {
+            return Ok(slot);
+        }
+
+        if !announced {
+            ui::info("Waiting for build slot (another build is running on this server)...");
+            announced = true;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Locks the first free slot in `dir` without waiting.
+pub fn try_acquire_in(dir: &Path, max: u32) -> Result<Option<BuildSlot>, AppError> {
+    for index in 1..=max.max(1) {
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(slot_path(dir, index))?;
+
+        let locked = unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0;
+        if locked {
+            return Ok(Some(BuildSlot { file, index }));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Marks a deploy as running until dropped. Deploys share the lock, so any
+/// number of them can hold it at once.
+#[derive(Debug)]
+pub struct DeployActivity {
+    file: File,
+}
+
+impl Drop for DeployActivity {
+    fn drop(&mut self) {
+        unsafe {
+            libc::flock(self.file.as_raw_fd(), libc::LOCK_UN);
+        }
+    }
+}
+
+/// Registers a running deploy on this server.
+pub fn begin_deploy() -> Result<DeployActivity, AppError> {
+    begin_deploy_in(Path::new(FLAASE_LOCKS_PATH))
+}
+
+pub fn begin_deploy_in(dir: &Path) -> Result<DeployActivity, AppError> {
+    std::fs::create_dir_all(dir)?;
+    let file = open_deploys_lock(dir)?;
+
+    // Blocks only while `deploy_in_progress` probes the lock
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_SH) } != 0 {
+        return Err(AppError::Io(std::io::Error::last_os_error()));
+    }
+    Ok(DeployActivity { file })
+}
+
+/// Returns true if a deploy is running on this server.
+pub fn deploy_in_progress() -> Result<bool, AppError> {
+    deploy_in_progress_in(Path::new(FLAASE_LOCKS_PATH))
+}
+
+pub fn deploy_in_progress_in(dir: &Path) -> Result<bool, AppError> {
+    if !dir.exists() {
+        return Ok(false);
+    }
+
+    let file = open_deploys_lock(dir)?;
+    let idle = unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0;
+    if idle {
+        unsafe {
+            libc::flock(file.as_raw_fd(), libc::LOCK_UN);
+        }
+    }
+    Ok(!idle)
+}
+
+fn open_deploys_lock(dir: &Path) -> Result<File, AppError> {
+    Ok(OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(dir.join("deploys.lock"))?)
+}
+
+fn slot_path(dir: &Path, index: u32) -> PathBuf {
+    dir.join(format!("build-slot-{}.lock", index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slots_are_exclusive_until_released() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let first = try_acquire_in(dir.path(), 2).unwrap().unwrap();
+        let second = try_acquire_in(dir.path(), 2).unwrap().unwrap();
+        assert_eq!(first.index(), 1);
+        assert_eq!(second.index(), 2);
+        assert!(try_acquire_in(dir.path(), 2).unwrap().is_none());
+
+        drop(first);
+        let again = try_acquire_in(dir.path(), 2).unwrap().unwrap();
+        assert_eq!(again.index(), 1);
+    }
+
+    #[test]
+    fn test_deploy_in_progress_while_a_deploy_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!deploy_in_progress_in(&dir.path().join("missing")).unwrap());
+        assert!(!deploy_in_progress_in(dir.path()).unwrap());
+
+        let first = begin_deploy_in(dir.path()).unwrap();
+        let second = begin_deploy_in(dir.path()).unwrap();
+        assert!(deploy_in_progress_in(dir.path()).unwrap());
+
+        // Probing never takes a build slot away from a deploy
+        let slot = try_acquire_in(dir.path(), 1).unwrap();
+        assert!(slot.is_some());
+
+        drop(first);
+        assert!(deploy_in_progress_in(dir.path()).unwrap());
+        drop(second);
+        assert!(!deploy_in_progress_in(dir.path()).unwrap());
+    }
+}
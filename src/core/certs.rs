@@ -0,0 +1,362 @@
+//! Imported TLS certificates served by Traefik.
+//!
+//! Certificates are stored inside the Traefik dynamic directory so the proxy
+//! container can read them. Traefik picks the right one per request via SNI,
+//! so a single wildcard certificate can serve many subdomain apps.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::config::{FLAASE_TRAEFIK_DYNAMIC_PATH, FLAASE_TRAEFIK_PATH};
+use crate::core::context::ExecutionContext;
+use crate::core::error::AppError;
+use crate::templates::traefik::{generate_tls_config, AppDomain};
+
+/// Directory (inside the Traefik container) where certificates are mounted.
+const TRAEFIK_CERTS_MOUNT: &str = "/etc/traefik/dynamic/certs";
+
+/// An imported certificate and the domains it covers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredCertificate {
+    pub name: String,
+    /// Domains from the certificate SANs (may include wildcards).
+    pub domains: Vec<String>,
+    pub imported_at: DateTime<Utc>,
+}
+
+impl StoredCertificate {
+    /// Returns true if this certificate is valid for the given host.
+    pub fn covers(&self, host: &str) -> bool {
+        self.domains.iter().any(|pattern| domain_matches(pattern, host))
+    }
+}
+
+/// Index of imported certificates (/opt/flaase/traefik/certs.yml).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CertificateStore {
+    #[serde(default)]
+    pub certificates: Vec<StoredCertificate>,
+}
+
+impl CertificateStore {
+    /// Host directory holding certificate and key files.
+    pub fn certs_dir() -> String {
+        format!("{}/certs", FLAASE_TRAEFIK_DYNAMIC_PATH)
+    }
+
+    fn index_path() -> String {
+        format!("{}/certs.yml", FLAASE_TRAEFIK_PATH)
+    }
+
+    /// Dynamic config file listing the certificates for Traefik.
+    /// Prefixed with an underscore so it can't clash with an app config.
+    fn tls_config_path() -> String {
+        format!("{}/_certificates.yml", FLAASE_TRAEFIK_DYNAMIC_PATH)
+    }
+
+    /// Loads the certificate index.
+    pub fn load() -> Result<Self, AppError> {
+        let path = Self::index_path();
+        if !Path::new(&path).exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| AppError::Config(format!("Failed to read certificates: {}", e)))?;
+
+        serde_yaml::from_str(&content)
+            .map_err(|e| AppError::Config(format!("Failed to parse certificates: {}", e)))
+    }
+
+    /// Saves the index and rewrites the Traefik TLS configuration.
+    fn save(&self, ctx: &ExecutionContext) -> Result<(), AppError> {
+        let content = serde_yaml::to_string(self)
+            .map_err(|e| AppError::Config(format!("Failed to serialize certificates: {}", e)))?;
+        ctx.write_file(&Self::index_path(), &content)?;
+
+        let files: Vec<(String, String)> = self
+            .certificates
+            .iter()
+            .map(|cert| {
+                (
+                    format!("{}/{}.crt", TRAEFIK_CERTS_MOUNT, cert.name),
+                    format!("{}/{}.key", TRAEFIK_CERTS_MOUNT, cert.name),
+                )
+            })
+            .collect();
+        ctx.write_file(&Self::tls_config_path(), &generate_tls_config(&files))
+    }
+
+    /// Finds the certificate serving a host, preferring exact matches over wildcards.
+    pub fn find_for(&self, host: &str) -> Option<&StoredCertificate> {
+        self.certificates
+            .iter()
+            .find(|cert| cert.domains.iter().any(|d| d.eq_ignore_ascii_case(host)))
+            .or_else(|| self.certificates.iter().find(|cert| cert.covers(host)))
+    }
+
+    /// Marks an app domain as served by an imported certificate when one covers it.
+    pub fn apply_to(&self, domain: AppDomain) -> AppDomain {
+        let covered = self.find_for(&domain.domain).is_some();
+        let www_covered = self
+            .find_for(&format!("www.{}", domain.domain))
+            .is_some();
+        domain.with_custom_cert(covered, www_covered)
+    }
+
+    /// Imports a certificate/key pair under a name, replacing any previous one.
+    pub fn import(
+        &mut self,
+        name: &str,
+        cert_path: &Path,
+        key_path: &Path,
+        ctx: &ExecutionContext,
+    ) -> Result<&StoredCertificate, AppError> {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(AppError::Validation(format!(
+                "Invalid certificate name '{}'. Use letters, numbers, '-' and '_'",
+                name
+            )));
+        }
+
+        let domains = read_certificate_domains(cert_path)?;
+        if domains.is_empty() {
+            return Err(AppError::Validation(
+                "Certificate does not contain any DNS name".into(),
+            ));
+        }
+        verify_key_matches(cert_path, key_path)?;
+
+        let cert = std::fs::read_to_string(cert_path)?;
+        let key = std::fs::read_to_string(key_path)?;
+
+        let dir = Self::certs_dir();
+        ctx.create_dir(&dir)?;
+        ctx.write_file(&format!("{}/{}.crt", dir, name), &cert)?;
+        write_private_key(&format!("{}/{}.key", dir, name), &key, ctx)?;
+
+        self.certificates.retain(|c| c.name != name);
+        self.certificates.push(StoredCertificate {
+            name: name.to_string(),
+            domains,
+            imported_at: Utc::now(),
+        });
+        self.save(ctx)?;
+
+        Ok(self.certificates.last().unwrap())
+    }
+
+    /// Removes an imported certificate. Returns false if it doesn't exist.
+    pub fn remove(&mut self, name: &str, ctx: &ExecutionContext) -> Result<bool, AppError> {
+        let before = self.certificates.len();
+        self.certificates.retain(|c| c.name != name);
+        if self.certificates.len() == before {
+            return Ok(false);
+        }
+
+        let dir = Self::certs_dir();
+        for ext in ["crt", "key"] {
+            let path = format!("{}/{}.{}", dir, name, ext);
+            if Path::new(&path).exists() && !ctx.is_dry_run() {
+                std::fs::remove_file(&path)?;
+            }
+        }
+
+        self.save(ctx)?;
+        Ok(true)
+    }
+}
+
+/// Marks the app domains covered by imported certificates. Without a
+/// readable store, every domain keeps using Let's Encrypt.
+pub fn apply_imported_certificates(domains: Vec<AppDomain>) -> Vec<AppDomain> {
+    let store = CertificateStore::load().unwrap_or_default();
+    domains.into_iter().map(|domain| store.apply_to(domain)).collect()
+}
+
+/// Checks a domain against a certificate name, supporting single-label
+/// wildcards (`*.example.com` matches `api.example.com` but not
+/// `example.com` or `a.b.example.com`).
+pub fn domain_matches(pattern: &str, host: &str) -> bool {
+    let pattern = pattern.to_ascii_lowercase();
+    let host = host.to_ascii_lowercase();
+
+    match pattern.strip_prefix("*.") {
+        Some(suffix) => match host.split_once('.') {
+            Some((label, rest)) => !label.is_empty() && rest == suffix,
+            None => false,
+        },
+        None => pattern == host,
+    }
+}
+
+/// Reads the DNS names of a certificate (SANs, falling back to the subject CN).
+fn read_certificate_domains(cert_path: &Path) -> Result<Vec<String>, AppError> {
+    let path = cert_path.to_string_lossy();
+
+    let output = std::process::Command::new("openssl")
+        .args(["x509", "-in", &path, "-noout", "-ext", "subjectAltName"])
+        .output()
+        .map_err(|e| AppError::Command(format!("Failed to run openssl: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(AppError::Validation(format!(
+            "Invalid certificate file: {}",
+            path
+        )));
+    }
+
+    let domains = parse_san_output(&String::from_utf8_lossy(&output.stdout));
+    if !domains.is_empty() {
+        return Ok(domains);
+    }
+
+    let output = std::process::Command::new("openssl")
+        .args(["x509", "-in", &path, "-noout", "-subject", "-nameopt", "RFC2253"])
+        .output()
+        .map_err(|e| AppError::Command(format!("Failed to run openssl: {}", e)))?;
+
+    let subject = String::from_utf8_lossy(&output.stdout);
+    Ok(subject
+        .trim()
+        .trim_start_matches("subject=")
+        .split(',')
+        .filter_map(|part| part.trim().strip_prefix("CN="))
+        .map(|cn| cn.to_string())
+        .collect())
+}
+
+/// Parses `openssl x509 -ext subjectAltName` output into DNS names.
+fn parse_san_output(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .skip_while(|line| !line.contains("Subject Alternative Name"))
+        .skip(1)
+        .flat_map(|line| line.split(','))
+        .filter_map(|entry| entry.trim().strip_prefix("DNS:"))
+        .map(|name| name.to_string())
+        .collect()
+}
+
+/// Ensures the private key belongs to the certificate.
+fn verify_key_matches(cert_path: &Path, key_path: &Path) -> Result<(), AppError> {
+    let public_key = |args: &[&str]| -> Result<String, AppError> {
+        let output = std::process::Command::new("openssl")
+            .args(args)
+            .output()
+            .map_err(|e| AppError::Command(format!("Failed to run openssl: {}", e)))?;
+        if !output.status.success() {
+            return Err(AppError::Validation(format!(
+                "Failed to read {}",
+                args.get(2).unwrap_or(&"file")
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    let cert = cert_path.to_string_lossy();
+    let key = key_path.to_string_lossy();
+
+    let cert_key = public_key(&["x509", "-in", &cert, "-noout", "-pubkey"])?;
+    let key_key = public_key(&["pkey", "-in", &key, "-pubout"])?;
+
+    if cert_key.trim() != key_key.trim() {
+        return Err(AppError::Validation(
+            "Private key does not match the certificate".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Writes a private key readable by its owner only. A previous key is
+/// removed first so the file is created with that mode, never widened.
+fn write_private_key(path: &str, key: &str, ctx: &ExecutionContext) -> Result<(), AppError> {
+    if ctx.is_dry_run() {
+        return ctx.write_file(path, key);
+    }
+
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+        _ => {}
+    }
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)?;
+    file.write_all(key.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_private_key_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("site.key");
+        std::fs::write(&path, "old").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        let ctx = ExecutionContext::new(false, false);
+        write_private_key(&path.to_string_lossy(), "new key", &ctx).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new key");
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_domain_matches() {
+        assert!(domain_matches("example.com", "example.com"));
+        assert!(domain_matches("*.example.com", "api.example.com"));
+        assert!(domain_matches("*.Example.com", "API.example.com"));
+
+        assert!(!domain_matches("*.example.com", "example.com"));
+        assert!(!domain_matches("*.example.com", "a.b.example.com"));
+        assert!(!domain_matches("example.com", "api.example.com"));
+    }
+
+    #[test]
+    fn test_parse_san_output() {
+        let output = "X509v3 Subject Alternative Name: \n    DNS:*.example.com, DNS:example.com, IP Address:10.0.0.1\n";
+        assert_eq!(
+            parse_san_output(output),
+            vec!["*.example.com".to_string(), "example.com".to_string()]
+        );
+        assert!(parse_san_output("No extensions in certificate\n").is_empty());
+    }
+
+    #[test]
+    fn test_apply_wildcard_to_subdomains() {
+        let store = CertificateStore {
+            certificates: vec![StoredCertificate {
+                name: "wildcard".to_string(),
+                domains: vec!["*.example.com".to_string()],
+                imported_at: Utc::now(),
+            }],
+        };
+
+        let api = store.apply_to(AppDomain::new("api.example.com", false));
+        let app = store.apply_to(AppDomain::new("app.example.com", true));
+        let other = store.apply_to(AppDomain::new("other.dev", false));
+
+        assert!(api.custom_cert);
+        assert!(app.custom_cert);
+        assert!(!app.www_custom_cert);
+        assert!(!other.custom_cert);
+    }
+}
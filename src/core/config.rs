@@ -10,6 +10,12 @@ pub const FLAASE_CONFIG_PATH: &str = "/opt/flaase/config.yml";
 pub const FLAASE_APPS_PATH: &str = "/opt/flaase/apps";
 pub const FLAASE_TRAEFIK_PATH: &str = "/opt/flaase/traefik";
 pub const FLAASE_TRAEFIK_DYNAMIC_PATH: &str = "/opt/flaase/traefik/dynamic";
+pub const FLAASE_LOCKS_PATH: &str = "/opt/flaase/locks";
+
+/// Default path prefix of the webhook and log endpoints routed by Traefik.
+pub const DEFAULT_WEBHOOK_PREFIX: &str = "/flaase";
+/// Default port of the webhook server.
+pub const DEFAULT_WEBHOOK_PORT: u16 = 9876;
 
 /// Server-level configuration stored in /opt/flaase/config.yml
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,6 +46,80 @@ pub struct ServerInfo {
 
     /// Deploy user information.
     pub deploy_user: DeployUserInfo,
+
+    /// Maximum number of image builds running at the same time (default: 1).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_builds: Option<u32>,
+
+    /// Path prefix of the webhook and log endpoints (default: /flaase).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_prefix: Option<String>,
+
+    /// Port the webhook server listens on (default: 9876).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_port: Option<u16>,
+
+    /// Whether Traefik was set up with the Let's Encrypt staging CA.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub acme_staging: bool,
+}
+
+impl ServerInfo {
+    /// Returns the build concurrency limit, defaulting to one build at a time.
+    pub fn effective_max_concurrent_builds(&self) -> u32 {
+        self.max_concurrent_builds
+            .unwrap_or(crate::core::build_slots::DEFAULT_MAX_CONCURRENT_BUILDS)
+            .max(1)
+    }
+
+    /// Returns the webhook path prefix, e.g. `/flaase` for `/flaase/webhook/<token>`.
+    pub fn effective_webhook_prefix(&self) -> String {
+        self.webhook_prefix
+            .as_deref()
+            .and_then(|prefix| normalize_webhook_prefix(prefix).ok())
+            .unwrap_or_else(|| DEFAULT_WEBHOOK_PREFIX.to_string())
+    }
+
+    /// Returns the port the webhook server listens on.
+    pub fn effective_webhook_port(&self) -> u16 {
+        self.webhook_port.unwrap_or(DEFAULT_WEBHOOK_PORT)
+    }
+}
+
+/// Webhook path prefix of this server, or the default when not initialized.
+pub fn webhook_prefix() -> String {
+    ServerConfig::load()
+        .map(|c| c.server.effective_webhook_prefix())
+        .unwrap_or_else(|_| DEFAULT_WEBHOOK_PREFIX.to_string())
+}
+
+/// Webhook server port of this server, or the default when not initialized.
+pub fn webhook_port() -> u16 {
+    ServerConfig::load()
+        .map(|c| c.server.effective_webhook_port())
+        .unwrap_or(DEFAULT_WEBHOOK_PORT)
+}
+
+/// Normalizes a webhook path prefix to `/segment[/segment...]` without a
+/// trailing slash. The root path is rejected so app routes stay untouched.
+pub fn normalize_webhook_prefix(prefix: &str) -> Result<String, AppError> {
+    let segments: Vec<&str> = prefix.split('/').filter(|s| !s.is_empty()).collect();
+
+    if segments.is_empty() {
+        return Err(AppError::Validation(
+            "Webhook prefix must contain at least one path segment (e.g. /flaase)".into(),
+        ));
+    }
+
+    let valid = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.');
+    if let Some(segment) = segments.iter().find(|s| !s.chars().all(valid) || **s == "..") {
+        return Err(AppError::Validation(format!(
+            "Invalid webhook prefix segment '{}': use letters, digits, '-', '_' or '.'",
+            segment
+        )));
+    }
+
+    Ok(format!("/{}", segments.join("/")))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,7 +131,7 @@ pub struct OsInfo {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContainerRuntimeInfo {
-    /// Runtime type: "docker" or "kubernetes" (future).
+    /// Runtime type: "docker" or "podman".
     #[serde(rename = "type")]
     pub runtime_type: String,
     pub version: String,
@@ -90,6 +170,10 @@ impl ServerConfig {
                 container_runtime,
                 reverse_proxy,
                 deploy_user,
+                max_concurrent_builds: None,
+                webhook_prefix: None,
+                webhook_port: None,
+                acme_staging: false,
             },
         }
     }
@@ -132,6 +216,7 @@ impl ServerConfig {
             FLAASE_APPS_PATH,
             FLAASE_TRAEFIK_PATH,
             FLAASE_TRAEFIK_DYNAMIC_PATH,
+            FLAASE_LOCKS_PATH,
         ]
     }
 }
@@ -156,3 +241,20 @@ impl std::fmt::Display for ExistingComponentAction {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_webhook_prefix() {
+        assert_eq!(normalize_webhook_prefix("/flaase").unwrap(), "/flaase");
+        assert_eq!(normalize_webhook_prefix("hooks/ci/").unwrap(), "/hooks/ci");
+        assert_eq!(normalize_webhook_prefix("//deploy").unwrap(), "/deploy");
+
+        assert!(normalize_webhook_prefix("/").is_err());
+        assert!(normalize_webhook_prefix("").is_err());
+        assert!(normalize_webhook_prefix("/hooks/../admin").is_err());
+        assert!(normalize_webhook_prefix("/hooks?x=1").is_err());
+    }
+}
@@ -58,6 +58,48 @@ impl ExecutionContext {
         Ok(cmd_output)
     }
 
+    /// Executes a shell command, writing `input` to its stdin.
+    /// The input is never echoed, so it is safe for passwords.
+    pub fn run_command_with_stdin(
+        &self,
+        cmd: &str,
+        args: &[&str],
+        input: &str,
+    ) -> Result<CommandOutput, AppError> {
+        use std::io::Write;
+
+        let full_cmd = format!("{} {}", cmd, args.join(" "));
+
+        if self.dry_run {
+            ui::info(&format!("[DRY-RUN] {}", full_cmd));
+            return Ok(CommandOutput::dry_run());
+        }
+
+        if self.verbose {
+            ui::info(&format!("Running: {}", full_cmd));
+        }
+
+        let mut child = Command::new(cmd)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| AppError::Command(format!("Failed to execute '{}': {}", cmd, e)))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(input.as_bytes()).map_err(|e| {
+                AppError::Command(format!("Failed to write to '{}': {}", cmd, e))
+            })?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .map_err(|e| AppError::Command(format!("Failed to execute '{}': {}", cmd, e)))?;
+
+        Ok(CommandOutput::from_output(output))
+    }
+
     /// Executes a shell command with sudo prefix.
     pub fn run_sudo(&self, cmd: &str, args: &[&str]) -> Result<CommandOutput, AppError> {
         let mut sudo_args = vec![cmd];
@@ -91,10 +133,12 @@ impl ExecutionContext {
             } else {
                 Stdio::null()
             })
-            .stderr(if self.verbose {
-                Stdio::inherit()
-            } else {
+            .stderr(if !self.verbose {
                 Stdio::null()
+            } else if ui::stderr_reserved() {
+                Stdio::from(std::io::stdout())
+            } else {
+                Stdio::inherit()
             })
             .status()
             .map_err(|e| AppError::Command(format!("Failed to execute '{}': {}", cmd, e)))?;
@@ -147,6 +191,28 @@ impl ExecutionContext {
 
         std::fs::create_dir_all(path).map_err(AppError::Io)
     }
+
+    /// Runs a command attached to the terminal (prompts, spinners, colors)
+    /// and returns its exit code.
+    pub fn run_interactive(&self, cmd: &str, args: &[&str]) -> Result<i32, AppError> {
+        let full_cmd = format!("{} {}", cmd, args.join(" "));
+
+        if self.dry_run {
+            ui::info(&format!("[DRY-RUN] {}", full_cmd));
+            return Ok(0);
+        }
+
+        if self.verbose {
+            ui::info(&format!("Running: {}", full_cmd));
+        }
+
+        let status = Command::new(cmd)
+            .args(args)
+            .status()
+            .map_err(|e| AppError::Command(format!("Failed to execute '{}': {}", cmd, e)))?;
+
+        Ok(status.code().unwrap_or(1))
+    }
 }
 
 impl Default for ExecutionContext {
@@ -0,0 +1,181 @@
+//! Scheduled jobs: each cron job gets a systemd timer that runs
+//! `fl cron run <app> <job>`, which starts a short-lived container from the
+//! app image with the app's environment.
+
+use std::path::{Path, PathBuf};
+
+use crate::core::app_config::{AppConfig, CronJob};
+use crate::core::backup::{cron_to_on_calendar, systemctl};
+use crate::core::error::AppError;
+
+/// Directory holding systemd unit files.
+const SYSTEMD_PATH: &str = "/etc/systemd/system";
+
+/// Installs and removes the systemd timers of an app's cron jobs.
+pub struct CronScheduler<'a> {
+    config: &'a AppConfig,
+}
+
+impl<'a> CronScheduler<'a> {
+    pub fn new(config: &'a AppConfig) -> Self {
+        Self { config }
+    }
+
+    /// Base name of the systemd units running a job.
+    pub fn unit_name(&self, job_name: &str) -> String {
+        format!("flaase-cron-{}-{}", self.config.name, job_name)
+    }
+
+    fn timer_path(&self, job_name: &str) -> PathBuf {
+        Path::new(SYSTEMD_PATH).join(format!("{}.timer", self.unit_name(job_name)))
+    }
+
+    fn service_path(&self, job_name: &str) -> PathBuf {
+        Path::new(SYSTEMD_PATH).join(format!("{}.service", self.unit_name(job_name)))
+    }
+
+    /// Returns true if the job's timer is installed.
+    pub fn is_installed(&self, job_name: &str) -> bool {
+        self.timer_path(job_name).exists()
+    }
+
+    /// Renders the service and timer units of a job.
+    fn unit_files(&self, job: &CronJob) -> Result<(String, String), AppError> {
+        validate_job_name(&job.name)?;
+        let on_calendar = cron_to_on_calendar(&job.schedule)?;
+
+        let exe_path = std::env::current_exe()
+            .map_err(|e| AppError::Config(format!("Failed to get executable path: {}", e)))?;
+
+        let service = format!(
+            r#"[Unit]
+Description=Flaase cron job {job} for {app}
+After=docker.service
+
+[Service]
+Type=oneshot
+ExecStart={exe} cron run {app} {job}
+"#,
+            app = self.config.name,
+            job = job.name,
+            exe = exe_path.display()
+        );
+
+        let timer = format!(
+            r#"[Unit]
+Description=Schedule for Flaase cron job {job} ({app})
+
+[Timer]
+# cron: {cron}
+OnCalendar={on_calendar}
+AccuracySec=1s
+
+[Install]
+WantedBy=timers.target
+"#,
+            app = self.config.name,
+            job = job.name,
+            cron = job.schedule,
+            on_calendar = on_calendar
+        );
+
+        Ok((service, timer))
+    }
+
+    /// Installs (or replaces) the timer for a job. Returns its `OnCalendar` value.
+    pub fn install(&self, job: &CronJob) -> Result<String, AppError> {
+        let on_calendar = cron_to_on_calendar(&job.schedule)?;
+        let (service, timer) = self.unit_files(job)?;
+
+        std::fs::write(self.service_path(&job.name), service)
+            .map_err(|e| AppError::Config(format!("Failed to write service file: {}", e)))?;
+        std::fs::write(self.timer_path(&job.name), timer)
+            .map_err(|e| AppError::Config(format!("Failed to write timer file: {}", e)))?;
+
+        systemctl(&["daemon-reload"])?;
+        systemctl(&["enable", "--now", &format!("{}.timer", self.unit_name(&job.name))])?;
+
+        Ok(on_calendar)
+    }
+
+    /// Removes the timer of a job. Returns false if none was installed.
+    pub fn uninstall(&self, job_name: &str) -> Result<bool, AppError> {
+        if !self.timer_path(job_name).exists() {
+            return Ok(false);
+        }
+
+        let _ = systemctl(&["disable", "--now", &format!("{}.timer", self.unit_name(job_name))]);
+
+        for path in [self.timer_path(job_name), self.service_path(job_name)] {
+            if path.exists() {
+                std::fs::remove_file(&path)?;
+            }
+        }
+
+        systemctl(&["daemon-reload"])?;
+        Ok(true)
+    }
+
+    /// Installs the timers of configured jobs that are missing or outdated,
+    /// e.g. after the config was edited or imported. Returns their names.
+    pub fn reconcile(&self) -> Result<Vec<String>, AppError> {
+        let mut installed = Vec::new();
+        for job in &self.config.cron_jobs {
+            let (service, timer) = self.unit_files(job)?;
+            let current = std::fs::read_to_string(self.service_path(&job.name))
+                .is_ok_and(|content| content == service)
+                && std::fs::read_to_string(self.timer_path(&job.name))
+                    .is_ok_and(|content| content == timer);
+            if !current {
+                self.install(job)?;
+                installed.push(job.name.clone());
+            }
+        }
+        Ok(installed)
+    }
+
+    /// Removes the timers of every configured job.
+    pub fn uninstall_all(&self) -> Result<(), AppError> {
+        for job in &self.config.cron_jobs {
+            self.uninstall(&job.name)?;
+        }
+        Ok(())
+    }
+}
+
+/// Validates a cron job name: lowercase letters, digits and dashes.
+pub fn validate_job_name(name: &str) -> Result<(), AppError> {
+    let valid = !name.is_empty()
+        && name.len() <= 32
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !name.starts_with('-')
+        && !name.ends_with('-');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::Validation(format!(
+            "Invalid job name '{}'. Use up to 32 lowercase letters, digits and dashes",
+            name
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_job_name() {
+        assert!(validate_job_name("scheduler").is_ok());
+        assert!(validate_job_name("cleanup-2").is_ok());
+
+        assert!(validate_job_name("").is_err());
+        assert!(validate_job_name("Scheduler").is_err());
+        assert!(validate_job_name("-cleanup").is_err());
+        assert!(validate_job_name("clean up").is_err());
+        assert!(validate_job_name(&"a".repeat(33)).is_err());
+    }
+}
@@ -1,21 +1,37 @@
 //! Deployment orchestration for applications.
 
+use std::collections::HashMap;
 use std::path::Path;
 use std::time::{Duration, Instant};
 
 use chrono::Utc;
 
-use crate::core::app_config::{AppConfig, CacheType, DatabaseType, HealthCheckConfig, Stack};
+use crate::core::app_config::{
+    AppConfig, BuildCacheMode, CacheConfig, CacheType, CronJob, DatabaseType, Framework,
+    HealthCheckConfig, HealthCheckType, Stack, StackConfig,
+};
+use crate::core::build_slots;
 use crate::core::context::ExecutionContext;
+use crate::core::cron::CronScheduler;
+use crate::core::deployments::{DeploymentHistory, DeploymentRecord, DeploymentStatus};
+use crate::core::env::EnvManager;
+use crate::core::env_crypto::{self, EnvCipher};
 use crate::core::error::AppError;
 use crate::core::registry::pull_image;
-use crate::core::secrets::SecretsManager;
-use crate::core::stack_detection::validate_nextjs_standalone_config;
+use crate::core::secrets::{AppSecrets, SecretsManager};
+use crate::core::stack_detection::{
+    detect_django_wsgi_module, detect_rust_binary_name, detect_stack,
+    validate_nextjs_standalone_config,
+};
 use crate::providers::container::{ContainerConfig, ContainerRuntime, RestartPolicy};
 use crate::providers::git::GitProvider;
 use crate::providers::reverse_proxy::ReverseProxy;
 use crate::templates::dockerfile;
 use crate::ui;
+use crate::utils::parse_memory_size;
+
+/// Buildx builder used for builds that export their cache locally.
+const BUILDX_BUILDER: &str = "flaase-builder";
 
 /// Hook execution phase.
 #[derive(Debug, Clone, Copy)]
@@ -38,12 +54,30 @@ pub enum DeployStep {
     StartDatabase,
     StartCache,
     StartApp,
-    ConfigureRouting,
     HealthCheck,
+    ConfigureRouting,
     PostDeployHooks,
 }
 
 impl DeployStep {
+    /// Stable identifier used in machine-readable event output.
+    pub fn id(&self) -> &str {
+        match self {
+            Self::CloneRepository => "clone_repository",
+            Self::PullImage => "pull_image",
+            Self::PreBuildHooks => "pre_build_hooks",
+            Self::BuildImage => "build_image",
+            Self::RunTests => "run_tests",
+            Self::PreDeployHooks => "pre_deploy_hooks",
+            Self::StartDatabase => "start_database",
+            Self::StartCache => "start_cache",
+            Self::StartApp => "start_app",
+            Self::ConfigureRouting => "configure_routing",
+            Self::HealthCheck => "health_check",
+            Self::PostDeployHooks => "post_deploy_hooks",
+        }
+    }
+
     pub fn display_name(&self) -> &str {
         match self {
             Self::CloneRepository => "Cloning repository",
@@ -80,12 +114,53 @@ pub struct UpdateResult {
     pub had_changes: bool,
 }
 
+impl UpdateResult {
+    /// One-line summary, e.g. `myapp: abc1234 → def5678, 42s, changes applied`.
+    pub fn summary(&self) -> String {
+        let new = short_commit(&self.new_commit);
+        if !self.had_changes {
+            return format!(
+                "{}: {}, {}, no changes, already up to date",
+                self.app_name,
+                new,
+                format_duration(self.duration)
+            );
+        }
+
+        let commits = match &self.old_commit {
+            Some(old) => format!("{} → {}", short_commit(old), new),
+            None => new.to_string(),
+        };
+        format!(
+            "{}: {}, {}, changes applied",
+            self.app_name,
+            commits,
+            format_duration(self.duration)
+        )
+    }
+}
+
+/// Abbreviates a commit SHA to its first 7 characters.
+fn short_commit(sha: &str) -> &str {
+    sha.get(..7).unwrap_or(sha)
+}
+
 /// Deployment orchestrator.
 pub struct Deployer<'a> {
     config: &'a AppConfig,
     runtime: &'a dyn ContainerRuntime,
     proxy: &'a dyn ReverseProxy,
     ctx: &'a ExecutionContext,
+    /// Emit step transitions as JSON lines on stderr.
+    events: bool,
+    /// Human label for this deployment (`fl deploy --tag`).
+    label: Option<String>,
+    /// Branch to check out before building (`fl deploy --from-branch`).
+    branch: Option<String>,
+    /// Git tag to check out on update (`fl update --git-tag`).
+    git_tag: Option<String>,
+    /// Re-resolve and record base image digests (`fl deploy --pin-base`).
+    pin_base: bool,
 }
 
 impl<'a> Deployer<'a> {
@@ -101,7 +176,81 @@ impl<'a> Deployer<'a> {
             runtime,
             proxy,
             ctx,
+            events: false,
+            label: None,
+            branch: None,
+            git_tag: None,
+            pin_base: false,
+        }
+    }
+
+    /// Enables the machine-readable event stream on stderr.
+    pub fn with_events(mut self, events: bool) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Labels the deployment, tagging its image so it can be rolled back to by name.
+    pub fn with_label(mut self, label: Option<&str>) -> Self {
+        self.label = label.map(|l| l.to_string());
+        self
+    }
+
+    /// Deploys the given branch instead of the repository's current one.
+    pub fn with_branch(mut self, branch: Option<&str>) -> Self {
+        self.branch = branch.map(|b| b.to_string());
+        self
+    }
+
+    /// Updates to the given tag instead of pulling the current branch.
+    pub fn with_git_tag(mut self, tag: Option<&str>) -> Self {
+        self.git_tag = tag.map(|t| t.to_string());
+        self
+    }
+
+    /// Resolves the generated Dockerfile's base images to digests and records them.
+    pub fn with_pin_base(mut self, pin_base: bool) -> Self {
+        self.pin_base = pin_base;
+        self
+    }
+
+    /// Runs a deployment step behind a spinner, emitting events if enabled.
+    fn run_step<T>(
+        &self,
+        step: DeployStep,
+        f: impl FnOnce() -> Result<T, AppError>,
+    ) -> Result<T, AppError> {
+        self.emit_event(step, "started");
+        let spinner = ui::ProgressBar::spinner(step.display_name());
+
+        match f() {
+            Ok(value) => {
+                spinner.finish("done");
+                self.emit_event(step, "done");
+                Ok(value)
+            }
+            Err(e) => {
+                self.emit_event(step, "failed");
+                Err(e)
+            }
+        }
+    }
+
+    /// Writes a single step transition as a JSON line to stderr. The CLI
+    /// reserves stderr for these lines (`ui::reserve_stderr`).
+    fn emit_event(&self, step: DeployStep, status: &str) {
+        if !self.events {
+            return;
         }
+
+        let event = serde_json::json!({
+            "app": self.config.name,
+            "step": step.id(),
+            "name": step.display_name(),
+            "status": status,
+            "timestamp": Utc::now().to_rfc3339(),
+        });
+        eprintln!("{}", event);
     }
 
     /// Container name prefix for this app.
@@ -119,6 +268,20 @@ impl<'a> Deployer<'a> {
         format!("{}-web", self.container_prefix())
     }
 
+    /// Web container names for every replica; the first one is the standard
+    /// web container so single-container apps are unaffected.
+    fn replica_container_names(&self) -> Vec<String> {
+        (1..=self.config.effective_replicas())
+            .map(|i| {
+                if i == 1 {
+                    self.web_container_name()
+                } else {
+                    format!("{}-{}", self.web_container_name(), i)
+                }
+            })
+            .collect()
+    }
+
     /// Blue container name (blue-green deployment).
     fn blue_container_name(&self) -> String {
         format!("{}-web-blue", self.container_prefix())
@@ -162,9 +325,12 @@ impl<'a> Deployer<'a> {
         } else if green_running && !blue_running {
             Ok("green")
         } else if blue_running && green_running {
-            // Both running - check which one was started last
-            // For now, default to blue as active
-            Ok("blue")
+            // Both running while the old slot awaits cleanup: the routed one
+            // is live
+            match self.routed_slot() {
+                Some("green") => Ok("green"),
+                _ => Ok("blue"),
+            }
         } else {
             Ok("none")
         }
@@ -179,6 +345,26 @@ impl<'a> Deployer<'a> {
         }
     }
 
+    /// Resolves the web container for a slot: "active" (the slot receiving
+    /// traffic, or the standard web container), "blue" or "green".
+    pub fn web_slot_container(&self, slot: &str) -> Result<String, AppError> {
+        match slot {
+            "active" if self.is_blue_green_enabled() => Ok(self
+                .active_container_name()?
+                .unwrap_or_else(|| self.web_container_name())),
+            "active" => Ok(self.web_container_name()),
+            "blue" | "green" if !self.is_blue_green_enabled() => Err(AppError::Validation(
+                format!("Blue-green deployment is not enabled for '{}'", self.config.name),
+            )),
+            "blue" => Ok(self.blue_container_name()),
+            "green" => Ok(self.green_container_name()),
+            _ => Err(AppError::Validation(format!(
+                "Unknown web slot '{}'. Use: active, blue, or green",
+                slot
+            ))),
+        }
+    }
+
     /// Gets the container name for the inactive slot (for new deployment).
     fn inactive_slot_container_name(&self) -> Result<String, AppError> {
         match self.active_slot()? {
@@ -193,9 +379,19 @@ impl<'a> Deployer<'a> {
         format!("{}-db", self.container_prefix())
     }
 
-    /// Cache container name.
-    fn cache_container_name(&self) -> String {
-        format!("{}-cache", self.container_prefix())
+    /// Container name for a cache instance.
+    fn cache_container_name(&self, cache: &CacheConfig) -> String {
+        format!("{}-{}", self.container_prefix(), cache.container_suffix())
+    }
+
+    /// Checks if any configured cache container is not running.
+    fn has_stopped_cache(&self) -> bool {
+        self.config.caches.iter().any(|cache| {
+            !self
+                .runtime
+                .container_is_running(&self.cache_container_name(cache), self.ctx)
+                .unwrap_or(false)
+        })
     }
 
     /// Base image name for this app.
@@ -213,6 +409,16 @@ impl<'a> Deployer<'a> {
         format!("{}:previous", self.image_name())
     }
 
+    /// Rollback point tag at the given depth (1 = the deploy before the current one).
+    fn previous_depth_tag(&self, depth: u32) -> String {
+        format!("{}:previous-{}", self.image_name(), depth)
+    }
+
+    /// Image tag for a deployment label.
+    fn labeled_image_tag(&self, label: &str) -> String {
+        format!("{}:{}", self.image_name(), label)
+    }
+
     /// Versioned image tag using commit SHA.
     fn versioned_image_tag(&self, commit_sha: &str) -> String {
         let short_sha = if commit_sha.len() >= 7 {
@@ -225,17 +431,66 @@ impl<'a> Deployer<'a> {
 
     /// Checks if an image exists.
     fn image_exists(&self, tag: &str) -> Result<bool, AppError> {
-        let result = self.ctx.run_command("docker", &["image", "inspect", tag]);
+        let result = self.ctx.run_command(self.runtime.command(), &["image", "inspect", tag]);
         Ok(result.is_ok() && result.unwrap().success)
     }
 
+    /// Depths of the `:previous-N` rollback points currently tagged for this app.
+    fn previous_depths(&self) -> Result<Vec<u32>, AppError> {
+        let output = self.ctx.run_command(
+            self.runtime.command(),
+            &["images", &self.image_name(), "--format", "{{.Tag}}"]
+        )?;
+
+        if !output.success {
+            return Ok(Vec::new());
+        }
+
+        Ok(output.stdout.lines().filter_map(previous_depth).collect())
+    }
+
+    /// Shifts rollback points one slot down and saves the current image as `:previous-1`.
+    /// Points beyond the configured depth are untagged; `:previous` always aliases `:previous-1`.
+    fn rotate_previous_images(&self) -> Result<(), AppError> {
+        let latest_tag = self.current_image_tag();
+        if !self.image_exists(&latest_tag)? {
+            return Ok(());
+        }
+
+        let keep = self.config.effective_keep_previous();
+
+        // Drop points that would fall past the retention depth after shifting
+        for depth in self.previous_depths()? {
+            if depth >= keep {
+                self.ctx
+                    .run_command(self.runtime.command(), &["rmi", &self.previous_depth_tag(depth)])
+                    .ok();
+            }
+        }
+
+        for depth in (1..keep).rev() {
+            let tag = self.previous_depth_tag(depth);
+            if self.image_exists(&tag)? {
+                self.tag_image(&tag, &self.previous_depth_tag(depth + 1))?;
+            }
+        }
+
+        self.tag_image(&latest_tag, &self.previous_depth_tag(1))?;
+        self.tag_image(&latest_tag, &self.previous_image_tag())
+    }
+
     /// Tags an image.
     fn tag_image(&self, source: &str, target: &str) -> Result<(), AppError> {
         if self.ctx.is_dry_run() {
-            ui::info(&format!("[DRY-RUN] docker tag {} {}", source, target));
+            ui::info(&format!(
+                "[DRY-RUN] {} tag {} {}",
+                self.runtime.command(),
+                source,
+                target
+            ));
             return Ok(());
         }
-        self.ctx.run_command("docker", &["tag", source, target])?
+        self.ctx.run_command(self.runtime.command(), &["tag", source, target])?
             .ensure_success("Failed to tag image")?;
         Ok(())
     }
@@ -245,9 +500,18 @@ impl<'a> Deployer<'a> {
         GitProvider::get_commit_hash(repo_path)
     }
 
+    /// Marks a deploy as running on the server (see `fl server update`).
+    fn begin_activity(&self) -> Result<Option<build_slots::DeployActivity>, AppError> {
+        if self.ctx.is_dry_run() {
+            return Ok(None);
+        }
+        build_slots::begin_deploy().map(Some)
+    }
+
     /// Executes a full deployment.
     pub fn deploy(&self) -> Result<DeployResult, AppError> {
         let start_time = Instant::now();
+        let _activity = self.begin_activity()?;
 
         // Branch based on deployment type
         let deploy_result = if self.config.is_image_deployment() {
@@ -263,9 +527,21 @@ impl<'a> Deployer<'a> {
             Ok(()) => {
                 // Update deployed_at timestamp
                 self.update_deployed_at()?;
+                self.sync_cron_timers();
+
+                // Tag the deployed image with its label
+                if let Some(ref label) = self.label {
+                    self.tag_image(&self.current_image_tag(), &self.labeled_image_tag(label))?;
+                }
 
                 let duration = start_time.elapsed();
-                let url = format!("https://{}", self.config.primary_domain());
+                self.record_deployment(DeploymentStatus::Success, duration);
+                let url = self.config.public_url();
+
+                // Only warns: DNS and certificates can lag behind a new domain
+                if let Some(warning) = self.check_public_url() {
+                    ui::warning(&warning);
+                }
 
                 Ok(DeployResult {
                     app_name: self.config.name.clone(),
@@ -275,6 +551,8 @@ impl<'a> Deployer<'a> {
                 })
             }
             Err(e) => {
+                self.record_deployment(DeploymentStatus::Failed, start_time.elapsed());
+
                 // Run failure hooks if configured (only for source deployments)
                 if self.config.is_source_deployment() {
                     let repo_path = self.config.repo_path();
@@ -308,9 +586,40 @@ impl<'a> Deployer<'a> {
         }
     }
 
+    /// Adds a labeled deployment to the app's history so its tag shows in the
+    /// logs (source deployments only). Unlabeled manual deploys aren't recorded.
+    fn record_deployment(&self, status: DeploymentStatus, duration: Duration) {
+        let Some(label) = &self.label else {
+            return;
+        };
+        if self.ctx.is_dry_run() || !self.config.is_source_deployment() {
+            return;
+        }
+
+        let repo_path = self.config.repo_path();
+        let commit_sha = self.get_commit_sha(&repo_path).unwrap_or_default();
+        let commit_message = GitProvider::get_commit_message(&repo_path).unwrap_or_default();
+        let branch = GitProvider::get_branch(&repo_path).unwrap_or_default();
+
+        let mut record = DeploymentRecord::manual(&commit_sha, &commit_message, &branch)
+            .with_duration(duration.as_secs())
+            .with_label(label);
+        record.status = status;
+        if !commit_sha.is_empty() {
+            record = record.with_image_tag(&self.versioned_image_tag(&commit_sha));
+        }
+
+        let path = self.config.deployments_path();
+        if let Ok(mut history) = DeploymentHistory::load(&path) {
+            history.add(record);
+            let _ = history.save(&path);
+        }
+    }
+
     /// Executes an update (zero-downtime deployment with before/after info).
     pub fn update(&self) -> Result<UpdateResult, AppError> {
         let start_time = Instant::now();
+        let _activity = self.begin_activity()?;
         let repo_path = self.config.repo_path();
 
         // Check if app was previously deployed
@@ -328,9 +637,10 @@ impl<'a> Deployer<'a> {
             Ok((new_commit, had_changes)) => {
                 // Update deployed_at timestamp
                 self.update_deployed_at()?;
+                self.sync_cron_timers();
 
                 let duration = start_time.elapsed();
-                let url = format!("https://{}", self.config.primary_domain());
+                let url = self.config.public_url();
 
                 Ok(UpdateResult {
                     app_name: self.config.name.clone(),
@@ -374,12 +684,26 @@ impl<'a> Deployer<'a> {
 
     /// Inner update logic - returns (new_commit_sha, had_changes).
     fn update_inner(&self, repo_path: &std::path::Path) -> Result<(String, bool), AppError> {
-        // Step 1: Pull latest changes
+        // Step 1: Pull latest changes (or check out the requested tag)
         let spinner = ui::ProgressBar::spinner("Pulling latest changes");
         let ssh_key = self.config.ssh_key.as_ref().ok_or_else(|| {
             AppError::Config("SSH key required for source deployments".into())
         })?;
-        let had_changes = GitProvider::pull(repo_path, ssh_key, self.ctx)?;
+        let had_changes = match &self.git_tag {
+            Some(tag) => GitProvider::checkout_tag(repo_path, tag, ssh_key, self.ctx)?,
+            // A previous tag deployment left HEAD detached: go back to the watched branch
+            None if GitProvider::get_branch(repo_path).is_ok_and(|b| b == "HEAD") => {
+                let branch = self
+                    .config
+                    .autodeploy_config
+                    .as_ref()
+                    .map(|a| a.branch.as_str())
+                    .unwrap_or("main");
+                GitProvider::checkout_branch(repo_path, branch, ssh_key)?;
+                true
+            }
+            None => GitProvider::pull(repo_path, ssh_key, self.ctx)?,
+        };
         spinner.finish(if had_changes { "updated" } else { "no changes" });
 
         // Get new commit SHA
@@ -389,6 +713,8 @@ impl<'a> Deployer<'a> {
         if !had_changes {
             let container = self.web_container_name();
             if self.runtime.container_is_running(&container, self.ctx).unwrap_or(false) {
+                // Still restore routing in case the Traefik file drifted
+                self.run_step(DeployStep::ConfigureRouting, || self.sync_routing())?;
                 return Ok((new_commit, false));
             }
             // App not running, continue with deployment
@@ -399,52 +725,36 @@ impl<'a> Deployer<'a> {
 
         // Step 2: Run pre-build hooks
         if self.has_hooks(HookPhase::PreBuild) {
-            let spinner = ui::ProgressBar::spinner(DeployStep::PreBuildHooks.display_name());
-            self.run_hooks(HookPhase::PreBuild, repo_path)?;
-            spinner.finish("done");
+            self.run_step(DeployStep::PreBuildHooks, || {
+                self.run_hooks(HookPhase::PreBuild, repo_path)
+            })?;
         }
 
         // Step 3: Build Docker image
-        let spinner = ui::ProgressBar::spinner(DeployStep::BuildImage.display_name());
-        let _commit_sha = self.build_image(repo_path)?;
-        spinner.finish("done");
+        let _commit_sha = self.run_step(DeployStep::BuildImage, || self.build_image(repo_path))?;
 
         // Step 4: Run tests
         if self.has_tests_enabled() {
-            let spinner = ui::ProgressBar::spinner(DeployStep::RunTests.display_name());
-            self.run_tests(repo_path)?;
-            spinner.finish("done");
+            self.run_step(DeployStep::RunTests, || self.run_tests(repo_path))?;
         }
 
         // Step 5: Run pre-deploy hooks
         if self.has_hooks(HookPhase::PreDeploy) {
-            let spinner = ui::ProgressBar::spinner(DeployStep::PreDeployHooks.display_name());
-            self.run_hooks(HookPhase::PreDeploy, repo_path)?;
-            spinner.finish("done");
+            self.run_step(DeployStep::PreDeployHooks, || {
+                self.run_hooks(HookPhase::PreDeploy, repo_path)
+            })?;
         }
 
         // Ensure network exists
         self.runtime.create_network(&self.network_name(), self.ctx)?;
 
-        // Step 6: Start database (if configured and not running)
-        if self.config.database.is_some() {
-            let db_container = self.db_container_name();
-            if !self.runtime.container_is_running(&db_container, self.ctx).unwrap_or(false) {
-                let spinner = ui::ProgressBar::spinner(DeployStep::StartDatabase.display_name());
-                self.start_database()?;
-                spinner.finish("done");
-            }
-        }
-
-        // Step 7: Start cache (if configured and not running)
-        if self.config.cache.is_some() {
-            let cache_container = self.cache_container_name();
-            if !self.runtime.container_is_running(&cache_container, self.ctx).unwrap_or(false) {
-                let spinner = ui::ProgressBar::spinner(DeployStep::StartCache.display_name());
-                self.start_cache()?;
-                spinner.finish("done");
-            }
-        }
+        // Steps 6-7: Start database and caches (if configured and not running)
+        let start_db = self.config.managed_database().is_some()
+            && !self
+                .runtime
+                .container_is_running(&self.db_container_name(), self.ctx)
+                .unwrap_or(false);
+        self.start_dependencies(start_db, self.has_stopped_cache())?;
 
         // Step 8: Start app container (with blue-green if enabled)
         // This handles:
@@ -452,27 +762,19 @@ impl<'a> Deployer<'a> {
         // - Health check on new container
         // - Switching traffic only if health check passes
         // - Stopping old container
-        let spinner = ui::ProgressBar::spinner(DeployStep::StartApp.display_name());
-        self.start_app()?;
-        spinner.finish("done");
+        self.run_step(DeployStep::StartApp, || self.start_app())?;
 
-        // Step 9: Configure Traefik routing (if not blue-green, which handles this)
+        // Steps 9-10: Health check, then route traffic (blue-green does both)
         if !self.is_blue_green_enabled() {
-            let spinner = ui::ProgressBar::spinner(DeployStep::ConfigureRouting.display_name());
-            self.configure_routing()?;
-            spinner.finish("done");
-
-            // Step 10: Health check (if not blue-green, which already did this)
-            let spinner = ui::ProgressBar::spinner(DeployStep::HealthCheck.display_name());
-            self.health_check()?;
-            spinner.finish("done");
+            self.run_step(DeployStep::HealthCheck, || self.health_check())?;
+            self.run_step(DeployStep::ConfigureRouting, || self.configure_routing())?;
         }
 
         // Step 11: Run post-deploy hooks
         if self.has_hooks(HookPhase::PostDeploy) {
-            let spinner = ui::ProgressBar::spinner(DeployStep::PostDeployHooks.display_name());
-            self.run_hooks(HookPhase::PostDeploy, repo_path)?;
-            spinner.finish("done");
+            self.run_step(DeployStep::PostDeployHooks, || {
+                self.run_hooks(HookPhase::PostDeploy, repo_path)
+            })?;
         }
 
         Ok((new_commit, had_changes))
@@ -482,76 +784,56 @@ impl<'a> Deployer<'a> {
     /// Inner deployment logic for source-based deployments (from Git).
     fn deploy_source_inner(&self, repo_path: &std::path::Path) -> Result<(), AppError> {
         // Step 1: Clone or pull repository
-        let spinner = ui::ProgressBar::spinner(DeployStep::CloneRepository.display_name());
-        self.sync_repository(repo_path)?;
-        spinner.finish("done");
+        self.run_step(DeployStep::CloneRepository, || self.sync_repository(repo_path))?;
 
         // Validate Next.js standalone configuration if applicable
         self.validate_stack_requirements(repo_path)?;
 
         // Step 2: Run pre-build hooks
         if self.has_hooks(HookPhase::PreBuild) {
-            let spinner = ui::ProgressBar::spinner(DeployStep::PreBuildHooks.display_name());
-            self.run_hooks(HookPhase::PreBuild, repo_path)?;
-            spinner.finish("done");
+            self.run_step(DeployStep::PreBuildHooks, || {
+                self.run_hooks(HookPhase::PreBuild, repo_path)
+            })?;
         }
 
         // Step 3: Build Docker image
-        let spinner = ui::ProgressBar::spinner(DeployStep::BuildImage.display_name());
-        let _commit_sha = self.build_image(repo_path)?;
-        spinner.finish("done");
+        let _commit_sha = self.run_step(DeployStep::BuildImage, || self.build_image(repo_path))?;
 
         // Step 4: Run tests
         if self.has_tests_enabled() {
-            let spinner = ui::ProgressBar::spinner(DeployStep::RunTests.display_name());
-            self.run_tests(repo_path)?;
-            spinner.finish("done");
+            self.run_step(DeployStep::RunTests, || self.run_tests(repo_path))?;
         }
 
         // Step 5: Run pre-deploy hooks
         if self.has_hooks(HookPhase::PreDeploy) {
-            let spinner = ui::ProgressBar::spinner(DeployStep::PreDeployHooks.display_name());
-            self.run_hooks(HookPhase::PreDeploy, repo_path)?;
-            spinner.finish("done");
+            self.run_step(DeployStep::PreDeployHooks, || {
+                self.run_hooks(HookPhase::PreDeploy, repo_path)
+            })?;
         }
 
         // Create network
         self.runtime.create_network(&self.network_name(), self.ctx)?;
 
-        // Step 6: Start database (if configured)
-        if self.config.database.is_some() {
-            let spinner = ui::ProgressBar::spinner(DeployStep::StartDatabase.display_name());
-            self.start_database()?;
-            spinner.finish("done");
-        }
-
-        // Step 7: Start cache (if configured)
-        if self.config.cache.is_some() {
-            let spinner = ui::ProgressBar::spinner(DeployStep::StartCache.display_name());
-            self.start_cache()?;
-            spinner.finish("done");
-        }
+        // Steps 6-7: Start database and cache (if configured)
+        self.start_dependencies(
+            self.config.managed_database().is_some(),
+            !self.config.caches.is_empty(),
+        )?;
 
         // Step 8: Start app container
-        let spinner = ui::ProgressBar::spinner(DeployStep::StartApp.display_name());
-        self.start_app()?;
-        spinner.finish("done");
+        self.run_step(DeployStep::StartApp, || self.start_app())?;
 
-        // Step 9: Configure Traefik routing
-        let spinner = ui::ProgressBar::spinner(DeployStep::ConfigureRouting.display_name());
-        self.configure_routing()?;
-        spinner.finish("done");
+        // Step 9: Health check before any replica receives traffic
+        self.run_step(DeployStep::HealthCheck, || self.health_check())?;
 
-        // Step 10: Health check
-        let spinner = ui::ProgressBar::spinner(DeployStep::HealthCheck.display_name());
-        self.health_check()?;
-        spinner.finish("done");
+        // Step 10: Configure Traefik routing
+        self.run_step(DeployStep::ConfigureRouting, || self.configure_routing())?;
 
         // Step 11: Run post-deploy hooks
         if self.has_hooks(HookPhase::PostDeploy) {
-            let spinner = ui::ProgressBar::spinner(DeployStep::PostDeployHooks.display_name());
-            self.run_hooks(HookPhase::PostDeploy, repo_path)?;
-            spinner.finish("done");
+            self.run_step(DeployStep::PostDeployHooks, || {
+                self.run_hooks(HookPhase::PostDeploy, repo_path)
+            })?;
         }
 
         Ok(())
@@ -564,51 +846,77 @@ impl<'a> Deployer<'a> {
         })?;
 
         // Step 1: Pull Docker image from registry
-        let spinner = ui::ProgressBar::spinner(DeployStep::PullImage.display_name());
-        // Load credentials for private registries
-        let credentials = if image_config.private {
-            crate::core::registry::load_credentials(&self.config.registry_auth_path())?
-        } else {
-            None
-        };
-        pull_image(image_config, credentials.as_ref(), self.ctx)?;
-        spinner.finish("done");
+        self.run_step(DeployStep::PullImage, || {
+            // Load credentials for private registries
+            let credentials = if image_config.private {
+                crate::core::registry::load_credentials(&self.config.registry_auth_path())?
+            } else {
+                None
+            };
+            pull_image(image_config, credentials.as_ref(), self.ctx)
+        })?;
 
         // Create network
         self.runtime.create_network(&self.network_name(), self.ctx)?;
 
-        // Step 2: Start database (if configured)
-        if self.config.database.is_some() {
-            let spinner = ui::ProgressBar::spinner(DeployStep::StartDatabase.display_name());
-            self.start_database()?;
-            spinner.finish("done");
-        }
-
-        // Step 3: Start cache (if configured)
-        if self.config.cache.is_some() {
-            let spinner = ui::ProgressBar::spinner(DeployStep::StartCache.display_name());
-            self.start_cache()?;
-            spinner.finish("done");
-        }
+        // Steps 2-3: Start database and cache (if configured)
+        self.start_dependencies(
+            self.config.managed_database().is_some(),
+            !self.config.caches.is_empty(),
+        )?;
 
         // Step 4: Start app container
-        let spinner = ui::ProgressBar::spinner(DeployStep::StartApp.display_name());
-        self.start_app()?;
-        spinner.finish("done");
+        self.run_step(DeployStep::StartApp, || self.start_app())?;
 
-        // Step 5: Configure Traefik routing
-        let spinner = ui::ProgressBar::spinner(DeployStep::ConfigureRouting.display_name());
-        self.configure_routing()?;
-        spinner.finish("done");
+        // Step 5: Health check before any replica receives traffic
+        self.run_step(DeployStep::HealthCheck, || self.health_check())?;
 
-        // Step 6: Health check
-        let spinner = ui::ProgressBar::spinner(DeployStep::HealthCheck.display_name());
-        self.health_check()?;
-        spinner.finish("done");
+        // Step 6: Configure Traefik routing
+        self.run_step(DeployStep::ConfigureRouting, || self.configure_routing())?;
 
         Ok(())
     }
 
+    /// Requests the health endpoint through the primary public domain.
+    /// Returns a warning when it isn't reachable from outside yet.
+    fn check_public_url(&self) -> Option<String> {
+        let health_config = self.config.effective_health_check();
+        if self.ctx.is_dry_run()
+            || health_config.check_type != HealthCheckType::Http
+            || !health_config.public_check.unwrap_or(true)
+        {
+            return None;
+        }
+
+        let url = public_health_url(&self.config.public_url(), &health_config.endpoint);
+        let spinner = ui::ProgressBar::spinner(&format!("Checking {}", url));
+
+        let output = self.ctx.run_command(
+            "curl",
+            &[
+                "-sS",
+                "-o",
+                "/dev/null",
+                "-w",
+                "%{http_code}",
+                "--max-time",
+                "10",
+                &url,
+            ],
+        );
+
+        let warning = match output {
+            Ok(output) => describe_public_check(&url, output.code, output.stdout.trim()),
+            Err(_) => None, // curl missing: nothing to report
+        };
+
+        match warning {
+            Some(_) => spinner.finish_error("not reachable"),
+            None => spinner.finish("reachable"),
+        }
+        warning
+    }
+
     /// Returns the Docker image to use for the app container.
     /// For image deployments: the pulled image reference
     /// For source deployments: the locally built image
@@ -624,11 +932,12 @@ impl<'a> Deployer<'a> {
     fn cleanup_on_failure(&self) {
         ui::warning("Cleaning up failed deployment...");
 
-        // Stop and remove web container
-        let web = self.web_container_name();
-        if self.runtime.container_exists(&web, self.ctx).unwrap_or(false) {
-            let _ = self.runtime.stop_container(&web, self.ctx);
-            let _ = self.runtime.remove_container(&web, self.ctx);
+        // Stop and remove web containers
+        for web in self.replica_container_names() {
+            if self.runtime.container_exists(&web, self.ctx).unwrap_or(false) {
+                let _ = self.runtime.stop_container(&web, self.ctx);
+                let _ = self.runtime.remove_container(&web, self.ctx);
+            }
         }
 
         // Note: We don't cleanup database/cache on failure as they might contain data
@@ -652,6 +961,10 @@ impl<'a> Deployer<'a> {
             GitProvider::clone(repository, repo_path, ssh_key, self.ctx)?;
         }
 
+        if let Some(branch) = &self.branch {
+            GitProvider::checkout_branch(repo_path, branch, ssh_key)?;
+        }
+
         Ok(())
     }
 
@@ -770,7 +1083,9 @@ impl<'a> Deployer<'a> {
         for hook in hooks {
             ui::info(&format!("  Hook: {}", hook.name));
 
-            let result = if hook.run_in_container {
+            let result = if hook.run_in_ephemeral {
+                self.run_hook_in_ephemeral(hook)
+            } else if hook.run_in_container {
                 self.run_hook_in_container(hook)
             } else {
                 self.run_hook_on_host(hook, repo_path)
@@ -830,107 +1145,584 @@ impl<'a> Deployer<'a> {
         Ok(())
     }
 
-    /// Builds the Docker image with caching and versioning.
-    fn build_image(&self, repo_path: &Path) -> Result<String, AppError> {
-        // Get commit SHA for versioning
-        let commit_sha = self.get_commit_sha(repo_path)?;
-        let versioned_tag = self.versioned_image_tag(&commit_sha);
-        let latest_tag = self.current_image_tag();
-        let previous_tag = self.previous_image_tag();
-
-        // Check if Dockerfile exists, otherwise generate one (only for source deployments)
-        if !dockerfile::exists(repo_path) {
-            let stack = self.config.stack.as_ref().ok_or_else(|| {
-                AppError::Config("Stack required for source deployments".into())
-            })?;
-            let port = self.config.effective_port();
-            let dockerfile_content = dockerfile::generate(*stack, port);
-            let dockerfile_path = dockerfile::path(repo_path);
+    /// Runs a hook in a throwaway container from the app image, on the app
+    /// network and with the app's env files, leaving the serving one alone.
+    fn run_hook_in_ephemeral(&self, hook: &crate::core::app_config::HookCommand) -> Result<(), AppError> {
+        let container_name = format!("{}-hook", self.container_prefix());
 
-            if self.ctx.is_dry_run() {
-                ui::info(&format!("[DRY-RUN] Write Dockerfile to {:?}", dockerfile_path));
-            } else {
-                std::fs::write(&dockerfile_path, dockerfile_content)
-                    .map_err(|e| AppError::Deploy(format!("Failed to write Dockerfile: {}", e)))?;
-            }
+        // A hook container left behind by an interrupted run would block the name
+        if self.runtime.container_exists(&container_name, self.ctx)? {
+            self.runtime.remove_container(&container_name, self.ctx)?;
         }
 
-        // Backup current image as previous (for rollback)
-        if self.image_exists(&latest_tag)? {
-            self.tag_image(&latest_tag, &previous_tag)?;
-        }
+        let (mut args, _decrypted) = self.ephemeral_run_args(container_name, "hook")?;
+        args.extend([
+            "--entrypoint".to_string(),
+            "sh".to_string(),
+            self.app_image(),
+            "-c".to_string(),
+            hook.command.clone(),
+        ]);
 
-        // Get build config
-        let build_config = self.config.autodeploy_config
-            .as_ref()
-            .and_then(|ad| ad.build.as_ref());
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let output = self.ctx.run_command(self.runtime.command(), &args)?;
 
-        // Build with caching if enabled
-        let use_buildkit = build_config
-            .map(|bc| bc.buildkit)
-            .unwrap_or(true);
-        let use_cache = build_config
-            .map(|bc| bc.cache_enabled)
-            .unwrap_or(true);
+        if !output.success {
+            return Err(AppError::HookFailed(output.stderr.trim().to_string()));
+        }
 
-        if self.ctx.is_dry_run() {
-            ui::info(&format!("[DRY-RUN] Build image {} with BUILDKIT={}", versioned_tag, use_buildkit));
-        } else {
-            // Set BuildKit environment variable if enabled
-            if use_buildkit {
-                std::env::set_var("DOCKER_BUILDKIT", "1");
-            }
+        Ok(())
+    }
 
-            // Build command with cache-from if enabled
-            let mut args = vec!["build", "-t", &versioned_tag];
+    /// Runs a scheduled job in a throwaway container from the app image.
+    /// The job's output is echoed so it lands in the timer's journal.
+    pub fn run_cron_job(&self, job: &CronJob) -> Result<(), AppError> {
+        let container_name = format!("{}-cron-{}", self.container_prefix(), job.name);
 
-            if use_cache && self.image_exists(&latest_tag)? {
-                args.push("--cache-from");
-                args.push(&latest_tag);
-            }
+        // The previous run is still going: skip rather than pile up containers
+        if self.runtime.container_is_running(&container_name, self.ctx)? {
+            return Err(AppError::Command(format!(
+                "Job '{}' is still running from a previous schedule",
+                job.name
+            )));
+        }
+        if self.runtime.container_exists(&container_name, self.ctx)? {
+            self.runtime.remove_container(&container_name, self.ctx)?;
+        }
 
-            args.push(repo_path.to_str().unwrap());
+        let limits = self.apply_limits(ContainerConfig::new(&container_name, &self.app_image()));
+        let (mut args, _decrypted) = self.ephemeral_run_args(container_name, "cron")?;
+        args.extend(limits.hard_limit_args());
+        args.extend([
+            "--entrypoint".to_string(),
+            "sh".to_string(),
+            self.app_image(),
+            "-c".to_string(),
+            job.command.clone(),
+        ]);
 
-            self.ctx.run_command_streaming("docker", &args)?
-                .ensure_success("Failed to build Docker image")?;
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let output = self.ctx.run_command(self.runtime.command(), &args)?;
 
-            // Tag as latest
-            self.tag_image(&versioned_tag, &latest_tag)?;
+        if !output.stdout.trim().is_empty() {
+            println!("{}", output.stdout.trim_end());
+        }
+        if !output.stderr.trim().is_empty() {
+            eprintln!("{}", output.stderr.trim_end());
         }
 
-        Ok(commit_sha)
+        if !output.success {
+            return Err(AppError::Command(format!("Job '{}' failed", job.name)));
+        }
+
+        Ok(())
     }
 
-    /// Starts the database container.
-    fn start_database(&self) -> Result<(), AppError> {
-        let db_config = self.config.database.as_ref().unwrap();
-        let container_name = self.db_container_name();
+    /// Common `run` arguments for a throwaway app container: app network,
+    /// labels, env files, volumes and the mounted secrets file. The returned
+    /// file holds decrypted variables and must be kept until the run ends.
+    fn ephemeral_run_args(
+        &self,
+        container_name: String,
+        service: &str,
+    ) -> Result<(Vec<String>, Option<DecryptedEnvFile>), AppError> {
+        let mut args = vec![
+            "run".to_string(),
+            "--rm".to_string(),
+            "--name".to_string(),
+            container_name,
+            "--network".to_string(),
+            self.network_name(),
+            "--label".to_string(),
+            "flaase.managed=true".to_string(),
+            "--label".to_string(),
+            format!("flaase.app={}", self.config.name),
+            "--label".to_string(),
+            format!("flaase.service={}", service),
+        ];
 
-        // Check if already running
-        if self.runtime.container_is_running(&container_name, self.ctx)? {
-            return Ok(());
+        for env_file in self.app_env_files() {
+            args.push("--env-file".to_string());
+            args.push(env_file);
         }
 
-        // Remove existing container if exists
-        if self.runtime.container_exists(&container_name, self.ctx)? {
-            self.runtime.remove_container(&container_name, self.ctx)?;
+        let decrypted = self.decrypted_env_file()?;
+        if let Some(file) = &decrypted {
+            args.push("--env-file".to_string());
+            args.push(file.path.to_string_lossy().to_string());
         }
 
-        // Load secrets
-        let secrets = SecretsManager::load_secrets(&self.config.secrets_path())?;
-        let db_secrets = secrets.database.as_ref().ok_or_else(|| {
-            AppError::Deploy("Database secrets not found".into())
-        })?;
+        if let Some(node_env) = self.injected_node_env() {
+            args.push("-e".to_string());
+            args.push(format!("NODE_ENV={}", node_env));
+        }
 
-        // Build container config based on database type
-        let mut container = ContainerConfig::new(&container_name, db_config.db_type.docker_image())
-            .network(&self.network_name())
-            .restart(RestartPolicy::UnlessStopped)
-            .label("flaase.managed", "true")
-            .label("flaase.app", &self.config.name)
-            .label("flaase.service", "database");
+        for vol in &self.config.volumes {
+            let host_path = format!("{}/{}", self.config.data_path().display(), vol.volume_name);
+            self.ctx.create_dir(&host_path)?;
+            args.push("-v".to_string());
+            args.push(format!("{}:{}", host_path, vol.container_path));
+        }
 
-        // Add data volume
+        if let Some((host_path, container_path)) = self.write_secrets_file()? {
+            args.push("-v".to_string());
+            args.push(format!("{}:{}:ro", host_path, container_path));
+        }
+
+        Ok((args, decrypted))
+    }
+
+    /// Env files passed to app containers, auto-generated first so user
+    /// values override them. Generated secrets are left out when they are
+    /// only delivered through the mounted secrets file.
+    fn app_env_files(&self) -> Vec<String> {
+        let file_only = self
+            .config
+            .secrets_file
+            .as_ref()
+            .map(|f| f.file_only)
+            .unwrap_or(false);
+
+        [self.config.auto_env_path(), self.config.env_path()]
+            .iter()
+            .filter(|path| !(file_only && *path == &self.config.auto_env_path()))
+            .filter(|path| path.exists())
+            .map(|path| path.to_string_lossy().to_string())
+            .collect()
+    }
+
+    /// Writes the decrypted variables to a private env file passed after the
+    /// other env files, so it overrides their ciphertext. Plain values never
+    /// appear on the `docker run` command line (`ps`, dry-run and verbose
+    /// output). The file is removed when the returned guard is dropped.
+    fn decrypted_env_file(&self) -> Result<Option<DecryptedEnvFile>, AppError> {
+        let vars = self.decrypted_env()?;
+        if vars.is_empty() {
+            return Ok(None);
+        }
+
+        let path = self
+            .config
+            .app_dir()
+            .join(format!(".env.decrypted.{}", std::process::id()));
+        let file = DecryptedEnvFile { path };
+        if self.ctx.is_dry_run() {
+            return Ok(Some(file));
+        }
+
+        let mut content = String::new();
+        for (key, value) in vars {
+            if value.contains('\n') {
+                return Err(AppError::Config(format!(
+                    "Encrypted variable {} spans several lines, which env files don't support",
+                    key
+                )));
+            }
+            content.push_str(&format!("{}={}\n", key, value));
+        }
+        file.write(&content)?;
+        Ok(Some(file))
+    }
+
+    /// Decrypts the variables stored encrypted in the env files.
+    fn decrypted_env(&self) -> Result<Vec<(String, String)>, AppError> {
+        let mut encrypted = Vec::new();
+        for path in self.app_env_files() {
+            encrypted.extend(
+                EnvManager::load_from_file(Path::new(&path))?
+                    .into_iter()
+                    .filter(|var| env_crypto::is_encrypted(&var.value)),
+            );
+        }
+        if encrypted.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let cipher = EnvCipher::load()?;
+        encrypted
+            .into_iter()
+            .map(|var| Ok((var.key, cipher.decrypt(&var.value)?)))
+            .collect()
+    }
+
+    /// Returns the `NODE_ENV` value to inject, if any.
+    ///
+    /// `-e` would override the env files, so a `NODE_ENV` defined there wins
+    /// and nothing is injected.
+    fn injected_node_env(&self) -> Option<&'static str> {
+        if !self.config.is_source_deployment() || !self.config.effective_inject_node_env() {
+            return None;
+        }
+
+        let user_defined = self.app_env_files().iter().any(|path| {
+            EnvManager::load_from_file(Path::new(path))
+                .map(|vars| vars.iter().any(|v| v.key == "NODE_ENV"))
+                .unwrap_or(false)
+        });
+
+        if user_defined {
+            None
+        } else {
+            Some("production")
+        }
+    }
+
+    /// Returns the stack configuration used to generate a Dockerfile, filling
+    /// in values detected from the repository (e.g. the Rust binary name).
+    fn effective_stack_config(&self, stack: Stack, repo_path: &Path) -> StackConfig {
+        let mut stack_config = self.config.stack_config.clone().unwrap_or_default();
+
+        if stack == Stack::Rust && stack_config.start_command.is_none() {
+            let binary = detect_rust_binary_name(repo_path)
+                .unwrap_or_else(|| self.config.name.clone());
+            stack_config.start_command = Some(format!("./{}", binary));
+        }
+
+        // Use the Go version from go.mod; modules refuse to build with older toolchains
+        if stack == Stack::Go && stack_config.version.is_none() {
+            let detected = detect_stack(repo_path);
+            if detected.stack == Some(Stack::Go) {
+                stack_config.version = detected.version;
+            }
+        }
+
+        // Install with the lockfile's package manager (npm ci fails in pnpm/yarn repos)
+        if matches!(stack, Stack::NextJs | Stack::NodeJs | Stack::NestJs)
+            && stack_config.package_manager.is_none()
+        {
+            stack_config.package_manager = detect_stack(repo_path).package_manager;
+        }
+
+        // Build Vite/CRA frontends before serving their output with nginx
+        if stack == Stack::Static {
+            let detected = detect_stack(repo_path);
+            if detected.stack == Some(Stack::Static) {
+                stack_config.package_manager =
+                    stack_config.package_manager.or(detected.package_manager);
+                stack_config.framework = stack_config.framework.or(detected.framework);
+            }
+        }
+
+        // Install with the repository's package manager and serve with its framework
+        if stack == Stack::Python {
+            let detected = detect_stack(repo_path);
+            if detected.stack == Some(Stack::Python) {
+                stack_config.package_manager =
+                    stack_config.package_manager.or(detected.package_manager);
+                stack_config.framework = stack_config.framework.or(detected.framework);
+            }
+
+            if stack_config.framework == Some(Framework::Django)
+                && stack_config.start_command.is_none()
+            {
+                if let Some(module) = detect_django_wsgi_module(repo_path) {
+                    stack_config.start_command = Some(format!(
+                        "gunicorn {}:application --bind 0.0.0.0:{}",
+                        module,
+                        self.config.effective_port()
+                    ));
+                }
+            }
+        }
+
+        stack_config
+    }
+
+    /// Points the generated Dockerfile at the recorded base image digests.
+    /// With `--pin-base`, resolves every base image to its current digest first
+    /// and saves the result so later builds reuse the exact same base.
+    fn pin_base_images(&self, content: &str) -> Result<String, AppError> {
+        let mut pins = self.config.base_image_digests.clone();
+
+        if self.pin_base {
+            for image in dockerfile::base_images(content) {
+                if self.ctx.is_dry_run() {
+                    ui::info(&format!("[DRY-RUN] Pin base image {} to its digest", image));
+                    continue;
+                }
+                let pinned = self.resolve_base_digest(&image)?;
+                ui::info(&format!("Pinned {}", pinned));
+                pins.insert(image, pinned);
+            }
+
+            if !self.ctx.is_dry_run() && pins != self.config.base_image_digests {
+                let mut config = AppConfig::load(&self.config.name)?;
+                config.base_image_digests = pins.clone();
+                config.save()?;
+            }
+        }
+
+        Ok(dockerfile::pin_base_images(content, &pins))
+    }
+
+    /// Pulls a base image and returns it as `image@sha256:...`.
+    fn resolve_base_digest(&self, image: &str) -> Result<String, AppError> {
+        self.runtime.pull_image(image, self.ctx)?;
+
+        let output = self.ctx.run_command(
+            self.runtime.command(),
+            &["image", "inspect", "--format", "{{index .RepoDigests 0}}", image],
+        )?;
+        output.ensure_success(&format!("Failed to resolve digest of '{}'", image))?;
+
+        let digest = output
+            .stdout
+            .trim()
+            .rsplit_once('@')
+            .map(|(_, digest)| digest.to_string())
+            .filter(|digest| digest.starts_with("sha256:"))
+            .ok_or_else(|| AppError::Docker(format!("No registry digest for '{}'", image)))?;
+
+        Ok(format!("{}@{}", image, digest))
+    }
+
+    /// Builds the Docker image with caching and versioning.
+    fn build_image(&self, repo_path: &Path) -> Result<String, AppError> {
+        // Get commit SHA for versioning
+        let commit_sha = self.get_commit_sha(repo_path)?;
+        let versioned_tag = self.versioned_image_tag(&commit_sha);
+        let latest_tag = self.current_image_tag();
+
+        // Check if Dockerfile exists, otherwise generate one (only for source deployments)
+        if !dockerfile::exists(repo_path) {
+            let stack = self.config.stack.as_ref().ok_or_else(|| {
+                AppError::Config("Stack required for source deployments".into())
+            })?;
+            let port = self.config.effective_port();
+            let stack_config = self.effective_stack_config(*stack, repo_path);
+            let dockerfile_content = self.pin_base_images(&dockerfile::with_healthcheck(
+                &dockerfile::generate_with_config(*stack, &stack_config, port),
+                *stack,
+                port,
+                &self.config.effective_health_check(),
+            ))?;
+            let dockerfile_path = dockerfile::path(repo_path);
+
+            if self.ctx.is_dry_run() {
+                ui::info(&format!("[DRY-RUN] Write Dockerfile to {:?}", dockerfile_path));
+            } else {
+                std::fs::write(&dockerfile_path, dockerfile_content)
+                    .map_err(|e| AppError::Deploy(format!("Failed to write Dockerfile: {}", e)))?;
+            }
+        }
+
+        // Keep the current image as a rollback point
+        self.rotate_previous_images()?;
+
+        // Get build config
+        let build_config = self.config.autodeploy_config
+            .as_ref()
+            .and_then(|ad| ad.build.as_ref());
+
+        // Build with caching if enabled
+        let use_buildkit = build_config
+            .map(|bc| bc.buildkit)
+            .unwrap_or(true);
+        let use_cache = build_config
+            .map(|bc| bc.cache_enabled)
+            .unwrap_or(true);
+        let cache_mode = build_config
+            .map(|bc| bc.cache_mode)
+            .unwrap_or_default();
+
+        // Hold a server-wide build slot so concurrent deploys don't exhaust memory
+        let _build_slot = if self.ctx.is_dry_run() {
+            None
+        } else {
+            Some(build_slots::acquire()?)
+        };
+
+        if self.ctx.is_dry_run() {
+            ui::info(&format!("[DRY-RUN] Build image {} with BUILDKIT={}", versioned_tag, use_buildkit));
+        } else if use_cache
+            && cache_mode == BuildCacheMode::Local
+            // buildx is Docker-only; Podman falls back to a regular cached build
+            && self.runtime.runtime_type() == "docker"
+        {
+            self.build_with_local_cache(repo_path, &versioned_tag)?;
+
+            // Tag as latest
+            self.tag_image(&versioned_tag, &latest_tag)?;
+        } else {
+            // Set BuildKit environment variable if enabled
+            if use_buildkit {
+                std::env::set_var("DOCKER_BUILDKIT", "1");
+            }
+
+            // Build command with cache-from if enabled
+            let mut args = vec!["build", "-t", &versioned_tag];
+
+            if use_cache && self.image_exists(&latest_tag)? {
+                args.push("--cache-from");
+                args.push(&latest_tag);
+            }
+
+            args.push(repo_path.to_str().unwrap());
+
+            self.ctx.run_command_streaming(self.runtime.command(), &args)?
+                .ensure_success("Failed to build Docker image")?;
+
+            // Tag as latest
+            self.tag_image(&versioned_tag, &latest_tag)?;
+        }
+
+        Ok(commit_sha)
+    }
+
+    /// Builds with buildx, importing and exporting the layer cache from a
+    /// directory under the app dir so it survives image pruning.
+    fn build_with_local_cache(&self, repo_path: &Path, tag: &str) -> Result<(), AppError> {
+        self.ensure_cache_builder()?;
+
+        let cache_dir = self.config.build_cache_path();
+        // Export to a fresh directory and swap it in, otherwise the local
+        // cache keeps every blob ever written.
+        let next_dir = cache_dir.with_extension("new");
+        let _ = std::fs::remove_dir_all(&next_dir);
+
+        let cache_from = format!("type=local,src={}", cache_dir.display());
+        let cache_to = format!("type=local,dest={},mode=max", next_dir.display());
+
+        let mut args = vec![
+            "buildx",
+            "build",
+            "--builder",
+            BUILDX_BUILDER,
+            "--load",
+            "-t",
+            tag,
+            "--cache-to",
+            &cache_to,
+        ];
+
+        if cache_dir.exists() {
+            args.push("--cache-from");
+            args.push(&cache_from);
+        }
+
+        args.push(repo_path.to_str().unwrap());
+
+        self.ctx.run_command_streaming(self.runtime.command(), &args)?
+            .ensure_success("Failed to build Docker image")?;
+
+        let _ = std::fs::remove_dir_all(&cache_dir);
+        std::fs::rename(&next_dir, &cache_dir).map_err(|e| {
+            AppError::Deploy(format!("Failed to store build cache: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    /// Creates the docker-container buildx builder needed for cache export.
+    fn ensure_cache_builder(&self) -> Result<(), AppError> {
+        let inspect = self
+            .ctx
+            .run_command(self.runtime.command(), &["buildx", "inspect", BUILDX_BUILDER])?;
+
+        if inspect.success {
+            return Ok(());
+        }
+
+        self.ctx
+            .run_command(
+                self.runtime.command(),
+                &[
+                    "buildx",
+                    "create",
+                    "--name",
+                    BUILDX_BUILDER,
+                    "--driver",
+                    "docker-container",
+                ],
+            )?
+            .ensure_success("Failed to create buildx builder (is docker buildx installed?)")?;
+
+        Ok(())
+    }
+
+    /// Starts the database and caches, concurrently when both are needed
+    /// since each waits for its service to come up.
+    fn start_dependencies(&self, database: bool, caches: bool) -> Result<(), AppError> {
+        match (database, caches) {
+            (false, false) => Ok(()),
+            (true, false) => self.run_step(DeployStep::StartDatabase, || self.start_database()),
+            (false, true) => self.run_step(DeployStep::StartCache, || self.start_caches()),
+            (true, true) => {
+                self.emit_event(DeployStep::StartDatabase, "started");
+                self.emit_event(DeployStep::StartCache, "started");
+                let spinner = ui::ProgressBar::spinner("Starting database and cache");
+
+                let (db_result, cache_result) = std::thread::scope(|scope| {
+                    let db = scope.spawn(|| self.start_database());
+                    let cache_result = self.start_caches();
+                    let db_result = db.join().unwrap_or_else(|_| {
+                        Err(AppError::Deploy("Database startup thread panicked".into()))
+                    });
+                    (db_result, cache_result)
+                });
+
+                for (step, result) in [
+                    (DeployStep::StartDatabase, &db_result),
+                    (DeployStep::StartCache, &cache_result),
+                ] {
+                    self.emit_event(step, if result.is_ok() { "done" } else { "failed" });
+                }
+
+                if db_result.is_err() || cache_result.is_err() {
+                    spinner.finish_error("failed");
+                } else {
+                    spinner.finish("done");
+                }
+                if let (Err(_), Err(cache_err)) = (&db_result, &cache_result) {
+                    ui::error(&format!("Cache failed to start: {}", cache_err));
+                }
+                db_result?;
+                cache_result
+            }
+        }
+    }
+
+    /// Starts the database container.
+    fn start_database(&self) -> Result<(), AppError> {
+        let db_config = self.config.managed_database().unwrap();
+        let container_name = self.db_container_name();
+        let exposed = db_config.exposed_binding();
+        let container_port = db_config.db_type.default_port();
+
+        // Check if already running with the configured port exposure and limits
+        if self.runtime.container_is_running(&container_name, self.ctx)?
+            && self.published_port_matches(&container_name, container_port, exposed)
+            && self.limits_match(&container_name)
+        {
+            return Ok(());
+        }
+
+        // Remove existing container if exists
+        if self.runtime.container_exists(&container_name, self.ctx)? {
+            self.runtime.remove_container(&container_name, self.ctx)?;
+        }
+
+        // Load secrets
+        let secrets = SecretsManager::load_secrets(&self.config.secrets_path())?;
+        let db_secrets = secrets.database.as_ref().ok_or_else(|| {
+            AppError::Deploy("Database secrets not found".into())
+        })?;
+
+        // Build container config based on database type
+        let image = db_config.docker_image();
+        let mut container = ContainerConfig::new(&container_name, &image)
+            .network(&self.network_name())
+            .restart(RestartPolicy::UnlessStopped)
+            .label("flaase.managed", "true")
+            .label("flaase.app", &self.config.name)
+            .label("flaase.service", "database");
+        container = self.apply_service_limits(container);
+
+        if let Some((address, port)) = exposed {
+            container = container.port_on(address, port, container_port);
+        }
+
+        // Add data volume
         let data_path = format!("{}/db", self.config.data_path().display());
         self.ctx.create_dir(&data_path)?;
 
@@ -950,6 +1742,16 @@ impl<'a> Deployer<'a> {
                     .env("MYSQL_ROOT_PASSWORD", &db_secrets.password)
                     .volume(&data_path, "/var/lib/mysql");
             }
+            DatabaseType::MariaDB => {
+                let root_password =
+                    db_secrets.root_password.as_deref().unwrap_or(&db_secrets.password);
+                container = container
+                    .env("MARIADB_USER", &db_secrets.username)
+                    .env("MARIADB_PASSWORD", &db_secrets.password)
+                    .env("MARIADB_DATABASE", &db_config.name)
+                    .env("MARIADB_ROOT_PASSWORD", root_password)
+                    .volume(&data_path, "/var/lib/mysql");
+            }
             DatabaseType::MongoDB => {
                 container = container
                     .env("MONGO_INITDB_ROOT_USERNAME", &db_secrets.username)
@@ -966,13 +1768,94 @@ impl<'a> Deployer<'a> {
         Ok(())
     }
 
-    /// Starts the cache container.
-    fn start_cache(&self) -> Result<(), AppError> {
-        let cache_config = self.config.cache.as_ref().unwrap();
-        let container_name = self.cache_container_name();
+    /// Checks whether a running service container publishes its port exactly as
+    /// configured, so changing `expose_port` recreates the container.
+    fn published_port_matches(
+        &self,
+        container: &str,
+        container_port: u16,
+        expected: Option<(&str, u16)>,
+    ) -> bool {
+        let port_spec = format!("{}/tcp", container_port);
+        let published = self
+            .ctx
+            .run_command(self.runtime.command(), &["port", container, &port_spec])
+            .ok()
+            .filter(|o| o.success)
+            .map(|o| o.stdout)
+            .unwrap_or_default();
+
+        published_binding_matches(&published, expected)
+    }
+
+    /// Starts every configured cache container.
+    fn start_caches(&self) -> Result<(), AppError> {
+        self.ensure_named_cache_secrets()?;
 
-        // Check if already running
-        if self.runtime.container_is_running(&container_name, self.ctx)? {
+        for cache_config in &self.config.caches {
+            self.start_cache(cache_config)?;
+        }
+
+        Ok(())
+    }
+
+    /// Generates secrets for named caches that don't have any yet and
+    /// refreshes the auto-generated env file with their connection URLs.
+    fn ensure_named_cache_secrets(&self) -> Result<(), AppError> {
+        let named: Vec<(&CacheConfig, &str)> = self
+            .config
+            .caches
+            .iter()
+            .filter_map(|cache| cache.name.as_deref().map(|name| (cache, name)))
+            .collect();
+        if named.is_empty() {
+            return Ok(());
+        }
+
+        let secrets_path = self.config.secrets_path();
+        let mut secrets = SecretsManager::load_secrets(&secrets_path)?;
+        let mut changed = false;
+
+        for (cache_config, name) in named {
+            if secrets.caches.contains_key(name) {
+                continue;
+            }
+            match SecretsManager::generate_cache_secrets(cache_config.cache_type) {
+                Some(cache_secrets) => {
+                    secrets.caches.insert(name.to_string(), cache_secrets);
+                    changed = true;
+                }
+                // No secrets to store, only make sure the URL is in the env file
+                None => changed |= !auto_env_defines(self.config, &cache_config.url_env_var()),
+            }
+        }
+
+        if !changed {
+            return Ok(());
+        }
+
+        if self.ctx.is_dry_run() {
+            ui::info("[DRY-RUN] Would generate secrets for additional caches");
+            return Ok(());
+        }
+
+        SecretsManager::save_secrets(&secrets_path, &secrets)?;
+
+        let env_vars = self.generated_env_vars(&secrets);
+        SecretsManager::write_env_file(&self.config.auto_env_path(), &env_vars)
+    }
+
+    /// Starts a single cache container.
+    fn start_cache(&self, cache_config: &CacheConfig) -> Result<(), AppError> {
+        let container_name = self.cache_container_name(cache_config);
+        let exposed = cache_config.exposed_binding();
+        let container_port = cache_config.cache_type.default_port();
+
+        // Check if already running with the configured port exposure and limits
+        if self.runtime.container_is_running(&container_name, self.ctx)?
+            && self.published_port_matches(&container_name, container_port, exposed)
+            && self.limits_match(&container_name)
+        {
             return Ok(());
         }
 
@@ -990,10 +1873,20 @@ impl<'a> Deployer<'a> {
             .label("flaase.managed", "true")
             .label("flaase.app", &self.config.name)
             .label("flaase.service", "cache");
+        container = self.apply_service_limits(container);
+
+        if let Some((address, port)) = exposed {
+            container = container.port_on(address, port, container_port);
+        }
+
+        let cache_secrets = match &cache_config.name {
+            Some(name) => secrets.caches.get(name),
+            None => secrets.cache.as_ref(),
+        };
 
         match cache_config.cache_type {
             CacheType::Redis => {
-                if let Some(cache_secrets) = &secrets.cache {
+                if let Some(cache_secrets) = cache_secrets {
                     container = container.command(vec![
                         "redis-server".to_string(),
                         "--requirepass".to_string(),
@@ -1001,6 +1894,7 @@ impl<'a> Deployer<'a> {
                     ]);
                 }
             }
+            CacheType::Memcached => {}
         }
 
         self.runtime.run_container(&container, self.ctx)?;
@@ -1014,7 +1908,14 @@ impl<'a> Deployer<'a> {
     /// Starts the app container.
     /// Uses blue-green deployment if enabled, otherwise standard deployment.
     fn start_app(&self) -> Result<(), AppError> {
-        if self.is_blue_green_enabled() {
+        if self.config.effective_replicas() > 1 {
+            if self.is_blue_green_enabled() {
+                return Err(AppError::Config(
+                    "Replicas can't be combined with blue-green deployments".into(),
+                ));
+            }
+            self.start_app_replicas()
+        } else if self.is_blue_green_enabled() {
             self.start_app_blue_green()
         } else {
             self.start_app_standard()
@@ -1023,19 +1924,98 @@ impl<'a> Deployer<'a> {
 
     /// Standard deployment (stop old, start new).
     fn start_app_standard(&self) -> Result<(), AppError> {
-        let container_name = self.web_container_name();
+        self.start_web_container(&self.web_container_name())?;
+        self.remove_extra_replicas(1)
+    }
+
+    /// Replicated deployment: one container per replica, load-balanced by Traefik.
+    fn start_app_replicas(&self) -> Result<(), AppError> {
+        let containers = self.replica_container_names();
+        for container in &containers {
+            self.start_web_container(container)?;
+        }
+        self.remove_extra_replicas(containers.len())
+    }
+
+    /// Reconciles running replicas with the configured count, starting from
+    /// `current` replicas, without restarting the ones that stay.
+    ///
+    /// New replicas are health-checked before Traefik sends them traffic;
+    /// removed ones are taken out of the load balancer before they stop.
+    pub fn scale(&self, current: u32) -> Result<(), AppError> {
+        if self.is_blue_green_enabled() && self.config.effective_replicas() > 1 {
+            return Err(AppError::Config(
+                "Replicas can't be combined with blue-green deployments".into(),
+            ));
+        }
+
+        let containers = self.replica_container_names();
+        let target = containers.len();
+        let current = current.max(1) as usize;
+
+        if target > current {
+            for container in &containers[current..] {
+                ui::info(&format!("  Starting replica {}", container));
+                self.start_web_container(container)?;
+            }
+            self.wait_start_period();
+            for container in &containers[current..] {
+                ui::info(&format!("  Running health check on {}...", container));
+                self.wait_until_healthy(container)?;
+            }
+            self.configure_routing()?;
+        } else {
+            self.configure_routing()?;
+            // Give Traefik time to reload before the removed replicas stop
+            if !self.ctx.is_dry_run() {
+                std::thread::sleep(Duration::from_secs(2));
+            }
+            self.remove_extra_replicas(target)?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes replica containers left over from a higher replica count.
+    fn remove_extra_replicas(&self, keep: usize) -> Result<(), AppError> {
+        let output = self.ctx.run_command(
+            self.runtime.command(),
+            &[
+                "ps", "-a",
+                "--filter", &format!("label=flaase.app={}", self.config.name),
+                "--filter", "label=flaase.service=web",
+                "--format", "{{.Names}}",
+            ],
+        )?;
+
+        let prefix = format!("{}-", self.web_container_name());
+        for container in output.stdout.lines() {
+            let index = container
+                .strip_prefix(&prefix)
+                .and_then(|i| i.parse::<usize>().ok());
+            if matches!(index, Some(i) if i > keep) {
+                self.runtime.stop_container(container, self.ctx).ok();
+                self.runtime.remove_container(container, self.ctx)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Starts (or recreates) a web container under the given name.
+    fn start_web_container(&self, container_name: &str) -> Result<(), AppError> {
         let port = self.config.effective_port();
 
         // Check if already running - stop it first
-        if self.runtime.container_exists(&container_name, self.ctx)? {
-            self.runtime.stop_container(&container_name, self.ctx).ok();
-            self.runtime.remove_container(&container_name, self.ctx)?;
+        if self.runtime.container_exists(container_name, self.ctx)? {
+            self.runtime.stop_container(container_name, self.ctx).ok();
+            self.runtime.remove_container(container_name, self.ctx)?;
         }
 
         // Find available host port
         let host_port = self.runtime.find_available_port(port, self.ctx)?;
 
-        let mut container = ContainerConfig::new(&container_name, &self.app_image())
+        let mut container = ContainerConfig::new(container_name, &self.app_image())
             .port(host_port, port)
             .network(&self.network_name())
             .restart(RestartPolicy::UnlessStopped)
@@ -1044,19 +2024,17 @@ impl<'a> Deployer<'a> {
             .label("flaase.service", "web");
 
         // Add environment files
-        let env_path = self.config.env_path();
-        let auto_env_path = self.config.auto_env_path();
-
-        if auto_env_path.exists() {
-            container = container.env_file(auto_env_path.to_str().unwrap());
+        for env_file in self.app_env_files() {
+            container = container.env_file(&env_file);
         }
-        if env_path.exists() {
-            container = container.env_file(env_path.to_str().unwrap());
+        let decrypted = self.decrypted_env_file()?;
+        if let Some(file) = &decrypted {
+            container = container.env_file(&file.path.to_string_lossy());
         }
 
-        // Set NODE_ENV for JS stacks (only for source deployments)
-        if self.config.is_source_deployment() {
-            container = container.env("NODE_ENV", "production");
+        // Set NODE_ENV for JS stacks unless the app sets its own
+        if let Some(node_env) = self.injected_node_env() {
+            container = container.env("NODE_ENV", node_env);
         }
 
         // Add volume mounts for image deployments
@@ -1068,14 +2046,135 @@ impl<'a> Deployer<'a> {
             }
         }
 
+        container = self.apply_resources(container);
+        container = self.mount_secrets_file(container)?;
+
         self.runtime.run_container(&container, self.ctx)?;
+        // The container keeps its env; the plain-text copy can go
+        drop(decrypted);
 
         // Connect to Traefik network for routing
-        self.runtime.connect_network(&container_name, "flaase-network", self.ctx)?;
+        self.runtime.connect_network(container_name, "flaase-network", self.ctx)?;
 
         Ok(())
     }
 
+    /// Renders the configured secrets file from the app's generated secrets.
+    /// Returns the host path and the container path to mount it at.
+    fn write_secrets_file(&self) -> Result<Option<(String, String)>, AppError> {
+        let Some(ref secrets_file) = self.config.secrets_file else {
+            return Ok(None);
+        };
+
+        let host_path = self.config.secrets_file_path();
+
+        if self.ctx.is_dry_run() {
+            ui::info(&format!("[DRY-RUN] Write secrets file {}", host_path.display()));
+        } else {
+            let secrets = SecretsManager::load_secrets(&self.config.secrets_path())?;
+            let vars: HashMap<String, String> = self
+                .generated_env_vars(&secrets)
+                .into_iter()
+                .filter(|(key, _)| secrets_file.includes(key))
+                .collect();
+            write_mounted_secrets_file(&host_path, &vars)?;
+
+            // Earlier versions rendered it straight into the app directory
+            let _ = std::fs::remove_file(self.config.app_dir().join("secrets.env"));
+        }
+
+        Ok(Some((
+            host_path.to_string_lossy().to_string(),
+            secrets_file.path.clone(),
+        )))
+    }
+
+    /// Mounts the rendered secrets file read-only into an app container.
+    fn mount_secrets_file(&self, container: ContainerConfig) -> Result<ContainerConfig, AppError> {
+        Ok(match self.write_secrets_file()? {
+            Some((host_path, container_path)) => {
+                container.volume_readonly(&host_path, &container_path)
+            }
+            None => container,
+        })
+    }
+
+    /// Connection variables generated from the app's secrets.
+    fn generated_env_vars(&self, secrets: &AppSecrets) -> HashMap<String, String> {
+        SecretsManager::generate_env_vars(
+            secrets,
+            self.config.database.as_ref(),
+            &self.config.caches,
+            &self.config.name,
+        )
+    }
+
+    /// Applies the app's configured resource reservations and limits to a container.
+    fn apply_resources(&self, mut container: ContainerConfig) -> ContainerConfig {
+        if let Some(ref resources) = self.config.resources {
+            if let Some(shares) = resources.cpu_shares {
+                container = container.cpu_shares(shares);
+            }
+            if let Some(ref reservation) = resources.memory_reservation {
+                container = container.memory_reservation(reservation);
+            }
+        }
+        self.apply_limits(container)
+    }
+
+    /// Applies the app's hard memory and CPU limits to a container.
+    fn apply_limits(&self, mut container: ContainerConfig) -> ContainerConfig {
+        if let Some(ref resources) = self.config.resources {
+            if let Some(ref memory) = resources.memory {
+                container = container.memory_limit(memory);
+            }
+            if let Some(cpus) = resources.cpus {
+                container = container.cpu_limit(cpus);
+            }
+        }
+        container
+    }
+
+    /// Applies the database and cache hard limits to a service container.
+    fn apply_service_limits(&self, mut container: ContainerConfig) -> ContainerConfig {
+        if let Some(ref resources) = self.config.resources {
+            if let Some(ref memory) = resources.service_memory {
+                container = container.memory_limit(memory);
+            }
+            if let Some(cpus) = resources.service_cpus {
+                container = container.cpu_limit(cpus);
+            }
+        }
+        container
+    }
+
+    /// Checks whether a running service container has the configured hard
+    /// limits, so changing them recreates the container.
+    fn limits_match(&self, container: &str) -> bool {
+        let inspected = self
+            .ctx
+            .run_command(
+                self.runtime.command(),
+                &[
+                    "inspect",
+                    "--format",
+                    "{{.HostConfig.Memory}} {{.HostConfig.NanoCpus}}",
+                    container,
+                ],
+            )
+            .ok()
+            .filter(|o| o.success)
+            .map(|o| o.stdout)
+            .unwrap_or_default();
+
+        let resources = self.config.resources.as_ref();
+        host_limits_match(
+            &inspected,
+            resources.and_then(|r| r.service_memory.as_deref()),
+            resources.and_then(|r| r.service_cpus),
+        )
+    }
+
     /// Blue-green deployment (zero-downtime).
     fn start_app_blue_green(&self) -> Result<(), AppError> {
         let port = self.config.effective_port();
@@ -1111,19 +2210,17 @@ impl<'a> Deployer<'a> {
             .label("flaase.slot", slot);
 
         // Add environment files
-        let env_path = self.config.env_path();
-        let auto_env_path = self.config.auto_env_path();
-
-        if auto_env_path.exists() {
-            container = container.env_file(auto_env_path.to_str().unwrap());
+        for env_file in self.app_env_files() {
+            container = container.env_file(&env_file);
         }
-        if env_path.exists() {
-            container = container.env_file(env_path.to_str().unwrap());
+        let decrypted = self.decrypted_env_file()?;
+        if let Some(file) = &decrypted {
+            container = container.env_file(&file.path.to_string_lossy());
         }
 
-        // Set NODE_ENV for JS stacks (only for source deployments)
-        if self.config.is_source_deployment() {
-            container = container.env("NODE_ENV", "production");
+        // Set NODE_ENV for JS stacks unless the app sets its own
+        if let Some(node_env) = self.injected_node_env() {
+            container = container.env("NODE_ENV", node_env);
         }
 
         // Add volume mounts for image deployments
@@ -1135,9 +2232,14 @@ impl<'a> Deployer<'a> {
             }
         }
 
+        container = self.apply_resources(container);
+        container = self.mount_secrets_file(container)?;
+
         // Start new container
         ui::info(&format!("  Starting new container: {}", new_container));
         self.runtime.run_container(&container, self.ctx)?;
+        // The container keeps its env; the plain-text copy can go
+        drop(decrypted);
 
         // Connect to Traefik network for routing
         self.runtime.connect_network(&new_container, "flaase-network", self.ctx)?;
@@ -1161,17 +2263,24 @@ impl<'a> Deployer<'a> {
                 ui::info(&format!("  Stopping old container: {}", old));
                 self.runtime.stop_container(&old, self.ctx).ok();
                 self.runtime.remove_container(&old, self.ctx).ok();
-            } else if auto_cleanup {
-                // Schedule cleanup in background
-                ui::info(&format!(
-                    "  Old container {} will be stopped in {}s (instant rollback available)",
-                    old, keep_seconds
-                ));
-                self.schedule_container_cleanup(&old, keep_seconds);
             } else {
+                if auto_cleanup {
+                    // Schedule cleanup in background
+                    ui::info(&format!(
+                        "  Old container {} will be stopped in {}s (instant rollback available)",
+                        old, keep_seconds
+                    ));
+                    self.schedule_container_cleanup(&old, keep_seconds);
+                } else {
+                    ui::info(&format!(
+                        "  Old container {} kept running (manual cleanup required)",
+                        old
+                    ));
+                }
                 ui::info(&format!(
-                    "  Old container {} kept running (manual cleanup required)",
-                    old
+                    "  Promote now with 'fl autodeploy blue-green {0} --promote', \
+                     or switch back with --abort",
+                    self.config.name
                 ));
             }
         }
@@ -1180,153 +2289,283 @@ impl<'a> Deployer<'a> {
         Ok(())
     }
 
-    /// Performs health check on a specific container.
-    fn health_check_container(&self, container_name: &str) -> Result<(), AppError> {
-        if self.ctx.is_dry_run() {
-            return Ok(());
+    /// Stops the previous blue-green slot now instead of waiting for the
+    /// scheduled cleanup. Returns the removed container, if one was running.
+    pub fn promote_blue_green(&self) -> Result<Option<String>, AppError> {
+        if !self.is_blue_green_enabled() {
+            return Err(AppError::Validation(format!(
+                "Blue-green deployment is not enabled for '{}'",
+                self.config.name
+            )));
         }
 
-        let health_config = self.config.effective_health_check();
-        let port = self.config.effective_port();
+        let Some(live) = self.active_container_name()? else {
+            return Err(AppError::Deploy("No blue-green slot is running".into()));
+        };
+        let previous = self.other_slot_container(&live);
 
-        for attempt in 1..=health_config.retries {
-            // Check if container is running
-            if !self.runtime.container_is_running(container_name, self.ctx)? {
-                return Err(AppError::Deploy(format!(
-                    "Container {} stopped unexpectedly",
-                    container_name
-                )));
-            }
+        if !self.runtime.container_exists(&previous, self.ctx)? {
+            return Ok(None);
+        }
 
-            // Try HTTP health check via Traefik
-            let url = format!("http://{}:{}{}", container_name, port, health_config.endpoint);
-            let timeout = health_config.timeout.to_string();
+        self.runtime.stop_container(&previous, self.ctx).ok();
+        self.runtime.remove_container(&previous, self.ctx)?;
+        Ok(Some(previous))
+    }
 
-            let result = self.ctx.run_command(
-                "docker",
-                &[
-                    "exec", "flaase-traefik",
-                    "wget", "-q", "--spider",
-                    "--timeout", &timeout,
-                    &url,
-                ],
-            );
+    /// Switches traffic back to the previous blue-green slot and tears down
+    /// the one that was just deployed. Returns the container now live.
+    pub fn abort_blue_green(&self) -> Result<String, AppError> {
+        if !self.is_blue_green_enabled() {
+            return Err(AppError::Validation(format!(
+                "Blue-green deployment is not enabled for '{}'",
+                self.config.name
+            )));
+        }
 
-            if result.is_ok() && result.as_ref().unwrap().success {
-                return Ok(());
-            }
+        let Some(live) = self.active_container_name()? else {
+            return Err(AppError::Deploy("No blue-green slot is running".into()));
+        };
+        let previous = self.other_slot_container(&live);
 
-            // Fallback: check inside the container itself
-            let wget_result = self.runtime.exec_in_container(
-                container_name,
-                &["wget", "-q", "--spider", &format!("http://localhost:{}{}", port, health_config.endpoint)],
-                self.ctx,
-            );
+        if !self.runtime.container_is_running(&previous, self.ctx)? {
+            return Err(AppError::Deploy(format!(
+                "Previous slot {} is no longer running; use 'fl rollback {}' instead",
+                previous, self.config.name
+            )));
+        }
 
-            if wget_result.is_ok() {
-                return Ok(());
-            }
+        // Already running, so no start period to wait out
+        ui::info(&format!("  Running health check on {}...", previous));
+        self.wait_until_healthy(&previous)?;
 
-            if attempt < health_config.retries {
-                std::thread::sleep(Duration::from_secs(health_config.interval as u64));
-            }
+        ui::info(&format!("  Switching traffic to {}...", previous));
+        self.configure_routing_for_container(&previous)?;
+
+        ui::info(&format!("  Removing {}", live));
+        self.runtime.stop_container(&live, self.ctx).ok();
+        self.runtime.remove_container(&live, self.ctx)?;
+
+        Ok(previous)
+    }
+
+    /// Returns the container of the slot opposite to `container`.
+    fn other_slot_container(&self, container: &str) -> String {
+        if container == self.blue_container_name() {
+            self.green_container_name()
+        } else {
+            self.blue_container_name()
+        }
+    }
+
+    /// Performs health check on a freshly started container.
+    fn health_check_container(&self, container_name: &str) -> Result<(), AppError> {
+        self.wait_start_period();
+        self.wait_until_healthy(container_name)
+    }
+
+    /// Sleeps for the health check start period so slow-booting apps aren't
+    /// probed before they can answer. Returns the time waited.
+    fn wait_start_period(&self) -> Duration {
+        let start_period = Duration::from_secs(self.config.effective_health_check().start_period);
+        if self.ctx.is_dry_run() || start_period.is_zero() {
+            return Duration::ZERO;
+        }
+
+        ui::info(&format!("  Waiting {}s for the app to start...", start_period.as_secs()));
+        std::thread::sleep(start_period);
+        start_period
+    }
+
+    /// Probes a container until it is healthy or the retry budget runs out.
+    fn wait_until_healthy(&self, container_name: &str) -> Result<(), AppError> {
+        if self.ctx.is_dry_run() {
+            return Ok(());
         }
 
+        let health_config = self.config.effective_health_check();
+        health_config.validate()?;
+
+        let Some(attempts) = self.probe_until_healthy(container_name, &health_config, || {
+            self.probe_container(container_name, &health_config)
+        })?
+        else {
+            return Ok(());
+        };
+
         // Get container logs for debugging
-        let logs = self.runtime.get_logs(container_name, 30, self.ctx)?;
+        let log_lines = health_config.failure_log_lines.unwrap_or(30);
+        let logs = self.runtime.get_logs(container_name, log_lines, self.ctx)?;
 
         Err(AppError::Deploy(format!(
             "Health check failed for {} after {} attempts.\n\nRecent logs:\n{}",
-            container_name, health_config.retries, logs
+            container_name, attempts, logs
         )))
     }
 
-    /// Configures Traefik routing to point to a specific container.
-    fn configure_routing_for_container(&self, container_name: &str) -> Result<(), AppError> {
-        use crate::core::secrets::SecretsManager;
-        use crate::templates::traefik::{generate_app_config_with_service, AppDomain};
-
-        let port = self.config.effective_port();
-
-        // Load secrets for auth info
-        let secrets = SecretsManager::load_secrets(&self.config.secrets_path()).ok();
-
-        // Build domain list with auth info
-        let mut domains = Vec::new();
-        for domain_config in &self.config.domains {
-            let mut app_domain = AppDomain::new(&domain_config.domain, domain_config.primary);
+    /// Runs `probe` until it passes or the health check's retry budget is
+    /// spent. Returns `None` once healthy, or the number of failed probes.
+    fn probe_until_healthy(
+        &self,
+        container_name: &str,
+        health_config: &HealthCheckConfig,
+        probe: impl Fn() -> bool,
+    ) -> Result<Option<u32>, AppError> {
+        let started = Instant::now();
+        let mut attempts = 0;
+
+        loop {
+            if !self.runtime.container_is_running(container_name, self.ctx)? {
+                return Err(AppError::Deploy(format!(
+                    "Container {} stopped unexpectedly",
+                    container_name
+                )));
+            }
 
-            // Add auth if configured
-            if let Some(ref secrets) = secrets {
-                if let Some(auth_secret) = secrets.auth.get(&domain_config.domain) {
-                    app_domain = app_domain.with_auth(&auth_secret.password_hash);
-                }
+            if probe() {
+                return Ok(None);
             }
+            attempts += 1;
 
-            domains.push(app_domain);
+            if !health_config.should_retry(attempts, started.elapsed()) {
+                return Ok(Some(attempts));
+            }
+            std::thread::sleep(Duration::from_secs(health_config.probe_delay()));
         }
+    }
 
-        // Generate and write Traefik config pointing to specific container
-        let traefik_config = generate_app_config_with_service(
-            &self.config.name,
-            &domains,
-            port,
-            container_name,
-        );
+    /// Configures Traefik routing to point to a specific container.
+    fn configure_routing_for_container(&self, container_name: &str) -> Result<(), AppError> {
+        let traefik_config = self.slot_routing_config(container_name);
         let traefik_path = format!(
             "{}/{}.yml",
             crate::core::FLAASE_TRAEFIK_DYNAMIC_PATH,
             self.config.name
         );
 
-        self.ctx.write_file(&traefik_path, &traefik_config)
+        self.ctx.write_file(&traefik_path, &traefik_config)?;
+
+        // Remember the live slot: both run while the old one awaits cleanup
+        let slot = if container_name == self.green_container_name() {
+            "green"
+        } else {
+            "blue"
+        };
+        self.ctx.write_file(&self.config.active_slot_path().to_string_lossy(), slot)
+    }
+
+    /// Traefik configuration sending all traffic to one slot container.
+    fn slot_routing_config(&self, container_name: &str) -> String {
+        use crate::templates::traefik::generate_app_config_with_service;
+
+        let port = self.config.effective_port();
+        let domains = self.routed_domains();
+
+        generate_app_config_with_service(&self.config.name, &domains, port, container_name)
     }
 
     /// Schedules container cleanup in background.
+    ///
+    /// The container is removed by ID, and only if its slot isn't live by
+    /// then: `--abort` may have switched traffic back to it, or a later
+    /// deploy may have recreated a container under the same name.
     fn schedule_container_cleanup(&self, container_name: &str, delay_seconds: u64) {
-        let container = container_name.to_string();
+        if self.ctx.is_dry_run() {
+            return;
+        }
+
+        let cli = self.runtime.command();
+        let Some(container_id) = self
+            .ctx
+            .run_command(cli, &["inspect", "--format", "{{.Id}}", container_name])
+            .ok()
+            .filter(|output| output.success)
+            .map(|output| output.stdout.trim().to_string())
+        else {
+            return;
+        };
+        let slot = if container_name == self.green_container_name() {
+            "green"
+        } else {
+            "blue"
+        };
+        let active_slot_path = self.config.active_slot_path();
 
         // Spawn a background thread to cleanup after delay
         std::thread::spawn(move || {
             std::thread::sleep(Duration::from_secs(delay_seconds));
 
-            // Use docker directly since we don't have access to runtime here
-            let _ = std::process::Command::new("docker")
-                .args(["stop", &container])
+            if read_slot(&active_slot_path) == Some(slot) {
+                return;
+            }
+
+            // Use the CLI directly since the runtime can't move into the thread
+            let _ = std::process::Command::new(cli)
+                .args(["stop", &container_id])
                 .output();
-            let _ = std::process::Command::new("docker")
-                .args(["rm", &container])
+            let _ = std::process::Command::new(cli)
+                .args(["rm", &container_id])
                 .output();
         });
     }
 
-    /// Configures Traefik routing for all domains.
-    fn configure_routing(&self) -> Result<(), AppError> {
-        use crate::core::secrets::SecretsManager;
-        use crate::templates::traefik::{generate_app_config, AppDomain};
-
-        let port = self.config.effective_port();
+    /// Rewrites the app's Traefik configuration from its current state,
+    /// restoring it if it was edited or deleted by hand.
+    pub fn sync_routing(&self) -> Result<(), AppError> {
+        match self.routing_target()? {
+            RoutingTarget::Maintenance => {
+                self.proxy.write_maintenance_config(&self.config.name, self.ctx)
+            }
+            RoutingTarget::Replicas => self.configure_routing(),
+            RoutingTarget::Slot(container) => self.configure_routing_for_container(&container),
+        }
+    }
 
-        // Load secrets for auth info
-        let secrets = SecretsManager::load_secrets(&self.config.secrets_path()).ok();
+    /// Returns the Traefik configuration `sync_routing` would write.
+    pub fn routing_config(&self) -> Result<String, AppError> {
+        use crate::templates::traefik::generate_maintenance_config;
 
-        // Build domain list with auth info
-        let mut domains = Vec::new();
-        for domain_config in &self.config.domains {
-            let mut app_domain = AppDomain::new(&domain_config.domain, domain_config.primary);
+        Ok(match self.routing_target()? {
+            RoutingTarget::Maintenance => generate_maintenance_config(&self.config.name),
+            RoutingTarget::Replicas => self.replicas_routing_config(),
+            RoutingTarget::Slot(container) => self.slot_routing_config(&container),
+        })
+    }
 
-            // Add auth if configured
-            if let Some(ref secrets) = secrets {
-                if let Some(auth_secret) = secrets.auth.get(&domain_config.domain) {
-                    app_domain = app_domain.with_auth(&auth_secret.password_hash);
-                }
-            }
+    /// Works out where the app's traffic should go right now.
+    fn routing_target(&self) -> Result<RoutingTarget, AppError> {
+        if !self.is_blue_green_enabled() {
+            let running = self
+                .runtime
+                .container_is_running(&self.web_container_name(), self.ctx)
+                .unwrap_or(false);
+            return Ok(if running { RoutingTarget::Replicas } else { RoutingTarget::Maintenance });
+        }
+
+        // Keep routing to the slot traffic was last switched to while it runs
+        let routed = self
+            .routed_slot()
+            .and_then(|slot| self.web_slot_container(slot).ok())
+            .filter(|container| {
+                self.runtime
+                    .container_is_running(container, self.ctx)
+                    .unwrap_or(false)
+            });
+
+        Ok(match routed.or(self.active_container_name()?) {
+            Some(container) => RoutingTarget::Slot(container),
+            None => RoutingTarget::Maintenance,
+        })
+    }
 
-            domains.push(app_domain);
-        }
+    /// Reads the blue-green slot traffic was last switched to.
+    fn routed_slot(&self) -> Option<&'static str> {
+        read_slot(&self.config.active_slot_path())
+    }
 
-        // Generate and write Traefik config
-        let traefik_config = generate_app_config(&self.config.name, &domains, port);
+    /// Configures Traefik routing for all domains.
+    fn configure_routing(&self) -> Result<(), AppError> {
+        let traefik_config = self.replicas_routing_config();
         let traefik_path = format!(
             "{}/{}.yml",
             crate::core::FLAASE_TRAEFIK_DYNAMIC_PATH,
@@ -1336,78 +2575,107 @@ impl<'a> Deployer<'a> {
         self.ctx.write_file(&traefik_path, &traefik_config)
     }
 
+    /// Traefik configuration balancing traffic over the web container and
+    /// its replicas.
+    fn replicas_routing_config(&self) -> String {
+        use crate::templates::traefik::{generate_app_config, generate_app_config_with_replicas};
+
+        let port = self.config.effective_port();
+        let domains = self.routed_domains();
+
+        if self.config.effective_replicas() > 1 {
+            generate_app_config_with_replicas(
+                &self.config.name,
+                &domains,
+                port,
+                &self.replica_container_names(),
+            )
+        } else {
+            generate_app_config(&self.config.name, &domains, port)
+        }
+    }
+
+    /// The app's domains as routed by Traefik, with their Basic Auth
+    /// middleware and imported certificates.
+    fn routed_domains(&self) -> Vec<crate::templates::traefik::AppDomain> {
+        use crate::core::secrets::SecretsManager;
+        use crate::templates::traefik::AppDomain;
+
+        // Load secrets for auth info
+        let secrets = SecretsManager::load_secrets(&self.config.secrets_path()).ok();
+
+        let domains = self
+            .config
+            .domains
+            .iter()
+            .map(|domain_config| {
+                let app_domain = AppDomain::new(&domain_config.domain, domain_config.primary)
+                    .with_path_prefix(domain_config.path_prefix.as_deref());
+
+                // Add auth if configured
+                match secrets.as_ref().and_then(|s| s.auth.get(&domain_config.domain)) {
+                    Some(auth_secret) => app_domain.with_auth(&auth_secret.password_hash),
+                    None => app_domain,
+                }
+            })
+            .collect();
+
+        crate::core::certs::apply_imported_certificates(domains)
+    }
+
     /// Performs health check on the app.
     fn health_check(&self) -> Result<(), AppError> {
         if self.ctx.is_dry_run() {
             return Ok(());
         }
 
-        let health_config = self.config.effective_health_check();
-        let container_name = self.web_container_name();
+        self.wait_start_period();
 
-        for attempt in 1..=health_config.retries {
-            // Check if container is running
-            if !self.runtime.container_is_running(&container_name, self.ctx)? {
-                return Err(AppError::Deploy("Container stopped unexpectedly".into()));
+        // Every replica must be healthy before it receives traffic
+        if self.config.effective_replicas() > 1 {
+            for container in self.replica_container_names() {
+                self.wait_until_healthy(&container)?;
             }
+            return Ok(());
+        }
 
-            // Try HTTP health check
-            if self.check_http_health(&health_config) {
-                return Ok(());
-            }
+        let health_config = self.config.effective_health_check();
+        health_config.validate()?;
+        let container_name = self.web_container_name();
 
-            if attempt < health_config.retries {
-                std::thread::sleep(Duration::from_secs(health_config.interval as u64));
-            }
-        }
+        let Some(attempts) = self.probe_until_healthy(&container_name, &health_config, || {
+            self.check_health(&health_config)
+        })?
+        else {
+            return Ok(());
+        };
 
         // Get container logs for debugging
-        let logs = self.runtime.get_logs(&container_name, 50, self.ctx)?;
+        let log_lines = health_config.failure_log_lines.unwrap_or(50);
+        let logs = self.runtime.get_logs(&container_name, log_lines, self.ctx)?;
 
         Err(AppError::Deploy(format!(
             "Health check failed after {} attempts.\n\nRecent logs:\n{}",
-            health_config.retries, logs
+            attempts, logs
         )))
     }
 
-    /// Checks HTTP health of the app.
-    fn check_http_health(&self, config: &HealthCheckConfig) -> bool {
+    /// Checks health of the app's web container.
+    fn check_health(&self, config: &HealthCheckConfig) -> bool {
         let container_name = self.web_container_name();
-        let port = self.config.effective_port();
-        let endpoint = &config.endpoint;
 
         // First check if container is running
         if !self.runtime.container_is_running(&container_name, self.ctx).unwrap_or(false) {
             return false;
         }
 
-        // Try health check via Traefik container (which is on the same network)
-        let url = format!("http://{}:{}{}", container_name, port, endpoint);
-        let timeout = config.timeout.to_string();
-
-        let result = self.ctx.run_command(
-            "docker",
-            &[
-                "exec", "flaase-traefik",
-                "wget", "-q", "--spider",
-                "--timeout", &timeout,
-                &url,
-            ],
-        );
-
-        if result.is_ok() && result.as_ref().unwrap().success {
+        if self.probe_container(&container_name, config) {
             return true;
         }
 
-        // Fallback: check inside the app container itself
-        let wget_result = self.runtime.exec_in_container(
-            &container_name,
-            &["wget", "-q", "--spider", &format!("http://localhost:{}{}", port, endpoint)],
-            self.ctx,
-        );
-
-        if wget_result.is_ok() {
-            return true;
+        // TCP and command checks are explicit opt-ins and must really pass
+        if config.check_type != HealthCheckType::Http {
+            return false;
         }
 
         // Last resort: just check if container is still running after startup
@@ -1415,23 +2683,78 @@ impl<'a> Deployer<'a> {
         self.runtime.container_is_running(&container_name, self.ctx).unwrap_or(false)
     }
 
+    /// Probes a container once with the configured check type.
+    /// HTTP and TCP probes go through the Traefik container (on the same
+    /// network) first, then fall back to a probe inside the container itself.
+    fn probe_container(&self, container_name: &str, config: &HealthCheckConfig) -> bool {
+        let port = self.config.effective_port().to_string();
+        let timeout = config.timeout.to_string();
+
+        let via_proxy = |probe: &[&str]| {
+            let mut args = vec!["exec", "flaase-traefik"];
+            args.extend(probe);
+            self.ctx
+                .run_command(self.runtime.command(), &args)
+                .is_ok_and(|output| output.success)
+        };
+        let in_container = |probe: &[&str]| {
+            self.runtime
+                .exec_in_container(container_name, probe, self.ctx)
+                .is_ok()
+        };
+
+        match config.check_type {
+            HealthCheckType::Http => {
+                let url = format!("http://{}:{}{}", container_name, port, config.endpoint);
+                let local_url = format!("http://localhost:{}{}", port, config.endpoint);
+                via_proxy(&["wget", "-q", "--spider", "--timeout", &timeout, &url])
+                    || in_container(&["wget", "-q", "--spider", &local_url])
+            }
+            HealthCheckType::Tcp => {
+                via_proxy(&["nc", "-z", "-w", &timeout, container_name, &port])
+                    || in_container(&["nc", "-z", "localhost", &port])
+            }
+            HealthCheckType::Command => match &config.command {
+                Some(command) => in_container(&["sh", "-c", command]),
+                None => false,
+            },
+        }
+    }
+
+    /// Installs cron timers missing from the server, e.g. for jobs added to
+    /// the config by hand or by `fl init --from-file`. Only warns on failure.
+    fn sync_cron_timers(&self) {
+        if self.ctx.is_dry_run() || self.config.cron_jobs.is_empty() {
+            return;
+        }
+
+        match CronScheduler::new(self.config).reconcile() {
+            Ok(installed) if !installed.is_empty() => {
+                ui::info(&format!("Scheduled cron jobs: {}", installed.join(", ")));
+            }
+            Ok(_) => {}
+            Err(e) => ui::warning(&format!("Failed to schedule cron jobs: {}", e)),
+        }
+    }
+
     /// Updates the deployed_at timestamp in the config.
     fn update_deployed_at(&self) -> Result<(), AppError> {
         if self.ctx.is_dry_run() {
             return Ok(());
         }
 
-        let mut config = self.config.clone();
+        // Reload so settings recorded during the deploy (pinned digests) are kept
+        let mut config = AppConfig::load(&self.config.name).unwrap_or_else(|_| self.config.clone());
         config.deployed_at = Some(Utc::now());
         config.save()
     }
 
     /// Stops the web container (database and cache stay running).
     pub fn stop(&self) -> Result<(), AppError> {
-        let container = self.web_container_name();
-
-        if self.runtime.container_is_running(&container, self.ctx)? {
-            self.runtime.stop_container(&container, self.ctx)?;
+        for container in self.replica_container_names() {
+            if self.runtime.container_is_running(&container, self.ctx)? {
+                self.runtime.stop_container(&container, self.ctx)?;
+            }
         }
 
         // Update Traefik to show 503 maintenance page
@@ -1442,31 +2765,24 @@ impl<'a> Deployer<'a> {
 
     /// Starts the web container and runs health check.
     pub fn start(&self) -> Result<(), AppError> {
-        // Ensure database is running if configured
-        if self.config.database.is_some() {
-            let db_container = self.db_container_name();
-            if !self.runtime.container_is_running(&db_container, self.ctx)? {
-                self.start_database()?;
-            }
-        }
+        let _activity = self.begin_activity()?;
 
-        // Ensure cache is running if configured
-        if self.config.cache.is_some() {
-            let cache_container = self.cache_container_name();
-            if !self.runtime.container_is_running(&cache_container, self.ctx)? {
-                self.start_cache()?;
-            }
-        }
+        // Ensure database and caches are running if configured
+        let start_db = self.config.managed_database().is_some()
+            && !self
+                .runtime
+                .container_is_running(&self.db_container_name(), self.ctx)?;
+        self.start_dependencies(start_db, self.has_stopped_cache())?;
 
         // Start app container
         self.start_app()?;
 
-        // Restore normal Traefik routing (remove maintenance page)
-        self.configure_routing()?;
-
         // Run health check
         self.health_check()?;
 
+        // Restore normal Traefik routing (remove maintenance page)
+        self.configure_routing()?;
+
         Ok(())
     }
 
@@ -1488,49 +2804,59 @@ impl<'a> Deployer<'a> {
         self.image_exists(&self.previous_image_tag()).unwrap_or(false)
     }
 
-    /// Rolls back to the previous deployment.
-    pub fn rollback(&self, target_sha: Option<&str>) -> Result<(), AppError> {
-        let target_tag = match target_sha {
-            Some(sha) => self.versioned_image_tag(sha),
-            None => self.previous_image_tag(),
-        };
-
-        if !self.image_exists(&target_tag)? {
-            return Err(AppError::RollbackFailed(
-                "No previous version available for rollback".into()
-            ));
-        }
-
-        ui::info(&format!("Rolling back to image: {}", target_tag));
-
-        // Tag rollback target as latest
-        let latest_tag = self.current_image_tag();
-        self.tag_image(&target_tag, &latest_tag)?;
+    /// Rolls back to the previous deployment, or to a commit SHA or deploy tag.
+    pub fn rollback(&self, target: Option<&str>) -> Result<(), AppError> {
+        let _activity = self.begin_activity()?;
+        let target_tag = self.restore_image(target)?;
+        ui::info(&format!("Rolled back to image: {}", target_tag));
 
         // Restart app with rolled-back image
         let spinner = ui::ProgressBar::spinner("Restarting app with previous version");
         self.start_app()?;
         spinner.finish("done");
 
-        // Reconfigure routing
-        let spinner = ui::ProgressBar::spinner("Reconfiguring routing");
-        self.configure_routing()?;
-        spinner.finish("done");
-
         // Health check
         let spinner = ui::ProgressBar::spinner("Running health check");
         self.health_check()?;
         spinner.finish("done");
 
+        // Reconfigure routing
+        let spinner = ui::ProgressBar::spinner("Reconfiguring routing");
+        self.configure_routing()?;
+        spinner.finish("done");
+
         ui::success("Rollback completed successfully");
 
         Ok(())
     }
 
+    /// Retags the rollback target (deploy tag, commit SHA, or `:previous`
+    /// by default) as `:latest`. Returns the target tag.
+    fn restore_image(&self, target: Option<&str>) -> Result<String, AppError> {
+        let target_tag = match target {
+            Some(name) if self.image_exists(&self.labeled_image_tag(name))? => {
+                self.labeled_image_tag(name)
+            }
+            Some(sha) => self.versioned_image_tag(sha),
+            None => self.previous_image_tag(),
+        };
+
+        if !self.image_exists(&target_tag)? {
+            return Err(AppError::RollbackFailed(
+                "No previous version available for rollback".into()
+            ));
+        }
+
+        // Tag rollback target as latest
+        self.tag_image(&target_tag, &self.current_image_tag())?;
+
+        Ok(target_tag)
+    }
+
     /// Lists available versions for rollback.
     pub fn list_available_versions(&self) -> Result<Vec<String>, AppError> {
         let output = self.ctx.run_command(
-            "docker",
+            self.runtime.command(),
             &["images", &self.image_name(), "--format", "{{.Tag}}"]
         )?;
 
@@ -1547,78 +2873,156 @@ impl<'a> Deployer<'a> {
         Ok(versions)
     }
 
+    /// Lists everything `destroy` would remove, without changing anything.
+    pub fn destroy_plan(&self, keep_data: bool) -> Result<DestroyPlan, AppError> {
+        let mut containers = self.replica_container_names();
+        containers.push(self.blue_container_name());
+        containers.push(self.green_container_name());
+        containers.push(self.db_container_name());
+        for cache in &self.config.caches {
+            containers.push(self.cache_container_name(cache));
+        }
+        let mut existing = Vec::new();
+        for container in containers {
+            if self.runtime.container_exists(&container, self.ctx)? {
+                existing.push(container);
+            }
+        }
+
+        let mut volumes = Vec::new();
+        if !keep_data {
+            volumes.push(format!("flaase-{}-db-data", self.config.name));
+            for cache in &self.config.caches {
+                volumes.push(format!(
+                    "flaase-{}-{}-data",
+                    self.config.name,
+                    cache.container_suffix()
+                ));
+            }
+        }
+
+        let network = self.network_name();
+        let network = if self.runtime.network_exists(&network, self.ctx)? {
+            Some(network)
+        } else {
+            None
+        };
+
+        // Every tag of the app image: latest, previous-N, versioned and labeled
+        let image = self.image_name();
+        let images = self
+            .ctx
+            .run_command(
+                self.runtime.command(),
+                &["images", "--format", "{{.Repository}}:{{.Tag}}", &image],
+            )
+            .map(|output| {
+                output
+                    .stdout
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.ends_with(":<none>"))
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let scheduler = CronScheduler::new(self.config);
+        let cron_timers = self
+            .config
+            .cron_jobs
+            .iter()
+            .filter(|job| scheduler.is_installed(&job.name))
+            .map(|job| format!("{}.timer", scheduler.unit_name(&job.name)))
+            .collect();
+
+        Ok(DestroyPlan {
+            containers: existing,
+            volumes,
+            network,
+            cron_timers,
+            proxy_config: format!(
+                "{}/{}.yml",
+                crate::core::FLAASE_TRAEFIK_DYNAMIC_PATH,
+                self.config.name
+            ),
+            images,
+            app_dir: self.config.app_dir(),
+        })
+    }
+
     /// Destroys all resources for this app.
     /// If keep_data is true, database and cache volumes are preserved.
     pub fn destroy(&self, keep_data: bool) -> Result<(), AppError> {
-        // Remove containers (they should already be stopped)
-        let containers = [
-            self.web_container_name(),
-            self.db_container_name(),
-            self.cache_container_name(),
-        ];
+        let plan = self.destroy_plan(keep_data)?;
 
-        for container in &containers {
-            if self.runtime.container_exists(container, self.ctx)? {
-                self.runtime.stop_container(container, self.ctx).ok();
-                self.runtime.remove_container(container, self.ctx)?;
-            }
+        // Stop scheduled jobs before the image they run disappears
+        if !self.ctx.is_dry_run() {
+            CronScheduler::new(self.config).uninstall_all()?;
         }
 
-        // Remove volumes if not keeping data
-        if !keep_data {
-            let volumes = [
-                format!("flaase-{}-db-data", self.config.name),
-                format!("flaase-{}-cache-data", self.config.name),
-            ];
+        // Remove containers (they should already be stopped)
+        for container in &plan.containers {
+            self.runtime.stop_container(container, self.ctx).ok();
+            self.runtime.remove_container(container, self.ctx)?;
+        }
 
-            for volume in &volumes {
-                // Use -f to ignore errors if volume doesn't exist
-                self.ctx
-                    .run_command("docker", &["volume", "rm", "-f", volume])
-                    .ok();
-            }
+        // Remove volumes if not keeping data
+        for volume in &plan.volumes {
+            // Use -f to ignore errors if volume doesn't exist
+            self.ctx
+                .run_command(self.runtime.command(), &["volume", "rm", "-f", volume])
+                .ok();
         }
 
         // Remove network
-        let network = self.network_name();
-        if self.runtime.network_exists(&network, self.ctx)? {
+        if let Some(network) = &plan.network {
             self.ctx
-                .run_command("docker", &["network", "rm", &network])
+                .run_command(self.runtime.command(), &["network", "rm", network])
                 .ok(); // Ignore errors, network might be in use
         }
 
         // Remove Traefik config
         self.proxy.remove_app_config(&self.config.name, self.ctx)?;
 
-        // Remove Docker images (current and previous)
-        let image = self.image_name();
-        self.ctx
-            .run_command("docker", &["rmi", "-f", &image])
-            .ok();
-        self.ctx
-            .run_command("docker", &["rmi", "-f", &format!("{}:latest", image)])
-            .ok();
-        self.ctx
-            .run_command("docker", &["rmi", "-f", &format!("{}:previous", image)])
-            .ok();
-
-        // Also remove any versioned tags
-        let output = self.ctx.run_command(
-            "docker",
-            &["images", "--format", "{{.Repository}}:{{.Tag}}", &image],
-        );
-        if let Ok(output) = output {
-            for line in output.stdout.lines() {
-                if !line.is_empty() {
-                    self.ctx.run_command("docker", &["rmi", "-f", line]).ok();
-                }
-            }
+        // Remove Docker images (every tag)
+        for image in &plan.images {
+            self.ctx.run_command(self.runtime.command(), &["rmi", "-f", image]).ok();
         }
 
         Ok(())
     }
 }
 
+/// Where an app's traffic is routed.
+enum RoutingTarget {
+    /// Nothing is running, so the maintenance page is served.
+    Maintenance,
+    /// The web container and its replicas.
+    Replicas,
+    /// A single blue-green slot container.
+    Slot(String),
+}
+
+/// Resources removed by `fl destroy`.
+#[derive(Debug, Clone)]
+pub struct DestroyPlan {
+    /// Existing containers (web, replicas, blue/green slots, database, caches).
+    pub containers: Vec<String>,
+    /// Data volumes (empty when data is kept).
+    pub volumes: Vec<String>,
+    /// The app network, if it exists.
+    pub network: Option<String>,
+    /// Installed systemd timers of the app's cron jobs.
+    pub cron_timers: Vec<String>,
+    /// Traefik dynamic configuration file.
+    pub proxy_config: String,
+    /// Image tags of the app.
+    pub images: Vec<String>,
+    /// App directory under /opt/flaase/apps.
+    pub app_dir: std::path::PathBuf,
+}
+
 /// Formats a duration for display.
 pub fn format_duration(duration: Duration) -> String {
     let secs = duration.as_secs();
@@ -1631,3 +3035,503 @@ pub fn format_duration(duration: Duration) -> String {
         format!("{}m {}s", mins, remaining_secs)
     }
 }
+
+/// Returns true if the app's auto-generated env file sets `key`.
+fn auto_env_defines(config: &AppConfig, key: &str) -> bool {
+    EnvManager::load_from_file(&config.auto_env_path())
+        .map(|vars| vars.iter().any(|v| v.key == key))
+        .unwrap_or(false)
+}
+
+/// Compares `<runtime> port` output (e.g. "127.0.0.1:5432") with the expected binding.
+fn published_binding_matches(published: &str, expected: Option<(&str, u16)>) -> bool {
+    let bindings: Vec<&str> = published
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+
+    match expected {
+        None => bindings.is_empty(),
+        Some((address, port)) => {
+            bindings.len() == 1 && bindings[0] == format!("{}:{}", address, port)
+        }
+    }
+}
+
+/// Compares `inspect` output ("<memory bytes> <nano cpus>", 0 = unlimited)
+/// with the configured hard limits.
+fn host_limits_match(inspected: &str, memory_limit: Option<&str>, cpus: Option<f64>) -> bool {
+    let mut fields = inspected.split_whitespace().map(str::parse::<u64>);
+    let (Some(Ok(memory)), Some(Ok(nano_cpus))) = (fields.next(), fields.next()) else {
+        return false;
+    };
+
+    let expected_memory = memory_limit
+        .and_then(|m| parse_memory_size(m).ok())
+        .unwrap_or(0);
+    let expected_nano_cpus = cpus.map(|c| (c * 1e9).round() as u64).unwrap_or(0);
+
+    memory == expected_memory && nano_cpus == expected_nano_cpus
+}
+
+/// Builds the public health check URL. Path-prefixed domains only route
+/// requests under their prefix, so the endpoint is placed under it.
+fn public_health_url(public_url: &str, endpoint: &str) -> String {
+    let endpoint = format!("/{}", endpoint.trim_start_matches('/'));
+    let (base, prefix) = match public_url.find("://").map(|i| i + 3) {
+        Some(start) => match public_url[start..].find('/') {
+            Some(slash) => public_url.split_at(start + slash),
+            None => (public_url, ""),
+        },
+        None => (public_url, ""),
+    };
+
+    // Compare whole path segments: "/api" covers "/api/health", not "/apiv2"
+    let prefix = prefix.trim_end_matches('/');
+    let under_prefix = endpoint
+        .strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'));
+
+    if prefix.is_empty() || under_prefix {
+        format!("{}{}", base, endpoint)
+    } else {
+        format!("{}{}{}", base, prefix, endpoint)
+    }
+}
+
+/// Writes a secrets file to bind-mount into a container. The app may run as
+/// any non-root user, so the file is world-readable (0644) inside a root-only
+/// directory (0700) that keeps other users of the host out.
+fn write_mounted_secrets_file(path: &Path, vars: &HashMap<String, String>) -> Result<(), AppError> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    if let Some(dir) = path.parent() {
+        if !dir.exists() {
+            std::fs::DirBuilder::new().mode(0o700).create(dir)?;
+        }
+        std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))?;
+    }
+    SecretsManager::write_env_file_with_mode(path, vars, 0o644)
+}
+
+/// Reads a blue-green slot name from a state file.
+fn read_slot(path: &Path) -> Option<&'static str> {
+    match std::fs::read_to_string(path).ok()?.trim() {
+        "blue" => Some("blue"),
+        "green" => Some("green"),
+        _ => None,
+    }
+}
+
+/// Env file holding decrypted variables, readable by root only and removed
+/// on drop.
+struct DecryptedEnvFile {
+    path: std::path::PathBuf,
+}
+
+impl DecryptedEnvFile {
+    fn write(&self, content: &str) -> Result<(), AppError> {
+        use std::io::Write;
+        use std::os::unix::fs::OpenOptionsExt;
+
+        // A file left by a crashed run with the same pid is ours to replace
+        let _ = std::fs::remove_file(&self.path);
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&self.path)?;
+        file.write_all(content.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl Drop for DecryptedEnvFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Explains a failed public check from curl's exit code and HTTP status.
+fn describe_public_check(url: &str, curl_code: i32, http_code: &str) -> Option<String> {
+    let reason = match curl_code {
+        0 => match http_code.parse::<u16>() {
+            Ok(status) if status < 500 => return None,
+            Ok(status) => format!("it returned HTTP {}", status),
+            Err(_) => "it returned no HTTP status".to_string(),
+        },
+        6 => "the domain doesn't resolve yet (check its DNS records)".to_string(),
+        7 => "nothing accepted the connection (check DNS and the firewall)".to_string(),
+        28 => "the request timed out".to_string(),
+        35 | 51 | 60 => "its SSL certificate isn't issued yet".to_string(),
+        code => format!("curl failed with exit code {}", code),
+    };
+
+    Some(format!(
+        "Public URL {} isn't reachable yet: {}. The deploy itself succeeded.",
+        url, reason
+    ))
+}
+
+/// Parses the depth of a `previous-N` rollback point tag.
+pub fn previous_depth(tag: &str) -> Option<u32> {
+    tag.strip_prefix("previous-")
+        .filter(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+        .and_then(|n| n.parse().ok())
+        .filter(|n| *n > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::app_config::{DatabaseConfig, Stack};
+    use crate::core::config::ContainerRuntimeInfo;
+    use crate::providers::package_manager::PackageManager;
+    use crate::providers::reverse_proxy::TraefikProxy;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_update_summary() {
+        let mut result = UpdateResult {
+            app_name: "myapp".into(),
+            url: "https://myapp.example.com".into(),
+            duration: Duration::from_secs(42),
+            old_commit: Some("abc1234567".into()),
+            new_commit: "def5678901".into(),
+            had_changes: true,
+        };
+        assert_eq!(result.summary(), "myapp: abc1234 → def5678, 42s, changes applied");
+
+        result.old_commit = None;
+        assert_eq!(result.summary(), "myapp: def5678, 42s, changes applied");
+
+        result.had_changes = false;
+        result.new_commit = "abc12".into();
+        assert_eq!(result.summary(), "myapp: abc12, 42s, no changes, already up to date");
+    }
+
+    /// Container CLI stand-in that keeps image tags as files next to it,
+    /// and the names of running containers in a `running` file.
+    const FAKE_CLI: &str = r#"#!/bin/sh
+store="$(dirname "$0")/images"
+key() { echo "$1" | tr ':/' '__'; }
+case "$1" in
+  image) [ "$2" = inspect ] && [ -f "$store/$(key "$3")" ] ;;
+  tag) cp "$store/$(key "$2")" "$store/$(key "$3")" ;;
+  images) ls "$store" | sed 's/_/:/' ;;
+  ps) name="${3#name=^}"; grep -qx "${name%$}" "$(dirname "$0")/running" && echo "$name" ;;
+  *) exit 1 ;;
+esac
+"#;
+
+    /// Runtime whose CLI is a script that only knows about image tags.
+    struct FakeRuntime {
+        cli: &'static str,
+    }
+
+    impl FakeRuntime {
+        fn new(dir: &Path) -> Self {
+            let cli = dir.join("fake-cli");
+            std::fs::write(&cli, FAKE_CLI).unwrap();
+            std::fs::set_permissions(&cli, std::fs::Permissions::from_mode(0o755)).unwrap();
+            std::fs::create_dir(dir.join("images")).unwrap();
+
+            let cli = cli.to_string_lossy().to_string();
+            Self { cli: Box::leak(cli.into_boxed_str()) }
+        }
+
+        fn images(&self) -> PathBuf {
+            Path::new(self.cli).with_file_name("images")
+        }
+
+        fn set_tag(&self, tag: &str, image_id: &str) {
+            std::fs::write(self.images().join(tag.replace([':', '/'], "_")), image_id).unwrap();
+        }
+
+        fn set_running(&self, container: &str) {
+            std::fs::write(Path::new(self.cli).with_file_name("running"), container).unwrap();
+        }
+
+        fn image_id(&self, tag: &str) -> String {
+            std::fs::read_to_string(self.images().join(tag.replace([':', '/'], "_"))).unwrap()
+        }
+    }
+
+    impl ContainerRuntime for FakeRuntime {
+        fn name(&self) -> &str {
+            "Fake"
+        }
+
+        fn runtime_type(&self) -> &str {
+            "fake"
+        }
+
+        fn command(&self) -> &'static str {
+            self.cli
+        }
+
+        fn is_installed(&self, _ctx: &ExecutionContext) -> Result<bool, AppError> {
+            Ok(true)
+        }
+
+        fn get_version(&self, _ctx: &ExecutionContext) -> Result<String, AppError> {
+            Ok("0".into())
+        }
+
+        fn install(
+            &self,
+            _pkg_manager: &dyn PackageManager,
+            _ctx: &ExecutionContext,
+        ) -> Result<(), AppError> {
+            Ok(())
+        }
+
+        fn start_service(&self, _ctx: &ExecutionContext) -> Result<(), AppError> {
+            Ok(())
+        }
+
+        fn enable_service(&self, _ctx: &ExecutionContext) -> Result<(), AppError> {
+            Ok(())
+        }
+
+        fn is_running(&self, _ctx: &ExecutionContext) -> Result<bool, AppError> {
+            Ok(true)
+        }
+
+        fn configure_daemon(&self, _ctx: &ExecutionContext) -> Result<bool, AppError> {
+            Ok(true)
+        }
+
+        fn get_info(&self, _ctx: &ExecutionContext) -> Result<ContainerRuntimeInfo, AppError> {
+            Ok(ContainerRuntimeInfo {
+                runtime_type: "fake".into(),
+                version: "0".into(),
+            })
+        }
+    }
+
+    fn demo_config() -> AppConfig {
+        AppConfig::new_source(
+            "demo".into(),
+            "git@github.com:acme/demo.git".into(),
+            PathBuf::from("/root/.ssh/id_ed25519"),
+            Stack::NodeJs,
+            None,
+            "demo.example.com".into(),
+            None,
+            None,
+            None,
+            false,
+        )
+    }
+
+    #[test]
+    fn test_rollback_retags_previous_as_latest() {
+        let dir = tempfile::tempdir().unwrap();
+        let runtime = FakeRuntime::new(dir.path());
+        runtime.set_tag("flaase-demo:latest", "sha256:new");
+        runtime.set_tag("flaase-demo:previous", "sha256:old");
+        runtime.set_tag("flaase-demo:abc1234", "sha256:older");
+
+        let config = demo_config();
+        let proxy = TraefikProxy::new();
+        let ctx = ExecutionContext::new(false, false);
+        let deployer = Deployer::new(&config, &runtime, &proxy, &ctx);
+
+        assert_eq!(deployer.restore_image(None).unwrap(), "flaase-demo:previous");
+        assert_eq!(runtime.image_id("flaase-demo:latest"), "sha256:old");
+
+        assert_eq!(deployer.restore_image(Some("abc1234")).unwrap(), "flaase-demo:abc1234");
+        assert_eq!(runtime.image_id("flaase-demo:latest"), "sha256:older");
+
+        assert!(matches!(
+            deployer.restore_image(Some("missing")),
+            Err(AppError::RollbackFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_destroy_plan_lists_resources_without_removing_them() {
+        let dir = tempfile::tempdir().unwrap();
+        let runtime = FakeRuntime::new(dir.path());
+        runtime.set_tag("flaase-demo:latest", "sha256:new");
+        runtime.set_tag("flaase-demo:previous", "sha256:old");
+
+        let mut config = demo_config();
+        config.database = Some(DatabaseConfig::new(DatabaseType::PostgreSQL, "demo"));
+        let proxy = TraefikProxy::new();
+        let ctx = ExecutionContext::new(false, false);
+        let deployer = Deployer::new(&config, &runtime, &proxy, &ctx);
+
+        let plan = deployer.destroy_plan(false).unwrap();
+        assert!(plan.containers.is_empty());
+        assert_eq!(plan.volumes, vec!["flaase-demo-db-data".to_string()]);
+        assert_eq!(plan.images, vec!["flaase-demo:latest", "flaase-demo:previous"]);
+        assert!(plan.cron_timers.is_empty());
+        assert!(plan.proxy_config.ends_with("/demo.yml"));
+        assert_eq!(runtime.image_id("flaase-demo:latest"), "sha256:new");
+
+        assert!(deployer.destroy_plan(true).unwrap().volumes.is_empty());
+    }
+
+    #[test]
+    fn test_routing_after_domain_add_keeps_replicas() {
+        let dir = tempfile::tempdir().unwrap();
+        let runtime = FakeRuntime::new(dir.path());
+        let proxy = TraefikProxy::new();
+        let ctx = ExecutionContext::new(false, false);
+
+        let image = crate::core::registry::parse_image_reference("ghcr.io/acme/demo:v1").unwrap();
+        let mut config = AppConfig::new_image(
+            "demo".into(),
+            image,
+            "demo.example.com".into(),
+            3000,
+            vec![],
+            None,
+            None,
+            None,
+        );
+        config.replicas = Some(3);
+        config.add_domain("shop.example.com");
+        let deployer = Deployer::new(&config, &runtime, &proxy, &ctx);
+
+        // Not running yet: the maintenance page is served
+        assert!(deployer.routing_config().unwrap().contains("demo-maintenance"));
+
+        runtime.set_running("flaase-demo-web");
+        let yaml = deployer.routing_config().unwrap();
+        assert!(yaml.contains("Host(`shop.example.com`)"));
+        assert!(yaml.contains(concat!(
+            "        servers:\n",
+            "          - url: \"http://flaase-demo-web:3000\"\n",
+            "          - url: \"http://flaase-demo-web-2:3000\"\n",
+            "          - url: \"http://flaase-demo-web-3:3000\"\n",
+        )));
+    }
+
+    #[test]
+    fn test_start_period_skipped_in_dry_run() {
+        let mut config = demo_config();
+        config.health_check = Some(HealthCheckConfig {
+            start_period: 600,
+            ..Default::default()
+        });
+        let runtime = crate::providers::create_container_runtime();
+        let proxy = TraefikProxy::new();
+        let ctx = ExecutionContext::new(true, false);
+        let deployer = Deployer::new(&config, runtime.as_ref(), &proxy, &ctx);
+
+        assert_eq!(deployer.wait_start_period(), Duration::ZERO);
+        assert!(deployer.health_check().is_ok());
+    }
+
+    #[test]
+    fn test_decrypted_env_file_is_private_and_removed() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".env.decrypted.1");
+        let file = DecryptedEnvFile { path: path.clone() };
+        file.write("API_KEY=s3cret\n").unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "API_KEY=s3cret\n");
+
+        drop(file);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_mounted_secrets_file_is_readable_by_the_container_user() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secrets").join("secrets.env");
+        let vars = HashMap::from([("DATABASE_URL".to_string(), "postgres://db".to_string())]);
+
+        write_mounted_secrets_file(&path, &vars).unwrap();
+        // Rewriting an earlier owner-only file opens it up too
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        write_mounted_secrets_file(&path, &vars).unwrap();
+
+        // The container user is neither the owner nor in the group
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o644);
+        let dir_mode = std::fs::metadata(path.parent().unwrap()).unwrap().permissions().mode();
+        assert_eq!(dir_mode & 0o777, 0o700);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "DATABASE_URL=postgres://db\n");
+    }
+
+    #[test]
+    fn test_read_slot() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("active-slot");
+        assert_eq!(read_slot(&path), None);
+
+        std::fs::write(&path, "green").unwrap();
+        assert_eq!(read_slot(&path), Some("green"));
+        std::fs::write(&path, "purple\n").unwrap();
+        assert_eq!(read_slot(&path), None);
+    }
+
+    #[test]
+    fn test_published_binding_matches() {
+        assert!(published_binding_matches("", None));
+        assert!(!published_binding_matches("0.0.0.0:5432\n[::]:5432\n", None));
+        assert!(published_binding_matches(
+            "127.0.0.1:15432\n",
+            Some(("127.0.0.1", 15432))
+        ));
+        assert!(!published_binding_matches("", Some(("127.0.0.1", 5432))));
+        assert!(!published_binding_matches(
+            "0.0.0.0:5432\n",
+            Some(("127.0.0.1", 5432))
+        ));
+    }
+
+    #[test]
+    fn test_host_limits_match() {
+        assert!(host_limits_match("0 0\n", None, None));
+        assert!(!host_limits_match("", None, None));
+
+        assert!(host_limits_match("536870912 1500000000", Some("512m"), Some(1.5)));
+        assert!(!host_limits_match("0 0", Some("512m"), Some(1.5)));
+        assert!(!host_limits_match("536870912 1000000000", Some("512m"), Some(1.5)));
+    }
+
+    #[test]
+    fn test_public_health_url() {
+        assert_eq!(
+            public_health_url("https://example.com", "/health"),
+            "https://example.com/health"
+        );
+        assert_eq!(
+            public_health_url("https://example.com/api", "health"),
+            "https://example.com/api/health"
+        );
+        assert_eq!(
+            public_health_url("https://example.com/api", "/api/health"),
+            "https://example.com/api/health"
+        );
+        assert_eq!(
+            public_health_url("https://example.com/api", "/apiv2/health"),
+            "https://example.com/api/apiv2/health"
+        );
+        assert_eq!(
+            public_health_url("https://example.com/api/", "/api"),
+            "https://example.com/api"
+        );
+    }
+
+    #[test]
+    fn test_describe_public_check() {
+        let url = "https://example.com/";
+        assert!(describe_public_check(url, 0, "200").is_none());
+        assert!(describe_public_check(url, 0, "401").is_none());
+        assert!(describe_public_check(url, 0, "502").unwrap().contains("HTTP 502"));
+        assert!(describe_public_check(url, 6, "000").unwrap().contains("doesn't resolve"));
+        assert!(describe_public_check(url, 60, "000").unwrap().contains("SSL certificate"));
+    }
+}
@@ -51,6 +51,12 @@ pub struct DeploymentRecord {
     /// If this was a rollback, the deployment ID we rolled back from.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rollback_from: Option<String>,
+    /// Human label attached with `fl deploy --tag`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Git tag whose push triggered the deployment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_tag: Option<String>,
 }
 
 impl DeploymentRecord {
@@ -119,6 +125,9 @@ pub struct PendingApproval {
     pub expires_at: DateTime<Utc>,
     /// Approval token for verification.
     pub approval_token: String,
+    /// Git tag to deploy, for tag-triggered deployments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_tag: Option<String>,
 }
 
 impl PendingApproval {
@@ -144,6 +153,7 @@ impl PendingApproval {
             requested_at: now,
             expires_at: now + Duration::minutes(timeout_minutes as i64),
             approval_token: generate_approval_token(),
+            git_tag: None,
         }
     }
 
@@ -203,11 +213,35 @@ impl DeploymentHistory {
         }
     }
 
+    /// Finds the most recent deployment of an image version, matched by
+    /// deploy tag or by commit SHA (short or long form).
+    pub fn find_version(&self, version: &str) -> Option<&DeploymentRecord> {
+        let matches = |record: &&DeploymentRecord| {
+            record.label.as_deref() == Some(version)
+                || (!record.commit_sha.is_empty()
+                    && (record.commit_sha.starts_with(version)
+                        || version.starts_with(&record.commit_sha)))
+        };
+
+        self.deployments
+            .iter()
+            .filter(matches)
+            .find(|record| record.status == DeploymentStatus::Success)
+            .or_else(|| self.deployments.iter().find(matches))
+    }
+
     /// Returns the most recent deployments (up to limit).
     pub fn recent(&self, limit: usize) -> &[DeploymentRecord] {
         let end = limit.min(self.deployments.len());
         &self.deployments[..end]
     }
+
+    /// Returns a page of deployments, newest first, skipping `offset` records.
+    pub fn page(&self, offset: usize, limit: usize) -> &[DeploymentRecord] {
+        let start = offset.min(self.deployments.len());
+        let end = start.saturating_add(limit).min(self.deployments.len());
+        &self.deployments[start..end]
+    }
 }
 
 impl DeploymentRecord {
@@ -233,6 +267,8 @@ impl DeploymentRecord {
             tests_passed: None,
             duration_seconds: None,
             rollback_from: None,
+            label: None,
+            git_tag: None,
         }
     }
 
@@ -252,6 +288,8 @@ impl DeploymentRecord {
             tests_passed: None,
             duration_seconds: None,
             rollback_from: None,
+            label: None,
+            git_tag: None,
         }
     }
 
@@ -276,6 +314,8 @@ impl DeploymentRecord {
             tests_passed: None,
             duration_seconds: None,
             rollback_from: Some(from_deployment_id.to_string()),
+            label: None,
+            git_tag: None,
         }
     }
 
@@ -285,6 +325,18 @@ impl DeploymentRecord {
         self
     }
 
+    /// Sets the Git tag that triggered the deployment.
+    pub fn with_git_tag(mut self, tag: Option<&str>) -> Self {
+        self.git_tag = tag.map(|t| t.to_string());
+        self
+    }
+
+    /// Sets the human label.
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
     /// Sets the tests result.
     pub fn with_tests_result(mut self, passed: bool) -> Self {
         self.tests_passed = Some(passed);
@@ -309,3 +361,45 @@ impl std::fmt::Display for DeploymentStatus {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_version() {
+        let mut history = DeploymentHistory::default();
+
+        let mut failed = DeploymentRecord::manual("abc1234", "Broken build", "main");
+        failed.status = DeploymentStatus::Failed;
+        let mut success = DeploymentRecord::manual("abc1234", "Fix login", "main");
+        success.status = DeploymentStatus::Success;
+        let tagged = DeploymentRecord::manual("def5678", "Release", "main").with_label("v1");
+
+        history.add(success);
+        history.add(failed);
+        history.add(tagged);
+
+        assert_eq!(history.find_version("abc1234").unwrap().commit_message, "Fix login");
+        assert_eq!(history.find_version("v1").unwrap().commit_sha, "def5678");
+        assert_eq!(history.find_version("def5678abcdef").unwrap().commit_message, "Release");
+        assert!(history.find_version("0000000").is_none());
+    }
+
+    #[test]
+    fn test_page() {
+        let mut history = DeploymentHistory::default();
+        for sha in ["aaa0001", "aaa0002", "aaa0003", "aaa0004", "aaa0005"] {
+            history.add(DeploymentRecord::manual(sha, "Deploy", "main"));
+        }
+
+        let shas = |records: &[DeploymentRecord]| {
+            records.iter().map(|r| r.commit_sha.clone()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(shas(history.page(0, 2)), ["aaa0005", "aaa0004"]);
+        assert_eq!(shas(history.page(4, 2)), ["aaa0001"]);
+        assert!(history.page(10, 2).is_empty());
+        assert_eq!(history.page(0, usize::MAX).len(), 5);
+    }
+}
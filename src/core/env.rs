@@ -26,6 +26,11 @@ pub struct EnvVar {
 }
 
 impl EnvVar {
+    /// Returns true if the value is stored encrypted (`fl env set --encrypt`).
+    pub fn is_encrypted(&self) -> bool {
+        crate::core::env_crypto::is_encrypted(&self.value)
+    }
+
     /// Returns a masked version of the value.
     pub fn masked_value(&self) -> String {
         if self.value.is_empty() {
@@ -33,7 +38,7 @@ impl EnvVar {
         }
 
         // Show non-sensitive values in plain text
-        if self.is_non_sensitive() {
+        if !self.is_encrypted() && self.is_non_sensitive() {
             return self.value.clone();
         }
 
@@ -65,6 +70,65 @@ impl EnvVar {
     }
 }
 
+/// Outcome of setting environment variables in a file.
+#[derive(Debug, Clone, Default)]
+pub struct EnvSetResult {
+    /// Keys that did not exist before.
+    pub added: Vec<String>,
+    /// Keys whose previous value was replaced.
+    pub overwritten: Vec<String>,
+    /// Keys that were already set to the same value.
+    pub unchanged: Vec<String>,
+}
+
+impl EnvSetResult {
+    /// Total number of keys written.
+    pub fn count(&self) -> usize {
+        self.added.len() + self.overwritten.len() + self.unchanged.len()
+    }
+}
+
+/// Outcome of merging concurrent edits of an env file.
+#[derive(Debug, Clone, Default)]
+pub struct EnvMerge {
+    /// Merged file content.
+    pub content: String,
+    /// Keys changed or removed on both sides differently (the edit wins).
+    pub conflicts: Vec<String>,
+}
+
+/// A user variable that shadows an auto-generated one.
+#[derive(Debug, Clone)]
+pub struct EnvOverride {
+    pub user: EnvVar,
+    pub auto: EnvVar,
+}
+
+impl EnvOverride {
+    /// Returns true if both files hold the same value.
+    pub fn same_value(&self) -> bool {
+        self.user.value == self.auto.value
+    }
+}
+
+/// Differences between two sets of environment variables.
+#[derive(Debug, Clone, Default)]
+pub struct EnvDiff {
+    /// Variables only defined on the left side.
+    pub only_left: Vec<EnvVar>,
+    /// Variables only defined on the right side.
+    pub only_right: Vec<EnvVar>,
+    /// Variables defined on both sides with different values (left, right).
+    pub changed: Vec<(EnvVar, EnvVar)>,
+}
+
+impl EnvDiff {
+    /// Returns true if both sides hold the same variables and values.
+    pub fn is_empty(&self) -> bool {
+        self.only_left.is_empty() && self.only_right.is_empty() && self.changed.is_empty()
+    }
+}
+
 /// Manager for environment variables.
 pub struct EnvManager;
 
@@ -111,6 +175,56 @@ impl EnvManager {
         Ok(vars)
     }
 
+    /// Returns the keys Flaase manages for the app: the ones it generates on
+    /// deploy and whatever its `.env.auto` currently defines.
+    pub fn managed_keys(app_dir: &Path) -> Result<Vec<String>, AppError> {
+        let mut keys: Vec<String> = Self::AUTO_KEYS.iter().map(|k| k.to_string()).collect();
+
+        let auto_path = app_dir.join(".env.auto");
+        if auto_path.exists() {
+            for key in Self::parse_env_file(&auto_path)?.into_keys() {
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+        }
+
+        Ok(keys)
+    }
+
+    /// Lists keys defined both in `user_path` and in the app's `.env.auto`.
+    ///
+    /// Containers load `.env.auto` first and the user file last, so the user
+    /// value wins.
+    pub fn auto_overrides(app_dir: &Path, user_path: &Path) -> Result<Vec<EnvOverride>, AppError> {
+        let auto_path = app_dir.join(".env.auto");
+        if !auto_path.exists() || !user_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let auto_vars = Self::parse_env_file(&auto_path)?;
+        let user_vars = Self::parse_env_file(user_path)?;
+
+        Ok(user_vars
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let auto_value = auto_vars.get(&key)?.clone();
+                Some(EnvOverride {
+                    user: EnvVar {
+                        key: key.clone(),
+                        value,
+                        source: EnvSource::User,
+                    },
+                    auto: EnvVar {
+                        key,
+                        value: auto_value,
+                        source: EnvSource::Auto,
+                    },
+                })
+            })
+            .collect())
+    }
+
     /// Loads only user-defined environment variables.
     pub fn load_user(app_dir: &Path) -> Result<BTreeMap<String, String>, AppError> {
         let user_path = app_dir.join(".env");
@@ -199,6 +313,40 @@ impl EnvManager {
         Ok((key, value))
     }
 
+    /// Resolves a value given as `@path` to the contents of that file.
+    /// `@@` escapes a literal leading `@`; other values are returned as is.
+    ///
+    /// Env files hold one variable per line, so multi-line JSON is compacted
+    /// and other multi-line content is rejected.
+    pub fn resolve_file_value(value: &str) -> Result<String, AppError> {
+        if let Some(literal) = value.strip_prefix("@@") {
+            return Ok(format!("@{}", literal));
+        }
+
+        let path = match value.strip_prefix('@') {
+            Some(path) if !path.is_empty() => path,
+            _ => return Ok(value.to_string()),
+        };
+
+        let content = fs::read_to_string(path)
+            .map_err(|e| AppError::Config(format!("Failed to read '{}': {}", path, e)))?;
+        let content = content.trim_end_matches(['\n', '\r']);
+
+        if !content.contains('\n') {
+            return Ok(content.to_string());
+        }
+
+        match serde_json::from_str::<serde_json::Value>(content) {
+            Ok(json) => serde_json::to_string(&json)
+                .map_err(|e| AppError::Config(format!("Failed to read '{}': {}", path, e))),
+            Err(_) => Err(AppError::Validation(format!(
+                "'{}' spans multiple lines, which env files can't store. \
+                 Encode it first (e.g. base64 -w0 {})",
+                path, path
+            ))),
+        }
+    }
+
     /// Validates an environment variable key.
     fn validate_key(key: &str) -> Result<(), AppError> {
         if key.is_empty() {
@@ -232,6 +380,11 @@ impl EnvManager {
         let content = fs::read_to_string(path)
             .map_err(|e| AppError::Config(format!("Failed to read env file: {}", e)))?;
 
+        Ok(Self::parse_env_content(&content))
+    }
+
+    /// Parses env file content into a map.
+    pub fn parse_env_content(content: &str) -> BTreeMap<String, String> {
         let mut vars = BTreeMap::new();
 
         for line in content.lines() {
@@ -250,7 +403,106 @@ impl EnvManager {
             }
         }
 
-        Ok(vars)
+        vars
+    }
+
+    /// Returns the SHA-256 checksum of env file content.
+    pub fn checksum(content: &str) -> String {
+        use sha2::{Digest, Sha256};
+
+        hex::encode(Sha256::digest(content.as_bytes()))
+    }
+
+    /// Three-way merges an edited env file (`ours`) with a version changed
+    /// on disk meanwhile (`theirs`), both derived from `base`.
+    /// The on-disk layout and comments are kept; the edits are applied on top.
+    /// On a conflict the edit wins, including a deletion.
+    pub fn merge(base: &str, ours: &str, theirs: &str) -> EnvMerge {
+        let base_vars = Self::parse_env_content(base);
+        let our_vars = Self::parse_env_content(ours);
+        let their_vars = Self::parse_env_content(theirs);
+
+        let mut conflicts = Vec::new();
+
+        // Keys we added or modified
+        let mut changed: BTreeMap<&str, &str> = BTreeMap::new();
+        for (key, value) in &our_vars {
+            if base_vars.get(key) == Some(value) {
+                continue;
+            }
+            if let Some(theirs) = their_vars.get(key) {
+                if theirs != value && base_vars.get(key) != Some(theirs) {
+                    conflicts.push(key.clone());
+                }
+            } else if base_vars.contains_key(key) {
+                // Modified here, removed on disk
+                conflicts.push(key.clone());
+            }
+            changed.insert(key, value);
+        }
+
+        // Keys we removed, even if they were modified on disk meanwhile
+        let mut removed = Vec::new();
+        for (key, value) in &base_vars {
+            if our_vars.contains_key(key) {
+                continue;
+            }
+            if their_vars.get(key).is_some_and(|theirs| theirs != value) {
+                conflicts.push(key.clone());
+            }
+            removed.push(key.as_str());
+        }
+
+        let mut content = String::new();
+        for line in theirs.lines() {
+            let key = line
+                .trim()
+                .split_once('=')
+                .filter(|_| !line.trim().starts_with('#'))
+                .map(|(key, _)| key.trim());
+
+            match key {
+                Some(key) if removed.contains(&key) => continue,
+                Some(key) if changed.contains_key(key) => {
+                    let value = changed.remove(key).unwrap_or_default();
+                    content.push_str(&format!("{}={}\n", key, Self::escape_value(value)));
+                }
+                _ => {
+                    content.push_str(line);
+                    content.push('\n');
+                }
+            }
+        }
+
+        // Keys missing from the on-disk file are appended
+        for (key, value) in changed {
+            content.push_str(&format!("{}={}\n", key, Self::escape_value(value)));
+        }
+
+        conflicts.sort();
+        conflicts.dedup();
+
+        EnvMerge { content, conflicts }
+    }
+
+    /// Renders variables as dotenv lines, masking sensitive values unless
+    /// `show_values` is set. Encrypted values are never decrypted.
+    pub fn to_dotenv(vars: &[EnvVar], show_values: bool) -> String {
+        vars.iter()
+            .map(|var| {
+                let value = if show_values {
+                    var.value.clone()
+                } else {
+                    var.masked_value()
+                };
+                format!("{}={}\n", var.key, Self::escape_value(&value))
+            })
+            .collect()
+    }
+
+    /// Returns true for a value masked by `EnvVar::masked_value`.
+    pub fn is_masked(value: &str) -> bool {
+        !value.is_empty() && value.chars().all(|c| c == '•')
     }
 
     /// Escapes a value for storage in an env file.
@@ -261,6 +513,10 @@ impl EnvManager {
             || value.contains('\'')
             || value.contains('$')
             || value.contains('\n')
+            || value.contains('\t')
+            || value.contains('#')
+            || value.contains('\\')
+            || value.contains('`')
         {
             // Use double quotes and escape internal quotes
             format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
@@ -285,7 +541,7 @@ impl EnvManager {
     }
 
     /// Writes content to an env file with restricted permissions.
-    fn write_env_file(path: &Path, content: &str) -> Result<(), AppError> {
+    pub fn write_env_file(path: &Path, content: &str) -> Result<(), AppError> {
         let mut file = OpenOptions::new()
             .write(true)
             .create(true)
@@ -319,6 +575,30 @@ impl EnvManager {
         (user_count, auto_count)
     }
 
+    /// Compares two sets of variables by key, sorted by key.
+    pub fn diff(left: &[EnvVar], right: &[EnvVar]) -> EnvDiff {
+        let left: BTreeMap<&str, &EnvVar> = left.iter().map(|v| (v.key.as_str(), v)).collect();
+        let right: BTreeMap<&str, &EnvVar> = right.iter().map(|v| (v.key.as_str(), v)).collect();
+
+        let mut diff = EnvDiff::default();
+        for (key, var) in &left {
+            match right.get(key) {
+                None => diff.only_left.push((*var).clone()),
+                Some(other) if other.value != var.value => {
+                    diff.changed.push(((*var).clone(), (*other).clone()))
+                }
+                Some(_) => {}
+            }
+        }
+        diff.only_right = right
+            .iter()
+            .filter(|(key, _)| !left.contains_key(*key))
+            .map(|(_, var)| (*var).clone())
+            .collect();
+
+        diff
+    }
+
     /// Loads environment variables from a specific file path.
     pub fn load_from_file(path: &Path) -> Result<Vec<EnvVar>, AppError> {
         if !path.exists() {
@@ -340,7 +620,12 @@ impl EnvManager {
     }
 
     /// Sets environment variables to a specific file path.
-    pub fn set_to_file(path: &Path, assignments: &[(String, String)]) -> Result<usize, AppError> {
+    pub fn set_to_file(
+        path: &Path,
+        assignments: &[(String, String)],
+    ) -> Result<EnvSetResult, AppError> {
+        Self::check_duplicate_keys(assignments)?;
+
         // Load existing vars from file
         let mut vars = if path.exists() {
             Self::parse_env_file(path)?
@@ -348,11 +633,15 @@ impl EnvManager {
             BTreeMap::new()
         };
 
-        let mut count = 0;
+        let mut result = EnvSetResult::default();
         for (key, value) in assignments {
             Self::validate_key(key)?;
-            vars.insert(key.clone(), value.clone());
-            count += 1;
+
+            match vars.insert(key.clone(), value.clone()) {
+                None => result.added.push(key.clone()),
+                Some(old) if old == *value => result.unchanged.push(key.clone()),
+                Some(_) => result.overwritten.push(key.clone()),
+            }
         }
 
         // Write back to file
@@ -362,7 +651,21 @@ impl EnvManager {
         }
 
         Self::write_env_file(path, &content)?;
-        Ok(count)
+        Ok(result)
+    }
+
+    /// Rejects assignment lists that set the same key more than once.
+    pub fn check_duplicate_keys(assignments: &[(String, String)]) -> Result<(), AppError> {
+        let mut seen = std::collections::HashSet::new();
+        for (key, _) in assignments {
+            if !seen.insert(key.as_str()) {
+                return Err(AppError::Validation(format!(
+                    "Key '{}' is set more than once",
+                    key
+                )));
+            }
+        }
+        Ok(())
     }
 
     /// Removes an environment variable from a specific file path.
@@ -437,6 +740,60 @@ impl EnvManager {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_auto_overrides() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join(".env.auto"),
+            "DATABASE_URL=postgres://auto\nREDIS_URL=redis://auto\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join(".env"),
+            "DATABASE_URL=postgres://external\nREDIS_URL=redis://auto\nAPI_KEY=x\n",
+        )
+        .unwrap();
+
+        let overrides = EnvManager::auto_overrides(dir.path(), &dir.path().join(".env")).unwrap();
+        let keys: Vec<&str> = overrides.iter().map(|o| o.user.key.as_str()).collect();
+        assert_eq!(keys, ["DATABASE_URL", "REDIS_URL"]);
+        assert!(!overrides[0].same_value());
+        assert!(overrides[1].same_value());
+    }
+
+    #[test]
+    fn test_managed_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(EnvManager::managed_keys(dir.path()).unwrap().contains(&"REDIS_URL".into()));
+
+        fs::write(dir.path().join(".env.auto"), "SESSIONS_REDIS_URL=redis://auto
+").unwrap();
+        let keys = EnvManager::managed_keys(dir.path()).unwrap();
+        assert!(keys.contains(&"SESSIONS_REDIS_URL".to_string()));
+        assert!(!keys.contains(&"API_KEY".to_string()));
+    }
+
+    #[test]
+    fn test_diff() {
+        let var = |key: &str, value: &str| EnvVar {
+            key: key.to_string(),
+            value: value.to_string(),
+            source: EnvSource::User,
+        };
+        let left = [var("API_URL", "https://staging"), var("DEBUG", "true"), var("TZ", "UTC")];
+        let right = [var("TZ", "UTC"), var("API_URL", "https://prod"), var("SENTRY_DSN", "x")];
+
+        let diff = EnvManager::diff(&left, &right);
+        assert_eq!(diff.only_left[0].key, "DEBUG");
+        assert_eq!(diff.only_right[0].key, "SENTRY_DSN");
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].0.value, "https://staging");
+        assert_eq!(diff.changed[0].1.value, "https://prod");
+
+        assert!(EnvManager::diff(&left, &left).is_empty());
+        assert_eq!(EnvManager::diff(&left, &[]).only_left.len(), 3);
+    }
+
     #[test]
     fn test_parse_assignment() {
         let (key, value) = EnvManager::parse_assignment("API_KEY=sk-123").unwrap();
@@ -451,6 +808,30 @@ mod tests {
         assert_eq!(value, "http://example.com?foo=bar");
     }
 
+    #[test]
+    fn test_resolve_file_value() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let token = dir.path().join("token");
+        fs::write(&token, "abc123\n").unwrap();
+        let creds = dir.path().join("creds.json");
+        fs::write(&creds, "{\n  \"type\": \"service_account\",\n  \"id\": 1\n}\n").unwrap();
+        let pem = dir.path().join("key.pem");
+        fs::write(&pem, "-----BEGIN KEY-----\nabc\n-----END KEY-----\n").unwrap();
+
+        let resolve = |path: &std::path::Path| {
+            EnvManager::resolve_file_value(&format!("@{}", path.display()))
+        };
+
+        assert_eq!(resolve(&token).unwrap(), "abc123");
+        assert_eq!(resolve(&creds).unwrap(), r#"{"id":1,"type":"service_account"}"#);
+        assert!(resolve(&pem).is_err());
+        assert!(resolve(&dir.path().join("missing")).is_err());
+
+        assert_eq!(EnvManager::resolve_file_value("plain").unwrap(), "plain");
+        assert_eq!(EnvManager::resolve_file_value("@@handle").unwrap(), "@handle");
+    }
+
     #[test]
     fn test_validate_key() {
         assert!(EnvManager::validate_key("API_KEY").is_ok());
@@ -462,6 +843,49 @@ mod tests {
         assert!(EnvManager::validate_key("").is_err());
     }
 
+    #[test]
+    fn test_parse_assignment_rejects_spaces_in_key() {
+        assert!(EnvManager::parse_assignment("bad key=1").is_err());
+    }
+
+    #[test]
+    fn test_check_duplicate_keys() {
+        let unique = vec![
+            ("PORT".to_string(), "3000".to_string()),
+            ("HOST".to_string(), "0.0.0.0".to_string()),
+        ];
+        assert!(EnvManager::check_duplicate_keys(&unique).is_ok());
+
+        let duplicated = vec![
+            ("PORT".to_string(), "3000".to_string()),
+            ("PORT".to_string(), "4000".to_string()),
+        ];
+        assert!(EnvManager::check_duplicate_keys(&duplicated).is_err());
+    }
+
+    #[test]
+    fn test_set_to_file_reports_added_and_overwritten() {
+        let dir = std::env::temp_dir().join(format!("flaase-env-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(".env");
+        let _ = fs::remove_file(&path);
+
+        let first = vec![("PORT".to_string(), "3000".to_string())];
+        let result = EnvManager::set_to_file(&path, &first).unwrap();
+        assert_eq!(result.added, vec!["PORT".to_string()]);
+
+        let second = vec![
+            ("PORT".to_string(), "4000".to_string()),
+            ("HOST".to_string(), "0.0.0.0".to_string()),
+        ];
+        let result = EnvManager::set_to_file(&path, &second).unwrap();
+        assert_eq!(result.added, vec!["HOST".to_string()]);
+        assert_eq!(result.overwritten, vec!["PORT".to_string()]);
+        assert_eq!(result.count(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_escape_unescape() {
         let original = "hello world";
@@ -472,6 +896,32 @@ mod tests {
         assert_eq!(unescaped, original);
     }
 
+    #[test]
+    fn test_to_dotenv_round_trips() {
+        let var = |key: &str, value: &str| EnvVar {
+            key: key.to_string(),
+            value: value.to_string(),
+            source: EnvSource::User,
+        };
+        let vars = vec![
+            var("GREETING", "hello world"),
+            var("NODE_ENV", "production"),
+            var("PATTERN", r#"a\b "c" #d"#),
+            var("API_KEY", "sk-123"),
+        ];
+
+        let shown = EnvManager::to_dotenv(&vars, true);
+        let parsed = EnvManager::parse_env_content(&shown);
+        for v in &vars {
+            assert_eq!(parsed[&v.key], v.value, "{}", v.key);
+        }
+
+        let masked = EnvManager::parse_env_content(&EnvManager::to_dotenv(&vars, false));
+        assert_eq!(masked["NODE_ENV"], "production");
+        assert!(EnvManager::is_masked(&masked["API_KEY"]));
+        assert!(!EnvManager::is_masked(""));
+    }
+
     #[test]
     fn test_masked_value() {
         let var = EnvVar {
@@ -487,5 +937,63 @@ mod tests {
             source: EnvSource::User,
         };
         assert_eq!(var.masked_value(), "production");
+
+        let var = EnvVar {
+            key: "NODE_ENV".to_string(),
+            value: "enc:v1:c2VjcmV0".to_string(),
+            source: EnvSource::User,
+        };
+        assert_eq!(var.masked_value(), "•".repeat(15));
+    }
+
+    #[test]
+    fn test_merge_applies_edits_on_top_of_disk_changes() {
+        let base = "# App\nPORT=3000\nHOST=localhost\nDEBUG=true\n";
+        let ours = "# App\nPORT=4000\nHOST=localhost\nAPI_KEY=abc\n";
+        let theirs = "# App\nPORT=3000\nHOST=0.0.0.0\nDEBUG=true\nREGION=eu\n";
+
+        let merged = EnvManager::merge(base, ours, theirs);
+
+        assert!(merged.conflicts.is_empty());
+        assert_eq!(
+            merged.content,
+            "# App\nPORT=4000\nHOST=0.0.0.0\nREGION=eu\nAPI_KEY=abc\n"
+        );
+    }
+
+    #[test]
+    fn test_merge_reports_conflicts() {
+        let base = "PORT=3000\nDEBUG=true\n";
+        let ours = "PORT=4000\n";
+        let theirs = "PORT=5000\nDEBUG=false\n";
+
+        let merged = EnvManager::merge(base, ours, theirs);
+
+        assert_eq!(merged.conflicts, vec!["DEBUG".to_string(), "PORT".to_string()]);
+        assert_eq!(merged.content, "PORT=4000\n");
+    }
+
+    #[test]
+    fn test_merge_deleted_here_modified_on_disk() {
+        let base = "PORT=3000\nDEBUG=true\n";
+        let ours = "PORT=3000\n";
+        let theirs = "PORT=3000\nDEBUG=false\n";
+
+        let merged = EnvManager::merge(base, ours, theirs);
+
+        assert_eq!(merged.conflicts, vec!["DEBUG".to_string()]);
+        assert_eq!(merged.content, "PORT=3000\n");
+    }
+
+    #[test]
+    fn test_merge_modified_here_deleted_on_disk() {
+        let base = "PORT=3000\nDEBUG=true\n";
+        let ours = "PORT=3000\nDEBUG=false\n";
+        let theirs = "PORT=3000\n";
+
+        let merged = EnvManager::merge(base, ours, theirs);
+
+        assert_eq!(merged.conflicts, vec!["DEBUG".to_string()]);
+        assert_eq!(merged.content, "PORT=3000\nDEBUG=false\n");
     }
 }
@@ -0,0 +1,152 @@
+//! Encryption at rest for environment variables flagged as sensitive.
+//!
+//! Encrypted values are stored in the env files as `enc:v1:<base64>` so the
+//! other variables stay plain text and easy to diff. The key is shared by all
+//! apps on the server and never leaves it.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+
+use crate::core::error::AppError;
+
+/// Prefix marking an encrypted env value.
+pub const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+/// Server key used to encrypt sensitive env values.
+pub const FLAASE_ENV_KEY_PATH: &str = "/opt/flaase/env.key";
+
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+/// Returns true if the value was written by `EnvCipher::encrypt`.
+pub fn is_encrypted(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_PREFIX)
+}
+
+/// AES-256-GCM cipher for env values.
+pub struct EnvCipher {
+    cipher: Aes256Gcm,
+}
+
+impl EnvCipher {
+    /// Creates a cipher from a raw 32-byte key.
+    pub fn from_key(key: &[u8; KEY_LEN]) -> Self {
+        Self {
+            cipher: Aes256Gcm::new(key.into()),
+        }
+    }
+
+    /// Loads the server key.
+    pub fn load() -> Result<Self, AppError> {
+        Self::load_from(Path::new(FLAASE_ENV_KEY_PATH))
+    }
+
+    /// Loads the server key, generating it on first use.
+    pub fn load_or_create() -> Result<Self, AppError> {
+        let path = Path::new(FLAASE_ENV_KEY_PATH);
+        if !path.exists() {
+            let key: [u8; KEY_LEN] = random_bytes()?;
+            let mut file = OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .mode(0o600)
+                .open(path)
+                .map_err(|e| AppError::Config(format!("Failed to create env key: {}", e)))?;
+            file.write_all(&key)?;
+        }
+
+        Self::load_from(path)
+    }
+
+    fn load_from(path: &Path) -> Result<Self, AppError> {
+        let bytes = fs::read(path).map_err(|e| {
+            AppError::Config(format!("Failed to read env key {}: {}", path.display(), e))
+        })?;
+        let key: [u8; KEY_LEN] = bytes.as_slice().try_into().map_err(|_| {
+            AppError::Config(format!("Env key {} is corrupted", path.display()))
+        })?;
+
+        Ok(Self::from_key(&key))
+    }
+
+    /// Encrypts a value into its `enc:v1:` form with a fresh nonce.
+    pub fn encrypt(&self, plaintext: &str) -> Result<String, AppError> {
+        let nonce: [u8; NONCE_LEN] = random_bytes()?;
+        let ciphertext = self
+            .cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_bytes())
+            .map_err(|_| AppError::Config("Failed to encrypt value".into()))?;
+
+        let mut payload = nonce.to_vec();
+        payload.extend(ciphertext);
+        Ok(format!("{}{}", ENCRYPTED_PREFIX, BASE64.encode(payload)))
+    }
+
+    /// Decrypts an `enc:v1:` value. Plain values are returned unchanged.
+    pub fn decrypt(&self, value: &str) -> Result<String, AppError> {
+        let Some(encoded) = value.strip_prefix(ENCRYPTED_PREFIX) else {
+            return Ok(value.to_string());
+        };
+
+        let invalid = || AppError::Config("Encrypted value is invalid or uses another key".into());
+        let payload = BASE64.decode(encoded).map_err(|_| invalid())?;
+        if payload.len() < NONCE_LEN {
+            return Err(invalid());
+        }
+
+        let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| invalid())?;
+
+        String::from_utf8(plaintext).map_err(|_| invalid())
+    }
+}
+
+/// Reads random bytes from the kernel CSPRNG.
+fn random_bytes<const N: usize>() -> Result<[u8; N], AppError> {
+    let mut bytes = [0u8; N];
+    File::open("/dev/urandom")
+        .and_then(|mut f| f.read_exact(&mut bytes))
+        .map_err(|e| AppError::Config(format!("Failed to read random bytes: {}", e)))?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let cipher = EnvCipher::from_key(&[7u8; KEY_LEN]);
+
+        let first = cipher.encrypt("sk_live_123").unwrap();
+        let second = cipher.encrypt("sk_live_123").unwrap();
+        assert!(is_encrypted(&first));
+        assert!(!first.contains("sk_live_123"));
+        assert_ne!(first, second, "each value gets its own nonce");
+
+        assert_eq!(cipher.decrypt(&first).unwrap(), "sk_live_123");
+        assert_eq!(cipher.decrypt("plain").unwrap(), "plain");
+    }
+
+    #[test]
+    fn test_decrypt_rejects_other_key_and_tampering() {
+        let encrypted = EnvCipher::from_key(&[1u8; KEY_LEN]).encrypt("secret").unwrap();
+
+        assert!(EnvCipher::from_key(&[2u8; KEY_LEN]).decrypt(&encrypted).is_err());
+
+        let mut tampered = encrypted.clone();
+        tampered.pop();
+        tampered.push(if encrypted.ends_with('A') { 'B' } else { 'A' });
+        assert!(EnvCipher::from_key(&[1u8; KEY_LEN]).decrypt(&tampered).is_err());
+    }
+}
@@ -76,6 +76,9 @@ pub enum AppError {
     #[error("Approval error: {0}")]
     Approval(String),
 
+    #[error("Backup error: {0}")]
+    Backup(String),
+
     #[error("Operation cancelled by user")]
     Cancelled,
 }
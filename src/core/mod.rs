@@ -1,25 +1,36 @@
 pub mod app_config;
+pub mod backup;
+pub mod build_slots;
+pub mod certs;
 pub mod config;
 pub mod context;
+pub mod cron;
 pub mod deploy;
 pub mod deployments;
 pub mod env;
+pub mod env_crypto;
 pub mod error;
 pub mod notifications;
 pub mod registry;
+pub mod remote;
 pub mod secrets;
 pub mod stack_detection;
 
 pub use app_config::{
-    AppConfig, ApprovalConfig, AutodeployConfig, BuildConfig, CacheConfig, CacheType,
-    DatabaseConfig, DatabaseType, DeploymentType, DiscordNotificationConfig, DomainAuth,
-    DomainConfig, EnvironmentConfig, Framework, HealthCheckConfig, HookCommand, HooksConfig,
-    ImageConfig, NotificationConfig, NotificationEvents, PackageManager, RateLimitConfig,
-    Registry, RegistryCredentials, RollbackConfig, SlackNotificationConfig, Stack, StackConfig,
+    AppConfig, ApprovalConfig, AutodeployConfig, BuildCacheMode, BuildConfig, CacheConfig,
+    CacheType, CronJob, DatabaseConfig, DatabaseType, DeploymentType, DiscordNotificationConfig,
+    DomainAuth, DomainConfig, EnvironmentConfig, Framework, HealthCheckConfig, HookCommand,
+    HooksConfig, ImageConfig, NotificationConfig, NotificationEvents, PackageManager,
+    RateLimitConfig, Registry, RegistryCredentials, ResourcesConfig, RollbackConfig,
+    SecretsFileConfig, SlackNotificationConfig, Stack, StackConfig, TelegramNotificationConfig,
     TestConfig, VolumeMount,
 };
-pub use stack_detection::{detect_stack, DetectionConfidence, DetectionResult};
+pub use stack_detection::{
+    detect_django_wsgi_module, detect_rust_binary_name, detect_stack, DetectionConfidence,
+    DetectionResult,
+};
 pub use registry::{detect_default_port, parse_image_reference, pull_image};
+pub use certs::{CertificateStore, StoredCertificate};
 pub use config::{
     ExistingComponentAction, ServerConfig, FLAASE_APPS_PATH, FLAASE_BASE_PATH, FLAASE_CONFIG_PATH,
     FLAASE_TRAEFIK_DYNAMIC_PATH, FLAASE_TRAEFIK_PATH,
@@ -29,7 +40,7 @@ pub use deploy::{format_duration, DeployResult, Deployer, DeployStep, UpdateResu
 pub use deployments::{
     DeploymentHistory, DeploymentRecord, DeploymentSource, DeploymentStatus, PendingApproval,
 };
-pub use env::{EnvManager, EnvSource, EnvVar};
+pub use env::{EnvManager, EnvMerge, EnvSetResult, EnvSource, EnvVar};
 pub use error::AppError;
 pub use notifications::{send_notifications, test_notification, DeploymentEvent};
 pub use secrets::{AppSecrets, AuthSecret, SecretsManager, WebhookSecret};
@@ -1,12 +1,19 @@
 //! Notification system for deployment events.
 
+use std::fs;
 use std::io::{Read, Write};
 use std::net::TcpStream;
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+use std::path::PathBuf;
 use std::time::Duration;
 
-use crate::core::app_config::{DiscordNotificationConfig, EmailNotificationConfig, NotificationConfig, SlackNotificationConfig};
+use crate::core::app_config::{
+    DiscordNotificationConfig, EmailNotificationConfig, NotificationConfig,
+    SlackNotificationConfig, TelegramNotificationConfig,
+};
 use crate::core::deployments::DeploymentStatus;
 use crate::core::error::AppError;
+use crate::core::secrets::SecretsManager;
 
 /// Deployment event for notifications.
 #[derive(Debug, Clone)]
@@ -64,6 +71,13 @@ pub fn send_notifications(
         }
     }
 
+    // Send to Telegram
+    if let Some(telegram) = &config.telegram {
+        if let Err(e) = send_telegram_notification(telegram, event) {
+            eprintln!("Failed to send Telegram notification: {}", e);
+        }
+    }
+
     Ok(())
 }
 
@@ -194,13 +208,11 @@ fn send_discord_notification(
     send_webhook_request(&config.webhook_url, &payload)
 }
 
-/// Sends an email notification via SMTP.
-fn send_email_notification(
-    config: &EmailNotificationConfig,
+/// Sends a Telegram notification through the Bot API.
+fn send_telegram_notification(
+    config: &TelegramNotificationConfig,
     event: &DeploymentEvent,
 ) -> Result<(), AppError> {
-    use std::process::Command;
-
     let (emoji, status_text) = match event.status {
         DeploymentStatus::Triggered => ("🚀", "started"),
         DeploymentStatus::PendingApproval => ("⏳", "awaiting approval"),
@@ -214,11 +226,71 @@ fn send_email_notification(
         .map(|d| format!(" in {}s", d))
         .unwrap_or_default();
 
-    let subject = format!(
-        "{} Deployment {} for {}{}",
-        emoji, status_text, event.app_name, duration_text
+    let mut text = format!(
+        "{} Deployment {} for <b>{}</b>{}\n\n\
+         <b>Branch:</b> {}\n\
+         <b>Commit:</b> <code>{}</code>\n\
+         <b>By:</b> {}\n\
+         <i>{}</i>",
+        emoji,
+        status_text,
+        escape_html(&event.app_name),
+        duration_text,
+        escape_html(&event.branch),
+        escape_html(&event.commit_sha),
+        escape_html(&event.triggered_by),
+        escape_html(&truncate_message(&event.commit_message, 100))
     );
 
+    if let Some(error) = &event.error_message {
+        text.push_str(&format!("\n\n<pre>{}</pre>", escape_html(error)));
+    }
+
+    let payload = serde_json::json!({
+        "chat_id": config.chat_id,
+        "text": text,
+        "parse_mode": "HTML",
+        "disable_web_page_preview": true
+    });
+
+    let url = format!("https://api.telegram.org/bot{}/sendMessage", config.bot_token);
+    send_webhook_request(&url, &payload)
+}
+
+/// Escapes text for Telegram's HTML parse mode.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Sends an email notification via SMTP.
+///
+/// curl speaks SMTP for us; timeouts keep an unreachable server from
+/// holding up the deployment.
+fn send_email_notification(
+    config: &EmailNotificationConfig,
+    event: &DeploymentEvent,
+) -> Result<(), AppError> {
+    use std::process::{Command, Stdio};
+
+    if config.to_emails.is_empty() {
+        return Err(AppError::Config("No email recipients configured".into()));
+    }
+
+    let status_text = match event.status {
+        DeploymentStatus::Triggered => "started",
+        DeploymentStatus::PendingApproval => "awaiting approval",
+        DeploymentStatus::Success => "succeeded",
+        DeploymentStatus::Failed => "failed",
+        DeploymentStatus::RolledBack => "rolled back",
+    };
+
+    let duration_text = event
+        .duration_secs
+        .map(|d| format!(" in {}s", d))
+        .unwrap_or_default();
+
     let from_name = config.from_name.as_deref().unwrap_or("Flaase");
     let from = format!("{} <{}>", from_name, config.from_email);
 
@@ -241,61 +313,98 @@ fn send_email_notification(
         body.push_str(&format!("\nError: {}\n", error));
     }
 
-    // Send via curl using SMTP
-    // Format: curl --url "smtp://host:port" --ssl-reqd --mail-from "from" --mail-rcpt "to" -T -
+    let email_content = format!(
+        "From: {}\r\n\
+         To: {}\r\n\
+         Subject: {}\r\n\
+         Date: {}\r\n\
+         Content-Type: text/plain; charset=utf-8\r\n\
+         \r\n\
+         {}",
+        from,
+        config.to_emails.join(", "),
+        email_subject(event, status_text),
+        chrono::Utc::now().to_rfc2822(),
+        body.replace('\n', "\r\n")
+    );
+
+    // STARTTLS upgrades a plain connection (587); otherwise use implicit TLS (465)
+    let smtp_url = if config.starttls {
+        format!("smtp://{}:{}", config.smtp_host, config.smtp_port)
+    } else {
+        format!("smtps://{}:{}", config.smtp_host, config.smtp_port)
+    };
+
+    // stdin carries the message, so the SMTP login goes in a private config
+    // file: on the command line any local user could read it in `ps`
+    let credentials = PrivateCurlConfig::write(&curl_config(
+        "user",
+        &format!("{}:{}", config.smtp_user, config.smtp_password),
+    ))?;
+
+    let mut curl_args = vec![
+        "-sS".to_string(),
+        "--connect-timeout".to_string(),
+        "10".to_string(),
+        "--max-time".to_string(),
+        "30".to_string(),
+        "--url".to_string(),
+        smtp_url,
+        "--mail-from".to_string(),
+        config.from_email.clone(),
+        "-K".to_string(),
+        credentials.path().to_string_lossy().to_string(),
+        "-T".to_string(),
+        "-".to_string(),
+    ];
+
     for to_email in &config.to_emails {
-        let smtp_url = if config.starttls {
-            format!("smtp://{}:{}", config.smtp_host, config.smtp_port)
-        } else {
-            format!("smtps://{}:{}", config.smtp_host, config.smtp_port)
-        };
+        curl_args.push("--mail-rcpt".to_string());
+        curl_args.push(to_email.clone());
+    }
 
-        let email_content = format!(
-            "From: {}\r\n\
-             To: {}\r\n\
-             Subject: {}\r\n\
-             Content-Type: text/plain; charset=utf-8\r\n\
-             \r\n\
-             {}",
-            from, to_email, subject, body
-        );
+    if config.starttls {
+        curl_args.push("--ssl-reqd".to_string());
+    }
 
-        let mut curl_args = vec![
-            "-s".to_string(),
-            "--url".to_string(),
-            smtp_url,
-            "--mail-from".to_string(),
-            config.from_email.clone(),
-            "--mail-rcpt".to_string(),
-            to_email.clone(),
-            "--user".to_string(),
-            format!("{}:{}", config.smtp_user, config.smtp_password),
-            "-T".to_string(),
-            "-".to_string(),
-        ];
-
-        if config.starttls {
-            curl_args.push("--ssl-reqd".to_string());
-        }
+    let mut child = Command::new("curl")
+        .args(&curl_args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| AppError::Config(format!("Failed to execute curl: {}", e)))?;
 
-        let output = Command::new("curl")
-            .args(&curl_args)
-            .stdin(std::process::Stdio::piped())
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped())
-            .spawn()
-            .map_err(|e| AppError::Config(format!("Failed to execute curl: {}", e)))?;
-
-        // Write email content to stdin
-        if let Some(mut stdin) = output.stdin {
-            stdin.write_all(email_content.as_bytes())
-                .map_err(|e| AppError::Config(format!("Failed to write email: {}", e)))?;
-        }
+    // Write the message, then close stdin so curl sends it
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(email_content.as_bytes())
+            .map_err(|e| AppError::Config(format!("Failed to write email: {}", e)))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| AppError::Config(format!("Failed to send email: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(AppError::Config(format!(
+            "SMTP delivery failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
     }
 
     Ok(())
 }
 
+/// Builds the email subject, e.g. `[Flaase] deploy succeeded: myapp @ abc1234`.
+fn email_subject(event: &DeploymentEvent, status_text: &str) -> String {
+    let short_sha: String = event.commit_sha.chars().take(7).collect();
+    format!(
+        "[Flaase] deploy {}: {} @ {}",
+        status_text, event.app_name, short_sha
+    )
+}
+
 /// Sends a webhook request using raw TCP/TLS.
 fn send_webhook_request(url: &str, payload: &serde_json::Value) -> Result<(), AppError> {
     let body = serde_json::to_string(payload)
@@ -391,7 +500,7 @@ fn send_http_request(host: &str, port: u16, request: &str) -> Result<(), AppErro
 /// Falls back to spawning curl if TLS is not available.
 fn send_https_request(host: &str, port: u16, request: &str) -> Result<(), AppError> {
     // Use curl as a reliable fallback for HTTPS
-    use std::process::Command;
+    use std::process::{Command, Stdio};
 
     // Extract the body from the request
     let body_start = request.find("\r\n\r\n").unwrap_or(request.len()) + 4;
@@ -404,16 +513,31 @@ fn send_https_request(host: &str, port: u16, request: &str) -> Result<(), AppErr
 
     let url = format!("https://{}:{}{}", host, port, path);
 
-    let output = Command::new("curl")
+    // Webhook URLs and bot tokens are secrets: hand the URL to curl as a
+    // config file on stdin so it never shows up in the process list.
+    // --fail turns HTTP error statuses into a failed exit.
+    let mut child = Command::new("curl")
         .args([
-            "-s",
+            "-sS",
+            "--fail",
+            "-K", "-",
             "-X", "POST",
             "-H", "Content-Type: application/json",
             "-d", body,
             "--max-time", "10",
-            &url,
         ])
-        .output()
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| AppError::Config(format!("Failed to execute curl: {}", e)))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(curl_config("url", &url).as_bytes())?;
+    }
+
+    let output = child
+        .wait_with_output()
         .map_err(|e| AppError::Config(format!("Failed to execute curl: {}", e)))?;
 
     if !output.status.success() {
@@ -427,6 +551,50 @@ fn send_https_request(host: &str, port: u16, request: &str) -> Result<(), AppErr
     Ok(())
 }
 
+/// Curl config line setting `option` to a quoted `value`.
+fn curl_config(option: &str, value: &str) -> String {
+    format!(
+        "{} = \"{}\"\n",
+        option,
+        value.replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
+/// A curl config file only its owner can read, in a fresh private
+/// directory that is removed on drop.
+struct PrivateCurlConfig {
+    dir: PathBuf,
+}
+
+impl PrivateCurlConfig {
+    fn write(content: &str) -> Result<Self, AppError> {
+        let dir = std::env::temp_dir().join(format!(
+            "flaase-curl-{}",
+            SecretsManager::generate_password(16)
+        ));
+        fs::DirBuilder::new().mode(0o700).create(&dir)?;
+        let config = Self { dir };
+
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(config.path())?;
+        file.write_all(content.as_bytes())?;
+        Ok(config)
+    }
+
+    fn path(&self) -> PathBuf {
+        self.dir.join("curlrc")
+    }
+}
+
+impl Drop for PrivateCurlConfig {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
 /// Truncates a message to a maximum length.
 fn truncate_message(msg: &str, max_len: usize) -> String {
     let first_line = msg.lines().next().unwrap_or(msg);
@@ -463,5 +631,59 @@ pub fn test_notification(config: &NotificationConfig, app_name: &str) -> Result<
         send_email_notification(email, &test_event)?;
     }
 
+    if let Some(telegram) = &config.telegram {
+        send_telegram_notification(telegram, &test_event)?;
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email_subject() {
+        let event = DeploymentEvent {
+            app_name: "myapp".to_string(),
+            commit_sha: "abc1234def5678".to_string(),
+            commit_message: "Fix checkout".to_string(),
+            branch: "main".to_string(),
+            triggered_by: "webhook".to_string(),
+            status: DeploymentStatus::Success,
+            duration_secs: Some(12),
+            error_message: None,
+        };
+
+        assert_eq!(
+            email_subject(&event, "succeeded"),
+            "[Flaase] deploy succeeded: myapp @ abc1234"
+        );
+    }
+
+    #[test]
+    fn test_curl_config() {
+        assert_eq!(
+            curl_config("url", "https://api.telegram.org:443/bot1:AA/sendMessage"),
+            "url = \"https://api.telegram.org:443/bot1:AA/sendMessage\"\n"
+        );
+        assert_eq!(curl_config("url", r#"https://h/a"b\c"#), "url = \"https://h/a\\\"b\\\\c\"\n");
+        assert_eq!(curl_config("user", "me:p\"w"), "user = \"me:p\\\"w\"\n");
+    }
+
+    #[test]
+    fn test_private_curl_config() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let config = PrivateCurlConfig::write("user = \"me:secret\"\n").unwrap();
+        let path = config.path();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "user = \"me:secret\"\n");
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        let dir_mode = fs::metadata(path.parent().unwrap()).unwrap().permissions().mode();
+        assert_eq!(dir_mode & 0o777, 0o700);
+
+        drop(config);
+        assert!(!path.parent().unwrap().exists());
+    }
+}
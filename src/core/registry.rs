@@ -1,10 +1,14 @@
 //! Docker registry operations and image management.
 
+use std::fs;
+use std::os::unix::fs::DirBuilderExt;
 use std::path::Path;
 
 use crate::core::app_config::{ImageConfig, Registry, RegistryCredentials};
 use crate::core::context::ExecutionContext;
 use crate::core::error::AppError;
+use crate::core::secrets::SecretsManager;
+use crate::providers::container_cli;
 
 /// Parses an image reference string into an ImageConfig.
 ///
@@ -111,12 +115,27 @@ fn parse_registry_and_name(full_name: &str) -> Result<(Registry, String), AppErr
     Ok((Registry::DockerHub, full_name.to_string()))
 }
 
+/// Derives an app name from an image reference (e.g. "ghcr.io/acme/my_api" -> "my-api").
+pub fn app_name_from_image(image_name: &str) -> String {
+    let base = image_name.rsplit('/').next().unwrap_or(image_name);
+    let mut name = String::new();
+    for c in base.to_lowercase().chars() {
+        if c.is_ascii_lowercase() || c.is_ascii_digit() {
+            name.push(c);
+        } else if !name.is_empty() && !name.ends_with('-') {
+            name.push('-');
+        }
+    }
+    name.truncate(50);
+    name.trim_end_matches('-').to_string()
+}
+
 /// Returns the default port for well-known images.
 pub fn detect_default_port(image_name: &str) -> Option<u16> {
     // Extract base image name (without registry prefix and tag)
     let base_name = image_name
         .split('/')
-        .last()
+        .next_back()
         .unwrap_or(image_name)
         .split(':')
         .next()
@@ -171,11 +190,11 @@ pub fn pull_image(
 
     // If credentials are provided, login first
     if let Some(creds) = credentials {
-        docker_login(image, creds, ctx)?;
+        docker_login(image, creds, None, ctx)?;
     }
 
     // Pull the image
-    let output = ctx.run_command("docker", &["pull", &image_ref])?;
+    let output = ctx.run_command(container_cli(), &["pull", &image_ref])?;
 
     if !output.success {
         // Logout if we logged in
@@ -196,52 +215,124 @@ pub fn pull_image(
     Ok(())
 }
 
-/// Logs into a Docker registry.
-fn docker_login(
+/// Verifies registry credentials by logging in and resolving the image
+/// manifest, without pulling any layers.
+pub fn verify_credentials(
     image: &ImageConfig,
     creds: &RegistryCredentials,
     ctx: &ExecutionContext,
 ) -> Result<(), AppError> {
-    let registry_url = match &image.registry {
-        Registry::DockerHub => "docker.io".to_string(),
-        Registry::Ghcr => "ghcr.io".to_string(),
-        Registry::Gcr => "gcr.io".to_string(),
-        Registry::Ecr { region } => format!("{}.dkr.ecr.amazonaws.com", region),
-        Registry::Custom { url } => url.clone(),
-    };
+    // Log in against a throwaway auth store so a failed check can't replace
+    // a login the user (or a running deploy) relies on
+    let auth = ThrowawayAuth::create()?;
+    docker_login(image, creds, Some(&auth), ctx)?;
 
-    let output = ctx.run_command(
-        "docker",
-        &[
-            "login",
-            &registry_url,
-            "-u",
-            &creds.username,
-            "--password-stdin",
-        ],
-    )?;
+    let image_ref = image.full_reference();
+    let args = auth.args(&["manifest", "inspect", &image_ref]);
+    let output = ctx.run_command(container_cli(), &args)?;
 
     if !output.success {
         return Err(AppError::Docker(format!(
-            "Failed to login to registry {}: {}",
-            registry_url, output.stderr
+            "Logged in, but {} is not accessible with these credentials: {}",
+            image_ref, output.stderr
         )));
     }
 
     Ok(())
 }
 
-/// Logs out from a Docker registry.
-fn docker_logout(image: &ImageConfig, ctx: &ExecutionContext) -> Result<(), AppError> {
-    let registry_url = match &image.registry {
+/// Registry host to log into for an image.
+fn registry_url(image: &ImageConfig) -> String {
+    match &image.registry {
         Registry::DockerHub => "docker.io".to_string(),
         Registry::Ghcr => "ghcr.io".to_string(),
         Registry::Gcr => "gcr.io".to_string(),
         Registry::Ecr { region } => format!("{}.dkr.ecr.amazonaws.com", region),
         Registry::Custom { url } => url.clone(),
+    }
+}
+
+/// Logs into a Docker registry, into `auth` instead of the CLI's own store
+/// when given.
+fn docker_login(
+    image: &ImageConfig,
+    creds: &RegistryCredentials,
+    auth: Option<&ThrowawayAuth>,
+    ctx: &ExecutionContext,
+) -> Result<(), AppError> {
+    let registry_url = registry_url(image);
+    let password = creds.secret().ok_or_else(|| {
+        AppError::Config(format!(
+            "No password stored for registry {}. Run 'fl registry login' again.",
+            registry_url
+        ))
+    })?;
+
+    let login = [
+        "login",
+        registry_url.as_str(),
+        "-u",
+        creds.username.as_str(),
+        "--password-stdin",
+    ];
+    let args = match auth {
+        Some(auth) => auth.args(&login),
+        None => login.to_vec(),
     };
+    let output = ctx.run_command_with_stdin(container_cli(), &args, &password)?;
+
+    if !output.success {
+        return Err(AppError::Docker(format!(
+            "Registry {} rejected the credentials for '{}': {}",
+            registry_url, creds.username, output.stderr
+        )));
+    }
 
-    ctx.run_command("docker", &["logout", &registry_url])?;
+    Ok(())
+}
+
+/// Private auth store for a one-off login, removed on drop.
+struct ThrowawayAuth {
+    dir: String,
+    auth_file: String,
+}
+
+impl ThrowawayAuth {
+    fn create() -> Result<Self, AppError> {
+        let dir = std::env::temp_dir().join(format!(
+            "flaase-registry-{}",
+            SecretsManager::generate_password(16)
+        ));
+        fs::DirBuilder::new().mode(0o700).create(&dir)?;
+        Ok(Self {
+            auth_file: dir.join("auth.json").to_string_lossy().to_string(),
+            dir: dir.to_string_lossy().to_string(),
+        })
+    }
+
+    /// Container CLI arguments that point `args` at this store: Docker takes
+    /// a config directory before the command, Podman an auth file after it.
+    fn args<'a>(&'a self, args: &[&'a str]) -> Vec<&'a str> {
+        if container_cli() == "podman" {
+            let mut with_auth = args.to_vec();
+            with_auth.extend(["--authfile", self.auth_file.as_str()]);
+            with_auth
+        } else {
+            let mut with_auth = vec!["--config", self.dir.as_str()];
+            with_auth.extend_from_slice(args);
+            with_auth
+        }
+    }
+}
+
+impl Drop for ThrowawayAuth {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+/// Logs out from a Docker registry.
+fn docker_logout(image: &ImageConfig, ctx: &ExecutionContext) -> Result<(), AppError> {
+    ctx.run_command(container_cli(), &["logout", &registry_url(image)])?;
     Ok(())
 }
 
@@ -286,7 +377,7 @@ pub fn load_credentials(credentials_path: &Path) -> Result<Option<RegistryCreden
 /// Checks if an image exists locally.
 pub fn image_exists_locally(image: &ImageConfig, ctx: &ExecutionContext) -> Result<bool, AppError> {
     let image_ref = image.full_reference();
-    let output = ctx.run_command("docker", &["image", "inspect", &image_ref])?;
+    let output = ctx.run_command(container_cli(), &["image", "inspect", &image_ref])?;
     Ok(output.success)
 }
 
@@ -294,6 +385,26 @@ pub fn image_exists_locally(image: &ImageConfig, ctx: &ExecutionContext) -> Resu
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_throwaway_auth_is_private_and_removed() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let auth = ThrowawayAuth::create().unwrap();
+        let dir = std::path::PathBuf::from(&auth.dir);
+        let mode = fs::metadata(&dir).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+
+        let args = auth.args(&["manifest", "inspect", "ghcr.io/acme/demo:v1"]);
+        if container_cli() == "podman" {
+            assert_eq!(&args[args.len() - 2..], ["--authfile", auth.auth_file.as_str()]);
+        } else {
+            assert_eq!(args[..2], ["--config", auth.dir.as_str()]);
+        }
+
+        drop(auth);
+        assert!(!dir.exists());
+    }
+
     #[test]
     fn test_parse_simple_image() {
         let config = parse_image_reference("nginx").unwrap();
@@ -345,6 +456,13 @@ mod tests {
         assert_eq!(detect_default_port("postgres"), Some(5432));
     }
 
+    #[test]
+    fn test_app_name_from_image() {
+        assert_eq!(app_name_from_image("nginx"), "nginx");
+        assert_eq!(app_name_from_image("acme/my_api"), "my-api");
+        assert_eq!(app_name_from_image("Uptime.Kuma"), "uptime-kuma");
+    }
+
     #[test]
     fn test_detect_unknown_port() {
         assert_eq!(detect_default_port("my-custom-app"), None);
@@ -0,0 +1,207 @@
+//! Remote servers driven over SSH.
+//!
+//! Aliases live in `~/.flaase/servers.yml` on the developer's machine:
+//!
+//! ```yaml
+//! servers:
+//!   prod:
+//!     host: 203.0.113.10
+//!     user: root
+//!     key: ~/.ssh/id_ed25519
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::AppError;
+
+/// A server reachable over SSH.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteServer {
+    /// Hostname or IP address.
+    pub host: String,
+    /// SSH user (default: the SSH client's default).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Private key file (default: the SSH client's default keys).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<PathBuf>,
+    /// SSH port (default: 22).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+}
+
+impl RemoteServer {
+    /// `user@host`, or just the host when no user is set.
+    pub fn destination(&self) -> String {
+        match &self.user {
+            Some(user) => format!("{}@{}", user, self.host),
+            None => self.host.clone(),
+        }
+    }
+
+    /// Arguments for `ssh` running `cmd args..` on the server. With `tty`,
+    /// a terminal is allocated for prompts and live output; without, SSH never
+    /// prompts so unattended commands can't hang.
+    pub fn ssh_args(&self, cmd: &str, args: &[&str], tty: bool) -> Vec<String> {
+        let mut ssh = vec![if tty { "-t" } else { "-T" }.to_string()];
+        if !tty {
+            ssh.extend(["-o".to_string(), "BatchMode=yes".to_string()]);
+        }
+        if let Some(key) = &self.key {
+            ssh.extend(["-i".to_string(), expand_home(key).to_string_lossy().to_string()]);
+        }
+        if let Some(port) = self.port {
+            ssh.extend(["-p".to_string(), port.to_string()]);
+        }
+        // `--` keeps a host or user starting with `-` from being read as an option
+        ssh.push("--".to_string());
+        ssh.push(self.destination());
+
+        // The remote side runs the words through a shell, so quote each one
+        let remote: Vec<String> = std::iter::once(cmd)
+            .chain(args.iter().copied())
+            .map(shell_quote)
+            .collect();
+        ssh.push(remote.join(" "));
+        ssh
+    }
+}
+
+/// Server aliases stored on the developer's machine.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServersConfig {
+    #[serde(default)]
+    pub servers: BTreeMap<String, RemoteServer>,
+}
+
+impl ServersConfig {
+    /// Path of the aliases file: `~/.flaase/servers.yml`.
+    pub fn path() -> PathBuf {
+        expand_home(Path::new("~/.flaase/servers.yml"))
+    }
+
+    /// Loads the aliases file.
+    pub fn load() -> Result<Self, AppError> {
+        Self::load_from(&Self::path())
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self, AppError> {
+        if !path.exists() {
+            return Err(AppError::Config(format!(
+                "No servers configured. Create {} to use --server.",
+                path.display()
+            )));
+        }
+
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| AppError::Config(format!("Failed to read {}: {}", path.display(), e)))?;
+        serde_yaml::from_str(&content)
+            .map_err(|e| AppError::Config(format!("Failed to parse {}: {}", path.display(), e)))
+    }
+
+    /// Returns the server of an alias.
+    pub fn resolve(&self, alias: &str) -> Result<&RemoteServer, AppError> {
+        self.servers.get(alias).ok_or_else(|| {
+            let known: Vec<&str> = self.servers.keys().map(String::as_str).collect();
+            AppError::Validation(format!(
+                "Unknown server '{}'. Known servers: {}",
+                alias,
+                if known.is_empty() { "none".to_string() } else { known.join(", ") }
+            ))
+        })
+    }
+}
+
+/// Quotes a word for a POSIX shell, leaving plain words untouched.
+pub fn shell_quote(word: &str) -> String {
+    let plain = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@,+%".contains(c));
+
+    if plain {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+/// Replaces a leading `~/` with the user's home directory.
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), std::env::var("HOME")) {
+        (Ok(rest), Ok(home)) => Path::new(&home).join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("deploy"), "deploy");
+        assert_eq!(shell_quote("/opt/flaase/apps"), "/opt/flaase/apps");
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(shell_quote("a b"), "'a b'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote("$(reboot)"), "'$(reboot)'");
+    }
+
+    #[test]
+    fn test_ssh_args() {
+        let server = RemoteServer {
+            host: "203.0.113.10".into(),
+            user: Some("root".into()),
+            key: Some(PathBuf::from("/keys/prod")),
+            port: Some(2222),
+        };
+
+        assert_eq!(
+            server.ssh_args("fl", &["deploy", "shop"], true),
+            ["-t", "-i", "/keys/prod", "-p", "2222", "--", "root@203.0.113.10", "fl deploy shop"]
+        );
+
+        let plain = RemoteServer {
+            host: "vps.example.com".into(),
+            user: None,
+            key: None,
+            port: None,
+        };
+        assert_eq!(
+            plain.ssh_args("sh", &["-c", "cat > /tmp/x"], false),
+            ["-T", "-o", "BatchMode=yes", "--", "vps.example.com", "sh -c 'cat > /tmp/x'"]
+        );
+
+        let hostile = RemoteServer {
+            host: "-oProxyCommand=reboot".into(),
+            user: None,
+            key: None,
+            port: None,
+        };
+        assert_eq!(hostile.ssh_args("fl", &[], false)[3..], ["--", "-oProxyCommand=reboot", "fl"]);
+    }
+
+    #[test]
+    fn test_servers_config_resolve() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("servers.yml");
+        assert!(ServersConfig::load_from(&path).is_err());
+
+        std::fs::write(
+            &path,
+            "servers:\n  prod:\n    host: 203.0.113.10\n    user: deploy\n  \
+             staging:\n    host: stg\n",
+        )
+        .unwrap();
+        let config = ServersConfig::load_from(&path).unwrap();
+
+        assert_eq!(config.resolve("prod").unwrap().destination(), "deploy@203.0.113.10");
+        assert_eq!(config.resolve("staging").unwrap().destination(), "stg");
+        let err = config.resolve("qa").unwrap_err().to_string();
+        assert!(err.contains("Known servers: prod, staging"));
+    }
+}
@@ -24,6 +24,8 @@ pub struct DetectionResult {
     pub confidence: DetectionConfidence,
     /// Files that were used for detection
     pub detected_files: Vec<String>,
+    /// Package managers whose lockfiles were all found (ambiguous detection)
+    pub lockfile_conflict: Vec<PackageManager>,
 }
 
 /// Confidence level of detection.
@@ -53,10 +55,11 @@ impl DetectionConfidence {
 
 /// Detects the stack from repository files.
 pub fn detect_stack(repo_path: &Path) -> DetectionResult {
-    let mut result = DetectionResult::default();
-
     // Check for Dockerfile first
-    result.has_dockerfile = repo_path.join("Dockerfile").exists();
+    let mut result = DetectionResult {
+        has_dockerfile: repo_path.join("Dockerfile").exists(),
+        ..Default::default()
+    };
 
     // Try to detect stack in order of specificity
     // Most specific first (Rust, Go) then less specific (Node.js, Python)
@@ -193,16 +196,7 @@ fn detect_python_package_manager(repo_path: &Path) -> Option<PackageManager> {
 
 /// Detects Node.js package manager and framework.
 fn detect_nodejs_details(repo_path: &Path, result: &mut DetectionResult) {
-    // Detect package manager from lockfiles
-    if repo_path.join("pnpm-lock.yaml").exists() {
-        result.package_manager = Some(PackageManager::Pnpm);
-        result.detected_files.push("pnpm-lock.yaml".to_string());
-    } else if repo_path.join("yarn.lock").exists() {
-        result.package_manager = Some(PackageManager::Yarn);
-        result.detected_files.push("yarn.lock".to_string());
-    } else {
-        result.package_manager = Some(PackageManager::Npm);
-    }
+    detect_nodejs_package_manager(repo_path, result);
 
     // Read package.json to detect framework
     if let Ok(content) = std::fs::read_to_string(repo_path.join("package.json")) {
@@ -220,6 +214,14 @@ fn detect_nodejs_details(repo_path: &Path, result: &mut DetectionResult) {
             return;
         }
 
+        // Frontends built to static files are served by nginx, not Node
+        if let Some(framework) = detect_static_frontend(&content) {
+            result.stack = Some(Stack::Static);
+            result.framework = Some(framework);
+            result.confidence = DetectionConfidence::High;
+            return;
+        }
+
         // Detect frameworks for generic Node.js
         if content.contains("\"express\"") {
             result.framework = Some(Framework::Express);
@@ -244,6 +246,82 @@ fn detect_nodejs_details(repo_path: &Path, result: &mut DetectionResult) {
     result.confidence = DetectionConfidence::High;
 }
 
+/// Detects a Vite or Create React App frontend without a Node server runtime.
+fn detect_static_frontend(package_json: &str) -> Option<Framework> {
+    const SERVER_DEPENDENCIES: &[&str] = &[
+        "\"express\"",
+        "\"fastify\"",
+        "\"hono\"",
+        "\"@sveltejs/kit\"",
+        "\"nuxt\"",
+        "\"@remix-run/",
+    ];
+    if SERVER_DEPENDENCIES.iter().any(|dep| package_json.contains(dep)) {
+        return None;
+    }
+
+    if package_json.contains("\"react-scripts\"") {
+        Some(Framework::CreateReactApp)
+    } else if package_json.contains("\"vite\"") {
+        Some(Framework::Vite)
+    } else {
+        None
+    }
+}
+
+/// Detects the Node.js package manager. The `packageManager` field of
+/// package.json wins; otherwise several lockfiles leave it undecided and
+/// record the conflict so the user can choose.
+fn detect_nodejs_package_manager(repo_path: &Path, result: &mut DetectionResult) {
+    let declared = std::fs::read_to_string(repo_path.join("package.json"))
+        .ok()
+        .and_then(|content| extract_package_manager_field(&content));
+
+    let lockfiles: Vec<(&str, PackageManager)> = [
+        ("pnpm-lock.yaml", PackageManager::Pnpm),
+        ("yarn.lock", PackageManager::Yarn),
+        ("package-lock.json", PackageManager::Npm),
+    ]
+    .into_iter()
+    .filter(|(file, _)| repo_path.join(file).exists())
+    .collect();
+
+    if let Some(pm) = declared {
+        result.package_manager = Some(pm);
+        result.detected_files.push("package.json (packageManager)".to_string());
+        return;
+    }
+
+    match lockfiles.as_slice() {
+        [] => result.package_manager = Some(PackageManager::Npm),
+        [(file, pm)] => {
+            result.package_manager = Some(*pm);
+            result.detected_files.push(file.to_string());
+        }
+        _ => {
+            result.package_manager = None;
+            for (file, pm) in &lockfiles {
+                result.detected_files.push(file.to_string());
+                result.lockfile_conflict.push(*pm);
+            }
+        }
+    }
+}
+
+/// Reads the package manager from package.json's `packageManager` field
+/// (e.g. "pnpm@8.15.0").
+fn extract_package_manager_field(package_json: &str) -> Option<PackageManager> {
+    let json: serde_json::Value = serde_json::from_str(package_json).ok()?;
+    let value = json.get("packageManager")?.as_str()?;
+
+    match value.split('@').next()? {
+        "npm" => Some(PackageManager::Npm),
+        "yarn" => Some(PackageManager::Yarn),
+        "pnpm" => Some(PackageManager::Pnpm),
+        _ => None,
+    }
+}
+
 /// Extracts Node.js version from package.json engines field.
 fn extract_node_version(package_json: &str) -> Option<String> {
     // Simple extraction - look for "node": ">=18" or similar
@@ -302,6 +380,24 @@ fn detect_python_framework(content: &str, result: &mut DetectionResult) {
     }
 }
 
+/// Finds the WSGI module of a Django project (e.g. "mysite.wsgi"), looking
+/// at the repository root and the project packages one level below it.
+pub fn detect_django_wsgi_module(repo_path: &Path) -> Option<String> {
+    if repo_path.join("wsgi.py").exists() {
+        return Some("wsgi".to_string());
+    }
+
+    let mut packages: Vec<String> = std::fs::read_dir(repo_path)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("wsgi.py").is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    packages.sort();
+
+    packages.into_iter().next().map(|pkg| format!("{}.wsgi", pkg))
+}
+
 /// Detects Rust details.
 fn detect_rust_details(repo_path: &Path, result: &mut DetectionResult) {
     if let Ok(content) = std::fs::read_to_string(repo_path.join("Cargo.toml")) {
@@ -397,6 +493,39 @@ fn detect_php_details(repo_path: &Path, result: &mut DetectionResult) {
     }
 }
 
+/// Returns the name of the binary built by a Rust project: the first
+/// `[[bin]]` target, or the package name.
+pub fn detect_rust_binary_name(repo_path: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(repo_path.join("Cargo.toml")).ok()?;
+    let mut section = "";
+    let mut package_name = None;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            section = line;
+            continue;
+        }
+        if !(section == "[[bin]]" || section == "[package]") {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if key.trim() != "name" {
+            continue;
+        }
+
+        let name = value.trim().trim_matches('"').trim_matches('\'').to_string();
+        if section == "[[bin]]" {
+            return Some(name);
+        }
+        package_name.get_or_insert(name);
+    }
+
+    package_name
+}
+
 /// Extracts a value from a TOML-like file.
 fn extract_toml_value(content: &str, key: &str) -> Option<String> {
     for line in content.lines() {
@@ -499,6 +628,37 @@ axum = "0.7"
         assert_eq!(result.confidence, DetectionConfidence::High);
     }
 
+    #[test]
+    fn test_detect_rust_binary_name() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"myapp\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        assert_eq!(detect_rust_binary_name(dir.path()), Some("myapp".into()));
+
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"myapp\"\n\n[[bin]]\nname = \"server\"\npath = \"src/main.rs\"\n",
+        )
+        .unwrap();
+        assert_eq!(detect_rust_binary_name(dir.path()), Some("server".into()));
+
+        fs::write(dir.path().join("Cargo.toml"), "[workspace]\nmembers = [\"api\"]\n").unwrap();
+        assert_eq!(detect_rust_binary_name(dir.path()), None);
+    }
+
+    #[test]
+    fn test_detect_django_wsgi_module() {
+        let dir = tempdir().unwrap();
+        assert_eq!(detect_django_wsgi_module(dir.path()), None);
+
+        fs::create_dir(dir.path().join("mysite")).unwrap();
+        fs::write(dir.path().join("mysite/wsgi.py"), "").unwrap();
+        assert_eq!(detect_django_wsgi_module(dir.path()), Some("mysite.wsgi".into()));
+    }
+
     #[test]
     fn test_detect_nextjs() {
         let dir = tempdir().unwrap();
@@ -514,6 +674,30 @@ axum = "0.7"
         assert_eq!(result.package_manager, Some(PackageManager::Yarn));
     }
 
+    #[test]
+    fn test_detect_static_frontend() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"devDependencies": {"vite": "5.0.0"}}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("pnpm-lock.yaml"), "").unwrap();
+
+        let result = detect_stack(dir.path());
+        assert_eq!(result.stack, Some(Stack::Static));
+        assert_eq!(result.framework, Some(Framework::Vite));
+        assert_eq!(result.package_manager, Some(PackageManager::Pnpm));
+
+        // A Vite app with its own server keeps the Node.js runtime
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"dependencies": {"express": "4.0.0"}, "devDependencies": {"vite": "5.0.0"}}"#,
+        )
+        .unwrap();
+        assert_eq!(detect_stack(dir.path()).stack, Some(Stack::NodeJs));
+    }
+
     #[test]
     fn test_detect_python_django() {
         let dir = tempdir().unwrap();
@@ -525,6 +709,32 @@ axum = "0.7"
         assert_eq!(result.framework, Some(Framework::Django));
     }
 
+    #[test]
+    fn test_detect_nodejs_lockfile_conflict() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("package.json"), r#"{"dependencies": {}}"#).unwrap();
+        fs::write(dir.path().join("package-lock.json"), "{}").unwrap();
+        fs::write(dir.path().join("yarn.lock"), "").unwrap();
+
+        let result = detect_stack(dir.path());
+        assert_eq!(result.package_manager, None);
+        assert_eq!(
+            result.lockfile_conflict,
+            vec![PackageManager::Yarn, PackageManager::Npm]
+        );
+
+        // The packageManager field settles it
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"packageManager": "yarn@4.1.0", "dependencies": {}}"#,
+        )
+        .unwrap();
+
+        let result = detect_stack(dir.path());
+        assert_eq!(result.package_manager, Some(PackageManager::Yarn));
+        assert!(result.lockfile_conflict.is_empty());
+    }
+
     #[test]
     fn test_detect_dockerfile() {
         let dir = tempdir().unwrap();
@@ -1,15 +1,22 @@
 use anyhow::Result;
 use clap::Parser;
 use flaase::cli::{
-    ApprovalCommands, AuthCommands, AutodeployCommands, Cli, Commands, DomainCommands,
-    EnvCommands, EnvDeployCommands, HooksCommands, NotifyCommands, ServerCommands, WebhookCommands,
+    AppCommands, ApprovalCommands, AuthCommands, AutodeployCommands, CertCommands, Cli, Commands,
+    CronCommands, DbCommands, DomainCommands, EnvCommands, EnvDeployCommands, HooksCommands,
+    NotificationCommands, NotifyCommands, RegistryCommands, ServerCommands, WebhookCommands,
 };
 use flaase::ui;
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    ui::set_assume_yes(cli.yes);
 
-    match cli.command {
+    if let Some(alias) = &cli.server {
+        let exit_code = flaase::cli::remote::forward(alias, cli.command.as_ref(), cli.verbose)?;
+        std::process::exit(exit_code);
+    }
+
+    let result = match cli.command {
         Some(command) => run_command(command, cli.verbose),
         None => {
             ui::header();
@@ -19,42 +26,194 @@ fn main() -> Result<()> {
             );
             Ok(())
         }
+    };
+
+    // Keep a reserved stderr (`fl deploy --events`) free of the final error
+    if let Err(e) = &result {
+        if ui::stderr_reserved() {
+            ui::error(&format!("Error: {:#}", e));
+            std::process::exit(1);
+        }
     }
+    result
 }
 
 fn run_command(command: Commands, verbose: bool) -> Result<()> {
     match command {
         Commands::Server { command } => match command {
-            ServerCommands::Init { dry_run } => {
-                flaase::cli::server::init(dry_run, verbose)?;
+            ServerCommands::Init {
+                dry_run,
+                unattended,
+                acme_email,
+                acme_staging,
+            } => {
+                flaase::cli::server::init(
+                    dry_run,
+                    verbose,
+                    unattended || ui::assume_yes(),
+                    acme_email,
+                    acme_staging,
+                )?;
                 Ok(())
             }
-            ServerCommands::Status => {
-                let exit_code = flaase::cli::server_status::status(verbose)?;
+            ServerCommands::Status { apps } => {
+                let exit_code = flaase::cli::server_status::status(apps, verbose)?;
                 if exit_code != 0 {
                     std::process::exit(exit_code);
                 }
                 Ok(())
             }
+            ServerCommands::Config {
+                max_concurrent_builds,
+                webhook_prefix,
+                webhook_port,
+            } => {
+                flaase::cli::server::config(
+                    max_concurrent_builds,
+                    webhook_prefix.as_deref(),
+                    webhook_port,
+                )?;
+                Ok(())
+            }
+            ServerCommands::Update { version } => {
+                flaase::cli::server_update::update(version.as_deref(), verbose)?;
+                Ok(())
+            }
         },
 
-        Commands::Init => {
-            flaase::cli::app::init(verbose)?;
+        Commands::Init { from_file } => {
+            match from_file {
+                Some(path) => flaase::cli::app::init_from_file(&path, verbose)?,
+                None => flaase::cli::app::init(verbose)?,
+            }
             Ok(())
         }
 
-        Commands::Status => {
-            flaase::cli::status::status(verbose)?;
+        Commands::App { command } => match command {
+            AppCommands::Adopt {
+                container,
+                name,
+                domain,
+                port,
+            } => {
+                flaase::cli::adopt::adopt(
+                    &container,
+                    name.as_deref(),
+                    domain.as_deref(),
+                    port,
+                    verbose,
+                )?;
+                Ok(())
+            }
+            AppCommands::Info { app } => {
+                flaase::cli::info::info(&app)?;
+                Ok(())
+            }
+            AppCommands::ExportConfig { app, with_env } => {
+                flaase::cli::app::export_config(&app, with_env)?;
+                Ok(())
+            }
+            AppCommands::Resources {
+                app,
+                cpu_shares,
+                memory_reservation,
+                memory,
+                cpus,
+                service_memory,
+                service_cpus,
+                clear,
+            } => {
+                let changes = flaase::core::app_config::ResourcesConfig {
+                    cpu_shares,
+                    memory_reservation,
+                    memory,
+                    cpus,
+                    service_memory,
+                    service_cpus,
+                };
+                flaase::cli::app::resources(&app, changes, clear)?;
+                Ok(())
+            }
+        },
+
+        Commands::Registry { command } => match command {
+            RegistryCommands::Login {
+                app,
+                username,
+                password_stdin,
+            } => {
+                flaase::cli::registry::login(&app, username.as_deref(), password_stdin, verbose)?;
+                Ok(())
+            }
+        },
+
+        Commands::Doctor { fix } => {
+            let exit_code = flaase::cli::doctor::doctor(fix, verbose)?;
+            if exit_code != 0 {
+                std::process::exit(exit_code);
+            }
             Ok(())
         }
 
-        Commands::Deploy { app } => {
-            flaase::cli::deploy::deploy(&app, verbose)?;
+        Commands::Status {
+            watch,
+            interval,
+            json,
+        } => {
+            flaase::cli::status::status(watch, interval, json)?;
             Ok(())
         }
 
-        Commands::Update { app } => {
-            flaase::cli::deploy::update(&app, verbose)?;
+        Commands::Deploy {
+            app,
+            events,
+            tag,
+            replicas,
+            keep_previous,
+            from_branch,
+            pin_base,
+        } => {
+            flaase::cli::deploy::deploy(
+                &app,
+                events,
+                tag.as_deref(),
+                replicas,
+                keep_previous,
+                from_branch.as_deref(),
+                pin_base,
+                verbose,
+            )?;
+            Ok(())
+        }
+
+        Commands::DeployImage {
+            image,
+            domain,
+            port,
+            name,
+            private,
+        } => {
+            flaase::cli::app::deploy_image(
+                &image,
+                &domain,
+                port,
+                name.as_deref(),
+                private,
+                verbose,
+            )?;
+            Ok(())
+        }
+
+        Commands::Update { app, git_tag } => {
+            flaase::cli::deploy::update(&app, git_tag.as_deref(), verbose)?;
+            Ok(())
+        }
+
+        Commands::Exec { app, command } => {
+            let exit_code = flaase::cli::exec::exec(&app, &command, verbose)?;
+            if exit_code != 0 {
+                std::process::exit(exit_code);
+            }
             Ok(())
         }
 
@@ -73,8 +232,19 @@ fn run_command(command: Commands, verbose: bool) -> Result<()> {
             Ok(())
         }
 
-        Commands::Destroy { app, force, keep_data } => {
-            flaase::cli::deploy::destroy(&app, force, keep_data, verbose)?;
+        Commands::Scale { app, count } => {
+            flaase::cli::deploy::scale(&app, count, verbose)?;
+            Ok(())
+        }
+
+        Commands::Destroy {
+            app,
+            force,
+            keep_data,
+            dry_run,
+        } => {
+            let force = force || ui::assume_yes();
+            flaase::cli::deploy::destroy(&app, force, keep_data, dry_run, verbose)?;
             Ok(())
         }
 
@@ -90,18 +260,49 @@ fn run_command(command: Commands, verbose: bool) -> Result<()> {
             lines,
             service,
             since,
+            since_deploy,
+            container,
+            web_slot,
+            no_pager,
         } => {
-            flaase::cli::logs::logs(&app, follow, no_follow, lines, &service, since.as_deref(), verbose)?;
+            flaase::cli::logs::logs(
+                &app,
+                follow,
+                no_follow,
+                lines,
+                &service,
+                since.as_deref(),
+                since_deploy,
+                container.as_deref(),
+                &web_slot,
+                no_pager,
+                verbose,
+            )?;
             Ok(())
         }
 
         Commands::Env { command } => match command {
-            EnvCommands::List { app, show, env } => {
-                flaase::cli::env::list(&app, show, env.as_deref())?;
+            EnvCommands::List {
+                app,
+                show,
+                show_key,
+                env,
+                diff_auto,
+            } => {
+                if diff_auto {
+                    flaase::cli::env::list_auto_overrides(&app, show, env.as_deref())?;
+                } else {
+                    flaase::cli::env::list(&app, show, &show_key, env.as_deref())?;
+                }
                 Ok(())
             }
-            EnvCommands::Set { app, vars, env } => {
-                flaase::cli::env::set(&app, &vars, env.as_deref())?;
+            EnvCommands::Set {
+                app,
+                vars,
+                env,
+                encrypt,
+            } => {
+                flaase::cli::env::set(&app, &vars, env.as_deref(), encrypt)?;
                 Ok(())
             }
             EnvCommands::Remove { app, key, env } => {
@@ -116,10 +317,37 @@ fn run_command(command: Commands, verbose: bool) -> Result<()> {
                 flaase::cli::env::copy(&app, &from, &to)?;
                 Ok(())
             }
+            EnvCommands::Export {
+                app,
+                include_auto,
+                show,
+                env,
+            } => {
+                flaase::cli::env::export(&app, include_auto, show, env.as_deref())?;
+                Ok(())
+            }
+            EnvCommands::Import { app, file, env } => {
+                flaase::cli::env::import(&app, &file, env.as_deref())?;
+                Ok(())
+            }
+            EnvCommands::Diff { app_a, app_b, show } => {
+                flaase::cli::env::diff(&app_a, &app_b, show)?;
+                Ok(())
+            }
             EnvCommands::Envs { app } => {
                 flaase::cli::env::envs(&app)?;
                 Ok(())
             }
+            EnvCommands::SecretsFile {
+                app,
+                path,
+                keys,
+                file_only,
+                disable,
+            } => {
+                flaase::cli::env::secrets_file(&app, path.as_deref(), &keys, file_only, disable)?;
+                Ok(())
+            }
         },
 
         Commands::Domain { command } => match command {
@@ -130,28 +358,71 @@ fn run_command(command: Commands, verbose: bool) -> Result<()> {
             DomainCommands::Add {
                 app,
                 domain,
+                path_prefix,
                 skip_dns_check,
             } => {
-                flaase::cli::domain::add(&app, &domain, skip_dns_check)?;
+                flaase::cli::domain::add(&app, &domain, path_prefix.as_deref(), skip_dns_check)?;
+                Ok(())
+            }
+            DomainCommands::PathPrefix { app, domain, path } => {
+                flaase::cli::domain::path_prefix(&app, &domain, path.as_deref())?;
                 Ok(())
             }
             DomainCommands::Remove { app, domain } => {
                 flaase::cli::domain::remove(&app, &domain)?;
                 Ok(())
             }
+            DomainCommands::Config { app } => {
+                flaase::cli::domain::config(&app)?;
+                Ok(())
+            }
+            DomainCommands::Sync { app } => {
+                flaase::cli::domain::sync(&app)?;
+                Ok(())
+            }
+            DomainCommands::Cert { command } => match command {
+                CertCommands::Import { name, cert, key } => {
+                    flaase::cli::domain::cert_import(&name, &cert, &key)?;
+                    Ok(())
+                }
+                CertCommands::List => {
+                    flaase::cli::domain::cert_list()?;
+                    Ok(())
+                }
+                CertCommands::Remove { name } => {
+                    flaase::cli::domain::cert_remove(&name)?;
+                    Ok(())
+                }
+            },
         },
 
         Commands::Autodeploy { command } => match command {
-            AutodeployCommands::Enable { app, branch } => {
-                flaase::cli::autodeploy::enable(&app, branch.as_deref())?;
+            AutodeployCommands::Enable {
+                app,
+                branch,
+                install,
+                no_install,
+            } => {
+                let install = match (install, no_install) {
+                    (true, _) => Some(true),
+                    (_, true) => Some(false),
+                    _ => None,
+                };
+                flaase::cli::autodeploy::enable(&app, branch.as_deref(), install)?;
                 Ok(())
             }
-            AutodeployCommands::Disable { app } => {
-                flaase::cli::autodeploy::disable(&app)?;
+            AutodeployCommands::Disable {
+                app,
+                rotate_on_disable,
+            } => {
+                flaase::cli::autodeploy::disable(&app, rotate_on_disable)?;
                 Ok(())
             }
-            AutodeployCommands::Status { app } => {
-                flaase::cli::autodeploy::status(&app)?;
+            AutodeployCommands::Status { app, all } => {
+                match app {
+                    Some(app) if !all => flaase::cli::autodeploy::status(&app)?,
+                    _ => flaase::cli::autodeploy::status_all()?,
+                }
                 Ok(())
             }
             AutodeployCommands::Secret { app } => {
@@ -162,8 +433,13 @@ fn run_command(command: Commands, verbose: bool) -> Result<()> {
                 flaase::cli::autodeploy::regenerate(&app)?;
                 Ok(())
             }
-            AutodeployCommands::Logs { app, limit } => {
-                flaase::cli::autodeploy::logs(&app, limit)?;
+            AutodeployCommands::Logs {
+                app,
+                limit,
+                offset,
+                json,
+            } => {
+                flaase::cli::autodeploy::logs(&app, offset, limit, json)?;
                 Ok(())
             }
             AutodeployCommands::Notify(notify_cmd) => match notify_cmd {
@@ -209,6 +485,20 @@ fn run_command(command: Commands, verbose: bool) -> Result<()> {
                     )?;
                     Ok(())
                 }
+                NotifyCommands::Telegram {
+                    app,
+                    bot_token,
+                    chat_id,
+                    remove,
+                } => {
+                    flaase::cli::autodeploy::notify_telegram(
+                        &app,
+                        bot_token.as_deref(),
+                        chat_id.as_deref(),
+                        remove,
+                    )?;
+                    Ok(())
+                }
                 NotifyCommands::Email {
                     app,
                     smtp_host,
@@ -249,6 +539,22 @@ fn run_command(command: Commands, verbose: bool) -> Result<()> {
                     Ok(())
                 }
             },
+            AutodeployCommands::Tags {
+                app,
+                pattern,
+                disable,
+            } => {
+                flaase::cli::autodeploy::tags(&app, pattern.as_deref(), disable)?;
+                Ok(())
+            }
+            AutodeployCommands::Paths {
+                app,
+                patterns,
+                clear,
+            } => {
+                flaase::cli::autodeploy::paths(&app, &patterns, clear)?;
+                Ok(())
+            }
             AutodeployCommands::RateLimit {
                 app,
                 enable,
@@ -290,6 +596,7 @@ fn run_command(command: Commands, verbose: bool) -> Result<()> {
                     timeout,
                     required,
                     in_container,
+                    ephemeral,
                 } => {
                     flaase::cli::autodeploy::hooks_add(
                         &app,
@@ -299,6 +606,7 @@ fn run_command(command: Commands, verbose: bool) -> Result<()> {
                         Some(timeout),
                         required,
                         in_container,
+                        ephemeral,
                     )?;
                     Ok(())
                 }
@@ -371,8 +679,15 @@ fn run_command(command: Commands, verbose: bool) -> Result<()> {
                 cache,
                 buildkit,
                 cache_from,
+                cache_mode,
             } => {
-                flaase::cli::autodeploy::build_config(&app, cache, buildkit, cache_from.as_deref())?;
+                flaase::cli::autodeploy::build_config(
+                    &app,
+                    cache,
+                    buildkit,
+                    cache_from.as_deref(),
+                    cache_mode.as_deref(),
+                )?;
                 Ok(())
             }
             AutodeployCommands::BlueGreen {
@@ -381,8 +696,22 @@ fn run_command(command: Commands, verbose: bool) -> Result<()> {
                 disable,
                 keep_old,
                 no_auto_cleanup,
+                promote,
+                abort,
             } => {
-                flaase::cli::autodeploy::blue_green_config(&app, enable, disable, keep_old, no_auto_cleanup)?;
+                if promote {
+                    flaase::cli::autodeploy::blue_green_promote(&app, verbose)?;
+                } else if abort {
+                    flaase::cli::autodeploy::blue_green_abort(&app, verbose)?;
+                } else {
+                    flaase::cli::autodeploy::blue_green_config(
+                        &app,
+                        enable,
+                        disable,
+                        keep_old,
+                        no_auto_cleanup,
+                    )?;
+                }
                 Ok(())
             }
         },
@@ -416,6 +745,44 @@ fn run_command(command: Commands, verbose: bool) -> Result<()> {
             }
         },
 
+        Commands::Notify { command } => match command {
+            NotificationCommands::Set { app } => {
+                flaase::cli::notify::set(&app)?;
+                Ok(())
+            }
+            NotificationCommands::Test { app } => {
+                flaase::cli::autodeploy::notify_test(&app)?;
+                Ok(())
+            }
+            NotificationCommands::Status { app } => {
+                flaase::cli::autodeploy::notify_status(&app)?;
+                Ok(())
+            }
+        },
+
+        Commands::Cron { command } => match command {
+            CronCommands::List { app } => {
+                flaase::cli::cron::list(&app)?;
+                Ok(())
+            }
+            CronCommands::Add {
+                app,
+                name,
+                schedule,
+                command,
+            } => {
+                flaase::cli::cron::add(&app, &name, &schedule, &command)?;
+                Ok(())
+            }
+            CronCommands::Remove { app, name } => {
+                flaase::cli::cron::remove(&app, &name)?;
+                Ok(())
+            }
+            CronCommands::Run { app, name } => {
+                flaase::cli::cron::run(&app, &name, verbose)?;
+                Ok(())
+            }
+        },
         Commands::Webhook { command } => match command {
             WebhookCommands::Serve { port, host } => {
                 flaase::cli::webhook::serve(&host, port, verbose)?;
@@ -434,5 +801,36 @@ fn run_command(command: Commands, verbose: bool) -> Result<()> {
                 Ok(())
             }
         },
+
+        Commands::Db { command } => match command {
+            DbCommands::Backup {
+                app,
+                output,
+                schedule,
+                keep_days,
+                list,
+                unschedule,
+            } => {
+                if list {
+                    flaase::cli::db::list(&app)?;
+                } else if unschedule {
+                    flaase::cli::db::unschedule(&app)?;
+                } else if let Some(cron) = schedule {
+                    let keep_days = keep_days.unwrap_or(flaase::cli::db::DEFAULT_KEEP_DAYS);
+                    flaase::cli::db::schedule(&app, &cron, keep_days)?;
+                } else {
+                    flaase::cli::db::backup(&app, output.as_deref(), keep_days)?;
+                }
+                Ok(())
+            }
+            DbCommands::Restore { app, input, force } => {
+                flaase::cli::db::restore(&app, &input, force)?;
+                Ok(())
+            }
+            DbCommands::Connect { app } => {
+                flaase::cli::db::connect(&app)?;
+                Ok(())
+            }
+        },
     }
 }
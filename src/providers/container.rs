@@ -1,17 +1,23 @@
-use crate::core::config::ContainerRuntimeInfo;
+use std::sync::OnceLock;
+
+use crate::core::config::{ContainerRuntimeInfo, ServerConfig};
 use crate::core::context::ExecutionContext;
 use crate::core::error::AppError;
 use crate::providers::package_manager::PackageManager;
 
 /// Trait for container runtime operations.
-/// Designed to support Docker now and Kubernetes in the future.
-pub trait ContainerRuntime {
-    /// Returns the name of the runtime (e.g., "docker", "kubernetes").
+/// Implemented for Docker and Podman, which share a compatible CLI: container
+/// operations default to running that CLI through `command()`.
+pub trait ContainerRuntime: Send + Sync {
+    /// Returns the name of the runtime (e.g., "Docker", "Podman").
     fn name(&self) -> &str;
 
     /// Returns the runtime type identifier for config.
     fn runtime_type(&self) -> &str;
 
+    /// Returns the CLI binary used for container commands.
+    fn command(&self) -> &'static str;
+
     /// Checks if the runtime is installed.
     fn is_installed(&self, ctx: &ExecutionContext) -> Result<bool, AppError>;
 
@@ -34,33 +40,194 @@ pub trait ContainerRuntime {
     /// Checks if the runtime service is running.
     fn is_running(&self, ctx: &ExecutionContext) -> Result<bool, AppError>;
 
+    /// Applies default daemon settings (log rotation, storage driver).
+    /// Returns false if a custom daemon configuration was left untouched.
+    fn configure_daemon(&self, ctx: &ExecutionContext) -> Result<bool, AppError>;
+
     /// Gets runtime info for the server config.
     fn get_info(&self, ctx: &ExecutionContext) -> Result<ContainerRuntimeInfo, AppError>;
 
+    /// Rejects container settings the runtime cannot honour, before running it.
+    fn check_container_config(
+        &self,
+        _config: &ContainerConfig,
+        _ctx: &ExecutionContext,
+    ) -> Result<(), AppError> {
+        Ok(())
+    }
+
     /// Runs a container with the specified configuration.
     fn run_container(
         &self,
         config: &ContainerConfig,
         ctx: &ExecutionContext,
-    ) -> Result<String, AppError>;
+    ) -> Result<String, AppError> {
+        self.check_container_config(config, ctx)?;
+
+        let mut args = vec!["run", "-d", "--name", &config.name];
+
+        // Restart policy
+        args.push("--restart");
+        args.push(config.restart_policy.as_str());
+
+        // Network
+        if let Some(ref network) = config.network {
+            args.push("--network");
+            args.push(network);
+        }
+
+        // Collect formatted strings that need to live long enough
+        let port_mappings: Vec<String> = config
+            .ports
+            .iter()
+            .map(|p| match &p.host_ip {
+                Some(ip) => format!("{}:{}:{}", ip, p.host, p.container),
+                None => format!("{}:{}", p.host, p.container),
+            })
+            .collect();
+
+        let volume_mappings: Vec<String> = config
+            .volumes
+            .iter()
+            .map(|v| {
+                if v.readonly {
+                    format!("{}:{}:ro", v.host, v.container)
+                } else {
+                    format!("{}:{}", v.host, v.container)
+                }
+            })
+            .collect();
+
+        let env_mappings: Vec<String> = config
+            .environment
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect();
+
+        let label_mappings: Vec<String> = config
+            .labels
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect();
+
+        // Ports
+        for port in &port_mappings {
+            args.push("-p");
+            args.push(port);
+        }
+
+        // Volumes
+        for vol in &volume_mappings {
+            args.push("-v");
+            args.push(vol);
+        }
+
+        // Environment
+        for env in &env_mappings {
+            args.push("-e");
+            args.push(env);
+        }
+
+        // Environment files
+        for env_file in &config.env_files {
+            args.push("--env-file");
+            args.push(env_file);
+        }
+
+        // Labels
+        for label in &label_mappings {
+            args.push("-l");
+            args.push(label);
+        }
+
+        // Resource reservations
+        let cpu_shares = config.cpu_shares.map(|s| s.to_string());
+        if let Some(ref shares) = cpu_shares {
+            args.push("--cpu-shares");
+            args.push(shares);
+        }
+        if let Some(ref reservation) = config.memory_reservation {
+            args.push("--memory-reservation");
+            args.push(reservation);
+        }
+
+        // Hard limits
+        let limit_args = config.hard_limit_args();
+        args.extend(limit_args.iter().map(String::as_str));
+
+        // Image
+        args.push(&config.image);
+
+        // Command
+        let cmd_args: Vec<&str>;
+        if let Some(ref cmd) = config.command {
+            cmd_args = cmd.iter().map(|s| s.as_str()).collect();
+            args.extend(&cmd_args);
+        }
+
+        let output = ctx.run_command(self.command(), &args)?;
+        output.ensure_success(&format!("Failed to run container '{}'", config.name))?;
+
+        Ok(output.stdout.trim().to_string())
+    }
 
     /// Stops a container by name or ID.
-    fn stop_container(&self, name: &str, ctx: &ExecutionContext) -> Result<(), AppError>;
+    fn stop_container(&self, name: &str, ctx: &ExecutionContext) -> Result<(), AppError> {
+        ctx.run_command(self.command(), &["stop", name])?
+            .ensure_success(&format!("Failed to stop container '{}'", name))?;
+        Ok(())
+    }
 
     /// Removes a container by name or ID.
-    fn remove_container(&self, name: &str, ctx: &ExecutionContext) -> Result<(), AppError>;
+    fn remove_container(&self, name: &str, ctx: &ExecutionContext) -> Result<(), AppError> {
+        ctx.run_command(self.command(), &["rm", "-f", name])?
+            .ensure_success(&format!("Failed to remove container '{}'", name))?;
+        Ok(())
+    }
 
     /// Checks if a container exists.
-    fn container_exists(&self, name: &str, ctx: &ExecutionContext) -> Result<bool, AppError>;
+    fn container_exists(&self, name: &str, ctx: &ExecutionContext) -> Result<bool, AppError> {
+        let output = ctx.run_command(
+            self.command(),
+            &["ps", "-a", "--filter", &format!("name=^{}$", name), "-q"],
+        )?;
+        Ok(!output.stdout.trim().is_empty())
+    }
 
     /// Checks if a container is running.
-    fn container_is_running(&self, name: &str, ctx: &ExecutionContext) -> Result<bool, AppError>;
+    fn container_is_running(&self, name: &str, ctx: &ExecutionContext) -> Result<bool, AppError> {
+        let output = ctx.run_command(
+            self.command(),
+            &["ps", "--filter", &format!("name=^{}$", name), "-q"],
+        )?;
+        Ok(!output.stdout.trim().is_empty())
+    }
 
     /// Creates a network.
-    fn create_network(&self, name: &str, ctx: &ExecutionContext) -> Result<(), AppError>;
+    fn create_network(&self, name: &str, ctx: &ExecutionContext) -> Result<(), AppError> {
+        if self.network_exists(name, ctx)? {
+            return Ok(());
+        }
+
+        ctx.run_command(self.command(), &["network", "create", name])?
+            .ensure_success(&format!("Failed to create network '{}'", name))?;
+        Ok(())
+    }
 
     /// Checks if a network exists.
-    fn network_exists(&self, name: &str, ctx: &ExecutionContext) -> Result<bool, AppError>;
+    fn network_exists(&self, name: &str, ctx: &ExecutionContext) -> Result<bool, AppError> {
+        let output = ctx.run_command(
+            self.command(),
+            &[
+                "network",
+                "ls",
+                "--filter",
+                &format!("name=^{}$", name),
+                "-q",
+            ],
+        )?;
+        Ok(!output.stdout.trim().is_empty())
+    }
 
     /// Builds a Docker image from a Dockerfile.
     fn build_image(
@@ -68,19 +235,75 @@ pub trait ContainerRuntime {
         tag: &str,
         context_dir: &str,
         ctx: &ExecutionContext,
-    ) -> Result<(), AppError>;
+    ) -> Result<(), AppError> {
+        ctx.run_command_streaming(self.command(), &["build", "-t", tag, context_dir])?
+            .ensure_success(&format!("Failed to build image '{}'", tag))?;
+        Ok(())
+    }
 
     /// Checks if a port is available on the host.
-    fn is_port_available(&self, port: u16, ctx: &ExecutionContext) -> Result<bool, AppError>;
+    fn is_port_available(&self, port: u16, ctx: &ExecutionContext) -> Result<bool, AppError> {
+        // Check if any container is using this port
+        let output = ctx.run_command(
+            self.command(),
+            &[
+                "ps",
+                "--format",
+                "{{.Ports}}",
+            ],
+        )?;
+
+        let port_str = format!(":{}", port);
+        let port_mapping = format!("0.0.0.0:{}", port);
+
+        for line in output.stdout.lines() {
+            if line.contains(&port_str) || line.contains(&port_mapping) {
+                return Ok(false);
+            }
+        }
+
+        // Also check if the port is bound by a non-Docker process
+        let ss_output = ctx.run_command("ss", &["-tuln"]);
+        if let Ok(ss) = ss_output {
+            let port_pattern = format!(":{} ", port);
+            let port_pattern2 = format!(":{}\n", port);
+            if ss.stdout.contains(&port_pattern) || ss.stdout.contains(&port_pattern2) {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
 
     /// Gets logs from a container.
-    fn get_logs(&self, name: &str, lines: u32, ctx: &ExecutionContext) -> Result<String, AppError>;
+    fn get_logs(&self, name: &str, lines: u32, ctx: &ExecutionContext) -> Result<String, AppError> {
+        let lines_str = lines.to_string();
+        let output = ctx.run_command(self.command(), &["logs", "--tail", &lines_str, name])?;
+        Ok(format!("{}\n{}", output.stdout, output.stderr))
+    }
 
     /// Pulls a Docker image.
-    fn pull_image(&self, image: &str, ctx: &ExecutionContext) -> Result<(), AppError>;
+    fn pull_image(&self, image: &str, ctx: &ExecutionContext) -> Result<(), AppError> {
+        ctx.run_command_streaming(self.command(), &["pull", image])?
+            .ensure_success(&format!("Failed to pull image '{}'", image))?;
+        Ok(())
+    }
 
     /// Finds an available port starting from the given port.
-    fn find_available_port(&self, start: u16, ctx: &ExecutionContext) -> Result<u16, AppError>;
+    fn find_available_port(&self, start: u16, ctx: &ExecutionContext) -> Result<u16, AppError> {
+        let max_attempts = 100;
+
+        for port in (start..).take(max_attempts) {
+            if self.is_port_available(port, ctx)? {
+                return Ok(port);
+            }
+        }
+
+        Err(AppError::Config(format!(
+            "Could not find available port starting from {}",
+            start
+        )))
+    }
 
     /// Connects a container to an additional network.
     fn connect_network(
@@ -88,7 +311,35 @@ pub trait ContainerRuntime {
         container: &str,
         network: &str,
         ctx: &ExecutionContext,
-    ) -> Result<(), AppError>;
+    ) -> Result<(), AppError> {
+        // Check if network exists, create if not
+        if !self.network_exists(network, ctx)? {
+            self.create_network(network, ctx)?;
+        }
+
+        // Network names are reported the same way by Docker and Podman
+        let output = ctx.run_command(
+            self.command(),
+            &[
+                "inspect",
+                container,
+                "--format",
+                "{{range $name, $_ := .NetworkSettings.Networks}}{{$name}} {{end}}",
+            ],
+        )?;
+
+        if output.stdout.split_whitespace().any(|name| name == network) {
+            return Ok(()); // Already connected
+        }
+
+        ctx.run_command(self.command(), &["network", "connect", network, container])?
+            .ensure_success(&format!(
+                "Failed to connect container '{}' to network '{}'",
+                container, network
+            ))?;
+
+        Ok(())
+    }
 
     /// Executes a command inside a running container.
     fn exec_in_container(
@@ -96,7 +347,21 @@ pub trait ContainerRuntime {
         container: &str,
         command: &[&str],
         ctx: &ExecutionContext,
-    ) -> Result<String, AppError>;
+    ) -> Result<String, AppError> {
+        let mut args = vec!["exec", container];
+        args.extend(command);
+
+        let output = ctx.run_command(self.command(), &args)?;
+
+        if output.success {
+            Ok(output.stdout)
+        } else {
+            Err(AppError::Docker(format!(
+                "Command failed in container: {}",
+                output.stderr
+            )))
+        }
+    }
 }
 
 /// Configuration for running a container.
@@ -112,6 +377,10 @@ pub struct ContainerConfig {
     pub restart_policy: RestartPolicy,
     pub labels: Vec<(String, String)>,
     pub command: Option<Vec<String>>,
+    pub cpu_shares: Option<u32>,
+    pub memory_reservation: Option<String>,
+    pub memory_limit: Option<String>,
+    pub cpu_limit: Option<f64>,
 }
 
 impl ContainerConfig {
@@ -127,11 +396,29 @@ impl ContainerConfig {
             restart_policy: RestartPolicy::UnlessStopped,
             labels: Vec::new(),
             command: None,
+            cpu_shares: None,
+            memory_reservation: None,
+            memory_limit: None,
+            cpu_limit: None,
         }
     }
 
     pub fn port(mut self, host: u16, container: u16) -> Self {
-        self.ports.push(PortMapping { host, container });
+        self.ports.push(PortMapping {
+            host_ip: None,
+            host,
+            container,
+        });
+        self
+    }
+
+    /// Publishes a port on a single host address (e.g. 127.0.0.1 only).
+    pub fn port_on(mut self, host_ip: &str, host: u16, container: u16) -> Self {
+        self.ports.push(PortMapping {
+            host_ip: Some(host_ip.to_string()),
+            host,
+            container,
+        });
         self
     }
 
@@ -182,10 +469,43 @@ impl ContainerConfig {
         self.command = Some(cmd);
         self
     }
+
+    pub fn cpu_shares(mut self, shares: u32) -> Self {
+        self.cpu_shares = Some(shares);
+        self
+    }
+
+    pub fn memory_reservation(mut self, size: &str) -> Self {
+        self.memory_reservation = Some(size.to_string());
+        self
+    }
+
+    pub fn memory_limit(mut self, size: &str) -> Self {
+        self.memory_limit = Some(size.to_string());
+        self
+    }
+
+    pub fn cpu_limit(mut self, cpus: f64) -> Self {
+        self.cpu_limit = Some(cpus);
+        self
+    }
+
+    /// `run` arguments for the hard memory and CPU limits.
+    pub fn hard_limit_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(ref memory) = self.memory_limit {
+            args.extend(["--memory".to_string(), memory.clone()]);
+        }
+        if let Some(cpus) = self.cpu_limit {
+            args.extend(["--cpus".to_string(), cpus.to_string()]);
+        }
+        args
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct PortMapping {
+    pub host_ip: Option<String>,
     pub host: u16,
     pub container: u16,
 }
@@ -219,11 +539,27 @@ impl RestartPolicy {
 /// Docker implementation of ContainerRuntime.
 pub struct DockerRuntime;
 
+/// Docker daemon configuration file.
+const DOCKER_DAEMON_CONFIG_PATH: &str = "/etc/docker/daemon.json";
+
 impl DockerRuntime {
     pub fn new() -> Self {
         Self
     }
 
+    /// Default daemon configuration written by Flaase.
+    fn default_daemon_config() -> &'static str {
+        r#"{
+  "log-driver": "json-file",
+  "log-opts": {
+    "max-size": "10m",
+    "max-file": "3"
+  },
+  "storage-driver": "overlay2"
+}
+"#
+    }
+
     /// Required packages for Docker installation.
     fn required_packages() -> &'static [&'static str] {
         &["docker.io", "docker-compose-v2", "containerd"]
@@ -245,6 +581,10 @@ impl ContainerRuntime for DockerRuntime {
         "docker"
     }
 
+    fn command(&self) -> &'static str {
+        "docker"
+    }
+
     fn is_installed(&self, ctx: &ExecutionContext) -> Result<bool, AppError> {
         match ctx.run_command("which", &["docker"]) {
             Ok(output) => Ok(output.success),
@@ -289,6 +629,28 @@ impl ContainerRuntime for DockerRuntime {
         Ok(())
     }
 
+    fn configure_daemon(&self, ctx: &ExecutionContext) -> Result<bool, AppError> {
+        let desired = Self::default_daemon_config();
+
+        if let Ok(existing) = std::fs::read_to_string(DOCKER_DAEMON_CONFIG_PATH) {
+            let existing = existing.trim();
+            if existing == desired.trim() {
+                return Ok(true);
+            }
+            // Respect any custom configuration the user already has
+            if !existing.is_empty() && existing != "{}" {
+                return Ok(false);
+            }
+        }
+
+        ctx.create_dir("/etc/docker")?;
+        ctx.write_file(DOCKER_DAEMON_CONFIG_PATH, desired)?;
+        ctx.run_command("systemctl", &["restart", "docker"])?
+            .ensure_success("Failed to restart Docker service")?;
+
+        Ok(true)
+    }
+
     fn is_running(&self, ctx: &ExecutionContext) -> Result<bool, AppError> {
         let output = ctx.run_command("systemctl", &["is-active", "docker"])?;
         Ok(output.success && output.stdout.trim() == "active")
@@ -301,283 +663,210 @@ impl ContainerRuntime for DockerRuntime {
             version,
         })
     }
+}
 
-    fn run_container(
-        &self,
-        config: &ContainerConfig,
-        ctx: &ExecutionContext,
-    ) -> Result<String, AppError> {
-        let mut args = vec!["run", "-d", "--name", &config.name];
-
-        // Restart policy
-        args.push("--restart");
-        args.push(config.restart_policy.as_str());
-
-        // Network
-        if let Some(ref network) = config.network {
-            args.push("--network");
-            args.push(network);
-        }
-
-        // Collect formatted strings that need to live long enough
-        let port_mappings: Vec<String> = config
-            .ports
-            .iter()
-            .map(|p| format!("{}:{}", p.host, p.container))
-            .collect();
-
-        let volume_mappings: Vec<String> = config
-            .volumes
-            .iter()
-            .map(|v| {
-                if v.readonly {
-                    format!("{}:{}:ro", v.host, v.container)
-                } else {
-                    format!("{}:{}", v.host, v.container)
-                }
-            })
-            .collect();
-
-        let env_mappings: Vec<String> = config
-            .environment
-            .iter()
-            .map(|(k, v)| format!("{}={}", k, v))
-            .collect();
-
-        let label_mappings: Vec<String> = config
-            .labels
-            .iter()
-            .map(|(k, v)| format!("{}={}", k, v))
-            .collect();
-
-        // Ports
-        for port in &port_mappings {
-            args.push("-p");
-            args.push(port);
-        }
-
-        // Volumes
-        for vol in &volume_mappings {
-            args.push("-v");
-            args.push(vol);
-        }
+/// Podman implementation of ContainerRuntime.
+///
+/// Podman has no daemon: containers are started directly by the CLI and
+/// `podman-restart.service` brings back the ones with a restart policy
+/// after a reboot.
+pub struct PodmanRuntime;
 
-        // Environment
-        for env in &env_mappings {
-            args.push("-e");
-            args.push(env);
-        }
+/// Podman configuration file for container defaults.
+const PODMAN_CONTAINERS_CONFIG_PATH: &str = "/etc/containers/containers.conf";
 
-        // Environment files
-        for env_file in &config.env_files {
-            args.push("--env-file");
-            args.push(env_file);
-        }
-
-        // Labels
-        for label in &label_mappings {
-            args.push("-l");
-            args.push(label);
-        }
+/// Lowest port an unprivileged process may bind.
+const UNPRIVILEGED_PORT_START_PATH: &str = "/proc/sys/net/ipv4/ip_unprivileged_port_start";
 
-        // Image
-        args.push(&config.image);
-
-        // Command
-        let cmd_args: Vec<&str>;
-        if let Some(ref cmd) = config.command {
-            cmd_args = cmd.iter().map(|s| s.as_str()).collect();
-            args.extend(&cmd_args);
-        }
+impl PodmanRuntime {
+    pub fn new() -> Self {
+        Self
+    }
 
-        let output = ctx.run_command("docker", &args)?;
-        output.ensure_success(&format!("Failed to run container '{}'", config.name))?;
+    /// Default container settings written by Flaase.
+    fn default_containers_config() -> &'static str {
+        r#"# Generated by Flaase
+[containers]
+log_driver = "k8s-file"
+log_size_max = 10485760
+"#
+    }
 
-        Ok(output.stdout.trim().to_string())
+    /// Returns true when Podman runs rootless, i.e. without root privileges.
+    pub fn is_rootless(&self, ctx: &ExecutionContext) -> bool {
+        ctx.run_command("podman", &["info", "--format", "{{.Host.Security.Rootless}}"])
+            .map(|output| output.success && output.stdout.trim() == "true")
+            .unwrap_or(false)
     }
+}
 
-    fn stop_container(&self, name: &str, ctx: &ExecutionContext) -> Result<(), AppError> {
-        ctx.run_command("docker", &["stop", name])?
-            .ensure_success(&format!("Failed to stop container '{}'", name))?;
-        Ok(())
+impl Default for PodmanRuntime {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
-    fn remove_container(&self, name: &str, ctx: &ExecutionContext) -> Result<(), AppError> {
-        ctx.run_command("docker", &["rm", "-f", name])?
-            .ensure_success(&format!("Failed to remove container '{}'", name))?;
-        Ok(())
+impl ContainerRuntime for PodmanRuntime {
+    fn name(&self) -> &str {
+        "Podman"
     }
 
-    fn container_exists(&self, name: &str, ctx: &ExecutionContext) -> Result<bool, AppError> {
-        let output = ctx.run_command(
-            "docker",
-            &["ps", "-a", "--filter", &format!("name=^{}$", name), "-q"],
-        )?;
-        Ok(!output.stdout.trim().is_empty())
+    fn runtime_type(&self) -> &str {
+        "podman"
     }
 
-    fn container_is_running(&self, name: &str, ctx: &ExecutionContext) -> Result<bool, AppError> {
-        let output = ctx.run_command(
-            "docker",
-            &["ps", "--filter", &format!("name=^{}$", name), "-q"],
-        )?;
-        Ok(!output.stdout.trim().is_empty())
+    fn command(&self) -> &'static str {
+        "podman"
     }
 
-    fn create_network(&self, name: &str, ctx: &ExecutionContext) -> Result<(), AppError> {
-        if self.network_exists(name, ctx)? {
-            return Ok(());
+    fn is_installed(&self, ctx: &ExecutionContext) -> Result<bool, AppError> {
+        match ctx.run_command("which", &["podman"]) {
+            Ok(output) => Ok(output.success),
+            Err(_) => Ok(false),
         }
-
-        ctx.run_command("docker", &["network", "create", name])?
-            .ensure_success(&format!("Failed to create network '{}'", name))?;
-        Ok(())
     }
 
-    fn network_exists(&self, name: &str, ctx: &ExecutionContext) -> Result<bool, AppError> {
-        let output = ctx.run_command(
-            "docker",
-            &[
-                "network",
-                "ls",
-                "--filter",
-                &format!("name=^{}$", name),
-                "-q",
-            ],
-        )?;
-        Ok(!output.stdout.trim().is_empty())
+    fn get_version(&self, ctx: &ExecutionContext) -> Result<String, AppError> {
+        let output = ctx.run_command("podman", &["--version"])?;
+        output.ensure_success("Failed to get Podman version")?;
+
+        // Parse version from "podman version 4.9.3"
+        let version = output
+            .stdout
+            .split_whitespace()
+            .nth(2)
+            .map(|v| v.to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        Ok(version)
     }
 
-    fn build_image(
+    fn install(
         &self,
-        tag: &str,
-        context_dir: &str,
+        pkg_manager: &dyn PackageManager,
         ctx: &ExecutionContext,
     ) -> Result<(), AppError> {
-        ctx.run_command_streaming("docker", &["build", "-t", tag, context_dir])?
-            .ensure_success(&format!("Failed to build image '{}'", tag))?;
+        pkg_manager.update(ctx)?;
+        pkg_manager.install(&["podman"], ctx)?;
         Ok(())
     }
 
-    fn is_port_available(&self, port: u16, ctx: &ExecutionContext) -> Result<bool, AppError> {
-        // Check if any container is using this port
-        let output = ctx.run_command(
-            "docker",
-            &[
-                "ps",
-                "--format",
-                "{{.Ports}}",
-            ],
-        )?;
+    fn start_service(&self, ctx: &ExecutionContext) -> Result<(), AppError> {
+        // Nothing to start, but restore containers that should be running
+        ctx.run_command("systemctl", &["start", "podman-restart.service"])?
+            .ensure_success("Failed to start podman-restart service")?;
+        Ok(())
+    }
 
-        let port_str = format!(":{}", port);
-        let port_mapping = format!("0.0.0.0:{}", port);
+    fn enable_service(&self, ctx: &ExecutionContext) -> Result<(), AppError> {
+        ctx.run_command("systemctl", &["enable", "podman-restart.service"])?
+            .ensure_success("Failed to enable podman-restart service")?;
+        Ok(())
+    }
 
-        for line in output.stdout.lines() {
-            if line.contains(&port_str) || line.contains(&port_mapping) {
-                return Ok(false);
-            }
-        }
+    fn configure_daemon(&self, ctx: &ExecutionContext) -> Result<bool, AppError> {
+        let desired = Self::default_containers_config();
 
-        // Also check if the port is bound by a non-Docker process
-        let ss_output = ctx.run_command("ss", &["-tuln"]);
-        if let Ok(ss) = ss_output {
-            let port_pattern = format!(":{} ", port);
-            let port_pattern2 = format!(":{}\n", port);
-            if ss.stdout.contains(&port_pattern) || ss.stdout.contains(&port_pattern2) {
+        if let Ok(existing) = std::fs::read_to_string(PODMAN_CONTAINERS_CONFIG_PATH) {
+            let existing = existing.trim();
+            if existing == desired.trim() {
+                return Ok(true);
+            }
+            // Respect any custom configuration the user already has
+            if !existing.is_empty() {
                 return Ok(false);
             }
         }
 
-        Ok(true)
-    }
+        ctx.create_dir("/etc/containers")?;
+        ctx.write_file(PODMAN_CONTAINERS_CONFIG_PATH, desired)?;
 
-    fn get_logs(&self, name: &str, lines: u32, ctx: &ExecutionContext) -> Result<String, AppError> {
-        let lines_str = lines.to_string();
-        let output = ctx.run_command("docker", &["logs", "--tail", &lines_str, name])?;
-        Ok(format!("{}\n{}", output.stdout, output.stderr))
+        Ok(true)
     }
 
-    fn pull_image(&self, image: &str, ctx: &ExecutionContext) -> Result<(), AppError> {
-        ctx.run_command_streaming("docker", &["pull", image])?
-            .ensure_success(&format!("Failed to pull image '{}'", image))?;
-        Ok(())
+    fn is_running(&self, ctx: &ExecutionContext) -> Result<bool, AppError> {
+        let output = ctx.run_command("podman", &["info", "--format", "{{.Host.Arch}}"])?;
+        Ok(output.success)
     }
 
-    fn find_available_port(&self, start: u16, ctx: &ExecutionContext) -> Result<u16, AppError> {
-        let mut port = start;
-        let max_attempts = 100;
-
-        for _ in 0..max_attempts {
-            if self.is_port_available(port, ctx)? {
-                return Ok(port);
-            }
-            port += 1;
-        }
-
-        Err(AppError::Config(format!(
-            "Could not find available port starting from {}",
-            start
-        )))
+    fn get_info(&self, ctx: &ExecutionContext) -> Result<ContainerRuntimeInfo, AppError> {
+        let version = self.get_version(ctx)?;
+        Ok(ContainerRuntimeInfo {
+            runtime_type: self.runtime_type().to_string(),
+            version,
+        })
     }
 
-    fn connect_network(
+    fn check_container_config(
         &self,
-        container: &str,
-        network: &str,
+        config: &ContainerConfig,
         ctx: &ExecutionContext,
     ) -> Result<(), AppError> {
-        // Check if already connected
-        let output = ctx.run_command(
-            "docker",
-            &["inspect", container, "--format", "{{range .NetworkSettings.Networks}}{{.NetworkID}}{{end}}"],
-        )?;
-
-        // Check if network exists, create if not
-        if !self.network_exists(network, ctx)? {
-            self.create_network(network, ctx)?;
+        if config.ports.is_empty() || ctx.is_dry_run() || !self.is_rootless(ctx) {
+            return Ok(());
         }
 
-        // Get network ID to check if already connected
-        let network_id = ctx.run_command("docker", &["network", "inspect", network, "-f", "{{.Id}}"])?;
-
-        if output.stdout.contains(&network_id.stdout[..12]) {
-            return Ok(()); // Already connected
+        // Rootless Podman can't publish privileged ports (e.g. Traefik's 80/443)
+        let port_start = std::fs::read_to_string(UNPRIVILEGED_PORT_START_PATH)
+            .ok()
+            .and_then(|v| v.trim().parse::<u16>().ok())
+            .unwrap_or(1024);
+
+        if let Some(port) = config.ports.iter().find(|p| p.host < port_start) {
+            return Err(AppError::Docker(format!(
+                "Rootless Podman cannot publish port {} for '{}'. Allow it with \
+                 'sysctl -w net.ipv4.ip_unprivileged_port_start={}' (persist it in \
+                 /etc/sysctl.d/) or run Flaase as root.",
+                port.host, config.name, port.host
+            )));
         }
 
-        ctx.run_command("docker", &["network", "connect", network, container])?
-            .ensure_success(&format!(
-                "Failed to connect container '{}' to network '{}'",
-                container, network
-            ))?;
-
         Ok(())
     }
+}
 
-    fn exec_in_container(
-        &self,
-        container: &str,
-        command: &[&str],
-        ctx: &ExecutionContext,
-    ) -> Result<String, AppError> {
-        let mut args = vec!["exec", container];
-        args.extend(command);
+/// Creates the container runtime configured for this server.
+///
+/// Uses the runtime recorded by `fl server init`; before init, Podman is
+/// picked when it is installed and Docker is not (or `docker` is Podman's
+/// compatibility wrapper).
+pub fn create_container_runtime() -> Box<dyn ContainerRuntime> {
+    match container_cli() {
+        "podman" => Box::new(PodmanRuntime::new()),
+        _ => Box::new(DockerRuntime::new()),
+    }
+}
 
-        let output = ctx.run_command("docker", &args)?;
+/// Returns the CLI binary of the server's container runtime, for code that
+/// runs container commands without a `ContainerRuntime` at hand.
+pub fn container_cli() -> &'static str {
+    static CLI: OnceLock<&'static str> = OnceLock::new();
+
+    CLI.get_or_init(|| {
+        if let Ok(config) = ServerConfig::load() {
+            return match config.server.container_runtime.runtime_type.as_str() {
+                "podman" => "podman",
+                _ => "docker",
+            };
+        }
 
-        if output.success {
-            Ok(output.stdout)
-        } else {
-            Err(AppError::Docker(format!(
-                "Command failed in container: {}",
-                output.stderr
-            )))
+        let ctx = ExecutionContext::new(false, false);
+        if !PodmanRuntime::new().is_installed(&ctx).unwrap_or(false) {
+            return "docker";
+        }
+        if !DockerRuntime::new().is_installed(&ctx).unwrap_or(false) {
+            return "podman";
         }
-    }
-}
 
-/// Creates the appropriate container runtime.
-/// Currently only Docker is supported.
-pub fn create_container_runtime() -> Box<dyn ContainerRuntime> {
-    Box::new(DockerRuntime::new())
+        // podman-docker installs a `docker` wrapper that runs Podman
+        let wraps_podman = ctx
+            .run_command("docker", &["--version"])
+            .map(|output| output.stdout.to_lowercase().contains("podman"))
+            .unwrap_or(false);
+        if wraps_podman {
+            "podman"
+        } else {
+            "docker"
+        }
+    })
 }
@@ -106,6 +106,143 @@ impl GitProvider {
         Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
     }
 
+    /// Gets the first line of the current commit message.
+    pub fn get_commit_message(repo_dir: &Path) -> Result<String, AppError> {
+        let output = std::process::Command::new("git")
+            .current_dir(repo_dir)
+            .args(["log", "-1", "--format=%s"])
+            .output()
+            .map_err(|e| AppError::Git(format!("Failed to get commit message: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(AppError::Git("Failed to get commit message".into()));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Gets the currently checked out branch.
+    pub fn get_branch(repo_dir: &Path) -> Result<String, AppError> {
+        let output = std::process::Command::new("git")
+            .current_dir(repo_dir)
+            .args(["rev-parse", "--abbrev-ref", "HEAD"])
+            .output()
+            .map_err(|e| AppError::Git(format!("Failed to get branch: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(AppError::Git("Failed to get branch".into()));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Fetches a remote branch and checks it out, tracking `origin/<branch>`.
+    pub fn checkout_branch(
+        repo_dir: &Path,
+        branch: &str,
+        ssh_key: &Path,
+    ) -> Result<(), AppError> {
+        let ssh_command = format!(
+            "ssh -i {} -o StrictHostKeyChecking=accept-new -o BatchMode=yes",
+            ssh_key.display()
+        );
+
+        // Shallow clones only know the default branch, so fetch it explicitly
+        let refspec = format!("+refs/heads/{0}:refs/remotes/origin/{0}", branch);
+        let fetch_output = std::process::Command::new("git")
+            .current_dir(repo_dir)
+            .env("GIT_SSH_COMMAND", &ssh_command)
+            .args(["fetch", "--depth", "1", "origin", &refspec])
+            .output()
+            .map_err(|e| AppError::Git(format!("Failed to fetch branch: {}", e)))?;
+
+        if !fetch_output.status.success() {
+            let stderr = String::from_utf8_lossy(&fetch_output.stderr);
+            return Err(AppError::Git(format!(
+                "Failed to fetch branch '{}': {}",
+                branch, stderr
+            )));
+        }
+
+        let remote_ref = format!("origin/{}", branch);
+        let checkout_output = std::process::Command::new("git")
+            .current_dir(repo_dir)
+            .args(["checkout", "-B", branch, &remote_ref])
+            .output()
+            .map_err(|e| AppError::Git(format!("Failed to checkout branch: {}", e)))?;
+
+        if !checkout_output.status.success() {
+            let stderr = String::from_utf8_lossy(&checkout_output.stderr);
+            return Err(AppError::Git(format!(
+                "Failed to checkout branch '{}': {}",
+                branch, stderr
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Fetches a tag and checks it out as a detached HEAD.
+    /// Returns whether HEAD moved.
+    pub fn checkout_tag(
+        repo_dir: &Path,
+        tag: &str,
+        ssh_key: &Path,
+        ctx: &ExecutionContext,
+    ) -> Result<bool, AppError> {
+        let ssh_command = format!(
+            "core.sshCommand=ssh -i {} -o StrictHostKeyChecking=accept-new -o BatchMode=yes",
+            ssh_key.display()
+        );
+        let repo = repo_dir.to_string_lossy();
+
+        let before = Self::get_commit_hash(repo_dir).ok();
+
+        // Force the refspec so a re-pushed tag replaces the local one
+        let refspec = format!("+refs/tags/{0}:refs/tags/{0}", tag);
+        let fetch_output = ctx.run_command(
+            "git",
+            &["-C", &repo, "-c", &ssh_command, "fetch", "origin", &refspec],
+        )?;
+        if !fetch_output.success {
+            return Err(AppError::Git(format!(
+                "Failed to fetch tag '{}': {}",
+                tag, fetch_output.stderr
+            )));
+        }
+
+        let tag_ref = format!("refs/tags/{}", tag);
+        let checkout_output =
+            ctx.run_command("git", &["-C", &repo, "checkout", "--detach", &tag_ref])?;
+        if !checkout_output.success {
+            return Err(AppError::Git(format!(
+                "Failed to checkout tag '{}': {}",
+                tag, checkout_output.stderr
+            )));
+        }
+
+        Ok(before != Self::get_commit_hash(repo_dir).ok())
+    }
+
+    /// Checks that a name is a valid git branch name.
+    pub fn validate_branch_name(branch: &str) -> Result<(), AppError> {
+        let valid = !branch.starts_with('-')
+            && std::process::Command::new("git")
+                .args(["check-ref-format", "--branch", branch])
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false);
+
+        if valid {
+            Ok(())
+        } else {
+            Err(AppError::Validation(format!(
+                "Invalid branch name '{}'",
+                branch
+            )))
+        }
+    }
+
     /// Checks if a directory is a git repository.
     pub fn is_repo(path: &Path) -> bool {
         path.join(".git").exists()
@@ -7,13 +7,18 @@ pub mod ssh;
 pub mod system;
 pub mod webhook;
 
-pub use container::{create_container_runtime, ContainerConfig, ContainerRuntime, DockerRuntime};
+pub use container::{
+    container_cli, create_container_runtime, ContainerConfig, ContainerRuntime, DockerRuntime,
+    PodmanRuntime,
+};
 pub use firewall::{
     create_firewall, Firewall, FirewallPolicy, Protocol, RequiredPorts, UfwFirewall,
 };
 pub use git::GitProvider;
 pub use package_manager::{create_package_manager, AptManager, PackageManager};
 pub use reverse_proxy::{create_reverse_proxy, ReverseProxy, TraefikProxy};
-pub use ssh::{SshKeyInfo, SshKeyType, SshProvider};
-pub use system::{SystemProvider, UserInfo, UserManager};
-pub use webhook::{GitHubPushEvent, WebhookProvider, FLAASE_WEBHOOK_PATH};
+pub use ssh::{GitConnectionStatus, SshKeyInfo, SshKeyType, SshProvider};
+pub use system::{KernelWarning, PortListener, SystemProvider, UserInfo, UserManager};
+pub use webhook::{
+    LogStreamOptions, PushEvent, WebhookProvider, WebhookSource, FLAASE_WEBHOOK_PATH,
+};
@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::core::config::{ReverseProxyInfo, FLAASE_TRAEFIK_DYNAMIC_PATH, FLAASE_TRAEFIK_PATH};
 use crate::core::context::ExecutionContext;
@@ -7,7 +7,7 @@ use crate::providers::container::{ContainerConfig, ContainerRuntime, RestartPoli
 
 /// Trait for reverse proxy operations.
 /// Allows for different implementations (Traefik, Nginx, Caddy, etc.).
-pub trait ReverseProxy {
+pub trait ReverseProxy: Send + Sync {
     /// Returns the name of the reverse proxy.
     fn name(&self) -> &str;
 
@@ -28,11 +28,13 @@ pub trait ReverseProxy {
         ctx: &ExecutionContext,
     ) -> Result<String, AppError>;
 
-    /// Installs and starts the reverse proxy.
+    /// Installs and starts the reverse proxy. With `acme_staging`,
+    /// certificates come from the Let's Encrypt staging CA.
     fn install(
         &self,
         runtime: &dyn ContainerRuntime,
         email: &str,
+        acme_staging: bool,
         ctx: &ExecutionContext,
     ) -> Result<(), AppError>;
 
@@ -64,24 +66,10 @@ pub trait ReverseProxy {
     fn create_config_dirs(&self, ctx: &ExecutionContext) -> Result<(), AppError>;
 
     /// Writes the static configuration.
-    fn write_static_config(&self, email: &str, ctx: &ExecutionContext) -> Result<(), AppError>;
-
-    /// Writes the dynamic configuration for an app.
-    fn write_app_config(
-        &self,
-        app_name: &str,
-        domain: &str,
-        container_port: u16,
-        ctx: &ExecutionContext,
-    ) -> Result<(), AppError>;
-
-    /// Writes the dynamic configuration for an app with auth support.
-    fn write_app_config_with_auth(
+    fn write_static_config(
         &self,
-        app_name: &str,
-        domain: &str,
-        container_port: u16,
-        auth_htpasswd: Option<&str>,
+        email: &str,
+        acme_staging: bool,
         ctx: &ExecutionContext,
     ) -> Result<(), AppError>;
 
@@ -93,6 +81,9 @@ pub trait ReverseProxy {
         -> Result<(), AppError>;
 }
 
+/// ACME directory of the Let's Encrypt staging environment.
+const LETS_ENCRYPT_STAGING_URL: &str = "https://acme-staging-v02.api.letsencrypt.org/directory";
+
 /// Traefik reverse proxy implementation.
 pub struct TraefikProxy {
     container_name: String,
@@ -120,7 +111,13 @@ impl TraefikProxy {
     }
 
     /// Generates the static Traefik configuration.
-    fn generate_static_config(&self, email: &str) -> String {
+    fn generate_static_config(&self, email: &str, acme_staging: bool) -> String {
+        let ca_server = if acme_staging {
+            format!("\n      caServer: {}", LETS_ENCRYPT_STAGING_URL)
+        } else {
+            String::new()
+        };
+
         format!(
             r#"# Traefik static configuration
 # Generated by Flaase
@@ -143,7 +140,7 @@ providers:
 certificatesResolvers:
   letsencrypt:
     acme:
-      email: {email}
+      email: {email}{ca_server}
       storage: /etc/traefik/acme.json
       httpChallenge:
         entryPoint: web
@@ -153,7 +150,8 @@ log:
 
 accessLog: {{}}
 "#,
-            email = email
+            email = email,
+            ca_server = ca_server
         )
     }
 
@@ -178,6 +176,45 @@ accessLog: {{}}
     }
 }
 
+/// A Traefik dynamic config file that Traefik will refuse to load.
+#[derive(Debug, Clone)]
+pub struct InvalidProxyConfig {
+    pub path: PathBuf,
+    pub error: String,
+}
+
+/// Parses every YAML file in a Traefik dynamic config directory and
+/// returns the ones that are not valid YAML, sorted by path.
+pub fn find_invalid_dynamic_configs(dir: &Path) -> Vec<InvalidProxyConfig> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut invalid: Vec<InvalidProxyConfig> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && matches!(
+                    path.extension().and_then(|e| e.to_str()),
+                    Some("yml") | Some("yaml")
+                )
+        })
+        .filter_map(|path| {
+            let error = match std::fs::read_to_string(&path) {
+                Ok(content) => serde_yaml::from_str::<serde_yaml::Value>(&content)
+                    .err()?
+                    .to_string(),
+                Err(e) => format!("unreadable: {}", e),
+            };
+            Some(InvalidProxyConfig { path, error })
+        })
+        .collect();
+
+    invalid.sort_by(|a, b| a.path.cmp(&b.path));
+    invalid
+}
+
 impl Default for TraefikProxy {
     fn default() -> Self {
         Self::new()
@@ -211,7 +248,7 @@ impl ReverseProxy for TraefikProxy {
         }
 
         let output = ctx.run_command(
-            "docker",
+            runtime.command(),
             &["exec", &self.container_name, "traefik", "version"],
         )?;
 
@@ -231,13 +268,14 @@ impl ReverseProxy for TraefikProxy {
         &self,
         runtime: &dyn ContainerRuntime,
         email: &str,
+        acme_staging: bool,
         ctx: &ExecutionContext,
     ) -> Result<(), AppError> {
         // Create config directories
         self.create_config_dirs(ctx)?;
 
         // Write static configuration
-        self.write_static_config(email, ctx)?;
+        self.write_static_config(email, acme_staging, ctx)?;
 
         // Create acme.json with proper permissions
         let acme_path = format!("{}/acme.json", FLAASE_TRAEFIK_PATH);
@@ -307,40 +345,14 @@ impl ReverseProxy for TraefikProxy {
         Ok(())
     }
 
-    fn write_static_config(&self, email: &str, ctx: &ExecutionContext) -> Result<(), AppError> {
-        let config = self.generate_static_config(email);
-        let path = format!("{}/traefik.yml", FLAASE_TRAEFIK_PATH);
-        ctx.write_file(&path, &config)
-    }
-
-    fn write_app_config(
+    fn write_static_config(
         &self,
-        app_name: &str,
-        domain: &str,
-        container_port: u16,
-        ctx: &ExecutionContext,
-    ) -> Result<(), AppError> {
-        self.write_app_config_with_auth(app_name, domain, container_port, None, ctx)
-    }
-
-    fn write_app_config_with_auth(
-        &self,
-        app_name: &str,
-        domain: &str,
-        container_port: u16,
-        auth_htpasswd: Option<&str>,
+        email: &str,
+        acme_staging: bool,
         ctx: &ExecutionContext,
     ) -> Result<(), AppError> {
-        use crate::templates::traefik::{generate_app_config, AppDomain};
-
-        let mut app_domain = AppDomain::new(domain, true);
-        if let Some(htpasswd) = auth_htpasswd {
-            app_domain = app_domain.with_auth(htpasswd);
-        }
-
-        let domains = vec![app_domain];
-        let config = generate_app_config(app_name, &domains, container_port);
-        let path = format!("{}/{}.yml", FLAASE_TRAEFIK_DYNAMIC_PATH, app_name);
+        let config = self.generate_static_config(email, acme_staging);
+        let path = format!("{}/traefik.yml", FLAASE_TRAEFIK_PATH);
         ctx.write_file(&path, &config)
     }
 
@@ -379,3 +391,35 @@ impl ReverseProxy for TraefikProxy {
 pub fn create_reverse_proxy() -> Box<dyn ReverseProxy> {
     Box::new(TraefikProxy::new())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_static_config_acme_staging() {
+        let proxy = TraefikProxy::new();
+        assert!(!proxy.generate_static_config("a@b.c", false).contains("caServer"));
+
+        let staging = proxy.generate_static_config("a@b.c", true);
+        let yaml: serde_yaml::Value = serde_yaml::from_str(&staging).unwrap();
+        let acme = &yaml["certificatesResolvers"]["letsencrypt"]["acme"];
+        assert_eq!(acme["caServer"].as_str(), Some(LETS_ENCRYPT_STAGING_URL));
+        assert_eq!(acme["email"].as_str(), Some("a@b.c"));
+    }
+
+    #[test]
+    fn test_find_invalid_dynamic_configs() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("good.yml"), "http:\n  routers: {}\n").unwrap();
+        std::fs::write(dir.path().join("broken.yml"), "http:\n  routers: [\n").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "not: [yaml").unwrap();
+
+        let invalid = find_invalid_dynamic_configs(dir.path());
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].path, dir.path().join("broken.yml"));
+        assert!(!invalid[0].error.is_empty());
+
+        assert!(find_invalid_dynamic_configs(&dir.path().join("missing")).is_empty());
+    }
+}
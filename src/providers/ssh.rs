@@ -68,6 +68,134 @@ impl SshKeyInfo {
     }
 }
 
+/// Outcome of an SSH connection test against a Git host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GitConnectionStatus {
+    Connected,
+    /// The host key could not be verified (usually a changed key).
+    HostKeyRejected,
+    /// The host refused the key.
+    PermissionDenied,
+    /// The host could not be reached (DNS, firewall, timeout).
+    Unreachable,
+    /// Any other failure, with the last line ssh printed.
+    Failed(String),
+}
+
+impl GitConnectionStatus {
+    /// Classifies the result of `ssh -T git@host`.
+    fn from_output(success: bool, stdout: &str, stderr: &str) -> Self {
+        // GitHub/GitLab/Bitbucket exit non-zero but print a greeting
+        let greetings = [
+            "successfully authenticated",
+            "Welcome to GitLab",
+            "authenticated via ssh key",
+        ];
+        if success || greetings.iter().any(|g| stdout.contains(g) || stderr.contains(g)) {
+            return GitConnectionStatus::Connected;
+        }
+
+        if stderr.contains("Host key verification failed")
+            || stderr.contains("REMOTE HOST IDENTIFICATION HAS CHANGED")
+        {
+            return GitConnectionStatus::HostKeyRejected;
+        }
+
+        if stderr.contains("Permission denied") {
+            return GitConnectionStatus::PermissionDenied;
+        }
+
+        let unreachable = [
+            "Could not resolve hostname",
+            "Connection timed out",
+            "Connection refused",
+            "Network is unreachable",
+            "No route to host",
+            "Operation timed out",
+        ];
+        if unreachable.iter().any(|m| stderr.contains(m)) {
+            return GitConnectionStatus::Unreachable;
+        }
+
+        let last_line = stderr
+            .lines()
+            .rev()
+            .map(str::trim)
+            .find(|l| !l.is_empty())
+            .unwrap_or("ssh exited without output");
+        GitConnectionStatus::Failed(last_line.to_string())
+    }
+
+    /// Returns true when the key was accepted.
+    pub fn is_connected(&self) -> bool {
+        matches!(self, GitConnectionStatus::Connected)
+    }
+
+    /// Returns a short description of why the test failed.
+    pub fn reason(&self, host: &str) -> String {
+        match self {
+            GitConnectionStatus::Connected => format!("Connected to {}", host),
+            GitConnectionStatus::HostKeyRejected => {
+                format!("Host key for {} was not accepted", host)
+            }
+            GitConnectionStatus::PermissionDenied => {
+                format!("Permission denied (publickey) by {}", host)
+            }
+            GitConnectionStatus::Unreachable => format!("{} is unreachable", host),
+            GitConnectionStatus::Failed(message) => format!("SSH test failed: {}", message),
+        }
+    }
+
+    /// Returns guidance for fixing the failure, specific to the Git provider.
+    pub fn hint(&self, repo_url: &str) -> Option<String> {
+        let host = SshProvider::extract_host(repo_url).unwrap_or_default();
+        match self {
+            GitConnectionStatus::Connected => None,
+            GitConnectionStatus::HostKeyRejected => Some(format!(
+                "The host key changed since it was first trusted. If this is expected, \
+                 run 'ssh-keygen -R {}' and try again.",
+                host
+            )),
+            GitConnectionStatus::PermissionDenied => Some(deploy_key_hint(repo_url, &host)),
+            GitConnectionStatus::Unreachable => Some(format!(
+                "Check that {} resolves and that outbound port 22 is allowed by the firewall.",
+                host
+            )),
+            GitConnectionStatus::Failed(_) => None,
+        }
+    }
+}
+
+/// Tells where to register the public key for the repository's provider.
+fn deploy_key_hint(repo_url: &str, host: &str) -> String {
+    let repo_path = repo_url
+        .split_once(':')
+        .map(|(_, path)| path.trim_end_matches(".git"))
+        .unwrap_or_default();
+
+    if host.contains("github") {
+        format!(
+            "Add the public key to the repository's GitHub deploy keys: \
+             https://{}/{}/settings/keys",
+            host, repo_path
+        )
+    } else if host.contains("gitlab") {
+        format!(
+            "Add the public key to the project's GitLab deploy keys: \
+             https://{}/{}/-/settings/repository",
+            host, repo_path
+        )
+    } else if host.contains("bitbucket") {
+        format!(
+            "Add the public key to the repository's Bitbucket access keys: \
+             https://{}/{}/admin/access-keys/",
+            host, repo_path
+        )
+    } else {
+        format!("Add the public key as a deploy key for {} on {}", repo_path, host)
+    }
+}
+
 /// SSH provider for key management and connection testing.
 pub struct SshProvider;
 
@@ -231,7 +359,7 @@ impl SshProvider {
         repo_url: &str,
         key_path: &Path,
         ctx: &ExecutionContext,
-    ) -> Result<bool, AppError> {
+    ) -> Result<GitConnectionStatus, AppError> {
         // Extract host from SSH URL (git@github.com:user/repo.git -> github.com)
         let host = Self::extract_host(repo_url)?;
 
@@ -252,19 +380,15 @@ impl SshProvider {
             ],
         )?;
 
-        // GitHub/GitLab return exit code 1 but with successful message
-        // Check stderr for success indicators
-        let success = output.success
-            || output.stderr.contains("successfully authenticated")
-            || output.stderr.contains("Welcome to GitLab")
-            || output.stdout.contains("successfully authenticated")
-            || output.stdout.contains("Welcome to GitLab");
-
-        Ok(success)
+        Ok(GitConnectionStatus::from_output(
+            output.success,
+            &output.stdout,
+            &output.stderr,
+        ))
     }
 
     /// Extracts the host from a Git SSH URL.
-    fn extract_host(url: &str) -> Result<String, AppError> {
+    pub fn extract_host(url: &str) -> Result<String, AppError> {
         // Format: git@github.com:user/repo.git
         let without_prefix = url
             .strip_prefix("git@")
@@ -303,6 +427,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_connection_status_from_output() {
+        let github_ok = "Hi user! You've successfully authenticated, \
+                         but GitHub does not provide shell access.";
+        assert!(GitConnectionStatus::from_output(false, "", github_ok).is_connected());
+        assert_eq!(
+            GitConnectionStatus::from_output(
+                false,
+                "",
+                "git@github.com: Permission denied (publickey)."
+            ),
+            GitConnectionStatus::PermissionDenied
+        );
+        assert_eq!(
+            GitConnectionStatus::from_output(false, "", "Host key verification failed."),
+            GitConnectionStatus::HostKeyRejected
+        );
+        assert_eq!(
+            GitConnectionStatus::from_output(
+                false,
+                "",
+                "ssh: Could not resolve hostname gitlab.example.com: Name or service not known"
+            ),
+            GitConnectionStatus::Unreachable
+        );
+        assert_eq!(
+            GitConnectionStatus::from_output(false, "", "warning\nkex_exchange_identification\n"),
+            GitConnectionStatus::Failed("kex_exchange_identification".into())
+        );
+    }
+
+    #[test]
+    fn test_permission_denied_hint_is_provider_specific() {
+        let hint = GitConnectionStatus::PermissionDenied
+            .hint("git@github.com:acme/api.git")
+            .unwrap();
+        assert!(hint.contains("https://github.com/acme/api/settings/keys"));
+
+        let hint = GitConnectionStatus::PermissionDenied
+            .hint("git@gitlab.com:acme/api.git")
+            .unwrap();
+        assert!(hint.contains("GitLab deploy keys"));
+    }
+
     #[test]
     fn test_key_type_display() {
         assert_eq!(SshKeyType::Ed25519.display_name(), "Ed25519 (recommended)");
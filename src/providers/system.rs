@@ -9,6 +9,47 @@ use crate::core::error::AppError;
 const SUPPORTED_OS: &[(&str, &[&str])] =
     &[("ubuntu", &["22.04", "24.04"]), ("debian", &["11", "12"])];
 
+/// Root of the cgroup hierarchy.
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// cgroup v2 controllers Docker relies on for resource limits.
+const REQUIRED_CGROUP_CONTROLLERS: &[&str] = &["cpu", "memory", "pids"];
+
+/// A missing kernel prerequisite and how to fix it.
+#[derive(Debug, Clone)]
+pub struct KernelWarning {
+    pub message: String,
+    pub remediation: String,
+}
+
+impl KernelWarning {
+    fn new(message: &str, remediation: &str) -> Self {
+        Self {
+            message: message.to_string(),
+            remediation: remediation.to_string(),
+        }
+    }
+}
+
+/// A process listening on a TCP port the server needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortListener {
+    pub port: u16,
+    /// Process name reported by `ss`, if visible.
+    pub process: Option<String>,
+}
+
+impl PortListener {
+    /// Systemd service that can be stopped to free the port, for web
+    /// servers commonly preinstalled on VPS images.
+    pub fn stoppable_service(&self) -> Option<&str> {
+        const WEB_SERVERS: &[&str] = &["apache2", "httpd", "nginx", "caddy", "lighttpd"];
+        self.process
+            .as_deref()
+            .filter(|process| WEB_SERVERS.contains(process))
+    }
+}
+
 /// System provider for OS detection, user management, and privilege checks.
 pub struct SystemProvider;
 
@@ -82,6 +123,85 @@ impl SystemProvider {
         )))
     }
 
+    /// Checks the kernel features Docker needs (cgroups, swap accounting, overlayfs).
+    /// Returns one warning per missing prerequisite.
+    pub fn check_kernel_prerequisites() -> Vec<KernelWarning> {
+        let mut warnings = Vec::new();
+        let root = Path::new(CGROUP_ROOT);
+        let grub_hint = "add it to GRUB_CMDLINE_LINUX in /etc/default/grub, \
+                         then run 'update-grub' and reboot";
+
+        if root.join("cgroup.controllers").exists() {
+            // cgroup v2: controllers are listed at the root
+            let controllers =
+                std::fs::read_to_string(root.join("cgroup.controllers")).unwrap_or_default();
+            let missing = missing_cgroup_controllers(&controllers);
+
+            if !missing.is_empty() {
+                warnings.push(KernelWarning::new(
+                    &format!("cgroup controllers not available: {}", missing.join(", ")),
+                    &format!(
+                        "Enable them with 'cgroup_enable={}' and {}",
+                        missing.join(","),
+                        grub_hint
+                    ),
+                ));
+            }
+
+            // Swap accounting shows up as memory.swap.max on child groups
+            let has_swap_accounting = ["system.slice", "user.slice", "init.scope"]
+                .iter()
+                .any(|group| root.join(group).join("memory.swap.max").exists());
+            if !has_swap_accounting {
+                warnings.push(KernelWarning::new(
+                    "Swap accounting is disabled (container memory limits won't cover swap)",
+                    &format!("Enable it with 'swapaccount=1' and {}", grub_hint),
+                ));
+            }
+        } else if root.join("memory").exists() {
+            // cgroup v1
+            if !root.join("memory/memory.memsw.limit_in_bytes").exists() {
+                warnings.push(KernelWarning::new(
+                    "Swap accounting is disabled (container memory limits won't cover swap)",
+                    &format!("Enable it with 'swapaccount=1' and {}", grub_hint),
+                ));
+            }
+        } else {
+            warnings.push(KernelWarning::new(
+                "No cgroup hierarchy found at /sys/fs/cgroup",
+                "Use a kernel built with cgroup support (CONFIG_CGROUPS) and make sure \
+                 /sys/fs/cgroup is mounted; containers will fail to start otherwise",
+            ));
+        }
+
+        let has_overlay = std::fs::read_to_string("/proc/filesystems")
+            .map(|fs| fs.lines().any(|line| line.trim_end().ends_with("overlay")))
+            .unwrap_or(false)
+            || std::process::Command::new("modinfo")
+                .arg("overlay")
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false);
+        if !has_overlay {
+            warnings.push(KernelWarning::new(
+                "The overlay filesystem is not available (Docker's overlay2 driver needs it)",
+                "Load it with 'modprobe overlay' or install your distribution's extra \
+                 kernel modules package",
+            ));
+        }
+
+        warnings
+    }
+
+    /// Returns the processes listening on any of `ports` (TCP), or nothing
+    /// when `ss` is unavailable.
+    pub fn port_listeners(ports: &[u16], ctx: &ExecutionContext) -> Vec<PortListener> {
+        match ctx.run_command("ss", &["-Htlnp"]) {
+            Ok(output) if output.success => parse_port_listeners(&output.stdout, ports),
+            _ => Vec::new(),
+        }
+    }
+
     /// Parses /etc/os-release into a key-value map.
     fn parse_os_release() -> Result<HashMap<String, String>, AppError> {
         let path = Path::new("/etc/os-release");
@@ -131,19 +251,15 @@ impl UserManager {
             return Self::get_user_info(username, ctx);
         }
 
-        // Create user with home directory
-        ctx.run_command(
-            "useradd",
-            &[
-                "--create-home",
-                "--shell",
-                "/bin/bash",
-                "--groups",
-                "docker",
-                username,
-            ],
-        )?
-        .ensure_success("Failed to create deploy user")?;
+        // Create user with home directory. Podman hosts have no docker group.
+        let mut args = vec!["--create-home", "--shell", "/bin/bash"];
+        if ctx.run_command("getent", &["group", "docker"])?.success {
+            args.extend(["--groups", "docker"]);
+        }
+        args.push(username);
+
+        ctx.run_command("useradd", &args)?
+            .ensure_success("Failed to create deploy user")?;
 
         // Disable password login (SSH key only)
         ctx.run_command("passwd", &["--delete", username])?
@@ -203,3 +319,78 @@ impl From<UserInfo> for crate::core::config::DeployUserInfo {
         }
     }
 }
+
+/// Parses `ss -Htlnp` output into the listeners on `ports`, one per port and
+/// process (IPv4 and IPv6 sockets are merged).
+fn parse_port_listeners(output: &str, ports: &[u16]) -> Vec<PortListener> {
+    let mut listeners: Vec<PortListener> = Vec::new();
+
+    for line in output.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let Some(port) = fields
+            .get(3)
+            .and_then(|local| local.rsplit_once(':'))
+            .and_then(|(_, port)| port.parse::<u16>().ok())
+        else {
+            continue;
+        };
+        if !ports.contains(&port) {
+            continue;
+        }
+
+        // users:(("apache2",pid=812,fd=4),("apache2",pid=815,fd=4))
+        let process = fields
+            .get(5)
+            .and_then(|users| users.split('"').nth(1))
+            .map(str::to_string);
+
+        let listener = PortListener { port, process };
+        if !listeners.contains(&listener) {
+            listeners.push(listener);
+        }
+    }
+
+    listeners
+}
+
+/// Returns the required cgroup v2 controllers missing from a
+/// `cgroup.controllers` listing.
+fn missing_cgroup_controllers(controllers: &str) -> Vec<&'static str> {
+    let available: Vec<&str> = controllers.split_whitespace().collect();
+    REQUIRED_CGROUP_CONTROLLERS
+        .iter()
+        .copied()
+        .filter(|c| !available.contains(c))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_cgroup_controllers() {
+        assert!(missing_cgroup_controllers("cpuset cpu io memory hugetlb pids rdma\n").is_empty());
+        assert_eq!(missing_cgroup_controllers("cpuset cpu io pids"), vec!["memory"]);
+        assert_eq!(missing_cgroup_controllers(""), vec!["cpu", "memory", "pids"]);
+    }
+
+    #[test]
+    fn test_parse_port_listeners() {
+        let output = concat!(
+            "LISTEN 0 511 0.0.0.0:80 0.0.0.0:* users:((\"apache2\",pid=812,fd=4))\n",
+            "LISTEN 0 511 [::]:80 [::]:* users:((\"apache2\",pid=812,fd=6))\n",
+            "LISTEN 0 4096 *:443 *:* users:((\"docker-proxy\",pid=90,fd=7))\n",
+            "LISTEN 0 128 0.0.0.0:22 0.0.0.0:* users:((\"sshd\",pid=1,fd=3))\n",
+            "LISTEN 0 128 127.0.0.1:8080 0.0.0.0:*\n",
+        );
+
+        let listeners = parse_port_listeners(output, &[80, 443, 8080]);
+        assert_eq!(listeners.len(), 3);
+        assert_eq!(listeners[0].port, 80);
+        assert_eq!(listeners[0].stoppable_service(), Some("apache2"));
+        assert_eq!(listeners[1].process.as_deref(), Some("docker-proxy"));
+        assert_eq!(listeners[1].stoppable_service(), None);
+        assert_eq!(listeners[2], PortListener { port: 8080, process: None });
+    }
+}
@@ -1,7 +1,9 @@
 //! Webhook handling for autodeploy functionality.
 
+use crate::core::config::webhook_prefix;
 use crate::core::error::AppError;
 use crate::core::{SecretsManager, FLAASE_BASE_PATH};
+use crate::providers::container_cli;
 
 /// Path to the webhook service directory.
 pub const FLAASE_WEBHOOK_PATH: &str = "/opt/flaase/webhook";
@@ -12,7 +14,27 @@ pub struct WebhookProvider;
 impl WebhookProvider {
     /// Generates the full webhook URL for an app.
     pub fn webhook_url(domain: &str, webhook_path: &str) -> String {
-        format!("https://{}/flaase/webhook/{}", domain, webhook_path)
+        Self::endpoint_url(domain, &webhook_prefix(), "webhook", webhook_path)
+    }
+
+    /// Generates the log streaming URL for an app.
+    pub fn logs_url(domain: &str, webhook_path: &str) -> String {
+        Self::endpoint_url(domain, &webhook_prefix(), "logs", webhook_path)
+    }
+
+    /// Builds `https://<domain><prefix>/<endpoint>/<webhook_path>`.
+    fn endpoint_url(domain: &str, prefix: &str, endpoint: &str, webhook_path: &str) -> String {
+        format!("https://{}{}/{}/{}", domain, prefix, endpoint, webhook_path)
+    }
+
+    /// Checks a bearer token against the app's webhook secret in constant time.
+    pub fn validate_token(provided: &str, secret: &str) -> bool {
+        provided.len() == secret.len()
+            && provided
+                .bytes()
+                .zip(secret.bytes())
+                .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+                == 0
     }
 
     /// Generates a unique webhook path for an app.
@@ -60,13 +82,23 @@ impl WebhookProvider {
 
     /// Generates the Traefik webhook router configuration.
     pub fn generate_traefik_webhook_config() -> String {
-        r#"# Traefik configuration for Flaase webhook endpoint
+        format!(
+            r#"# Traefik configuration for Flaase webhook endpoint
 # Generated by Flaase
 
 http:
   routers:
     flaase-webhook:
-      rule: "PathPrefix(`/flaase/webhook/`)"
+      rule: "PathPrefix(`{prefix}/webhook/`)"
+      entryPoints:
+        - websecure
+      service: flaase-webhook
+      priority: 100
+      tls:
+        certResolver: letsencrypt
+
+    flaase-logs:
+      rule: "PathPrefix(`{prefix}/logs/`)"
       entryPoints:
         - websecure
       service: flaase-webhook
@@ -79,8 +111,9 @@ http:
       loadBalancer:
         servers:
           - url: "http://flaase-webhook:8080"
-"#
-        .to_string()
+"#,
+            prefix = webhook_prefix()
+        )
     }
 
     /// Generates the webhook service docker-compose configuration.
@@ -127,7 +160,7 @@ networks:
     /// Checks if the webhook service is running.
     pub fn is_running() -> bool {
         // Check if the flaase-webhook container exists and is running
-        std::process::Command::new("docker")
+        std::process::Command::new(container_cli())
             .args(["ps", "-q", "-f", "name=flaase-webhook"])
             .output()
             .map(|o| !o.stdout.is_empty())
@@ -135,18 +168,181 @@ networks:
     }
 }
 
-/// Represents a GitHub webhook payload for push events.
+/// Default number of log lines sent before following.
+const LOG_STREAM_DEFAULT_TAIL: u32 = 100;
+
+/// Maximum number of log lines a client may request.
+const LOG_STREAM_MAX_TAIL: u32 = 1000;
+
+/// Options for a remote log streaming request (`?tail=200&follow=false`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogStreamOptions {
+    /// Number of recent lines to send first (bounded).
+    pub tail: u32,
+    /// Keep the connection open and stream new lines.
+    pub follow: bool,
+}
+
+impl LogStreamOptions {
+    /// Parses the query string of a log streaming request.
+    pub fn from_query(query: &str) -> Self {
+        let mut options = Self {
+            tail: LOG_STREAM_DEFAULT_TAIL,
+            follow: true,
+        };
+
+        for pair in query.split('&') {
+            match pair.split_once('=') {
+                Some(("tail", value)) => {
+                    if let Ok(tail) = value.parse::<u32>() {
+                        options.tail = tail.min(LOG_STREAM_MAX_TAIL);
+                    }
+                }
+                Some(("follow", value)) => options.follow = value != "false" && value != "0",
+                _ => {}
+            }
+        }
+
+        options
+    }
+}
+
+/// Git hosting provider that sent a webhook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookSource {
+    GitHub,
+    Gitea,
+    Bitbucket,
+}
+
+impl WebhookSource {
+    /// Detects the provider from the request headers.
+    /// Gitea also sends GitHub-compatible headers, so it is checked first.
+    pub fn detect<'a>(header_names: impl Iterator<Item = &'a str>) -> Self {
+        let mut source = WebhookSource::GitHub;
+        for name in header_names {
+            if name.eq_ignore_ascii_case("x-gitea-event") {
+                return WebhookSource::Gitea;
+            }
+            if name.eq_ignore_ascii_case("x-event-key") {
+                source = WebhookSource::Bitbucket;
+            }
+        }
+        source
+    }
+
+    /// Returns the display name.
+    pub fn display_name(&self) -> &str {
+        match self {
+            WebhookSource::GitHub => "GitHub",
+            WebhookSource::Gitea => "Gitea",
+            WebhookSource::Bitbucket => "Bitbucket",
+        }
+    }
+
+    /// Header carrying the HMAC signature of the payload.
+    pub fn signature_header(&self) -> &str {
+        match self {
+            WebhookSource::GitHub => "x-hub-signature-256",
+            WebhookSource::Gitea => "x-gitea-signature",
+            WebhookSource::Bitbucket => "x-hub-signature",
+        }
+    }
+
+    /// Header carrying the event type.
+    pub fn event_header(&self) -> &str {
+        match self {
+            WebhookSource::GitHub => "x-github-event",
+            WebhookSource::Gitea => "x-gitea-event",
+            WebhookSource::Bitbucket => "x-event-key",
+        }
+    }
+
+    /// Returns whether the event type is a push event.
+    pub fn is_push_event(&self, event_type: &str) -> bool {
+        match self {
+            WebhookSource::GitHub | WebhookSource::Gitea => event_type == "push",
+            WebhookSource::Bitbucket => event_type == "repo:push",
+        }
+    }
+
+    /// Validates the payload signature for this provider.
+    /// Gitea sends the bare hex digest, the others use the `sha256=` prefix.
+    pub fn validate_signature(&self, payload: &[u8], signature: &str, secret: &str) -> bool {
+        match self {
+            WebhookSource::Gitea => WebhookProvider::validate_signature(
+                payload,
+                &format!("sha256={}", signature.trim()),
+                secret,
+            ),
+            _ => WebhookProvider::validate_signature(payload, signature, secret),
+        }
+    }
+}
+
+impl std::fmt::Display for WebhookSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+/// Most commits GitHub lists in a push payload; longer pushes are truncated.
+const MAX_PAYLOAD_COMMITS: usize = 20;
+
+/// Collects the files touched by the commits of a GitHub or Gitea push.
+/// Returns nothing when the list may be incomplete: the payload was
+/// truncated or a commit came without its file lists.
+fn changed_files(commits: &serde_json::Value) -> Vec<String> {
+    let commits = commits.as_array().map(Vec::as_slice).unwrap_or_default();
+    if commits.len() >= MAX_PAYLOAD_COMMITS {
+        return Vec::new();
+    }
+
+    let mut changed_files: Vec<String> = Vec::new();
+    for commit in commits {
+        for key in ["added", "modified", "removed"] {
+            let Some(files) = commit[key].as_array() else {
+                return Vec::new();
+            };
+            for file in files.iter().filter_map(|f| f.as_str()) {
+                if !changed_files.iter().any(|f| f == file) {
+                    changed_files.push(file.to_string());
+                }
+            }
+        }
+    }
+    changed_files
+}
+
+/// Represents a push event from any supported Git provider.
 #[derive(Debug, Clone)]
-pub struct GitHubPushEvent {
+pub struct PushEvent {
     pub repository: String,
+    /// Pushed branch (empty for tag pushes).
     pub branch: String,
+    /// Pushed tag, for `refs/tags/` pushes.
+    pub tag: Option<String>,
+    /// Whether the push deleted the ref.
+    pub deleted: bool,
+    /// Files added, modified or removed by the pushed commits (empty when the
+    /// provider doesn't list them, or lists only some of them).
+    pub changed_files: Vec<String>,
     pub commit_sha: String,
     pub commit_message: String,
     pub pusher: String,
 }
 
-impl GitHubPushEvent {
+impl PushEvent {
+    /// Parses a push event payload sent by the given provider.
+    pub fn parse(source: WebhookSource, json: &str) -> Result<Self, AppError> {
+        match source {
+            WebhookSource::GitHub | WebhookSource::Gitea => Self::from_json(json),
+            WebhookSource::Bitbucket => Self::from_bitbucket_json(json),
+        }
+    }
+
     /// Parses a GitHub push event from JSON payload.
+    /// Gitea push payloads use the same shape.
     pub fn from_json(json: &str) -> Result<Self, AppError> {
         let value: serde_json::Value = serde_json::from_str(json)
             .map_err(|e| AppError::Config(format!("Invalid webhook payload: {}", e)))?;
@@ -156,11 +352,18 @@ impl GitHubPushEvent {
             .as_str()
             .ok_or_else(|| AppError::Config("Missing 'ref' in payload".into()))?;
 
-        // Extract branch name from ref
-        let branch = ref_str
-            .strip_prefix("refs/heads/")
-            .unwrap_or(ref_str)
-            .to_string();
+        // Extract branch or tag name from ref
+        let (branch, tag) = match ref_str.strip_prefix("refs/tags/") {
+            Some(tag) => (String::new(), Some(tag.to_string())),
+            None => (
+                ref_str.strip_prefix("refs/heads/").unwrap_or(ref_str).to_string(),
+                None,
+            ),
+        };
+
+        let deleted = value["deleted"].as_bool().unwrap_or(false);
+
+        let changed_files = changed_files(&value["commits"]);
 
         // Extract repository name
         let repository = value["repository"]["full_name"]
@@ -176,22 +379,80 @@ impl GitHubPushEvent {
             .take(7)
             .collect();
 
-        let commit_message = value["head_commit"]["message"]
+        let commit_message = first_line(value["head_commit"]["message"].as_str());
+
+        // GitHub sends pusher.name, Gitea sends pusher.login/username
+        let pusher = value["pusher"]["name"]
             .as_str()
-            .unwrap_or("")
-            .lines()
-            .next()
-            .unwrap_or("")
+            .or_else(|| value["pusher"]["login"].as_str())
+            .or_else(|| value["pusher"]["username"].as_str())
+            .unwrap_or("unknown")
             .to_string();
 
-        let pusher = value["pusher"]["name"]
+        Ok(Self {
+            repository,
+            branch,
+            tag,
+            deleted,
+            changed_files,
+            commit_sha,
+            commit_message,
+            pusher,
+        })
+    }
+
+    /// Parses a Bitbucket Cloud `repo:push` payload.
+    /// Only the first branch or tag change is considered.
+    pub fn from_bitbucket_json(json: &str) -> Result<Self, AppError> {
+        let value: serde_json::Value = serde_json::from_str(json)
+            .map_err(|e| AppError::Config(format!("Invalid webhook payload: {}", e)))?;
+
+        // Deletions have no "new" state, so they never match
+        let change = value["push"]["changes"]
+            .as_array()
+            .and_then(|changes| {
+                changes.iter().find(|c| {
+                    matches!(c["new"]["type"].as_str(), Some("branch") | Some("tag"))
+                })
+            })
+            .ok_or_else(|| AppError::Config("Missing branch change in payload".into()))?;
+
+        let name = change["new"]["name"]
+            .as_str()
+            .ok_or_else(|| AppError::Config("Missing branch name in payload".into()))?
+            .to_string();
+
+        let (branch, tag) = match change["new"]["type"].as_str() {
+            Some("tag") => (String::new(), Some(name)),
+            _ => (name, None),
+        };
+
+        let repository = value["repository"]["full_name"]
+            .as_str()
+            .unwrap_or("unknown")
+            .to_string();
+
+        let commit_sha = change["new"]["target"]["hash"]
+            .as_str()
+            .unwrap_or("")
+            .chars()
+            .take(7)
+            .collect();
+
+        let commit_message = first_line(change["new"]["target"]["message"].as_str());
+
+        let pusher = value["actor"]["nickname"]
             .as_str()
+            .or_else(|| value["actor"]["display_name"].as_str())
             .unwrap_or("unknown")
             .to_string();
 
         Ok(Self {
             repository,
             branch,
+            tag,
+            deleted: false,
+            changed_files: Vec::new(),
             commit_sha,
             commit_message,
             pusher,
@@ -199,6 +460,16 @@ impl GitHubPushEvent {
     }
 }
 
+/// Returns the first line of an optional commit message.
+fn first_line(message: Option<&str>) -> String {
+    message
+        .unwrap_or("")
+        .lines()
+        .next()
+        .unwrap_or("")
+        .to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -210,9 +481,172 @@ mod tests {
         assert!(path.len() > 10);
     }
 
+    #[test]
+    fn test_validate_token() {
+        assert!(WebhookProvider::validate_token("s3cret", "s3cret"));
+        assert!(!WebhookProvider::validate_token("s3cres", "s3cret"));
+        assert!(!WebhookProvider::validate_token("s3cre", "s3cret"));
+        assert!(!WebhookProvider::validate_token("", "s3cret"));
+    }
+
+    #[test]
+    fn test_log_stream_options() {
+        assert_eq!(
+            LogStreamOptions::from_query(""),
+            LogStreamOptions { tail: 100, follow: true }
+        );
+        assert_eq!(
+            LogStreamOptions::from_query("tail=20&follow=false"),
+            LogStreamOptions { tail: 20, follow: false }
+        );
+        assert_eq!(LogStreamOptions::from_query("tail=999999").tail, 1000);
+        assert_eq!(LogStreamOptions::from_query("tail=abc").tail, 100);
+    }
+
+    #[test]
+    fn test_detect_source() {
+        let github = ["content-type", "x-github-event", "x-hub-signature-256"];
+        assert_eq!(WebhookSource::detect(github.into_iter()), WebhookSource::GitHub);
+
+        let gitea = ["X-GitHub-Event", "X-Gitea-Event", "X-Gitea-Signature"];
+        assert_eq!(WebhookSource::detect(gitea.into_iter()), WebhookSource::Gitea);
+
+        let bitbucket = ["X-Event-Key", "X-Hub-Signature"];
+        assert_eq!(WebhookSource::detect(bitbucket.into_iter()), WebhookSource::Bitbucket);
+    }
+
+    #[test]
+    fn test_gitea_signature_without_prefix() {
+        use hmac::{Hmac, Mac};
+        use sha2::Sha256;
+
+        let payload = br#"{"ref":"refs/heads/main"}"#;
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(payload);
+        let digest = hex::encode(mac.finalize().into_bytes());
+
+        assert!(WebhookSource::Gitea.validate_signature(payload, &digest, "secret"));
+        assert!(WebhookSource::Bitbucket.validate_signature(
+            payload,
+            &format!("sha256={}", digest),
+            "secret"
+        ));
+        assert!(!WebhookSource::Gitea.validate_signature(payload, &digest, "other"));
+    }
+
+    #[test]
+    fn test_parse_gitea_push() {
+        let json = r#"{
+            "ref": "refs/heads/develop",
+            "after": "0123456789abcdef",
+            "head_commit": {"message": "Fix bug\n\nDetails"},
+            "repository": {"full_name": "org/app"},
+            "pusher": {"login": "alice"}
+        }"#;
+        let event = PushEvent::parse(WebhookSource::Gitea, json).unwrap();
+        assert_eq!(event.branch, "develop");
+        assert!(event.changed_files.is_empty());
+        assert_eq!(event.commit_sha, "0123456");
+        assert_eq!(event.commit_message, "Fix bug");
+        assert_eq!(event.pusher, "alice");
+    }
+
+    #[test]
+    fn test_parse_bitbucket_push() {
+        let json = r#"{
+            "actor": {"display_name": "Bob", "nickname": "bob"},
+            "repository": {"full_name": "team/app"},
+            "push": {"changes": [{
+                "new": {
+                    "type": "branch",
+                    "name": "main",
+                    "target": {"hash": "fedcba9876543210", "message": "Release\n"}
+                }
+            }]}
+        }"#;
+        let event = PushEvent::parse(WebhookSource::Bitbucket, json).unwrap();
+        assert_eq!(event.branch, "main");
+        assert_eq!(event.commit_sha, "fedcba9");
+        assert_eq!(event.commit_message, "Release");
+        assert_eq!(event.pusher, "bob");
+        assert_eq!(event.repository, "team/app");
+    }
+
+    #[test]
+    fn test_parse_bitbucket_tag_push() {
+        let json = r#"{"push": {"changes": [{"new": {"type": "tag", "name": "v1"}}]}}"#;
+        let event = PushEvent::parse(WebhookSource::Bitbucket, json).unwrap();
+        assert_eq!(event.tag.as_deref(), Some("v1"));
+        assert!(event.branch.is_empty());
+
+        let deletion = r#"{"push": {"changes": [{"new": null, "old": {"type": "tag"}}]}}"#;
+        assert!(PushEvent::parse(WebhookSource::Bitbucket, deletion).is_err());
+    }
+
+    #[test]
+    fn test_parse_github_tag_push() {
+        let json = r#"{
+            "ref": "refs/tags/v1.2.3",
+            "after": "abcdef0123456789",
+            "head_commit": {"message": "Release 1.2.3"},
+            "repository": {"full_name": "org/app"},
+            "pusher": {"name": "alice"}
+        }"#;
+        let event = PushEvent::parse(WebhookSource::GitHub, json).unwrap();
+        assert_eq!(event.tag.as_deref(), Some("v1.2.3"));
+        assert!(event.branch.is_empty());
+        assert!(!event.deleted);
+        assert_eq!(event.commit_sha, "abcdef0");
+
+        let deleted = r#"{"ref": "refs/tags/v1.2.3", "deleted": true}"#;
+        assert!(PushEvent::parse(WebhookSource::GitHub, deleted).unwrap().deleted);
+    }
+
+    #[test]
+    fn test_parse_changed_files() {
+        let json = r#"{
+            "ref": "refs/heads/main",
+            "commits": [
+                {"added": ["apps/api/new.rs"], "modified": ["docs/a.md"], "removed": []},
+                {"added": [], "modified": ["docs/a.md"], "removed": ["old.txt"]}
+            ]
+        }"#;
+        let event = PushEvent::parse(WebhookSource::GitHub, json).unwrap();
+        assert_eq!(event.changed_files, ["apps/api/new.rs", "docs/a.md", "old.txt"]);
+
+        // A commit without file lists makes the whole list unreliable
+        let partial = r#"{
+            "ref": "refs/heads/main",
+            "commits": [{"added": ["docs/a.md"], "modified": [], "removed": []}, {"id": "abc"}]
+        }"#;
+        let event = PushEvent::parse(WebhookSource::GitHub, partial).unwrap();
+        assert!(event.changed_files.is_empty());
+
+        // GitHub lists at most 20 commits, so a longer push may touch more files
+        let commit = r#"{"added": [], "modified": ["docs/a.md"], "removed": []}"#;
+        let truncated = format!(
+            r#"{{"ref": "refs/heads/main", "commits": [{}]}}"#,
+            vec![commit; MAX_PAYLOAD_COMMITS].join(",")
+        );
+        let event = PushEvent::parse(WebhookSource::GitHub, &truncated).unwrap();
+        assert!(event.changed_files.is_empty());
+    }
+
     #[test]
     fn test_webhook_url() {
-        let url = WebhookProvider::webhook_url("example.com", "my-app-abc123");
+        // The server's configured prefix is injected, so the host's config doesn't matter
+        let url = WebhookProvider::endpoint_url(
+            "example.com",
+            crate::core::config::DEFAULT_WEBHOOK_PREFIX,
+            "webhook",
+            "my-app-abc123",
+        );
         assert_eq!(url, "https://example.com/flaase/webhook/my-app-abc123");
     }
+
+    #[test]
+    fn test_endpoint_url_with_custom_prefix() {
+        let url = WebhookProvider::endpoint_url("example.com", "/hooks/ci", "logs", "my-app-abc");
+        assert_eq!(url, "https://example.com/hooks/ci/logs/my-app-abc");
+    }
 }
@@ -1,13 +1,18 @@
 //! Dockerfile templates for different application stacks.
 
-use crate::core::app_config::{Framework, PackageManager, Stack, StackConfig};
+use std::collections::BTreeMap;
 
-/// Generates a Dockerfile for the given stack.
-pub fn generate(stack: Stack, port: u16) -> String {
+use crate::core::app_config::{
+    Framework, HealthCheckConfig, HealthCheckType, PackageManager, Stack, StackConfig,
+};
+
+/// Generates a Dockerfile for the given stack. Node.js stacks install with
+/// `package_manager`, defaulting to npm when it is unknown.
+pub fn generate(stack: Stack, port: u16, package_manager: Option<PackageManager>) -> String {
     match stack {
-        Stack::NextJs => generate_nextjs(port),
-        Stack::NodeJs => generate_nodejs(port),
-        Stack::NestJs => generate_nestjs(port),
+        Stack::NextJs => generate_nextjs(port, package_manager),
+        Stack::NodeJs => generate_nodejs(port, package_manager),
+        Stack::NestJs => generate_nestjs(port, package_manager),
         Stack::Laravel => generate_laravel(port),
         Stack::Python => generate_python_default(port),
         Stack::Go => generate_go_default(port),
@@ -31,16 +36,68 @@ pub fn generate_with_config(stack: Stack, config: &StackConfig, port: u16) -> St
         Stack::Php => generate_php(config, port),
         Stack::Static => generate_static(config, port),
         // For these stacks, we use the default templates
-        Stack::NextJs => generate_nextjs(port),
-        Stack::NodeJs => generate_nodejs(port),
-        Stack::NestJs => generate_nestjs(port),
+        Stack::NextJs => generate_nextjs(port, config.package_manager),
+        Stack::NodeJs => generate_nodejs(port, config.package_manager),
+        Stack::NestJs => generate_nestjs(port, config.package_manager),
         Stack::Laravel => generate_laravel(port),
         Stack::Dockerfile => String::new(),
     }
 }
 
+/// Install and build commands of a Node.js package manager.
+struct NodeCommands {
+    /// Lockfile copied next to package.json before installing.
+    lockfile: &'static str,
+    /// Extra setup step (corepack for yarn and pnpm).
+    setup: &'static str,
+    install: &'static str,
+    install_prod: &'static str,
+    build: &'static str,
+    build_if_present: &'static str,
+}
+
+impl NodeCommands {
+    fn for_manager(package_manager: Option<PackageManager>) -> Self {
+        match package_manager {
+            Some(PackageManager::Yarn) => Self {
+                lockfile: "yarn.lock",
+                setup: "RUN corepack enable\n",
+                install: "yarn install --frozen-lockfile",
+                install_prod: "yarn install --frozen-lockfile --production",
+                build: "yarn build",
+                build_if_present: concat!(
+                    "if node -e \"process.exit(require('./package.json').scripts?.build ? 0 : 1)\"",
+                    "; then yarn build; fi"
+                ),
+            },
+            Some(PackageManager::Pnpm) => Self {
+                lockfile: "pnpm-lock.yaml",
+                setup: "RUN corepack enable\n",
+                install: "pnpm install --frozen-lockfile",
+                install_prod: "pnpm install --frozen-lockfile --prod",
+                build: "pnpm build",
+                build_if_present: "pnpm run --if-present build",
+            },
+            // npm, also the default when the package manager is unknown
+            _ => Self {
+                lockfile: "package-lock.json*",
+                setup: "",
+                install: "if [ -f package-lock.json ]; then npm ci; else npm install; fi",
+                install_prod: concat!(
+                    "if [ -f package-lock.json ]; then npm ci --omit=dev; \\\n",
+                    "    else npm install --omit=dev; fi"
+                ),
+                build: "npm run build",
+                build_if_present: "npm run build --if-present",
+            },
+        }
+    }
+}
+
 /// Generates a Dockerfile for Next.js applications.
-fn generate_nextjs(port: u16) -> String {
+fn generate_nextjs(port: u16, package_manager: Option<PackageManager>) -> String {
+    let node = NodeCommands::for_manager(package_manager);
+
     format!(
         r#"# Next.js Dockerfile
 # Generated by Flaase
@@ -51,30 +108,20 @@ FROM node:20-alpine AS base
 FROM base AS deps
 RUN apk add --no-cache libc6-compat
 WORKDIR /app
-
-# Install dependencies based on the preferred package manager
-COPY package.json yarn.lock* package-lock.json* pnpm-lock.yaml* ./
-RUN \
-  if [ -f yarn.lock ]; then yarn --frozen-lockfile; \
-  elif [ -f package-lock.json ]; then npm ci; \
-  elif [ -f pnpm-lock.yaml ]; then corepack enable pnpm && pnpm i --frozen-lockfile; \
-  else echo "Lockfile not found." && exit 1; \
-  fi
+{setup}
+# Install dependencies
+COPY package.json {lockfile} ./
+RUN {install}
 
 # Rebuild the source code only when needed
 FROM base AS builder
 WORKDIR /app
-COPY --from=deps /app/node_modules ./node_modules
+{setup}COPY --from=deps /app/node_modules ./node_modules
 COPY . .
 
 ENV NEXT_TELEMETRY_DISABLED=1
 
-RUN \
-  if [ -f yarn.lock ]; then yarn build; \
-  elif [ -f package-lock.json ]; then npm run build; \
-  elif [ -f pnpm-lock.yaml ]; then corepack enable pnpm && pnpm build; \
-  else echo "Lockfile not found." && exit 1; \
-  fi
+RUN {build}
 
 # Production image, copy all the files and run next
 FROM base AS runner
@@ -101,12 +148,18 @@ ENV HOSTNAME="0.0.0.0"
 
 CMD ["node", "server.js"]
 "#,
+        setup = node.setup,
+        lockfile = node.lockfile,
+        install = node.install,
+        build = node.build,
         port = port
     )
 }
 
 /// Generates a Dockerfile for Node.js applications.
-fn generate_nodejs(port: u16) -> String {
+fn generate_nodejs(port: u16, package_manager: Option<PackageManager>) -> String {
+    let node = NodeCommands::for_manager(package_manager);
+
     format!(
         r#"# Node.js Dockerfile
 # Generated by Flaase
@@ -114,21 +167,16 @@ fn generate_nodejs(port: u16) -> String {
 FROM node:20-alpine AS builder
 
 WORKDIR /app
-
+{setup}
 # Install dependencies
-COPY package.json yarn.lock* package-lock.json* pnpm-lock.yaml* ./
-RUN \
-  if [ -f yarn.lock ]; then yarn --frozen-lockfile; \
-  elif [ -f package-lock.json ]; then npm ci; \
-  elif [ -f pnpm-lock.yaml ]; then corepack enable pnpm && pnpm i --frozen-lockfile; \
-  else npm install; \
-  fi
+COPY package.json {lockfile} ./
+RUN {install}
 
 # Copy source
 COPY . .
 
 # Build if there's a build script
-RUN npm run build --if-present
+RUN {build_if_present}
 
 # Production image
 FROM node:20-alpine AS runner
@@ -151,12 +199,18 @@ EXPOSE {port}
 
 CMD ["node", "dist/index.js"]
 "#,
+        setup = node.setup,
+        lockfile = node.lockfile,
+        install = node.install,
+        build_if_present = node.build_if_present,
         port = port
     )
 }
 
 /// Generates a Dockerfile for NestJS applications.
-fn generate_nestjs(port: u16) -> String {
+fn generate_nestjs(port: u16, package_manager: Option<PackageManager>) -> String {
+    let node = NodeCommands::for_manager(package_manager);
+
     format!(
         r#"# NestJS Dockerfile
 # Generated by Flaase
@@ -164,19 +218,14 @@ fn generate_nestjs(port: u16) -> String {
 FROM node:20-alpine AS builder
 
 WORKDIR /app
-
+{setup}
 # Install dependencies
-COPY package.json yarn.lock* package-lock.json* pnpm-lock.yaml* ./
-RUN \
-  if [ -f yarn.lock ]; then yarn --frozen-lockfile; \
-  elif [ -f package-lock.json ]; then npm ci; \
-  elif [ -f pnpm-lock.yaml ]; then corepack enable pnpm && pnpm i --frozen-lockfile; \
-  else npm install; \
-  fi
+COPY package.json {lockfile} ./
+RUN {install}
 
 # Copy source and build
 COPY . .
-RUN npm run build
+RUN {build}
 
 # Production image
 FROM node:20-alpine AS runner
@@ -184,15 +233,10 @@ FROM node:20-alpine AS runner
 WORKDIR /app
 
 ENV NODE_ENV=production
-
+{setup}
 # Copy package files and install production dependencies only
-COPY package.json yarn.lock* package-lock.json* pnpm-lock.yaml* ./
-RUN \
-  if [ -f yarn.lock ]; then yarn --frozen-lockfile --production; \
-  elif [ -f package-lock.json ]; then npm ci --only=production; \
-  elif [ -f pnpm-lock.yaml ]; then corepack enable pnpm && pnpm i --frozen-lockfile --prod; \
-  else npm install --only=production; \
-  fi
+COPY package.json {lockfile} ./
+RUN {install_prod}
 
 # Copy built application
 COPY --from=builder /app/dist ./dist
@@ -208,6 +252,11 @@ EXPOSE {port}
 
 CMD ["node", "dist/main.js"]
 "#,
+        setup = node.setup,
+        lockfile = node.lockfile,
+        install = node.install,
+        install_prod = node.install_prod,
+        build = node.build,
         port = port
     )
 }
@@ -278,6 +327,115 @@ CMD ["php", "artisan", "octane:start", "--server=frankenphp", "--host=0.0.0.0",
     )
 }
 
+/// Adds a HEALTHCHECK instruction probing the app's health endpoint (or
+/// running the configured command), so `docker ps` reports the container as
+/// healthy or unhealthy. TCP checks are left to the deployer, as runtime
+/// images don't reliably ship a tool to open a bare connection.
+pub fn with_healthcheck(
+    dockerfile: &str,
+    stack: Stack,
+    port: u16,
+    health: &HealthCheckConfig,
+) -> String {
+    if dockerfile.is_empty() || dockerfile.contains("\nHEALTHCHECK ") {
+        return dockerfile.to_string();
+    }
+
+    let probe = match (health.check_type, &health.command) {
+        (HealthCheckType::Http, _) => http_probe(stack, port, health),
+        (HealthCheckType::Command, Some(command)) => command.clone(),
+        _ => return dockerfile.to_string(),
+    };
+
+    let instruction = format!(
+        "HEALTHCHECK --interval={}s --timeout={}s --start-period={}s --retries={} \\\n  CMD {} || exit 1\n\n",
+        health.interval,
+        health.timeout,
+        health.start_period.saturating_add(health.probe_window().as_secs()),
+        health.retries,
+        probe
+    );
+
+    // Insert right before the final CMD so it lands in the runtime stage
+    match dockerfile.rfind("\nCMD ") {
+        Some(pos) => {
+            let mut result = dockerfile.to_string();
+            result.insert_str(pos + 1, &instruction);
+            result
+        }
+        None => format!("{}\n{}", dockerfile.trim_end(), instruction.trim_end()),
+    }
+}
+
+/// Builds the command probing the health endpoint inside the container.
+fn http_probe(stack: Stack, port: u16, health: &HealthCheckConfig) -> String {
+    let endpoint = if health.endpoint.starts_with('/') {
+        health.endpoint.clone()
+    } else {
+        format!("/{}", health.endpoint)
+    };
+    let url = format!("http://127.0.0.1:{}{}", port, endpoint);
+
+    // Use a tool that exists in the runtime image of each stack
+    match stack {
+        Stack::Python => format!(
+            "python -c \"import urllib.request; urllib.request.urlopen('{}', timeout={})\"",
+            url, health.timeout
+        ),
+        Stack::Ruby => format!(
+            "ruby -rnet/http -e \"exit Net::HTTP.get_response(URI('{}')).code.to_i < 400\"",
+            url
+        ),
+        Stack::Rust => format!("curl -fsS -o /dev/null {}", url),
+        _ => format!("wget -q --spider {}", url),
+    }
+}
+
+/// Splits a `FROM` line into its image and optional stage name.
+fn parse_from(line: &str) -> Option<(&str, Option<&str>)> {
+    let mut tokens = line.split_whitespace();
+    if !tokens.next()?.eq_ignore_ascii_case("FROM") {
+        return None;
+    }
+
+    let mut tokens = tokens.skip_while(|t| t.starts_with("--"));
+    let image = tokens.next()?;
+    let stage = match tokens.next() {
+        Some(kw) if kw.eq_ignore_ascii_case("AS") => tokens.next(),
+        _ => None,
+    };
+    Some((image, stage))
+}
+
+/// Returns the external base images of a Dockerfile in order, skipping
+/// references to earlier build stages and `scratch`.
+pub fn base_images(dockerfile: &str) -> Vec<String> {
+    let mut stages: Vec<&str> = Vec::new();
+    let mut images: Vec<String> = Vec::new();
+
+    for (image, stage) in dockerfile.lines().filter_map(parse_from) {
+        let external = image != "scratch" && !image.contains('@') && !stages.contains(&image);
+        if external && !images.iter().any(|i| i == image) {
+            images.push(image.to_string());
+        }
+        stages.extend(stage);
+    }
+
+    images
+}
+
+/// Rewrites `FROM` lines to the pinned `image@sha256:...` of their base image.
+pub fn pin_base_images(dockerfile: &str, pins: &BTreeMap<String, String>) -> String {
+    let mut result = String::with_capacity(dockerfile.len());
+    for line in dockerfile.split_inclusive('\n') {
+        match parse_from(line).and_then(|(image, _)| Some((image, pins.get(image)?))) {
+            Some((image, pinned)) => result.push_str(&line.replacen(image, pinned, 1)),
+            None => result.push_str(line),
+        }
+    }
+    result
+}
+
 /// Checks if a Dockerfile exists in the given directory.
 pub fn exists(repo_dir: &std::path::Path) -> bool {
     repo_dir.join("Dockerfile").exists()
@@ -305,10 +463,23 @@ fn generate_python_default(port: u16) -> String {
     )
 }
 
+/// Default start command for a Python framework.
+fn python_start_command(framework: Option<Framework>, port: u16) -> String {
+    match framework {
+        Some(Framework::Django) => format!("gunicorn wsgi:application --bind 0.0.0.0:{}", port),
+        Some(Framework::FastApi) => format!("uvicorn main:app --host 0.0.0.0 --port {}", port),
+        Some(Framework::Flask) => format!("gunicorn app:app --bind 0.0.0.0:{}", port),
+        _ => "python main.py".to_string(),
+    }
+}
+
 /// Python Dockerfile with configuration.
 fn generate_python(config: &StackConfig, port: u16) -> String {
     let version = config.version.as_deref().unwrap_or("3.12");
-    let start_cmd = config.start_command.as_deref().unwrap_or("python main.py");
+    let start_cmd = config
+        .start_command
+        .clone()
+        .unwrap_or_else(|| python_start_command(config.framework, port));
 
     let install_deps = match config.package_manager {
         Some(PackageManager::Poetry) => r#"
@@ -328,8 +499,9 @@ RUN pip install uv
 # Copy dependency files
 COPY pyproject.toml uv.lock* ./
 
-# Install dependencies
-RUN uv sync --frozen --no-dev"#,
+# Install dependencies into /app/.venv
+RUN uv sync --frozen --no-dev
+ENV PATH="/app/.venv/bin:$PATH""#,
         Some(PackageManager::Pipenv) => r#"
 # Install pipenv
 RUN pip install pipenv
@@ -384,7 +556,7 @@ CMD {start_cmd}
         install_deps = install_deps,
         build_step = build_step,
         port = port,
-        start_cmd = format_cmd(start_cmd),
+        start_cmd = format_cmd(&start_cmd),
     )
 }
 
@@ -547,19 +719,27 @@ fn generate_rust_default(port: u16) -> String {
 }
 
 /// Rust Dockerfile with configuration.
+///
+/// The binary name comes from the start command (e.g. "./server --port 80"),
+/// which the deployer fills from Cargo.toml when it isn't configured. The
+/// binary is copied to ./app and run with the command's arguments.
 fn generate_rust(config: &StackConfig, port: u16) -> String {
-    let binary_name = config
-        .start_command
-        .as_deref()
-        .unwrap_or("./app")
-        .trim_start_matches("./");
+    let start_cmd = config.start_command.as_deref().unwrap_or("./app").trim();
+    let (binary, args) = start_cmd.split_once(char::is_whitespace).unwrap_or((start_cmd, ""));
+    let binary_name = binary.trim_start_matches("./");
+    let run_cmd = format!("./app {}", args);
+    let version = config.version.as_deref().unwrap_or("1");
 
     format!(
         r#"# Rust Dockerfile
 # Generated by Flaase
 
 # Build stage with cargo-chef for dependency caching
-FROM rust:latest AS chef
+FROM rust:{version}-slim AS chef
+RUN apt-get update && apt-get install -y --no-install-recommends \
+    pkg-config \
+    libssl-dev \
+    && rm -rf /var/lib/apt/lists/*
 RUN cargo install cargo-chef
 WORKDIR /app
 
@@ -580,12 +760,12 @@ FROM debian:bookworm-slim
 
 RUN apt-get update && apt-get install -y --no-install-recommends \
     ca-certificates \
+    curl \
     libssl3 \
     && rm -rf /var/lib/apt/lists/*
 
 WORKDIR /app
 
-# Copy binary - adjust name as needed
 COPY --from=builder /app/target/release/{binary_name} ./app
 
 # Create non-root user
@@ -594,9 +774,11 @@ USER appuser
 
 EXPOSE {port}
 
-CMD ["./app"]
+CMD {run_cmd}
 "#,
+        version = version,
         binary_name = binary_name,
+        run_cmd = format_cmd(run_cmd.trim_end()),
         port = port,
     )
 }
@@ -755,12 +937,38 @@ fn generate_static_default(port: u16) -> String {
     generate_static(&StackConfig::default(), port)
 }
 
-/// Static site Dockerfile with configuration.
+/// nginx server block serving the site with SPA fallback to `index.html`.
+fn static_nginx_config(port: u16) -> String {
+    format!(
+        r#"RUN printf 'server {{\n\
+    listen {port};\n\
+    root /usr/share/nginx/html;\n\
+    index index.html;\n\
+    location ~ /\\. {{\n\
+        deny all;\n\
+    }}\n\
+    location / {{\n\
+        try_files $uri $uri/ /index.html;\n\
+    }}\n\
+}}' > /etc/nginx/conf.d/default.conf"#,
+        port = port
+    )
+}
+
+/// Static site Dockerfile with configuration. Vite and Create React App
+/// projects (or any with a build command) are built first and their
+/// output directory is served; plain sites are copied as-is.
 fn generate_static(config: &StackConfig, port: u16) -> String {
-    let has_build = config.build_command.is_some();
+    let has_build = config.build_command.is_some()
+        || matches!(config.framework, Some(Framework::Vite | Framework::CreateReactApp));
 
     if has_build {
-        let build_cmd = config.build_command.as_deref().unwrap_or("npm run build");
+        let node = NodeCommands::for_manager(config.package_manager);
+        let build_cmd = config.build_command.as_deref().unwrap_or(node.build);
+        let output_dir = match config.framework {
+            Some(Framework::CreateReactApp) => "build",
+            _ => "dist",
+        };
 
         format!(
             r#"# Static Site Dockerfile
@@ -770,10 +978,10 @@ fn generate_static(config: &StackConfig, port: u16) -> String {
 FROM node:20-alpine AS builder
 
 WORKDIR /app
-
+{setup}
 # Install dependencies
-COPY package*.json ./
-RUN npm ci
+COPY package.json {lockfile} ./
+RUN {install}
 
 # Copy source and build
 COPY . .
@@ -783,23 +991,21 @@ RUN {build_cmd}
 FROM nginx:alpine
 
 # Copy built files
-COPY --from=builder /app/dist /usr/share/nginx/html
+COPY --from=builder /app/{output_dir} /usr/share/nginx/html
 
 # Custom nginx config for SPA
-RUN printf 'server {{\n\
-    listen {port};\n\
-    root /usr/share/nginx/html;\n\
-    index index.html;\n\
-    location / {{\n\
-        try_files $uri $uri/ /index.html;\n\
-    }}\n\
-}}' > /etc/nginx/conf.d/default.conf
+{nginx_config}
 
 EXPOSE {port}
 
 CMD ["nginx", "-g", "daemon off;"]
 "#,
+            setup = node.setup,
+            lockfile = node.lockfile,
+            install = node.install,
             build_cmd = build_cmd,
+            output_dir = output_dir,
+            nginx_config = static_nginx_config(port),
             port = port,
         )
     } else {
@@ -813,19 +1019,13 @@ FROM nginx:alpine
 COPY . /usr/share/nginx/html
 
 # Custom nginx config for SPA
-RUN printf 'server {{\n\
-    listen {port};\n\
-    root /usr/share/nginx/html;\n\
-    index index.html;\n\
-    location / {{\n\
-        try_files $uri $uri/ /index.html;\n\
-    }}\n\
-}}' > /etc/nginx/conf.d/default.conf
+{nginx_config}
 
 EXPOSE {port}
 
 CMD ["nginx", "-g", "daemon off;"]
 "#,
+            nginx_config = static_nginx_config(port),
             port = port,
         )
     }
@@ -854,17 +1054,201 @@ mod tests {
 
     #[test]
     fn test_generate_nextjs() {
-        let dockerfile = generate(Stack::NextJs, 3000);
+        let dockerfile = generate(Stack::NextJs, 3000, None);
         assert!(dockerfile.contains("FROM node:20-alpine"));
         assert!(dockerfile.contains("EXPOSE 3000"));
         assert!(dockerfile.contains("Next.js"));
     }
 
+    #[test]
+    fn test_generate_node_per_package_manager() {
+        let npm = generate(Stack::NestJs, 3000, None);
+        assert!(npm.contains("COPY package.json package-lock.json* ./"));
+        assert!(npm.contains("then npm ci; else npm install; fi"));
+        assert!(npm.contains("npm ci --omit=dev"));
+        assert!(!npm.contains("corepack"));
+        assert_eq!(npm, generate(Stack::NestJs, 3000, Some(PackageManager::Npm)));
+
+        let pnpm = generate(Stack::NestJs, 3000, Some(PackageManager::Pnpm));
+        assert!(pnpm.contains("COPY package.json pnpm-lock.yaml ./"));
+        assert!(pnpm.contains("RUN corepack enable"));
+        assert!(pnpm.contains("RUN pnpm install --frozen-lockfile\n"));
+        assert!(pnpm.contains("RUN pnpm install --frozen-lockfile --prod"));
+        assert!(pnpm.contains("RUN pnpm build"));
+        assert!(!pnpm.contains("npm ci"));
+
+        let yarn = generate(Stack::NextJs, 3000, Some(PackageManager::Yarn));
+        assert!(yarn.contains("COPY package.json yarn.lock ./"));
+        assert!(yarn.contains("RUN yarn install --frozen-lockfile"));
+        assert!(yarn.contains("RUN yarn build"));
+        assert!(!yarn.contains("npm ci"));
+    }
+
+    #[test]
+    fn test_pin_base_images() {
+        let dockerfile = generate(Stack::NextJs, 3000, None);
+        assert_eq!(base_images(&dockerfile), vec!["node:20-alpine"]);
+
+        let pins = BTreeMap::from([(
+            "node:20-alpine".to_string(),
+            "node:20-alpine@sha256:abc".to_string(),
+        )]);
+        let pinned = pin_base_images(&dockerfile, &pins);
+        assert!(pinned.contains("FROM node:20-alpine@sha256:abc AS base\n"));
+        assert!(pinned.contains("FROM base AS deps\n"));
+        assert!(base_images(&pinned).is_empty());
+
+        let go = "FROM --platform=linux/amd64 golang:1.22 AS build\nFROM scratch\n";
+        assert_eq!(base_images(go), vec!["golang:1.22"]);
+    }
+
+    #[test]
+    fn test_generate_static() {
+        let plain = generate(Stack::Static, 80, None);
+        assert!(plain.contains("# Copy static files\nCOPY . /usr/share/nginx/html"));
+        assert!(plain.contains("try_files $uri $uri/ /index.html;"));
+        assert!(plain.contains("listen 80;"));
+        assert!(!plain.contains("node:20-alpine"));
+
+        let config = StackConfig {
+            framework: Some(Framework::CreateReactApp),
+            package_manager: Some(PackageManager::Yarn),
+            ..Default::default()
+        };
+        let cra = generate_with_config(Stack::Static, &config, 80);
+        assert!(cra.contains("RUN yarn install --frozen-lockfile"));
+        assert!(cra.contains("RUN yarn build"));
+        assert!(cra.contains("COPY --from=builder /app/build /usr/share/nginx/html"));
+
+        let config = StackConfig {
+            framework: Some(Framework::Vite),
+            ..Default::default()
+        };
+        let vite = generate_with_config(Stack::Static, &config, 80);
+        assert!(vite.contains("RUN npm run build"));
+        assert!(vite.contains("COPY --from=builder /app/dist /usr/share/nginx/html"));
+    }
+
     #[test]
     fn test_generate_laravel() {
-        let dockerfile = generate(Stack::Laravel, 8000);
+        let dockerfile = generate(Stack::Laravel, 8000, None);
         assert!(dockerfile.contains("FROM php:8.3-fpm-alpine"));
         assert!(dockerfile.contains("EXPOSE 8000"));
         assert!(dockerfile.contains("Laravel"));
     }
+
+    #[test]
+    fn test_generate_rust() {
+        let config = StackConfig {
+            start_command: Some("./server".into()),
+            ..Default::default()
+        };
+        let dockerfile = generate_with_config(Stack::Rust, &config, 8080);
+        assert!(dockerfile.contains("FROM rust:1-slim AS chef"));
+        assert!(dockerfile.contains("RUN cargo build --release"));
+        assert!(dockerfile.contains("COPY --from=builder /app/target/release/server ./app"));
+        assert!(dockerfile.contains("CMD [\"./app\"]"));
+
+        // Arguments of the start command are kept
+        let config = StackConfig {
+            start_command: Some("./server --port 8080 --workers 4".into()),
+            ..Default::default()
+        };
+        let dockerfile = generate_with_config(Stack::Rust, &config, 8080);
+        assert!(dockerfile.contains("COPY --from=builder /app/target/release/server ./app"));
+        assert!(dockerfile.contains(r#"CMD ["./app", "--port", "8080", "--workers", "4"]"#));
+
+        // The runtime stage doesn't run as root
+        let runtime = &dockerfile[dockerfile.rfind("\nFROM ").unwrap()..];
+        assert!(runtime.contains("FROM debian:bookworm-slim"));
+        assert!(runtime.contains("USER appuser"));
+    }
+
+    #[test]
+    fn test_generate_python_package_managers() {
+        let python = |package_manager| {
+            let config = StackConfig {
+                package_manager: Some(package_manager),
+                framework: Some(Framework::FastApi),
+                ..Default::default()
+            };
+            generate_with_config(Stack::Python, &config, 8000)
+        };
+
+        let pip = python(PackageManager::Pip);
+        assert!(pip.contains("RUN pip install --no-cache-dir -r requirements.txt"));
+
+        let poetry = python(PackageManager::Poetry);
+        assert!(poetry.contains("COPY pyproject.toml poetry.lock* ./"));
+        assert!(poetry.contains("poetry install --no-interaction --no-ansi --only main"));
+
+        let pipenv = python(PackageManager::Pipenv);
+        assert!(pipenv.contains("RUN pipenv install --system --deploy"));
+
+        let uv = python(PackageManager::Uv);
+        assert!(uv.contains("RUN uv sync --frozen --no-dev"));
+        assert!(uv.contains("ENV PATH=\"/app/.venv/bin:$PATH\""));
+
+        for dockerfile in [pip, poetry, pipenv, uv] {
+            assert!(dockerfile.contains("EXPOSE 8000"));
+            assert!(dockerfile.contains("uvicorn main:app --host 0.0.0.0 --port 8000"));
+        }
+    }
+
+    #[test]
+    fn test_python_start_command_follows_framework() {
+        let start = |framework| python_start_command(Some(framework), 8000);
+        assert_eq!(start(Framework::Django), "gunicorn wsgi:application --bind 0.0.0.0:8000");
+        assert_eq!(start(Framework::Flask), "gunicorn app:app --bind 0.0.0.0:8000");
+        assert_eq!(python_start_command(None, 8000), "python main.py");
+    }
+
+    #[test]
+    fn test_generate_go() {
+        let config = StackConfig {
+            version: Some("1.23".into()),
+            ..Default::default()
+        };
+        let dockerfile = generate_with_config(Stack::Go, &config, 8080);
+        assert!(dockerfile.contains("FROM golang:1.23-alpine AS builder"));
+        assert!(dockerfile.contains("RUN CGO_ENABLED=0 GOOS=linux go build"));
+        assert!(dockerfile.contains("EXPOSE 8080"));
+    }
+
+    #[test]
+    fn test_with_healthcheck() {
+        let health = HealthCheckConfig {
+            endpoint: "health".to_string(),
+            ..Default::default()
+        };
+        let dockerfile =
+            with_healthcheck(&generate(Stack::NodeJs, 3000, None), Stack::NodeJs, 3000, &health);
+
+        let healthcheck = dockerfile.find("HEALTHCHECK --interval=5s --timeout=30s").unwrap();
+        assert!(healthcheck < dockerfile.rfind("\nCMD ").unwrap());
+        assert!(dockerfile.contains("wget -q --spider http://127.0.0.1:3000/health || exit 1"));
+
+        let python =
+            with_healthcheck(&generate(Stack::Python, 8000, None), Stack::Python, 8000, &health);
+        assert!(python.contains("urllib.request.urlopen('http://127.0.0.1:8000/health'"));
+
+        // Applying twice doesn't duplicate the instruction
+        assert_eq!(with_healthcheck(&dockerfile, Stack::NodeJs, 3000, &health), dockerfile);
+
+        let command = HealthCheckConfig {
+            check_type: HealthCheckType::Command,
+            command: Some("pg_isready -h localhost".to_string()),
+            ..Default::default()
+        };
+        let node = generate(Stack::NodeJs, 3000, None);
+        assert!(with_healthcheck(&node, Stack::NodeJs, 3000, &command)
+            .contains("CMD pg_isready -h localhost || exit 1"));
+
+        // TCP checks are only run by the deployer
+        let tcp = HealthCheckConfig {
+            check_type: HealthCheckType::Tcp,
+            ..Default::default()
+        };
+        assert_eq!(with_healthcheck(&node, Stack::NodeJs, 3000, &tcp), node);
+    }
 }
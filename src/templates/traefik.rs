@@ -42,7 +42,7 @@ pub fn generate_app_config(app_name: &str, domains: &[AppDomain], container_port
         // HTTP router (for ACME challenge and redirect)
         routers.push_str(&format!(
             r#"    {router_name}-http:
-      rule: "Host(`{domain}`)"
+      rule: "{rule}"
       entryPoints:
         - web
       service: {app_name}
@@ -50,7 +50,7 @@ pub fn generate_app_config(app_name: &str, domains: &[AppDomain], container_port
         - {app_name}-redirect-https
 "#,
             router_name = router_name,
-            domain = domain.domain,
+            rule = domain.rule(),
             app_name = app_name
         ));
 
@@ -58,16 +58,15 @@ pub fn generate_app_config(app_name: &str, domains: &[AppDomain], container_port
         if https_middlewares.is_empty() {
             routers.push_str(&format!(
                 r#"    {router_name}:
-      rule: "Host(`{domain}`)"
+      rule: "{rule}"
       entryPoints:
         - websecure
       service: {app_name}
-      tls:
-        certResolver: letsencrypt
-"#,
+{tls}"#,
                 router_name = router_name,
-                domain = domain.domain,
-                app_name = app_name
+                rule = domain.rule(),
+                app_name = app_name,
+                tls = tls_block(domain.custom_cert)
             ));
         } else {
             let middlewares_list = https_middlewares
@@ -77,19 +76,18 @@ pub fn generate_app_config(app_name: &str, domains: &[AppDomain], container_port
                 .join("\n");
             routers.push_str(&format!(
                 r#"    {router_name}:
-      rule: "Host(`{domain}`)"
+      rule: "{rule}"
       entryPoints:
         - websecure
       service: {app_name}
       middlewares:
 {middlewares_list}
-      tls:
-        certResolver: letsencrypt
-"#,
+{tls}"#,
                 router_name = router_name,
-                domain = domain.domain,
+                rule = domain.rule(),
                 app_name = app_name,
-                middlewares_list = middlewares_list
+                middlewares_list = middlewares_list,
+                tls = tls_block(domain.custom_cert)
             ));
         }
 
@@ -98,7 +96,7 @@ pub fn generate_app_config(app_name: &str, domains: &[AppDomain], container_port
             // HTTP www router
             routers.push_str(&format!(
                 r#"    {app_name}-www-http:
-      rule: "Host(`www.{domain}`)"
+      rule: "{rule}"
       entryPoints:
         - web
       service: {app_name}
@@ -106,22 +104,21 @@ pub fn generate_app_config(app_name: &str, domains: &[AppDomain], container_port
         - {app_name}-redirect-https
 "#,
                 app_name = app_name,
-                domain = domain.domain
+                rule = domain.www_rule()
             ));
 
             // HTTPS www router (inherits auth from primary domain)
             if https_middlewares.is_empty() {
                 routers.push_str(&format!(
                     r#"    {app_name}-www:
-      rule: "Host(`www.{domain}`)"
+      rule: "{rule}"
       entryPoints:
         - websecure
       service: {app_name}
-      tls:
-        certResolver: letsencrypt
-"#,
+{tls}"#,
                     app_name = app_name,
-                    domain = domain.domain
+                    rule = domain.www_rule(),
+                    tls = tls_block(domain.www_custom_cert)
                 ));
             } else {
                 let middlewares_list = https_middlewares
@@ -131,18 +128,17 @@ pub fn generate_app_config(app_name: &str, domains: &[AppDomain], container_port
                     .join("\n");
                 routers.push_str(&format!(
                     r#"    {app_name}-www:
-      rule: "Host(`www.{domain}`)"
+      rule: "{rule}"
       entryPoints:
         - websecure
       service: {app_name}
       middlewares:
 {middlewares_list}
-      tls:
-        certResolver: letsencrypt
-"#,
+{tls}"#,
                     app_name = app_name,
-                    domain = domain.domain,
-                    middlewares_list = middlewares_list
+                    rule = domain.www_rule(),
+                    middlewares_list = middlewares_list,
+                    tls = tls_block(domain.www_custom_cert)
                 ));
             }
         }
@@ -195,6 +191,39 @@ pub fn generate_app_config_with_service(
     domains: &[AppDomain],
     container_port: u16,
     container_name: &str,
+) -> String {
+    generate_app_config_with_servers(
+        app_name,
+        domains,
+        container_port,
+        &[container_name.to_string()],
+        &format!("blue-green: {}", container_name),
+    )
+}
+
+/// Generates a Traefik dynamic configuration load-balancing across app replicas.
+pub fn generate_app_config_with_replicas(
+    app_name: &str,
+    domains: &[AppDomain],
+    container_port: u16,
+    containers: &[String],
+) -> String {
+    generate_app_config_with_servers(
+        app_name,
+        domains,
+        container_port,
+        containers,
+        &format!("{} replicas", containers.len()),
+    )
+}
+
+/// Generates a Traefik dynamic configuration routing to the given containers.
+fn generate_app_config_with_servers(
+    app_name: &str,
+    domains: &[AppDomain],
+    container_port: u16,
+    containers: &[String],
+    note: &str,
 ) -> String {
     let mut routers = String::new();
     let mut services = String::new();
@@ -234,7 +263,7 @@ pub fn generate_app_config_with_service(
         // HTTP router (for ACME challenge and redirect)
         routers.push_str(&format!(
             r#"    {router_name}-http:
-      rule: "Host(`{domain}`)"
+      rule: "{rule}"
       entryPoints:
         - web
       service: {app_name}
@@ -242,7 +271,7 @@ pub fn generate_app_config_with_service(
         - {app_name}-redirect-https
 "#,
             router_name = router_name,
-            domain = domain.domain,
+            rule = domain.rule(),
             app_name = app_name
         ));
 
@@ -250,16 +279,15 @@ pub fn generate_app_config_with_service(
         if https_middlewares.is_empty() {
             routers.push_str(&format!(
                 r#"    {router_name}:
-      rule: "Host(`{domain}`)"
+      rule: "{rule}"
       entryPoints:
         - websecure
       service: {app_name}
-      tls:
-        certResolver: letsencrypt
-"#,
+{tls}"#,
                 router_name = router_name,
-                domain = domain.domain,
-                app_name = app_name
+                rule = domain.rule(),
+                app_name = app_name,
+                tls = tls_block(domain.custom_cert)
             ));
         } else {
             let middlewares_list = https_middlewares
@@ -269,19 +297,18 @@ pub fn generate_app_config_with_service(
                 .join("\n");
             routers.push_str(&format!(
                 r#"    {router_name}:
-      rule: "Host(`{domain}`)"
+      rule: "{rule}"
       entryPoints:
         - websecure
       service: {app_name}
       middlewares:
 {middlewares_list}
-      tls:
-        certResolver: letsencrypt
-"#,
+{tls}"#,
                 router_name = router_name,
-                domain = domain.domain,
+                rule = domain.rule(),
                 app_name = app_name,
-                middlewares_list = middlewares_list
+                middlewares_list = middlewares_list,
+                tls = tls_block(domain.custom_cert)
             ));
         }
 
@@ -289,7 +316,7 @@ pub fn generate_app_config_with_service(
         if domain.primary && !domain.domain.starts_with("www.") {
             routers.push_str(&format!(
                 r#"    {app_name}-www-http:
-      rule: "Host(`www.{domain}`)"
+      rule: "{rule}"
       entryPoints:
         - web
       service: {app_name}
@@ -297,21 +324,20 @@ pub fn generate_app_config_with_service(
         - {app_name}-redirect-https
 "#,
                 app_name = app_name,
-                domain = domain.domain
+                rule = domain.www_rule()
             ));
 
             if https_middlewares.is_empty() {
                 routers.push_str(&format!(
                     r#"    {app_name}-www:
-      rule: "Host(`www.{domain}`)"
+      rule: "{rule}"
       entryPoints:
         - websecure
       service: {app_name}
-      tls:
-        certResolver: letsencrypt
-"#,
+{tls}"#,
                     app_name = app_name,
-                    domain = domain.domain
+                    rule = domain.www_rule(),
+                    tls = tls_block(domain.www_custom_cert)
                 ));
             } else {
                 let middlewares_list = https_middlewares
@@ -321,33 +347,36 @@ pub fn generate_app_config_with_service(
                     .join("\n");
                 routers.push_str(&format!(
                     r#"    {app_name}-www:
-      rule: "Host(`www.{domain}`)"
+      rule: "{rule}"
       entryPoints:
         - websecure
       service: {app_name}
       middlewares:
 {middlewares_list}
-      tls:
-        certResolver: letsencrypt
-"#,
+{tls}"#,
                     app_name = app_name,
-                    domain = domain.domain,
-                    middlewares_list = middlewares_list
+                    rule = domain.www_rule(),
+                    middlewares_list = middlewares_list,
+                    tls = tls_block(domain.www_custom_cert)
                 ));
             }
         }
     }
 
-    // Generate service pointing to specific container
+    // Generate service pointing to the given containers
+    let servers = containers
+        .iter()
+        .map(|container| {
+            format!("          - url: \"http://{}:{}\"\n", container, container_port)
+        })
+        .collect::<String>();
     services.push_str(&format!(
         r#"    {app_name}:
       loadBalancer:
         servers:
-          - url: "http://{container_name}:{port}"
-"#,
+{servers}"#,
         app_name = app_name,
-        container_name = container_name,
-        port = container_port
+        servers = servers
     ));
 
     // Generate middlewares (redirect + auth)
@@ -364,7 +393,7 @@ pub fn generate_app_config_with_service(
 
     format!(
         r#"# Traefik dynamic configuration for {app_name}
-# Generated by Flaase (blue-green: {container_name})
+# Generated by Flaase ({note})
 
 http:
   routers:
@@ -373,7 +402,7 @@ http:
 {services}
 {middlewares}"#,
         app_name = app_name,
-        container_name = container_name,
+        note = note,
         routers = routers,
         services = services,
         middlewares = middlewares
@@ -414,6 +443,42 @@ http:
     )
 }
 
+/// Returns the router TLS block: an imported certificate picked by SNI,
+/// or a Let's Encrypt certificate.
+fn tls_block(custom_cert: bool) -> &'static str {
+    if custom_cert {
+        "      tls: {}\n"
+    } else {
+        "      tls:\n        certResolver: letsencrypt\n"
+    }
+}
+
+/// Generates the Traefik TLS configuration listing imported certificates.
+/// Traefik selects the matching certificate for each request via SNI.
+pub fn generate_tls_config(certificates: &[(String, String)]) -> String {
+    let mut entries = String::new();
+    for (cert_file, key_file) in certificates {
+        entries.push_str(&format!(
+            "    - certFile: {}\n      keyFile: {}\n",
+            cert_file, key_file
+        ));
+    }
+
+    if entries.is_empty() {
+        return "# Imported TLS certificates\n# Generated by Flaase\n".to_string();
+    }
+
+    format!(
+        r#"# Imported TLS certificates
+# Generated by Flaase
+
+tls:
+  certificates:
+{entries}"#,
+        entries = entries
+    )
+}
+
 /// Domain configuration for an app.
 #[derive(Debug, Clone)]
 pub struct AppDomain {
@@ -421,6 +486,20 @@ pub struct AppDomain {
     pub primary: bool,
     /// Optional authentication (htpasswd format: "username:hash")
     pub auth: Option<DomainAuthConfig>,
+    /// Served by an imported certificate instead of Let's Encrypt.
+    pub custom_cert: bool,
+    /// Same as `custom_cert`, for the www. alias of a primary domain.
+    pub www_custom_cert: bool,
+    /// Only route requests under this path (e.g. "/api").
+    pub path_prefix: Option<String>,
+}
+
+/// Builds a Host rule, narrowed to a path prefix when one is set.
+fn host_rule(host: &str, path_prefix: Option<&str>) -> String {
+    match path_prefix {
+        Some(prefix) => format!("Host(`{}`) && PathPrefix(`{}`)", host, prefix),
+        None => format!("Host(`{}`)", host),
+    }
 }
 
 /// Authentication configuration for a domain.
@@ -436,9 +515,35 @@ impl AppDomain {
             domain: domain.to_string(),
             primary,
             auth: None,
+            custom_cert: false,
+            www_custom_cert: false,
+            path_prefix: None,
         }
     }
 
+    /// Restricts routing for this domain to a path prefix.
+    pub fn with_path_prefix(mut self, path_prefix: Option<&str>) -> Self {
+        self.path_prefix = path_prefix.map(|p| p.to_string());
+        self
+    }
+
+    /// Traefik router rule for this domain.
+    fn rule(&self) -> String {
+        host_rule(&self.domain, self.path_prefix.as_deref())
+    }
+
+    /// Traefik router rule for the www. alias of this domain.
+    fn www_rule(&self) -> String {
+        host_rule(&format!("www.{}", self.domain), self.path_prefix.as_deref())
+    }
+
+    /// Marks the domain (and its www. alias) as covered by an imported certificate.
+    pub fn with_custom_cert(mut self, domain: bool, www: bool) -> Self {
+        self.custom_cert = domain;
+        self.www_custom_cert = www;
+        self
+    }
+
     pub fn with_auth(mut self, htpasswd_line: &str) -> Self {
         self.auth = Some(DomainAuthConfig {
             htpasswd_line: htpasswd_line.to_string(),
@@ -501,4 +606,53 @@ mod tests {
         assert!(config.contains("my-app-auth-secure-example-com:"));
         assert!(!config.contains("my-app-auth-public-example-com:"));
     }
+
+    #[test]
+    fn test_generate_app_config_with_imported_cert() {
+        let domains = vec![AppDomain::new("app.example.com", true).with_custom_cert(true, false)];
+        let config = generate_app_config("my-app", &domains, 3000);
+
+        // Domain router relies on the imported cert, www alias still uses ACME
+        assert!(config.contains("      tls: {}\n"));
+        assert!(config.contains("certResolver: letsencrypt"));
+    }
+
+    #[test]
+    fn test_generate_app_config_with_path_prefix() {
+        let domains = vec![AppDomain::new("example.com", true).with_path_prefix(Some("/api"))];
+        let config = generate_app_config("my-app", &domains, 3000);
+
+        assert!(config.contains("rule: \"Host(`example.com`) && PathPrefix(`/api`)\""));
+        assert!(config.contains("rule: \"Host(`www.example.com`) && PathPrefix(`/api`)\""));
+        assert!(!config.contains("rule: \"Host(`example.com`)\""));
+    }
+
+    #[test]
+    fn test_generate_app_config_with_replicas() {
+        let domains = vec![AppDomain::new("example.com", false)];
+        let containers = vec![
+            "flaase-my-app-web".to_string(),
+            "flaase-my-app-web-2".to_string(),
+        ];
+        let config = generate_app_config_with_replicas("my-app", &domains, 80, &containers);
+
+        assert!(config.contains("(2 replicas)"));
+        assert!(config.contains(concat!(
+            "        servers:\n",
+            "          - url: \"http://flaase-my-app-web:80\"\n",
+            "          - url: \"http://flaase-my-app-web-2:80\"\n",
+        )));
+    }
+
+    #[test]
+    fn test_generate_tls_config() {
+        let config = generate_tls_config(&[(
+            "/etc/traefik/dynamic/certs/wildcard.crt".to_string(),
+            "/etc/traefik/dynamic/certs/wildcard.key".to_string(),
+        )]);
+
+        assert!(config.contains("tls:\n  certificates:\n"));
+        assert!(config.contains("certFile: /etc/traefik/dynamic/certs/wildcard.crt"));
+        assert!(config.contains("keyFile: /etc/traefik/dynamic/certs/wildcard.key"));
+    }
 }
@@ -7,8 +7,8 @@ pub use components::{Confirm, Select, TextInput};
 
 // Re-export output utilities
 pub use output::{
-    error, error_with_hint, header, info, section, step, step_done, step_failed, success, url,
-    warning,
+    error, error_with_hint, header, info, page, section, step, step_done, step_failed, success,
+    url, warning,
 };
 
 // Re-export progress utilities
@@ -17,6 +17,7 @@ pub use progress::{MultiProgress, ProgressBar};
 // Convenience functions that wrap the components for simpler usage
 
 use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Prompts for text input.
 pub fn input(prompt: &str) -> io::Result<String> {
@@ -57,7 +58,53 @@ pub fn select<T: AsRef<str>>(prompt: &str, items: &[T]) -> io::Result<usize> {
     Select::new(prompt, items).run()
 }
 
+/// Prompts for selection with the item at `default` pre-selected.
+pub fn select_with_default<T: AsRef<str>>(
+    prompt: &str,
+    items: &[T],
+    default: usize,
+) -> io::Result<usize> {
+    Select::new(prompt, items).default(default).run()
+}
+
 /// Prompts for a yes/no confirmation.
 pub fn confirm(prompt: &str, default: bool) -> io::Result<bool> {
     Confirm::new(prompt).default(default).run()
 }
+
+/// Set by the global `--yes` flag to auto-confirm destructive actions.
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+
+/// Makes `confirm_action` answer yes without prompting.
+pub fn set_assume_yes(value: bool) {
+    ASSUME_YES.store(value, Ordering::Relaxed);
+}
+
+/// Returns true if destructive actions are auto-confirmed (`--yes`).
+pub fn assume_yes() -> bool {
+    ASSUME_YES.load(Ordering::Relaxed)
+}
+
+/// Set by `fl deploy --events` so stderr carries only the JSON event stream.
+static STDERR_RESERVED: AtomicBool = AtomicBool::new(false);
+
+/// Sends errors, progress and verbose command output to stdout, leaving
+/// stderr to machine-readable output.
+pub fn reserve_stderr() {
+    STDERR_RESERVED.store(true, Ordering::Relaxed);
+}
+
+/// Returns true if stderr is reserved for machine-readable output.
+pub fn stderr_reserved() -> bool {
+    STDERR_RESERVED.load(Ordering::Relaxed)
+}
+
+/// Prompts for confirmation of a destructive action, skipped with `--yes`.
+/// Prompts guarding secrets (e.g. printing plain-text values) use `confirm`
+/// instead, so `--yes` never reveals them.
+pub fn confirm_action(prompt: &str, default: bool) -> io::Result<bool> {
+    if assume_yes() {
+        return Ok(true);
+    }
+    confirm(prompt, default)
+}
@@ -21,7 +21,7 @@ pub fn success(message: &str) {
 
 /// Prints an error message with a red cross.
 pub fn error(message: &str) {
-    eprintln!("{} {}", style("✗").red(), message);
+    print_error(&format!("{} {}", style("✗").red(), message));
 }
 
 /// Prints a warning message in yellow.
@@ -41,8 +41,17 @@ pub fn url(url: &str) {
 
 /// Prints an error with a hint for resolution.
 pub fn error_with_hint(message: &str, hint: &str) {
-    eprintln!("{} {}", style("✗").red(), message);
-    eprintln!("  {} {}", style("→").dim(), hint);
+    print_error(&format!("{} {}", style("✗").red(), message));
+    print_error(&format!("  {} {}", style("→").dim(), hint));
+}
+
+/// Writes an error line to stderr, or to stdout while stderr is reserved.
+fn print_error(line: &str) {
+    if super::stderr_reserved() {
+        println!("{}", line);
+    } else {
+        eprintln!("{}", line);
+    }
 }
 
 /// Prints a step in progress (spinner style).
@@ -68,3 +77,42 @@ pub fn section(title: &str) {
     println!("{}", style(title).bold());
     println!();
 }
+
+/// Default pager: `-F` exits when the text fits on one screen, `-R` keeps ANSI
+/// colors and `-X` leaves the output on screen after quitting.
+const DEFAULT_PAGER: &str = "less -FRX";
+
+/// Prints text through `$PAGER` (default `less -FRX`) when stdout is a terminal.
+/// Falls back to printing directly if the pager can't be started.
+pub fn page(text: &str) {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    if !Term::stdout().is_term() {
+        print!("{}", text);
+        return;
+    }
+
+    let pager = std::env::var("PAGER")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_PAGER.to_string());
+    let mut parts = pager.split_whitespace();
+    let program = parts.next().unwrap_or("less");
+
+    let child = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn();
+
+    match child {
+        Ok(mut child) => {
+            if let Some(mut stdin) = child.stdin.take() {
+                // The pager may exit before reading everything (e.g. user quits)
+                let _ = stdin.write_all(text.as_bytes());
+            }
+            let _ = child.wait();
+        }
+        Err(_) => print!("{}", text),
+    }
+}
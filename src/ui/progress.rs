@@ -1,5 +1,5 @@
 use console::style;
-use indicatif::{ProgressBar as IndicatifBar, ProgressStyle};
+use indicatif::{ProgressBar as IndicatifBar, ProgressDrawTarget, ProgressStyle};
 use std::time::Duration;
 
 /// A pnpm-style progress bar for deployment operations.
@@ -20,8 +20,7 @@ impl ProgressBar {
                 .expect("Invalid progress bar template")
                 .progress_chars("█░"),
         );
-        bar.enable_steady_tick(Duration::from_millis(100));
-        Self { bar }
+        Self::start(bar)
     }
 
     /// Creates a spinner for indeterminate progress.
@@ -32,6 +31,14 @@ impl ProgressBar {
                 .template(&format!("{{spinner:.cyan}} {}", style(label).dim()))
                 .expect("Invalid spinner template"),
         );
+        Self::start(bar)
+    }
+
+    /// Starts drawing, on stdout while stderr is reserved.
+    fn start(bar: IndicatifBar) -> Self {
+        if super::stderr_reserved() {
+            bar.set_draw_target(ProgressDrawTarget::stdout());
+        }
         bar.enable_steady_tick(Duration::from_millis(100));
         Self { bar }
     }
@@ -1,5 +1,7 @@
 pub mod validation;
 
 pub use validation::{
-    is_app_name_available, validate_app_name, validate_domain, validate_git_ssh_url,
+    is_app_name_available, normalize_git_url, parse_memory_size, validate_app_name,
+    validate_cache_name, validate_deploy_tag, validate_domain, validate_git_ssh_url,
+    validate_memory_size, validate_path_prefix, validate_port,
 };
@@ -2,6 +2,7 @@
 
 use std::path::Path;
 
+use crate::core::app_config::AppConfig;
 use crate::core::error::AppError;
 use crate::core::FLAASE_APPS_PATH;
 
@@ -48,12 +49,105 @@ pub fn validate_app_name(name: &str) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Validates the name of an additional cache instance. It ends up in a
+/// container name and an env var, so only lowercase letters, numbers and
+/// hyphens are allowed.
+pub fn validate_cache_name(name: &str) -> Result<(), AppError> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err(AppError::Validation(format!(
+            "Invalid cache name '{}': use lowercase letters, numbers, and hyphens",
+            name
+        )));
+    }
+
+    if name.starts_with('-') || name.ends_with('-') {
+        return Err(AppError::Validation(format!(
+            "Invalid cache name '{}': it cannot start or end with a hyphen",
+            name
+        )));
+    }
+
+    Ok(())
+}
+
 /// Checks if an app name is already taken.
 pub fn is_app_name_available(name: &str) -> bool {
     let app_path = format!("{}/{}", FLAASE_APPS_PATH, name);
     !Path::new(&app_path).exists()
 }
 
+/// Validates the port an app listens on.
+/// Rejects 0 and returns warnings for privileged ports and ports
+/// explicitly set for another app.
+pub fn validate_port(port: u16) -> Result<Vec<String>, AppError> {
+    validate_port_in(port, Path::new(FLAASE_APPS_PATH))
+}
+
+/// `validate_port` against the apps of a given apps directory.
+fn validate_port_in(port: u16, apps_dir: &Path) -> Result<Vec<String>, AppError> {
+    if port == 0 {
+        return Err(AppError::Validation("Port must be between 1 and 65535".into()));
+    }
+
+    let mut warnings = Vec::new();
+
+    if port < 1024 {
+        warnings.push(format!(
+            "Port {} is privileged; apps running as a non-root user can't bind it",
+            port
+        ));
+    }
+
+    // Stack defaults are shared by design (every app runs in its own
+    // container), so only ports chosen explicitly are worth flagging
+    for app in AppConfig::list_in(apps_dir).unwrap_or_default() {
+        if let Ok(config) = AppConfig::load_in(apps_dir, &app) {
+            if config.port == Some(port) {
+                warnings.push(format!("Port {} is already set for app '{}'", port, app));
+            }
+        }
+    }
+
+    Ok(warnings)
+}
+
+/// Canonicalizes a repository URL to `git@host:user/repo.git`.
+///
+/// Trims whitespace and trailing slashes, adds a missing `.git` suffix, and
+/// converts `https://host/user/repo` and `ssh://git@host/user/repo` forms to
+/// the SCP-like SSH form, so equivalent inputs are stored identically.
+pub fn normalize_git_url(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/');
+    if url.is_empty() {
+        return String::new();
+    }
+
+    let scp_like = if let Some(rest) = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .or_else(|| url.strip_prefix("ssh://git@"))
+    {
+        match rest.split_once('/') {
+            // ssh:// URLs with a custom port can't be expressed in SCP form
+            Some((host, path)) if !host.contains(':') => {
+                let host = host.rsplit('@').next().unwrap_or(host);
+                format!("git@{}:{}", host, path)
+            }
+            _ => return url.to_string(),
+        }
+    } else {
+        url.to_string()
+    };
+
+    let path = scp_like.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    format!("{}.git", path)
+}
+
 /// Validates a Git SSH URL.
 /// Must be in format: git@host:user/repo.git
 pub fn validate_git_ssh_url(url: &str) -> Result<(), AppError> {
@@ -152,6 +246,114 @@ pub fn validate_domain(domain: &str) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Validates a deployment tag (used as a Docker image tag).
+/// Must be 1-128 letters, numbers, dots, hyphens or underscores,
+/// and not one of the tags Flaase manages itself.
+pub fn validate_deploy_tag(tag: &str) -> Result<(), AppError> {
+    if tag.is_empty() || tag.len() > 128 {
+        return Err(AppError::Validation(
+            "Deploy tag must be between 1 and 128 characters".into(),
+        ));
+    }
+
+    if !tag
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+    {
+        return Err(AppError::Validation(
+            "Deploy tag must contain only letters, numbers, dots, hyphens, and underscores".into(),
+        ));
+    }
+
+    if tag.starts_with('.') || tag.starts_with('-') {
+        return Err(AppError::Validation(
+            "Deploy tag cannot start with a dot or a hyphen".into(),
+        ));
+    }
+
+    // Images are also tagged with the short commit SHA, which rollback accepts too
+    if tag.len() == 7 && tag.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(AppError::Validation(format!(
+            "'{}' looks like a commit SHA and would clash with the commit's image tag",
+            tag
+        )));
+    }
+
+    if tag == "latest" || tag == "previous" || tag.starts_with("previous-") {
+        return Err(AppError::Validation(format!(
+            "'{}' is reserved by Flaase",
+            tag
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validates a routing path prefix such as `/api` or `/app/v2`.
+/// Must start with a slash, not end with one, and use URL-safe path characters.
+pub fn validate_path_prefix(prefix: &str) -> Result<(), AppError> {
+    if !prefix.starts_with('/') || prefix.len() < 2 {
+        return Err(AppError::Validation(
+            "Path prefix must start with '/' and not be the root path (e.g. /api)".into(),
+        ));
+    }
+
+    if prefix.ends_with('/') || prefix.contains("//") {
+        return Err(AppError::Validation(
+            "Path prefix cannot end with '/' or contain empty segments".into(),
+        ));
+    }
+
+    if !prefix
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '-' | '_' | '.' | '~'))
+    {
+        return Err(AppError::Validation(
+            "Path prefix must contain only letters, numbers, '/', '-', '_', '.' and '~'".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validates a Docker memory size such as `512m` or `1g`.
+/// A plain number is taken as bytes; units are b, k, m or g.
+pub fn validate_memory_size(size: &str) -> Result<(), AppError> {
+    parse_memory_size(size).map(|_| ())
+}
+
+/// Parses a Docker memory size into bytes (units are powers of 1024).
+pub fn parse_memory_size(size: &str) -> Result<u64, AppError> {
+    let size = size.to_ascii_lowercase();
+    let digits = size.trim_end_matches(['b', 'k', 'm', 'g']);
+    let unit = &size[digits.len()..];
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) || unit.len() > 1 {
+        return Err(AppError::Validation(format!(
+            "Invalid memory size '{}'. Use a number with an optional unit, e.g. 512m or 1g",
+            size
+        )));
+    }
+
+    let value = match digits.parse::<u64>() {
+        Ok(0) | Err(_) => {
+            return Err(AppError::Validation("Memory size must be greater than zero".into()))
+        }
+        Ok(value) => value,
+    };
+
+    let multiplier: u64 = match unit {
+        "k" => 1 << 10,
+        "m" => 1 << 20,
+        "g" => 1 << 30,
+        _ => 1,
+    };
+
+    value
+        .checked_mul(multiplier)
+        .ok_or_else(|| AppError::Validation(format!("Memory size '{}' is too large", size)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -180,6 +382,26 @@ mod tests {
         assert!(validate_git_ssh_url("git@gitlab.com:org/project.git").is_ok());
     }
 
+    #[test]
+    fn test_normalize_git_url() {
+        let canonical = "git@github.com:user/repo.git";
+        for input in [
+            "git@github.com:user/repo.git",
+            "git@github.com:user/repo",
+            " git@github.com:user/repo.git/ ",
+            "https://github.com/user/repo",
+            "https://github.com/user/repo.git/",
+            "ssh://git@github.com/user/repo.git",
+        ] {
+            assert_eq!(normalize_git_url(input), canonical, "input: {}", input);
+        }
+
+        assert_eq!(
+            normalize_git_url("ssh://git@git.example.com:2222/user/repo.git"),
+            "ssh://git@git.example.com:2222/user/repo.git"
+        );
+    }
+
     #[test]
     fn test_validate_git_ssh_url_invalid() {
         assert!(validate_git_ssh_url("").is_err());
@@ -203,4 +425,86 @@ mod tests {
         assert!(validate_domain("example.com.").is_err());
         assert!(validate_domain("-example.com").is_err());
     }
+
+    #[test]
+    fn test_validate_port() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(validate_port_in(0, dir.path()).is_err());
+        assert!(validate_port_in(3000, dir.path()).unwrap().is_empty());
+        assert!(validate_port_in(443, dir.path()).unwrap()[0].contains("privileged"));
+
+        for (app, port) in [("api", Some(4000)), ("web", None)] {
+            let image = crate::core::registry::parse_image_reference("nginx").unwrap();
+            let domain = format!("{}.example.com", app);
+            let mut config =
+                AppConfig::new_image(app.into(), image, domain, 8080, vec![], None, None, None);
+            config.port = port;
+
+            std::fs::create_dir(dir.path().join(app)).unwrap();
+            let yaml = serde_yaml::to_string(&config).unwrap();
+            std::fs::write(dir.path().join(app).join("config.yml"), yaml).unwrap();
+        }
+
+        let warnings = validate_port_in(4000, dir.path()).unwrap();
+        assert_eq!(warnings, ["Port 4000 is already set for app 'api'"]);
+        // The image default port of "web" isn't an explicit choice
+        assert!(validate_port_in(8080, dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_validate_cache_name() {
+        assert!(validate_cache_name("sessions").is_ok());
+        assert!(validate_cache_name("queue-2").is_ok());
+
+        assert!(validate_cache_name("").is_err());
+        assert!(validate_cache_name("Sessions").is_err());
+        assert!(validate_cache_name("my_cache").is_err());
+        assert!(validate_cache_name("-queue").is_err());
+        assert!(validate_cache_name("a b").is_err());
+    }
+
+    #[test]
+    fn test_validate_deploy_tag() {
+        assert!(validate_deploy_tag("release-2024-06").is_ok());
+        assert!(validate_deploy_tag("v1.2.3_rc1").is_ok());
+
+        assert!(validate_deploy_tag("").is_err());
+        assert!(validate_deploy_tag("latest").is_err());
+        assert!(validate_deploy_tag("previous").is_err());
+        assert!(validate_deploy_tag("previous-2").is_err());
+        assert!(validate_deploy_tag("-release").is_err());
+        assert!(validate_deploy_tag("release/2024").is_err());
+        assert!(validate_deploy_tag("abc1234").is_err());
+        assert!(validate_deploy_tag("cafe").is_ok());
+    }
+
+    #[test]
+    fn test_validate_memory_size() {
+        assert!(validate_memory_size("512m").is_ok());
+        assert!(validate_memory_size("1G").is_ok());
+        assert!(validate_memory_size("1048576").is_ok());
+
+        assert!(validate_memory_size("").is_err());
+        assert!(validate_memory_size("m").is_err());
+        assert!(validate_memory_size("0m").is_err());
+
+        assert_eq!(parse_memory_size("512m").unwrap(), 512 * 1024 * 1024);
+        assert_eq!(parse_memory_size("2G").unwrap(), 2 * 1024 * 1024 * 1024);
+        assert_eq!(parse_memory_size("100b").unwrap(), 100);
+        assert!(validate_memory_size("1.5g").is_err());
+        assert!(validate_memory_size("512mb").is_err());
+    }
+
+    #[test]
+    fn test_validate_path_prefix() {
+        assert!(validate_path_prefix("/api").is_ok());
+        assert!(validate_path_prefix("/app/v2").is_ok());
+
+        assert!(validate_path_prefix("").is_err());
+        assert!(validate_path_prefix("/").is_err());
+        assert!(validate_path_prefix("api").is_err());
+        assert!(validate_path_prefix("/api/").is_err());
+        assert!(validate_path_prefix("/a//b").is_err());
+        assert!(validate_path_prefix("/api`)").is_err());
+    }
 }